@@ -81,6 +81,7 @@ fn create_test_issue(i: usize) -> Issue {
         labels: vec![format!("label-{}", i % 5)],
         dependencies: vec![],
         comments: vec![],
+        org_extras: None,
     }
 }
 
@@ -0,0 +1,593 @@
+//! Batch command implementation.
+//!
+//! `batch` reads one JSON operation per line (stdin, or `--file`) and
+//! applies them all in a single mutation: every operation lands or none
+//! does, and the batch is one `undo` step. A `create` may name its issue
+//! with a `handle`; later lines refer to it as `$handle` wherever an issue
+//! ID goes.
+//!
+//! ```text
+//! {"op":"create","handle":"epic","title":"Auth rewrite","type":"epic"}
+//! {"op":"create","handle":"login","title":"Login form","parent":"$epic"}
+//! {"op":"dep_add","id":"$login","depends_on":"bd-12"}
+//! {"op":"label","id":"$login","add":["frontend"]}
+//! {"op":"comment","id":"$epic","text":"Split out of bd-9"}
+//! {"op":"update","id":"bd-12","status":"in_progress"}
+//! {"op":"close","id":"bd-7","reason":"superseded"}
+//! ```
+//!
+//! IDs for new issues are picked before the transaction starts. If another
+//! writer takes one in the meantime the insert fails and the batch rolls
+//! back like any other failure.
+
+use crate::cli::commands::create::{self, CreateConfig};
+use crate::cli::commands::update;
+use crate::cli::{BatchArgs, CreateArgs, UpdateArgs};
+use crate::config;
+use crate::config::workflow::Workflow;
+use crate::error::{BeadsError, Result, StructuredError};
+use crate::model::DependencyType;
+use crate::output::OutputContext;
+use crate::storage::SqliteStorage;
+use crate::storage::batch::{BatchStep, StepOutcome};
+use crate::util::id::{IdResolver, ResolverConfig};
+use crate::validation::LabelValidator;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+
+/// One line of batch input.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Op {
+    Create(CreateOp),
+    Update(UpdateOp),
+    #[serde(alias = "dep")]
+    DepAdd(DepAddOp),
+    Label(LabelOp),
+    Comment(CommentOp),
+    Close(CloseOp),
+}
+
+impl Op {
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::Create(_) => "create",
+            Self::Update(_) => "update",
+            Self::DepAdd(_) => "dep_add",
+            Self::Label(_) => "label",
+            Self::Comment(_) => "comment",
+            Self::Close(_) => "close",
+        }
+    }
+}
+
+/// Priority as a number (`1`) or string (`"P1"`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum PriorityValue {
+    Number(i32),
+    Text(String),
+}
+
+impl PriorityValue {
+    /// The value as `--priority` would take it.
+    pub(crate) fn to_arg(&self) -> String {
+        match self {
+            Self::Number(n) => n.to_string(),
+            Self::Text(text) => text.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CreateOp {
+    /// Name later lines use as `$handle`.
+    #[serde(default)]
+    handle: Option<String>,
+    title: String,
+    #[serde(default, rename = "type")]
+    issue_type: Option<String>,
+    #[serde(default)]
+    priority: Option<PriorityValue>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    assignee: Option<String>,
+    #[serde(default)]
+    owner: Option<String>,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    parent: Option<String>,
+    /// `id` or `type:id`, as for `create --deps`.
+    #[serde(default)]
+    deps: Vec<String>,
+    #[serde(default)]
+    estimate: Option<i32>,
+    #[serde(default)]
+    due: Option<String>,
+    #[serde(default)]
+    defer: Option<String>,
+    #[serde(default)]
+    external_ref: Option<String>,
+    #[serde(default)]
+    status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct UpdateOp {
+    id: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    design: Option<String>,
+    #[serde(default)]
+    acceptance_criteria: Option<String>,
+    #[serde(default)]
+    notes: Option<String>,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    priority: Option<PriorityValue>,
+    #[serde(default, rename = "type")]
+    issue_type: Option<String>,
+    /// Empty string clears.
+    #[serde(default)]
+    assignee: Option<String>,
+    #[serde(default)]
+    owner: Option<String>,
+    #[serde(default)]
+    due: Option<String>,
+    #[serde(default)]
+    defer: Option<String>,
+    #[serde(default)]
+    estimate: Option<i32>,
+    #[serde(default)]
+    external_ref: Option<String>,
+    /// Allow starting a blocked issue.
+    #[serde(default)]
+    force: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DepAddOp {
+    id: String,
+    depends_on: String,
+    #[serde(default, rename = "type")]
+    dep_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LabelOp {
+    id: String,
+    #[serde(default)]
+    add: Vec<String>,
+    #[serde(default)]
+    remove: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CommentOp {
+    id: String,
+    text: String,
+    #[serde(default)]
+    author: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CloseOp {
+    id: String,
+    #[serde(default)]
+    reason: Option<String>,
+    #[serde(default)]
+    session: Option<String>,
+    #[serde(default)]
+    force: bool,
+}
+
+/// JSON output for the batch command.
+#[derive(Debug, Serialize)]
+pub struct BatchOutput {
+    pub committed: bool,
+    pub results: Vec<OpResult>,
+}
+
+/// What happened to one input line.
+#[derive(Debug, Serialize)]
+pub struct OpResult {
+    pub line: usize,
+    pub op: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handle: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// `applied`, `unchanged`, `failed`, `rolled_back` or `not_run`.
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+}
+
+/// Execute the batch command.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or parsed, or the error of
+/// the first operation that fails; nothing is written in either case.
+pub fn execute(args: &BatchArgs, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let input = match &args.file {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            input
+        }
+    };
+    let ops = parse_ops(&input)?;
+
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let workflow = Workflow::from_layer(&layer)?;
+    let create_config = create::create_config(&layer)?;
+    let mut planner = Planner {
+        storage: &storage_ctx.storage,
+        resolver: IdResolver::new(ResolverConfig::with_prefix(
+            create_config.id_config.prefix.clone(),
+        )),
+        create_config,
+        handles: HashMap::new(),
+        reserved: HashSet::new(),
+    };
+
+    let mut steps = Vec::with_capacity(ops.len());
+    let mut handles = Vec::with_capacity(ops.len());
+    for (index, (_, op)) in ops.iter().enumerate() {
+        handles.push(match op {
+            Op::Create(create) => create.handle.clone(),
+            _ => None,
+        });
+        match planner.plan(op) {
+            Ok(step) => steps.push(step),
+            Err(error) => {
+                report(&ops, &handles, &[], index, &error, ctx, args.robot);
+                return Err(error);
+            }
+        }
+    }
+    let actor = planner.create_config.actor.clone();
+
+    let storage = &mut storage_ctx.storage;
+    match storage.apply_batch(&steps, &actor, |issue, status| {
+        workflow.check_transition(issue, status)
+    }) {
+        Ok(outcomes) => {
+            tracing::info!(count = outcomes.len(), "Applied batch");
+            if let Some(last) = outcomes.last() {
+                crate::util::set_last_touched_id(&beads_dir, &last.id);
+            }
+            let results = ops
+                .iter()
+                .zip(handles)
+                .zip(&outcomes)
+                .map(|(((line, op), handle), outcome)| OpResult {
+                    line: *line,
+                    op: op.name(),
+                    handle,
+                    id: Some(outcome.id.clone()),
+                    status: if outcome.changed {
+                        "applied"
+                    } else {
+                        "unchanged"
+                    },
+                    error: None,
+                })
+                .collect();
+            print_output(
+                &BatchOutput {
+                    committed: true,
+                    results,
+                },
+                ctx,
+                args.robot,
+            );
+        }
+        Err(failure) => {
+            let failed = failure.step.unwrap_or(steps.len().saturating_sub(1));
+            let ids: Vec<StepOutcome> = steps
+                .iter()
+                .map(|step| StepOutcome {
+                    id: step.issue_id().to_string(),
+                    changed: false,
+                })
+                .collect();
+            report(
+                &ops,
+                &handles,
+                &ids,
+                failed,
+                &failure.error,
+                ctx,
+                args.robot,
+            );
+            return Err(failure.error);
+        }
+    }
+
+    storage_ctx.flush_no_db_if_dirty()?;
+    Ok(())
+}
+
+/// Parse non-blank lines into operations, keeping 1-based line numbers.
+fn parse_ops(input: &str) -> Result<Vec<(usize, Op)>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .map(|op| (index + 1, op))
+                .map_err(|e| BeadsError::JsonlParse {
+                    line: index + 1,
+                    reason: e.to_string(),
+                })
+        })
+        .collect()
+}
+
+/// Turns operations into resolved steps, allocating IDs for new issues.
+struct Planner<'a> {
+    storage: &'a SqliteStorage,
+    resolver: IdResolver,
+    create_config: CreateConfig,
+    handles: HashMap<String, String>,
+    /// IDs allocated by earlier creates in this batch.
+    reserved: HashSet<String>,
+}
+
+impl Planner<'_> {
+    fn plan(&mut self, op: &Op) -> Result<BatchStep> {
+        let actor = self.create_config.actor.clone();
+        let step = match op {
+            Op::Create(create) => self.plan_create(create)?,
+            Op::Update(op) => {
+                let args = UpdateArgs {
+                    title: op.title.clone(),
+                    description: op.description.clone(),
+                    design: op.design.clone(),
+                    acceptance_criteria: op.acceptance_criteria.clone(),
+                    notes: op.notes.clone(),
+                    status: op.status.clone(),
+                    priority: op.priority.as_ref().map(PriorityValue::to_arg),
+                    type_: op.issue_type.clone(),
+                    assignee: op.assignee.clone(),
+                    owner: op.owner.clone(),
+                    due: op.due.clone(),
+                    defer: op.defer.clone(),
+                    estimate: op.estimate,
+                    external_ref: op.external_ref.clone(),
+                    ..UpdateArgs::default()
+                };
+                BatchStep::Update {
+                    id: self.resolve(&op.id)?,
+                    update: Box::new(update::build_update(&args, &actor, false)?),
+                    force: op.force,
+                }
+            }
+            Op::DepAdd(op) => BatchStep::DepAdd {
+                id: self.resolve(&op.id)?,
+                depends_on: self.resolve(&op.depends_on)?,
+                dep_type: parse_dep_type(op.dep_type.as_deref().unwrap_or("blocks"))?,
+            },
+            Op::Label(op) => {
+                let mut add = Vec::with_capacity(op.add.len());
+                for label in &op.add {
+                    let label = label.trim();
+                    LabelValidator::validate(label)
+                        .map_err(|e| BeadsError::validation("label", e.message))?;
+                    add.push(label.to_string());
+                }
+                BatchStep::Label {
+                    id: self.resolve(&op.id)?,
+                    add,
+                    remove: op.remove.iter().map(|l| l.trim().to_string()).collect(),
+                }
+            }
+            Op::Comment(op) => {
+                if op.text.trim().is_empty() {
+                    return Err(BeadsError::validation("text", "cannot be empty"));
+                }
+                BatchStep::Comment {
+                    id: self.resolve(&op.id)?,
+                    author: op.author.clone().unwrap_or(actor),
+                    text: op.text.clone(),
+                }
+            }
+            Op::Close(op) => BatchStep::Close {
+                id: self.resolve(&op.id)?,
+                reason: op.reason.clone().unwrap_or_else(|| "done".to_string()),
+                session: op.session.clone(),
+                force: op.force,
+            },
+        };
+        Ok(step)
+    }
+
+    fn plan_create(&mut self, op: &CreateOp) -> Result<BatchStep> {
+        if let Some(handle) = &op.handle {
+            if handle.is_empty() || self.handles.contains_key(handle) {
+                return Err(BeadsError::validation(
+                    "handle",
+                    format!("handle '{handle}' is empty or already used"),
+                ));
+            }
+        }
+        let deps = op
+            .deps
+            .iter()
+            .map(|dep| match dep.split_once(':') {
+                Some((dep_type, id)) => Ok(format!("{dep_type}:{}", self.resolve(id)?)),
+                None => self.resolve(dep),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let args = CreateArgs {
+            title: Some(op.title.clone()),
+            type_: op.issue_type.clone(),
+            priority: op.priority.as_ref().map(PriorityValue::to_arg),
+            description: op.description.clone(),
+            assignee: op.assignee.clone(),
+            owner: op.owner.clone(),
+            labels: op.labels.clone(),
+            parent: op.parent.as_deref().map(|p| self.resolve(p)).transpose()?,
+            deps,
+            estimate: op.estimate,
+            due: op.due.clone(),
+            defer: op.defer.clone(),
+            external_ref: op.external_ref.clone(),
+            status: op.status.clone(),
+            ..CreateArgs::default()
+        };
+        let issue = create::build_issue(self.storage, &args, &self.create_config, &self.reserved)?;
+
+        self.reserved.insert(issue.id.clone());
+        if let Some(handle) = &op.handle {
+            self.handles.insert(handle.clone(), issue.id.clone());
+        }
+        Ok(BatchStep::Create(Box::new(issue)))
+    }
+
+    /// Resolve `$handle` or a (possibly partial) issue ID.
+    fn resolve(&self, reference: &str) -> Result<String> {
+        if let Some(handle) = reference.strip_prefix('$') {
+            return self.handles.get(handle).cloned().ok_or_else(|| {
+                BeadsError::validation("handle", format!("unknown handle '${handle}'"))
+            });
+        }
+        create::resolve_id(&self.resolver, self.storage, reference)
+    }
+}
+
+/// Parse a dependency type, rejecting custom types (usually typos).
+pub(crate) fn parse_dep_type(value: &str) -> Result<DependencyType> {
+    let normalized = if value.eq_ignore_ascii_case("blocked-by") {
+        "blocks"
+    } else {
+        value
+    };
+    match normalized.parse::<DependencyType>() {
+        Ok(DependencyType::Custom(_)) | Err(_) => Err(BeadsError::validation(
+            "type",
+            format!("unknown dependency type '{value}'"),
+        )),
+        Ok(dep_type) => Ok(dep_type),
+    }
+}
+
+/// Report a batch that was rolled back: earlier lines were undone, the
+/// failing line carries its error and later lines never ran.
+fn report(
+    ops: &[(usize, Op)],
+    handles: &[Option<String>],
+    planned: &[StepOutcome],
+    failed: usize,
+    error: &BeadsError,
+    ctx: &OutputContext,
+    robot: bool,
+) {
+    let (line, op) = &ops[failed];
+    if !(ctx.is_json() || robot) {
+        ctx.warning(&format!(
+            "Batch rolled back at line {line} ({}); nothing was written",
+            op.name()
+        ));
+        return;
+    }
+
+    let results = ops
+        .iter()
+        .enumerate()
+        .map(|(index, (line, op))| OpResult {
+            line: *line,
+            op: op.name(),
+            handle: handles.get(index).cloned().flatten(),
+            id: planned.get(index).map(|outcome| outcome.id.clone()),
+            status: match index.cmp(&failed) {
+                std::cmp::Ordering::Less => "rolled_back",
+                std::cmp::Ordering::Equal => "failed",
+                std::cmp::Ordering::Greater => "not_run",
+            },
+            error: (index == failed)
+                .then(|| StructuredError::from_error(error).to_json()["error"].clone()),
+        })
+        .collect();
+    print_output(
+        &BatchOutput {
+            committed: false,
+            results,
+        },
+        ctx,
+        robot,
+    );
+}
+
+fn print_output(output: &BatchOutput, ctx: &OutputContext, robot: bool) {
+    if ctx.is_json() {
+        ctx.json_pretty(output);
+    } else if robot {
+        OutputContext::from_flags(true, false, true).json_pretty(output);
+    } else if !ctx.is_quiet() {
+        for result in &output.results {
+            let id = result.id.as_deref().unwrap_or_default();
+            match result.status {
+                "applied" => println!("\u{2713} {} {id}", result.op),
+                _ => println!("  {} {id} (unchanged)", result.op),
+            }
+        }
+        println!(
+            "Applied {} operation{}",
+            output.results.len(),
+            if output.results.len() == 1 { "" } else { "s" }
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ops_tracks_lines_and_rejects_unknown_fields() {
+        let input = "{\"op\":\"create\",\"title\":\"A\",\"handle\":\"a\"}\n\n\
+                     {\"op\":\"dep\",\"id\":\"$a\",\"depends_on\":\"bd-1\"}\n";
+        let ops = parse_ops(input).unwrap();
+        assert_eq!(ops.len(), 2);
+        assert_eq!(ops[1].0, 3);
+        assert_eq!(ops[1].1.name(), "dep_add");
+
+        let err = parse_ops("{\"op\":\"close\",\"id\":\"x\",\"why\":1}").unwrap_err();
+        assert!(matches!(err, BeadsError::JsonlParse { line: 1, .. }));
+        assert!(parse_ops("{\"op\":\"explode\"}").is_err());
+    }
+
+    #[test]
+    fn parse_dep_type_rejects_typos() {
+        assert_eq!(
+            parse_dep_type("blocked-by").unwrap(),
+            DependencyType::Blocks
+        );
+        assert_eq!(
+            parse_dep_type("parent-child").unwrap(),
+            DependencyType::ParentChild
+        );
+        assert!(parse_dep_type("blcoks").is_err());
+    }
+}
@@ -0,0 +1,207 @@
+//! Claim, heartbeat and leases command implementations.
+//!
+//! `claim` is `update --claim` plus an optional lease, written in one
+//! transaction. Every command here first releases expired leases, so a
+//! lapsed claim is handed back before anyone looks at or competes for it.
+
+use crate::cli::commands::create::resolve_id;
+use crate::cli::commands::update;
+use crate::cli::{ClaimArgs, HeartbeatArgs, LeasesArgs, UpdateArgs};
+use crate::config;
+use crate::config::workflow::Workflow;
+use crate::error::Result;
+use crate::output::OutputContext;
+use crate::storage::lease::{ExpiredLease, Lease};
+use crate::util::id::{IdResolver, ResolverConfig};
+use crate::util::time::{format_duration, parse_duration};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// JSON output for the claim command.
+#[derive(Debug, Serialize)]
+pub struct ClaimOutput {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub assignee: Option<String>,
+    pub lease: Option<Lease>,
+}
+
+/// JSON output for the leases command.
+#[derive(Debug, Serialize)]
+pub struct LeasesOutput {
+    pub leases: Vec<Lease>,
+    pub expired: Vec<ExpiredLease>,
+}
+
+/// Execute the claim command.
+///
+/// # Errors
+///
+/// Returns an error if the ID or TTL is invalid, the issue is blocked or
+/// already claimed, or a storage write fails.
+pub fn execute_claim(
+    args: &ClaimArgs,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let ttl = args
+        .ttl
+        .as_deref()
+        .map(|ttl| parse_duration(ttl, "ttl"))
+        .transpose()?;
+
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let config_layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let actor = config::resolve_actor(&config_layer);
+    let id = resolve_id(&resolver(&config_layer), &storage_ctx.storage, &args.id)?;
+
+    let storage = &mut storage_ctx.storage;
+    let expired = storage.expire_leases(&actor, Utc::now())?;
+    report_expired(&expired, ctx);
+
+    let update_args = UpdateArgs {
+        ids: vec![id.clone()],
+        claim: true,
+        force: args.force,
+        ..UpdateArgs::default()
+    };
+    let claim_exclusive = config::claim_exclusive_from_layer(&config_layer);
+    let claim = update::build_update(&update_args, &actor, claim_exclusive)?;
+    let workflow = Workflow::from_layer(&config_layer)?;
+    update::check_update(storage, &id, &update_args, &claim, &workflow)?;
+    let (issue, lease) = storage.claim_with_lease(&id, &claim, &actor, ttl, Utc::now())?;
+    crate::util::set_last_touched_id(&beads_dir, &id);
+
+    if ctx.is_json() {
+        ctx.json_pretty(&ClaimOutput {
+            id: issue.id,
+            title: issue.title,
+            status: issue.status.as_str().to_string(),
+            assignee: issue.assignee,
+            lease,
+        });
+    } else {
+        print!("\u{2713} Claimed {}: {}", issue.id, issue.title);
+        match &lease {
+            Some(lease) => println!(" (lease expires in {})", format_duration(lease.ttl())),
+            None => println!(),
+        }
+    }
+
+    storage_ctx.flush_no_db_if_dirty()?;
+    Ok(())
+}
+
+/// Execute the heartbeat command.
+///
+/// # Errors
+///
+/// Returns an error if the ID or TTL is invalid, the issue has no live
+/// lease, another actor holds it, or a storage write fails.
+pub fn execute_heartbeat(
+    args: &HeartbeatArgs,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let ttl = args
+        .ttl
+        .as_deref()
+        .map(|ttl| parse_duration(ttl, "ttl"))
+        .transpose()?;
+
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let config_layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let actor = config::resolve_actor(&config_layer);
+    let id = resolve_id(&resolver(&config_layer), &storage_ctx.storage, &args.id)?;
+
+    let storage = &mut storage_ctx.storage;
+    let now = Utc::now();
+    let expired = storage.expire_leases(&actor, now)?;
+    report_expired(&expired, ctx);
+    let lease = storage.renew_lease(&id, &actor, ttl, now)?;
+    crate::util::set_last_touched_id(&beads_dir, &id);
+
+    if ctx.is_json() {
+        ctx.json_pretty(&lease);
+    } else {
+        println!(
+            "\u{2713} Renewed lease on {} (expires in {})",
+            lease.issue_id,
+            format_duration(lease.ttl())
+        );
+    }
+
+    storage_ctx.flush_no_db_if_dirty()?;
+    Ok(())
+}
+
+/// Execute the leases command.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be opened or a write fails.
+pub fn execute_leases(
+    args: &LeasesArgs,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let config_layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let actor = config::resolve_actor(&config_layer);
+
+    let storage = &mut storage_ctx.storage;
+    let now = Utc::now();
+    let expired = storage.expire_leases(&actor, now)?;
+    let leases: Vec<Lease> = storage
+        .list_leases()?
+        .into_iter()
+        .filter(|lease| args.actor.as_ref().is_none_or(|a| *a == lease.actor))
+        .collect();
+
+    if ctx.is_json() {
+        ctx.json_pretty(&LeasesOutput { leases, expired });
+    } else {
+        report_expired(&expired, ctx);
+        if leases.is_empty() {
+            println!("No active leases.");
+        }
+        for lease in &leases {
+            println!(
+                "{}  {}  expires in {} (ttl {})",
+                lease.issue_id,
+                lease.actor,
+                remaining(lease, now),
+                format_duration(lease.ttl())
+            );
+        }
+    }
+
+    storage_ctx.flush_no_db_if_dirty()?;
+    Ok(())
+}
+
+fn resolver(config_layer: &config::ConfigLayer) -> IdResolver {
+    let id_config = config::id_config_from_layer(config_layer);
+    IdResolver::new(ResolverConfig::with_prefix(id_config.prefix))
+}
+
+fn remaining(lease: &Lease, now: DateTime<Utc>) -> String {
+    format_duration(lease.expires_at - now)
+}
+
+/// Tell the user about claims that lapsed; JSON output stays clean.
+fn report_expired(expired: &[ExpiredLease], ctx: &OutputContext) {
+    if ctx.is_json() || ctx.is_quiet() {
+        return;
+    }
+    for item in expired.iter().filter(|item| item.released) {
+        println!(
+            "\u{2298} Lease on {} held by {} expired; issue reopened",
+            item.lease.issue_id, item.lease.actor
+        );
+    }
+}
@@ -2,10 +2,11 @@
 
 use crate::cli::CloseArgs as CliCloseArgs;
 use crate::config;
+use crate::config::workflow::Workflow;
 use crate::error::{BeadsError, Result};
 use crate::model::Status;
 use crate::output::OutputContext;
-use crate::storage::IssueUpdate;
+use crate::storage::{IssueUpdate, SqliteStorage};
 use crate::util::id::{IdResolver, ResolverConfig, find_matching_ids};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
@@ -53,7 +54,7 @@ pub fn execute_cli(
 }
 
 /// Result of a close operation for JSON output.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct CloseResult {
     pub closed: Vec<ClosedIssue>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
@@ -77,7 +78,7 @@ pub struct UnblockedIssue {
     pub priority: i32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ClosedIssue {
     pub id: String,
     pub title: String,
@@ -87,7 +88,7 @@ pub struct ClosedIssue {
     pub close_reason: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct SkippedIssue {
     pub id: String,
     pub reason: String,
@@ -136,6 +137,7 @@ pub fn execute_with_args(
     let actor = config::resolve_actor(&config_layer);
     let id_config = config::id_config_from_layer(&config_layer);
     let resolver = IdResolver::new(ResolverConfig::with_prefix(id_config.prefix));
+    let workflow = Workflow::from_layer(&config_layer)?;
     let all_ids = storage_ctx.storage.get_all_ids()?;
     let storage = &mut storage_ctx.storage;
 
@@ -185,73 +187,14 @@ pub fn execute_with_args(
         let id = &resolved.id;
         tracing::info!(id = %id, "Closing issue");
 
-        // Get current issue
-        let Some(issue) = storage.get_issue(id)? else {
-            skipped_issues.push(SkippedIssue {
-                id: id.clone(),
-                reason: "issue not found".to_string(),
-            });
-            continue;
-        };
-
-        // Check if already closed
-        if issue.status.is_terminal() {
-            skipped_issues.push(SkippedIssue {
-                id: id.clone(),
-                reason: format!("already {}", issue.status.as_str()),
-            });
-            continue;
-        }
-
-        // Check if blocked (unless --force)
-        if !args.force && storage.is_blocked(id)? {
-            let mut blocker_ids = storage
-                .get_blocked_issues()?
-                .into_iter()
-                .find(|(issue, _)| issue.id == *id)
-                .map(|(_, blockers)| blockers)
-                .unwrap_or_default();
-            if blocker_ids.is_empty() {
-                blocker_ids = storage.get_dependencies(id)?;
+        match close_issue_impl(storage, id, args, &workflow, &actor)? {
+            CloseOutcome::Closed(closed) => {
+                // Update last touched
+                crate::util::set_last_touched_id(&beads_dir, id);
+                closed_issues.push(closed);
             }
-            tracing::debug!(blocked_by = ?blocker_ids, "Issue is blocked");
-            let reason = if blocker_ids.is_empty() {
-                "blocked by dependencies".to_string()
-            } else {
-                format!("blocked by: {}", blocker_ids.join(", "))
-            };
-            skipped_issues.push(SkippedIssue {
-                id: id.clone(),
-                reason,
-            });
-            continue;
+            CloseOutcome::Skipped(skipped) => skipped_issues.push(skipped),
         }
-
-        // Build update
-        let now = Utc::now();
-        let close_reason = args.reason.clone().unwrap_or_else(|| "done".to_string());
-        let update = IssueUpdate {
-            status: Some(Status::Closed),
-            closed_at: Some(Some(now)),
-            close_reason: Some(Some(close_reason.clone())),
-            closed_by_session: args.session.clone().map(Some),
-            ..Default::default()
-        };
-
-        // Apply update
-        storage.update_issue(id, &update, &actor)?;
-        tracing::info!(id = %id, reason = ?args.reason, "Issue closed");
-
-        // Update last touched
-        crate::util::set_last_touched_id(&beads_dir, id);
-
-        closed_issues.push(ClosedIssue {
-            id: id.clone(),
-            title: issue.title.clone(),
-            status: "closed".to_string(),
-            closed_at: now.to_rfc3339(),
-            close_reason: Some(close_reason),
-        });
     }
 
     // Handle suggest-next: find issues that became unblocked
@@ -345,6 +288,94 @@ pub fn execute_with_args(
     Ok(())
 }
 
+/// Outcome of closing a single issue.
+#[derive(Debug)]
+pub enum CloseOutcome {
+    Closed(ClosedIssue),
+    Skipped(SkippedIssue),
+}
+
+/// Core logic for closing one resolved issue.
+///
+/// Issues that are missing, already terminal, or blocked (without
+/// `force`) are skipped rather than failing the whole command. Prints
+/// nothing.
+///
+/// # Errors
+///
+/// Returns an error if the workflow forbids closing or a storage operation fails.
+pub fn close_issue_impl(
+    storage: &mut SqliteStorage,
+    id: &str,
+    args: &CloseArgs,
+    workflow: &Workflow,
+    actor: &str,
+) -> Result<CloseOutcome> {
+    // Get current issue
+    let Some(issue) = storage.get_issue(id)? else {
+        return Ok(CloseOutcome::Skipped(SkippedIssue {
+            id: id.to_string(),
+            reason: "issue not found".to_string(),
+        }));
+    };
+
+    // Check if already closed
+    if issue.status.is_terminal() {
+        return Ok(CloseOutcome::Skipped(SkippedIssue {
+            id: id.to_string(),
+            reason: format!("already {}", issue.status.as_str()),
+        }));
+    }
+
+    workflow.check_transition(&issue, &Status::Closed)?;
+
+    // Check if blocked (unless --force)
+    if !args.force && storage.is_blocked(id)? {
+        let mut blocker_ids = storage
+            .get_blocked_issues()?
+            .into_iter()
+            .find(|(issue, _)| issue.id == id)
+            .map(|(_, blockers)| blockers)
+            .unwrap_or_default();
+        if blocker_ids.is_empty() {
+            blocker_ids = storage.get_dependencies(id)?;
+        }
+        tracing::debug!(blocked_by = ?blocker_ids, "Issue is blocked");
+        let reason = if blocker_ids.is_empty() {
+            "blocked by dependencies".to_string()
+        } else {
+            format!("blocked by: {}", blocker_ids.join(", "))
+        };
+        return Ok(CloseOutcome::Skipped(SkippedIssue {
+            id: id.to_string(),
+            reason,
+        }));
+    }
+
+    // Build update
+    let now = Utc::now();
+    let close_reason = args.reason.clone().unwrap_or_else(|| "done".to_string());
+    let update = IssueUpdate {
+        status: Some(Status::Closed),
+        closed_at: Some(Some(now)),
+        close_reason: Some(Some(close_reason.clone())),
+        closed_by_session: args.session.clone().map(Some),
+        ..Default::default()
+    };
+
+    // Apply update
+    storage.update_issue(id, &update, actor)?;
+    tracing::info!(id = %id, reason = ?args.reason, "Issue closed");
+
+    Ok(CloseOutcome::Closed(ClosedIssue {
+        id: id.to_string(),
+        title: issue.title,
+        status: "closed".to_string(),
+        closed_at: now.to_rfc3339(),
+        close_reason: Some(close_reason),
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
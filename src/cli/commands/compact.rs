@@ -0,0 +1,453 @@
+//! Compact command implementation.
+//!
+//! Shrinks issues closed long ago so the export stays small:
+//! - Level 1 replaces description, design and notes with a summary: the
+//!   lead sentence of each paragraph, in order, up to [`SUMMARY_CHARS`], or
+//!   the output of an external summarizer command
+//! - Level 2 keeps only the title and the close reason
+//!
+//! The original issue is appended to `.beads/history/<id>.jsonl` before it
+//! is rewritten, and `undo` restores it as well.
+
+use crate::cli::CompactArgs;
+use crate::config;
+use crate::error::{BeadsError, Result};
+use crate::model::{Issue, Status};
+use crate::output::OutputContext;
+use crate::storage::ListFilters;
+use crate::util::time::parse_past_timestamp;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tracing::{debug, info};
+
+/// Directory under `.beads/` holding the originals of compacted issues.
+pub const HISTORY_DIR: &str = "history";
+
+/// Longest built-in summary of one field, in characters.
+const SUMMARY_CHARS: usize = 240;
+
+/// One line of `.beads/history/<id>.jsonl`.
+#[derive(Debug, Serialize)]
+struct HistoryRecord<'a> {
+    compacted_at: DateTime<Utc>,
+    level: i32,
+    original: &'a Issue,
+}
+
+/// JSON output for `compact`.
+#[derive(Debug, Serialize)]
+struct CompactOutput {
+    dry_run: bool,
+    level: i32,
+    closed_before: DateTime<Utc>,
+    compacted: Vec<CompactedIssue>,
+}
+
+#[derive(Debug, Serialize)]
+struct CompactedIssue {
+    id: String,
+    title: String,
+    /// Bytes of description, design, acceptance criteria and notes before.
+    size_before: usize,
+    /// The same, after.
+    size_after: usize,
+}
+
+/// Execute the compact command.
+///
+/// # Errors
+///
+/// Returns an error if the arguments are invalid, the summarizer fails, or
+/// the history file or database cannot be written.
+pub fn execute(args: &CompactArgs, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    if !(1..=2).contains(&args.level) {
+        return Err(BeadsError::validation("level", "must be 1 or 2"));
+    }
+    let level = i32::from(args.level);
+    let closed_before = parse_past_timestamp(&args.closed_before, "closed-before")?;
+
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let actor = config::resolve_actor(&layer);
+    let summarizer = args
+        .summarizer
+        .clone()
+        .or_else(|| config::compact_summarizer_from_layer(&layer));
+
+    let filters = ListFilters {
+        statuses: Some(vec![Status::Closed]),
+        include_closed: true,
+        include_deferred: true,
+        ..ListFilters::default()
+    };
+    let candidates: Vec<Issue> = storage_ctx
+        .storage
+        .list_issues(&filters)?
+        .into_iter()
+        .filter(|issue| {
+            !issue.pinned
+                && issue.closed_at.is_some_and(|at| at < closed_before)
+                && issue.compaction_level.unwrap_or(0) < level
+        })
+        .collect();
+    debug!(count = candidates.len(), %closed_before, level, "Compaction candidates");
+
+    let now = Utc::now();
+    let commit = if args.dry_run {
+        None
+    } else {
+        head_commit(&beads_dir)
+    };
+    let mut compacted = Vec::new();
+    for original in candidates {
+        let Some(mut issue) = compact(&original, level, summarizer.as_deref())? else {
+            continue;
+        };
+        issue.compaction_level = Some(level);
+        issue.compacted_at = Some(now);
+        issue.compacted_at_commit.clone_from(&commit);
+        // Keep the size of the pristine text when compacting further.
+        issue.original_size = original
+            .original_size
+            .or_else(|| Some(i32::try_from(text_size(&original)).unwrap_or(i32::MAX)));
+
+        if !args.dry_run {
+            append_history(&beads_dir, &original, level, now)?;
+            storage_ctx.storage.compact_issue(&issue, &actor)?;
+        }
+        compacted.push(CompactedIssue {
+            size_before: text_size(&original),
+            size_after: text_size(&issue),
+            id: issue.id,
+            title: issue.title,
+        });
+    }
+    if !args.dry_run {
+        info!(count = compacted.len(), level, "Issues compacted");
+        storage_ctx.flush_no_db_if_dirty()?;
+    }
+
+    let output = CompactOutput {
+        dry_run: args.dry_run,
+        level,
+        closed_before,
+        compacted,
+    };
+    if ctx.is_json() {
+        ctx.json_pretty(&output);
+        return Ok(());
+    }
+    if output.compacted.is_empty() {
+        println!("Nothing to compact");
+        return Ok(());
+    }
+    let verb = if args.dry_run {
+        "Would compact"
+    } else {
+        "Compacted"
+    };
+    let saved: usize = output
+        .compacted
+        .iter()
+        .map(|item| item.size_before - item.size_after)
+        .sum();
+    ctx.success(&format!(
+        "{verb} {} issue(s) to level {level}, saving {saved} bytes",
+        output.compacted.len()
+    ));
+    for item in &output.compacted {
+        println!(
+            "  {} {} ({} -> {} bytes)",
+            item.id, item.title, item.size_before, item.size_after
+        );
+    }
+    Ok(())
+}
+
+/// The compacted form of `original`, or `None` if it would not shrink.
+fn compact(original: &Issue, level: i32, summarizer: Option<&str>) -> Result<Option<Issue>> {
+    let mut issue = original.clone();
+    if level >= 2 {
+        issue.description = None;
+        issue.design = None;
+        issue.acceptance_criteria = None;
+        issue.notes = None;
+    } else {
+        let id = original.id.as_str();
+        issue.description = summarize(
+            id,
+            "description",
+            original.description.as_deref(),
+            summarizer,
+        )?;
+        issue.design = summarize(id, "design", original.design.as_deref(), summarizer)?;
+        issue.notes = summarize(id, "notes", original.notes.as_deref(), summarizer)?;
+    }
+    Ok((text_size(&issue) < text_size(original)).then_some(issue))
+}
+
+/// Summarize one field, keeping it when the summary is no shorter.
+fn summarize(
+    id: &str,
+    field: &str,
+    text: Option<&str>,
+    summarizer: Option<&str>,
+) -> Result<Option<String>> {
+    let Some(text) = text.filter(|text| !text.trim().is_empty()) else {
+        return Ok(None);
+    };
+    let summary = match summarizer {
+        Some(command) => run_summarizer(command, id, field, text)?,
+        None => extractive_summary(text),
+    };
+    if summary.chars().count() >= text.chars().count() {
+        return Ok(Some(text.to_string()));
+    }
+    Ok(Some(summary).filter(|summary| !summary.is_empty()))
+}
+
+/// Run the external summarizer through `sh -c` with the field text on stdin.
+///
+/// The issue ID and field name are passed as `BR_ISSUE_ID` and `BR_FIELD`.
+fn run_summarizer(command: &str, id: &str, field: &str, text: &str) -> Result<String> {
+    let failed =
+        |reason: String| BeadsError::Config(format!("summarizer failed on {id} {field}: {reason}"));
+    let mut child = Command::new("sh")
+        .args(["-c", command])
+        .env("BR_ISSUE_ID", id)
+        .env("BR_FIELD", field)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| failed(e.to_string()))?;
+
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| failed("no stdin".to_string()))?;
+    let input = text.to_string();
+    // Write from another thread so a summarizer that streams output cannot
+    // deadlock against a full pipe.
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child
+        .wait_with_output()
+        .map_err(|e| failed(e.to_string()))?;
+    // A summarizer that stops reading early is fine; its output decides.
+    let _ = writer.join();
+
+    if !output.status.success() {
+        return Err(failed(output.status.to_string()));
+    }
+    let summary = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if summary.is_empty() {
+        return Err(failed("no output".to_string()));
+    }
+    Ok(summary)
+}
+
+/// Deterministic extractive summary: the lead sentence of each paragraph
+/// and list item, in order, while they fit in [`SUMMARY_CHARS`].
+///
+/// Headings and fenced code are skipped. A lead sentence that does not fit
+/// on its own is cut at a word boundary.
+#[must_use]
+pub fn extractive_summary(text: &str) -> String {
+    let mut summary = String::new();
+    for sentence in lead_sentences(text) {
+        let extra = usize::from(!summary.is_empty()) + sentence.chars().count();
+        if summary.chars().count() + extra > SUMMARY_CHARS {
+            if summary.is_empty() {
+                summary = truncate_words(&sentence, SUMMARY_CHARS);
+            }
+            break;
+        }
+        if !summary.is_empty() {
+            summary.push(' ');
+        }
+        summary.push_str(&sentence);
+    }
+    summary
+}
+
+fn lead_sentences(text: &str) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+    let mut current = String::new();
+    let mut in_fence = false;
+    let mut flush = |current: &mut String| {
+        if !current.is_empty() {
+            paragraphs.push(std::mem::take(current));
+        }
+    };
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with("```") || line.starts_with("~~~") {
+            in_fence = !in_fence;
+            flush(&mut current);
+            continue;
+        }
+        if in_fence || line.starts_with('#') {
+            flush(&mut current);
+            continue;
+        }
+        let item = line
+            .strip_prefix("- ")
+            .or_else(|| line.strip_prefix("* "))
+            .or_else(|| line.strip_prefix("+ "));
+        if line.is_empty() || item.is_some() {
+            flush(&mut current);
+        }
+        let content = item.unwrap_or(line).trim_start_matches('>').trim();
+        if content.is_empty() {
+            continue;
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(content);
+    }
+    flush(&mut current);
+
+    paragraphs
+        .iter()
+        .map(|paragraph| {
+            let mut lead = first_sentence(paragraph).to_string();
+            if !lead.ends_with(['.', '!', '?', ':', ';']) {
+                lead.push('.');
+            }
+            lead
+        })
+        .collect()
+}
+
+/// Text up to and including the first `.`, `!` or `?` followed by a space.
+fn first_sentence(paragraph: &str) -> &str {
+    let mut chars = paragraph.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        if matches!(c, '.' | '!' | '?')
+            && chars.peek().is_some_and(|(_, next)| next.is_whitespace())
+        {
+            return &paragraph[..index + c.len_utf8()];
+        }
+    }
+    paragraph
+}
+
+/// Cut `text` to at most `max` characters at a word boundary, marking the cut.
+fn truncate_words(text: &str, max: usize) -> String {
+    let mut cut = String::new();
+    for word in text.split_whitespace() {
+        let extra = usize::from(!cut.is_empty()) + word.chars().count();
+        if cut.chars().count() + extra + 1 > max {
+            break;
+        }
+        if !cut.is_empty() {
+            cut.push(' ');
+        }
+        cut.push_str(word);
+    }
+    cut.push('…');
+    cut
+}
+
+/// Bytes of text that compaction may shrink.
+fn text_size(issue: &Issue) -> usize {
+    [
+        &issue.description,
+        &issue.design,
+        &issue.acceptance_criteria,
+        &issue.notes,
+    ]
+    .iter()
+    .map(|field| field.as_deref().map_or(0, str::len))
+    .sum()
+}
+
+fn append_history(
+    beads_dir: &Path,
+    original: &Issue,
+    level: i32,
+    compacted_at: DateTime<Utc>,
+) -> Result<()> {
+    let dir = beads_dir.join(HISTORY_DIR);
+    fs::create_dir_all(&dir)?;
+    let line = serde_json::to_string(&HistoryRecord {
+        compacted_at,
+        level,
+        original,
+    })?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(format!("{}.jsonl", original.id)))?;
+    writeln!(file, "{line}")?;
+    file.sync_all()?;
+    Ok(())
+}
+
+/// Current commit of the repository holding `.beads/`, if any.
+fn head_commit(beads_dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(beads_dir)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !commit.is_empty()).then_some(commit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_keeps_lead_sentences_in_order() {
+        let text = "## Background\n\nLogin fails on Safari. The cookie is dropped because \
+                    SameSite is unset.\n\n```\nSet-Cookie: sid=1\n```\n\n- add SameSite=Lax\n\
+                    - cover it with a test\n";
+        assert_eq!(
+            extractive_summary(text),
+            "Login fails on Safari. add SameSite=Lax. cover it with a test."
+        );
+        assert_eq!(extractive_summary(text), extractive_summary(text));
+    }
+
+    #[test]
+    fn summary_cuts_long_sentences_at_words() {
+        let text = "word ".repeat(100);
+        let summary = extractive_summary(&text);
+        assert!(summary.chars().count() <= SUMMARY_CHARS);
+        assert!(summary.ends_with("word…"), "{summary}");
+    }
+
+    #[test]
+    fn compact_levels() {
+        let mut issue = Issue {
+            id: "bd-1".to_string(),
+            title: "Fix login".to_string(),
+            description: Some(format!("Short lead. {}", "More detail. ".repeat(40))),
+            design: Some("Tiny.".to_string()),
+            notes: Some("A note.".to_string()),
+            acceptance_criteria: Some("Works on Safari.".to_string()),
+            close_reason: Some("Fixed in 1.2".to_string()),
+            ..Issue::default()
+        };
+        let level1 = compact(&issue, 1, None).unwrap().expect("shrinks");
+        assert_eq!(level1.description.as_deref(), Some("Short lead."));
+        assert_eq!(level1.design.as_deref(), Some("Tiny."));
+        assert_eq!(level1.acceptance_criteria, issue.acceptance_criteria);
+
+        let level2 = compact(&level1, 2, None).unwrap().expect("shrinks");
+        assert!(level2.description.is_none() && level2.acceptance_criteria.is_none());
+        assert_eq!(level2.close_reason.as_deref(), Some("Fixed in 1.2"));
+
+        issue.description = Some("Already short.".to_string());
+        assert!(compact(&issue, 1, None).unwrap().is_none());
+    }
+}
@@ -3,7 +3,7 @@ use crate::config;
 use crate::error::Result;
 use crate::model::{IssueType, Priority, Status};
 use crate::output::{OutputContext, OutputMode};
-use crate::storage::{ListFilters, SqliteStorage};
+use crate::storage::{FilterExpr, ListFilters, SqliteStorage};
 use rich_rust::prelude::*;
 use serde::Serialize;
 use std::collections::BTreeMap;
@@ -63,6 +63,13 @@ pub fn execute(
     filters.include_closed = filters.include_closed || args.include_closed;
     filters.include_templates = args.include_templates;
     filters.title_contains.clone_from(&args.title_contains);
+    if let Some(expr) = args.where_.as_deref() {
+        let expr = FilterExpr::parse(expr)?;
+        if expr.references_status() {
+            filters.include_closed = true;
+        }
+        filters.filter_expr = Some(expr);
+    }
 
     let issues = storage.list_issues(&filters)?;
     let total = issues.len();
@@ -304,6 +311,7 @@ mod tests {
             dependencies: vec![],
             comments: vec![],
             content_hash: None,
+            org_extras: None,
         }
     }
 
@@ -4,11 +4,12 @@ use crate::error::{BeadsError, Result};
 use crate::model::{Dependency, DependencyType, Issue, IssueType, Priority, Status};
 use crate::output::OutputContext;
 use crate::storage::SqliteStorage;
-use crate::util::id::{IdGenerator, child_id};
+use crate::util::id::{IdGenerator, IdResolver, child_id, find_matching_ids};
 use crate::util::markdown_import::{parse_dependency, parse_markdown_file};
 use crate::util::time::parse_flexible_timestamp;
 use crate::validation::{IssueValidator, LabelValidator};
 use chrono::{DateTime, Utc};
+use std::collections::HashSet;
 use std::path::Path;
 use std::str::FromStr;
 
@@ -20,6 +21,36 @@ pub struct CreateConfig {
     pub actor: String,
 }
 
+/// Build the create settings from the merged config.
+///
+/// # Errors
+///
+/// Returns an error if the default priority or issue type is invalid.
+pub fn create_config(layer: &config::ConfigLayer) -> Result<CreateConfig> {
+    Ok(CreateConfig {
+        id_config: config::id_config_from_layer(layer),
+        default_priority: config::default_priority_from_layer(layer)?,
+        default_issue_type: config::default_issue_type_from_layer(layer)?,
+        actor: config::resolve_actor(layer),
+    })
+}
+
+/// Resolve a full or partial issue ID against the database.
+///
+/// # Errors
+///
+/// Returns an error if no issue matches or the input is ambiguous.
+pub fn resolve_id(resolver: &IdResolver, storage: &SqliteStorage, input: &str) -> Result<String> {
+    let all_ids = storage.get_all_ids()?;
+    resolver
+        .resolve(
+            input,
+            |id| storage.id_exists(id).unwrap_or(false),
+            |hash| find_matching_ids(&all_ids, hash),
+        )
+        .map(|resolved| resolved.id)
+}
+
 /// Execute the create command.
 ///
 /// # Errors
@@ -50,12 +81,7 @@ pub fn execute(args: &CreateArgs, cli: &config::CliOverrides, ctx: &OutputContex
     let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
     let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
 
-    let config = CreateConfig {
-        id_config: config::id_config_from_layer(&layer),
-        default_priority: config::default_priority_from_layer(&layer)?,
-        default_issue_type: config::default_issue_type_from_layer(&layer)?,
-        actor: config::resolve_actor(&layer),
-    };
+    let config = create_config(&layer)?;
 
     let issue = create_issue_impl(&mut storage_ctx.storage, args, &config)?;
 
@@ -108,12 +134,42 @@ pub fn execute(args: &CreateArgs, cli: &config::CliOverrides, ctx: &OutputContex
 /// - ID generation fails
 /// - Validation fails
 /// - Storage write fails
-#[allow(clippy::too_many_lines)]
 pub fn create_issue_impl(
     storage: &mut SqliteStorage,
     args: &CreateArgs,
     config: &CreateConfig,
 ) -> Result<Issue> {
+    let issue = build_issue(storage, args, config, &HashSet::new())?;
+
+    // Dry Run check - return early
+    if args.dry_run {
+        return Ok(issue);
+    }
+
+    // Create (atomic)
+    storage.create_issue(&issue, &config.actor)?;
+
+    Ok(issue)
+}
+
+/// Build and validate the issue `args` describe, without writing it.
+///
+/// `reserved` holds IDs that are taken but not stored yet (earlier creates
+/// in a `batch`); they count as existing for ID generation and parents.
+///
+/// # Errors
+///
+/// Returns an error if the title is empty, the parent does not exist, or a
+/// field or relation is invalid.
+#[allow(clippy::too_many_lines)]
+pub fn build_issue(
+    storage: &SqliteStorage,
+    args: &CreateArgs,
+    config: &CreateConfig,
+    reserved: &HashSet<String>,
+) -> Result<Issue> {
+    let exists = |id: &str| reserved.contains(id) || storage.id_exists(id).unwrap_or(false);
+
     // 1. Resolve title
     let title = args
         .title
@@ -132,7 +188,7 @@ pub fn create_issue_impl(
     // instead of a random hash-based ID
     let id = if let Some(parent_id) = &args.parent {
         // Verify parent exists
-        if !storage.id_exists(parent_id).unwrap_or(false) {
+        if !exists(parent_id) {
             return Err(BeadsError::IssueNotFound {
                 id: parent_id.clone(),
             });
@@ -143,12 +199,12 @@ pub fn create_issue_impl(
         let candidate = child_id(parent_id, next_num);
 
         // Double-check the ID doesn't exist (race condition safety)
-        if storage.id_exists(&candidate).unwrap_or(false) {
+        if exists(&candidate) {
             // Extremely unlikely, but handle by incrementing
             let mut num = next_num + 1;
             loop {
                 let alt = child_id(parent_id, num);
-                if !storage.id_exists(&alt).unwrap_or(false) {
+                if !exists(&alt) {
                     break alt;
                 }
                 num += 1;
@@ -165,14 +221,11 @@ pub fn create_issue_impl(
     } else {
         // Standard ID generation for non-child issues
         let id_gen = IdGenerator::new(config.id_config.clone());
-        let count = storage.count_issues()?;
+        let count = storage.count_issues()? + reserved.len();
         id_gen.generate(
-            title,
-            None, // description
+            title, None, // description
             None, // creator
-            now,
-            count,
-            |id| storage.id_exists(id).unwrap_or(false),
+            now, count, exists,
         )
     };
 
@@ -248,6 +301,7 @@ pub fn create_issue_impl(
         labels: vec![],
         dependencies: vec![],
         comments: vec![],
+        org_extras: None,
     };
 
     // Compute content hash
@@ -262,14 +316,6 @@ pub fn create_issue_impl(
     // 6. Populate Relations (labels & dependencies)
     populate_relations(&mut issue, args, &config.actor, now);
 
-    // 7. Dry Run check - return early
-    if args.dry_run {
-        return Ok(issue);
-    }
-
-    // 8. Create (atomic)
-    storage.create_issue(&issue, &config.actor)?;
-
     Ok(issue)
 }
 
@@ -514,6 +560,7 @@ fn execute_import(
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         };
 
         issue.content_hash = Some(issue.compute_content_hash());
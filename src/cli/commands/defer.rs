@@ -481,6 +481,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         }
     }
 
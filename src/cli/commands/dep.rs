@@ -74,14 +74,14 @@ pub fn execute(
 }
 
 /// JSON output for dep add/remove operations
-#[derive(Serialize)]
-struct DepActionResult {
-    status: String,
-    issue_id: String,
-    depends_on_id: String,
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct DepActionResult {
+    pub status: String,
+    pub issue_id: String,
+    pub depends_on_id: String,
     #[serde(rename = "type")]
-    dep_type: String,
-    action: String,
+    pub dep_type: String,
+    pub action: String,
 }
 
 /// JSON output for dep list
@@ -133,45 +133,8 @@ fn dep_add(
         resolve_issue_id(storage, resolver, all_ids, &args.depends_on)?
     };
 
-    // Parse and validate dependency type
-    let dep_type_str = &args.dep_type;
-    let dep_type: DependencyType = dep_type_str.parse().map_err(|_| BeadsError::Validation {
-        field: "type".to_string(),
-        reason: format!("Invalid dependency type: {dep_type_str}"),
-    })?;
-
-    // Disallow accidental custom types from typos
-    if let DependencyType::Custom(_) = dep_type {
-        // We enforce standard types for reliability unless it looks like a deliberate custom type
-        // For now, let's strictly enforce known types to prevent typos like "parent_child"
-        // which would otherwise be accepted as a non-blocking custom type.
-        return Err(BeadsError::Validation {
-            field: "type".to_string(),
-            reason: format!(
-                "Unknown dependency type: '{dep_type_str}'. \
-                 Allowed types: blocks, parent-child, conditional-blocks, waits-for, \
-                 related, discovered-from, replies-to, relates-to, duplicates, \
-                 supersedes, caused-by"
-            ),
-        });
-    }
-
-    // Self-dependency check
-    if issue_id == depends_on_id {
-        return Err(BeadsError::SelfDependency { id: issue_id });
-    }
-
-    // Cycle check for blocking types only
-    if dep_type.is_blocking()
-        && !depends_on_id.starts_with("external:")
-        && storage.would_create_cycle(&issue_id, &depends_on_id, true)?
-    {
-        return Err(BeadsError::DependencyCycle {
-            path: format!("{issue_id} -> {depends_on_id}"),
-        });
-    }
-
-    let added = storage.add_dependency(&issue_id, &depends_on_id, dep_type.as_str(), actor)?;
+    let (dep_type, added) =
+        add_dependency_impl(storage, &issue_id, &depends_on_id, &args.dep_type, actor)?;
 
     if ctx.is_json() || ctx.is_toon() {
         let result = DepActionResult {
@@ -221,6 +184,65 @@ fn dep_add(
     Ok(())
 }
 
+/// Core logic for adding a dependency between two resolved ids.
+///
+/// Validates the type (custom types are rejected to catch typos), refuses
+/// self-dependencies and blocking cycles, then adds the link. Returns the
+/// parsed type and whether a new link was created.
+///
+/// # Errors
+///
+/// Returns an error if validation fails or the storage write fails.
+pub fn add_dependency_impl(
+    storage: &mut SqliteStorage,
+    issue_id: &str,
+    depends_on_id: &str,
+    dep_type_str: &str,
+    actor: &str,
+) -> Result<(DependencyType, bool)> {
+    // Parse and validate dependency type
+    let dep_type: DependencyType = dep_type_str.parse().map_err(|_| BeadsError::Validation {
+        field: "type".to_string(),
+        reason: format!("Invalid dependency type: {dep_type_str}"),
+    })?;
+
+    // Disallow accidental custom types from typos
+    if let DependencyType::Custom(_) = dep_type {
+        // We enforce standard types for reliability unless it looks like a deliberate custom type
+        // For now, let's strictly enforce known types to prevent typos like "parent_child"
+        // which would otherwise be accepted as a non-blocking custom type.
+        return Err(BeadsError::Validation {
+            field: "type".to_string(),
+            reason: format!(
+                "Unknown dependency type: '{dep_type_str}'. \
+                 Allowed types: blocks, parent-child, conditional-blocks, waits-for, \
+                 related, discovered-from, replies-to, relates-to, duplicates, \
+                 supersedes, caused-by"
+            ),
+        });
+    }
+
+    // Self-dependency check
+    if issue_id == depends_on_id {
+        return Err(BeadsError::SelfDependency {
+            id: issue_id.to_string(),
+        });
+    }
+
+    // Cycle check for blocking types only
+    if dep_type.is_blocking()
+        && !depends_on_id.starts_with("external:")
+        && storage.would_create_cycle(issue_id, depends_on_id, true)?
+    {
+        return Err(BeadsError::DependencyCycle {
+            path: format!("{issue_id} -> {depends_on_id}"),
+        });
+    }
+
+    let added = storage.add_dependency(issue_id, depends_on_id, dep_type.as_str(), actor)?;
+    Ok((dep_type, added))
+}
+
 fn dep_remove(
     args: &DepRemoveArgs,
     storage: &mut SqliteStorage,
@@ -912,6 +934,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         }
     }
 
@@ -349,6 +349,10 @@ fn discover_jsonl(beads_dir: &Path) -> Option<PathBuf> {
 }
 
 fn check_export_file(path: &Path, checks: &mut Vec<CheckResult>) -> Result<usize> {
+    if path.is_dir() {
+        return Ok(check_markdown_export(path, checks));
+    }
+
     // Check if this is an Org file
     if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("org")) {
         // Org-specific validation
@@ -441,6 +445,40 @@ fn check_export_file(path: &Path, checks: &mut Vec<CheckResult>) -> Result<usize
     }
 }
 
+fn check_markdown_export(path: &Path, checks: &mut Vec<CheckResult>) -> usize {
+    match crate::sync::markdown_bridge::read_issues(path) {
+        Ok(issues) => {
+            let total = issues.len();
+            push_check(
+                checks,
+                "jsonl.parse",
+                CheckStatus::Ok,
+                Some(format!("Parsed {total} issues from markdown files")),
+                Some(serde_json::json!({
+                    "path": path.display().to_string(),
+                    "records": total,
+                    "format": "markdown"
+                })),
+            );
+            total
+        }
+        Err(err) => {
+            push_check(
+                checks,
+                "jsonl.parse",
+                CheckStatus::Error,
+                Some(format!("Failed to parse markdown export: {err}")),
+                Some(serde_json::json!({
+                    "path": path.display().to_string(),
+                    "format": "markdown",
+                    "error": err.to_string()
+                })),
+            );
+            0
+        }
+    }
+}
+
 fn check_db_count(
     conn: &Connection,
     jsonl_count: Option<usize>,
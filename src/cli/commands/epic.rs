@@ -174,7 +174,7 @@ fn load_epic_statuses(storage: &SqliteStorage) -> Result<Vec<EpicStatus>> {
         let total_children = parent_children.len();
         let closed_children = parent_children
             .iter()
-            .filter(|c| matches!(c.status, Status::Closed | Status::Tombstone))
+            .filter(|c| c.status.is_terminal())
             .count();
         let eligible_for_close = total_children > 0 && closed_children == total_children;
 
@@ -478,6 +478,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         }
     }
 
@@ -0,0 +1,172 @@
+//! Gc command implementation.
+//!
+//! Purges tombstones older than the retention window from the database
+//! together with their labels, comments, events and any dependency that
+//! still points at them. Every purge is recorded in the manifest
+//! (see [`crate::sync::purge`]) before the rows go, so clones that import
+//! later neither resurrect the issues nor keep their own copies.
+
+use crate::cli::GcArgs;
+use crate::config;
+use crate::error::{BeadsError, Result};
+use crate::model::{Issue, Status};
+use crate::output::OutputContext;
+use crate::storage::ListFilters;
+use crate::sync::purge::{self, PurgeRecord};
+use crate::util::time::parse_duration;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use tracing::{debug, info};
+
+/// JSON output for `gc`.
+#[derive(Debug, Serialize)]
+struct GcOutput {
+    dry_run: bool,
+    /// Tombstones deleted before this instant are expired; absent when
+    /// retention keeps them forever.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cutoff: Option<DateTime<Utc>>,
+    purged: Vec<PurgedTombstone>,
+}
+
+#[derive(Debug, Serialize)]
+struct PurgedTombstone {
+    id: String,
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+/// Execute the gc command.
+///
+/// # Errors
+///
+/// Returns an error if no retention is configured and `--older-than` is not
+/// given, or if the manifest, database or export cannot be written.
+pub fn execute(args: &GcArgs, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let actor = config::resolve_actor(&layer);
+
+    let now = Utc::now();
+    let retention = retention(
+        args.older_than.as_deref(),
+        storage_ctx.paths.metadata.deletions_retention_days,
+    )?;
+    let cutoff = retention.and_then(|keep| now.checked_sub_signed(keep));
+
+    let filters = ListFilters {
+        statuses: Some(vec![Status::Tombstone]),
+        include_closed: true,
+        include_deferred: true,
+        include_templates: true,
+        ..ListFilters::default()
+    };
+    let expired: Vec<Issue> = storage_ctx
+        .storage
+        .list_issues(&filters)?
+        .into_iter()
+        .filter(|issue| cutoff.is_some_and(|cutoff| is_expired(issue, cutoff)))
+        .collect();
+    debug!(count = expired.len(), ?cutoff, "Expired tombstones found");
+
+    if !args.dry_run && !expired.is_empty() {
+        let records: Vec<PurgeRecord> = expired
+            .iter()
+            .map(|issue| PurgeRecord {
+                id: issue.id.clone(),
+                deleted_at: issue.deleted_at,
+                purged_at: now,
+                purged_by: actor.clone(),
+            })
+            .collect();
+        // Record first: a purge that is not in the manifest could come back.
+        purge::append_purge_records(&storage_ctx.paths.beads_dir, &records)?;
+        let ids: Vec<String> = records.into_iter().map(|record| record.id).collect();
+        let purged = storage_ctx.storage.purge_issues(&ids)?;
+        info!(purged, "Expired tombstones purged");
+
+        if storage_ctx.no_db || !cli.no_auto_flush.unwrap_or(false) {
+            crate::sync::flush(&mut storage_ctx.storage, &storage_ctx.paths.beads_dir)?;
+        }
+    }
+
+    let output = GcOutput {
+        dry_run: args.dry_run,
+        cutoff,
+        purged: expired
+            .into_iter()
+            .map(|issue| PurgedTombstone {
+                id: issue.id,
+                title: issue.title,
+                deleted_at: issue.deleted_at,
+            })
+            .collect(),
+    };
+
+    if ctx.is_json() {
+        ctx.json_pretty(&output);
+        return Ok(());
+    }
+    if output.purged.is_empty() {
+        println!("No expired tombstones");
+        return Ok(());
+    }
+    let verb = if args.dry_run {
+        "Would purge"
+    } else {
+        "Purged"
+    };
+    ctx.success(&format!(
+        "{verb} {} expired tombstone(s)",
+        output.purged.len()
+    ));
+    for tombstone in &output.purged {
+        let deleted = tombstone.deleted_at.map_or_else(String::new, |at| {
+            format!(" (deleted {})", at.format("%Y-%m-%d"))
+        });
+        println!("  {} {}{deleted}", tombstone.id, tombstone.title);
+    }
+    Ok(())
+}
+
+/// How long tombstones are kept, or `None` to keep them forever.
+///
+/// `--older-than` wins over `deletions_retention_days`; a retention of 0
+/// days keeps tombstones forever, matching [`Issue::is_expired_tombstone`].
+fn retention(older_than: Option<&str>, retention_days: Option<u64>) -> Result<Option<Duration>> {
+    if let Some(older_than) = older_than {
+        return parse_duration(older_than, "older-than").map(Some);
+    }
+    match retention_days {
+        None => Err(BeadsError::validation(
+            "older-than",
+            "no retention configured: set deletions_retention_days in \
+             .beads/metadata.json or pass --older-than",
+        )),
+        Some(0) => Ok(None),
+        Some(days) => Ok(i64::try_from(days).ok().and_then(Duration::try_days)),
+    }
+}
+
+fn is_expired(issue: &Issue, cutoff: DateTime<Utc>) -> bool {
+    issue.status == Status::Tombstone && issue.deleted_at.is_some_and(|at| at < cutoff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retention_prefers_older_than_and_keeps_zero_forever() {
+        assert_eq!(
+            retention(Some("2w"), Some(30)).unwrap(),
+            Some(Duration::weeks(2))
+        );
+        assert_eq!(retention(None, Some(30)).unwrap(), Some(Duration::days(30)));
+        assert_eq!(retention(None, Some(0)).unwrap(), None);
+        assert!(retention(None, None).is_err());
+        assert!(retention(Some("soon"), Some(30)).is_err());
+    }
+}
@@ -4,10 +4,12 @@
 //!
 //! - `br graph <issue-id>`: Show all dependents of an issue (what depends on it)
 //! - `br graph --all`: Show connected components for `open`/`in_progress`/`blocked` issues
+//! - `--format dot|graphml|d2|mermaid`: Export either graph for other tools
 
 use crate::cli::GraphArgs;
 use crate::config;
 use crate::error::{BeadsError, Result};
+use crate::format::graph::{ExportEdge, ExportNode, GraphExport, GraphFormat};
 use crate::model::{DependencyType, Issue, Status};
 use crate::output::{OutputContext, OutputMode};
 use crate::storage::{ListFilters, SqliteStorage};
@@ -66,6 +68,32 @@ pub fn execute(args: &GraphArgs, cli: &config::CliOverrides, ctx: &OutputContext
     let resolver = IdResolver::new(ResolverConfig::with_prefix(id_config.prefix));
     let all_ids = storage_ctx.storage.get_all_ids()?;
 
+    let export_format = if ctx.is_json() || args.format.eq_ignore_ascii_case("text") {
+        None
+    } else {
+        Some(args.format.parse::<GraphFormat>()?)
+    };
+
+    if let Some(format) = export_format {
+        let root = if args.all {
+            None
+        } else {
+            let issue_id = args.issue.as_ref().ok_or_else(|| {
+                BeadsError::validation("issue", "Issue ID required unless --all is specified")
+            })?;
+            Some(resolve_issue_id(
+                &storage_ctx.storage,
+                &resolver,
+                &all_ids,
+                issue_id,
+            )?)
+        };
+        let graph = export_graph(&storage_ctx.storage, root.as_deref())?;
+        // Use print! directly to avoid rich_rust markup interpretation
+        print!("{}", graph.render(format));
+        return Ok(());
+    }
+
     if args.all {
         graph_all(&storage_ctx.storage, args.compact, ctx)
     } else {
@@ -477,6 +505,88 @@ fn calculate_depths(
     depths
 }
 
+/// Collect issues and every dependency between them for export.
+///
+/// With a root, the graph is the root plus everything that transitively
+/// depends on it (as in `br graph <id>`); otherwise it is every
+/// `open`/`in_progress`/`blocked` issue (as in `br graph --all`).
+fn export_graph(storage: &SqliteStorage, root: Option<&str>) -> Result<GraphExport> {
+    let all_dependencies = storage.get_all_dependency_records()?;
+
+    let issues: Vec<Issue> = if let Some(root_id) = root {
+        let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+        for dep in all_dependencies.values().flatten() {
+            if dep.dep_type.affects_ready_work() {
+                dependents
+                    .entry(dep.depends_on_id.as_str())
+                    .or_default()
+                    .push(dep.issue_id.as_str());
+            }
+        }
+        let mut reached: HashSet<&str> = HashSet::from([root_id]);
+        let mut queue: VecDeque<&str> = VecDeque::from([root_id]);
+        while let Some(current) = queue.pop_front() {
+            for &dependent in dependents.get(current).into_iter().flatten() {
+                if reached.insert(dependent) {
+                    queue.push_back(dependent);
+                }
+            }
+        }
+
+        let filters = ListFilters {
+            include_closed: true,
+            include_deferred: true,
+            ..Default::default()
+        };
+        storage
+            .list_issues(&filters)?
+            .into_iter()
+            .filter(|issue| reached.contains(issue.id.as_str()))
+            .collect()
+    } else {
+        let filters = ListFilters {
+            statuses: Some(vec![Status::Open, Status::InProgress, Status::Blocked]),
+            include_closed: false,
+            include_templates: false,
+            ..Default::default()
+        };
+        storage.list_issues(&filters)?
+    };
+
+    let mut nodes: Vec<ExportNode> = issues
+        .into_iter()
+        .map(|issue| ExportNode {
+            id: issue.id,
+            title: issue.title,
+            status: issue.status,
+            priority: issue.priority.0,
+            issue_type: issue.issue_type,
+        })
+        .collect();
+    nodes.sort_by(|a, b| a.priority.cmp(&b.priority).then_with(|| a.id.cmp(&b.id)));
+
+    let in_graph: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+    let mut edges: Vec<ExportEdge> = nodes
+        .iter()
+        .filter_map(|node| all_dependencies.get(&node.id))
+        .flatten()
+        .filter(|dep| in_graph.contains(dep.depends_on_id.as_str()))
+        .map(|dep| ExportEdge {
+            from: dep.issue_id.clone(),
+            to: dep.depends_on_id.clone(),
+            dep_type: dep.dep_type.clone(),
+        })
+        .collect();
+    edges.sort_by(|a, b| {
+        a.from
+            .cmp(&b.from)
+            .then_with(|| a.to.cmp(&b.to))
+            .then_with(|| a.dep_type.as_str().cmp(b.dep_type.as_str()))
+    });
+
+    Ok(GraphExport { nodes, edges })
+}
+
 fn resolve_issue_id(
     storage: &SqliteStorage,
     resolver: &IdResolver,
@@ -3,7 +3,9 @@ use crate::cli::HistoryCommands;
 use crate::config;
 use crate::error::{BeadsError, Result};
 use crate::output::OutputContext;
+use crate::storage::timeline::{self, FieldChange};
 use crate::sync::history;
+use crate::util::id::{IdResolver, ResolverConfig};
 use rich_rust::prelude::*;
 use serde_json::json;
 use std::path::Path;
@@ -28,10 +30,93 @@ pub fn execute(args: HistoryArgs, cli: &config::CliOverrides, ctx: &OutputContex
         Some(HistoryCommands::Prune { keep, older_than }) => {
             prune_backups(&history_dir, keep, older_than, ctx)
         }
+        Some(HistoryCommands::Field { id, field }) => {
+            field_history(&beads_dir, &id, &field, cli, ctx)
+        }
         Some(HistoryCommands::List) | None => list_backups(&history_dir, ctx),
     }
 }
 
+/// Show the timeline of one field of an issue.
+fn field_history(
+    beads_dir: &Path,
+    id_input: &str,
+    field_input: &str,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let field = timeline::canonical_field(field_input).ok_or_else(|| {
+        BeadsError::validation(
+            "field",
+            format!(
+                "unknown field '{field_input}' (expected one of: {})",
+                timeline::field_names().join(", ")
+            ),
+        )
+    })?;
+
+    let storage_ctx = config::open_storage_with_cli(beads_dir, cli)?;
+    let storage = &storage_ctx.storage;
+    let config_layer = config::load_config(beads_dir, Some(storage), cli)?;
+    let id_config = config::id_config_from_layer(&config_layer);
+    let resolver = IdResolver::new(ResolverConfig::with_prefix(id_config.prefix));
+    let resolution = resolver.resolve(
+        id_input,
+        |id| storage.id_exists(id).unwrap_or(false),
+        |hash| storage.find_ids_by_hash(hash).unwrap_or_default(),
+    )?;
+    let changes = storage.get_field_history(&resolution.id, field)?;
+
+    if ctx.is_json() {
+        ctx.json_pretty(&json!({
+            "id": resolution.id,
+            "field": field,
+            "changes": changes,
+        }));
+        return Ok(());
+    }
+
+    if ctx.is_quiet() {
+        return Ok(());
+    }
+
+    if changes.is_empty() {
+        println!("No recorded changes to {field} of {}", resolution.id);
+        return Ok(());
+    }
+    println!("History of {field} for {}:", resolution.id);
+    let actor_width = changes.iter().map(|c| c.actor.len()).max().unwrap_or(0);
+    for change in &changes {
+        println!(
+            "  {}  {:<actor_width$}  {}",
+            change.at.format("%Y-%m-%d %H:%M:%S"),
+            change.actor,
+            describe_change(field, change)
+        );
+    }
+    Ok(())
+}
+
+/// One-line description of a field change.
+fn describe_change(field: &str, change: &FieldChange) -> String {
+    let quoted =
+        |value: Option<&str>| value.map_or_else(|| "(empty)".to_string(), |v| format!("'{v}'"));
+    let old = change.old_value.as_deref();
+    let new = change.new_value.as_deref();
+    if change.event_type == crate::model::EventType::Created {
+        return format!("created with {}", quoted(new));
+    }
+    if timeline::COLLECTION_FIELDS.contains(&field) {
+        return match (old, new) {
+            (Some(old), Some(new)) => format!("- {old}  + {new}"),
+            (Some(old), None) => format!("- {old}"),
+            (None, Some(new)) => format!("+ {new}"),
+            (None, None) => change.event_type.as_str().to_string(),
+        };
+    }
+    format!("{} -> {}", quoted(old), quoted(new))
+}
+
 /// List available backups.
 fn list_backups(history_dir: &Path, ctx: &OutputContext) -> Result<()> {
     let backups = history::list_backups(history_dir, None)?;
@@ -0,0 +1,541 @@
+//! Import command implementation.
+//!
+//! `import csv` turns the rows of a spreadsheet export into issues. `--map`
+//! names the issue field each column fills; columns already named after a
+//! field need no mapping, and other columns are ignored. `--values`
+//! translates a column's values before they are parsed, so a QA sheet's
+//! severities become priorities:
+//!
+//! ```text
+//! br import csv bugs.csv --map "Summary=title,Sev=priority,Key=external_ref" \
+//!     --values "Sev: Blocker=0,Major=1,Minor=3"
+//! ```
+//!
+//! Every row is built and validated exactly like `create` and then created
+//! on its own, so a bad row is reported without holding back the others.
+//! A row whose external ref is already taken, by an existing issue or an
+//! earlier row, is skipped; re-importing an updated sheet only adds the new
+//! rows.
+
+use crate::cli::commands::create::{self, CreateConfig, create_config, resolve_id};
+use crate::cli::{CreateArgs, ImportCommands, ImportCsvArgs};
+use crate::config;
+use crate::error::{BeadsError, Result, StructuredError};
+use crate::format::csv::{Record, parse_records};
+use crate::model::Issue;
+use crate::output::OutputContext;
+use crate::storage::SqliteStorage;
+use crate::util::id::{IdResolver, ResolverConfig};
+use crate::validation::IssueValidator;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, info};
+
+/// Issue fields a column can fill.
+const FIELDS: &[&str] = &[
+    "title",
+    "description",
+    "design",
+    "acceptance_criteria",
+    "notes",
+    "type",
+    "priority",
+    "status",
+    "assignee",
+    "owner",
+    "labels",
+    "external_ref",
+    "due",
+    "defer",
+    "estimate",
+    "parent",
+];
+
+/// What `import csv` did with one row.
+#[derive(Debug, Serialize)]
+struct RowResult {
+    /// Line the row starts on.
+    line: usize,
+    /// `created`, `would_create`, `exists` or `failed`.
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    /// The issue that would be created (`--dry-run`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issue: Option<Box<Issue>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<Value>,
+}
+
+/// JSON output for `import csv`.
+#[derive(Debug, Serialize)]
+struct ImportCsvOutput {
+    dry_run: bool,
+    rows: Vec<RowResult>,
+}
+
+/// Execute the import command.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed, or the mapping is
+/// invalid; nothing is created then. Otherwise every row is reported before
+/// returning the first row's failure.
+pub fn execute(
+    command: &ImportCommands,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    match command {
+        ImportCommands::Csv(args) => execute_csv(args, cli, ctx),
+    }
+}
+
+fn execute_csv(
+    args: &ImportCsvArgs,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let mut records = parse_records(&std::fs::read_to_string(&args.file)?)?.into_iter();
+    let Some(header) = records.next() else {
+        return Err(BeadsError::validation("file", "has no header row"));
+    };
+    let mapping = Mapping::new(
+        &header.fields,
+        args.map.as_deref(),
+        &args.values,
+        &args.label_separator,
+    )?;
+
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let mut importer = Importer {
+        resolver: IdResolver::new(ResolverConfig::with_prefix(
+            config::id_config_from_layer(&layer).prefix,
+        )),
+        config: create_config(&layer)?,
+        dry_run: args.dry_run,
+        reserved: HashSet::new(),
+        refs: HashMap::new(),
+    };
+
+    let mut rows = Vec::new();
+    let mut first_error = None;
+    for record in records {
+        let line = record.line;
+        match importer.import(&mut storage_ctx.storage, &mapping, &record) {
+            Ok(row) => rows.push(row),
+            Err(error) => {
+                debug!(line, %error, "Row failed");
+                rows.push(RowResult {
+                    line,
+                    status: "failed",
+                    id: None,
+                    title: mapping.title(&record.fields),
+                    issue: None,
+                    error: Some(StructuredError::from_error(&error).to_json()["error"].clone()),
+                });
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+    info!(
+        file = %args.file.display(),
+        rows = rows.len(),
+        dry_run = args.dry_run,
+        "CSV import finished"
+    );
+
+    if ctx.is_json() {
+        ctx.json_pretty(&ImportCsvOutput {
+            dry_run: args.dry_run,
+            rows,
+        });
+    } else {
+        print_rows(&rows, args, ctx);
+    }
+
+    if !args.dry_run {
+        storage_ctx.flush_no_db_if_dirty()?;
+    }
+    first_error.map_or(Ok(()), Err)
+}
+
+fn print_rows(rows: &[RowResult], args: &ImportCsvArgs, ctx: &OutputContext) {
+    for row in rows {
+        let id = row.id.as_deref().unwrap_or("-");
+        let title = row.title.as_deref().unwrap_or_default();
+        if let Some(issue) = &row.issue {
+            let labels = if issue.labels.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", issue.labels.join(", "))
+            };
+            println!(
+                "{:<12} line {}: {id} [{}] [{}] {title}{labels}",
+                row.status, row.line, issue.priority, issue.issue_type
+            );
+            continue;
+        }
+        println!("{:<12} line {}: {id} {title}", row.status, row.line);
+        if let Some(message) = row
+            .error
+            .as_ref()
+            .and_then(|error| error["message"].as_str())
+        {
+            println!("{:<12} {message}", "");
+        }
+    }
+
+    let count = |status| rows.iter().filter(|row| row.status == status).count();
+    let (verb, created) = if args.dry_run {
+        ("Would create", count("would_create"))
+    } else {
+        ("Created", count("created"))
+    };
+    ctx.info(&format!(
+        "{verb} {created} issue{} from {} ({} already imported, {} failed)",
+        if created == 1 { "" } else { "s" },
+        args.file.display(),
+        count("exists"),
+        count("failed")
+    ));
+}
+
+/// Turns CSV rows into issues, one at a time.
+struct Importer {
+    resolver: IdResolver,
+    config: CreateConfig,
+    dry_run: bool,
+    /// IDs previewed by earlier rows of a dry run.
+    reserved: HashSet<String>,
+    /// External refs taken by earlier rows, with their issue IDs.
+    refs: HashMap<String, String>,
+}
+
+impl Importer {
+    fn import(
+        &mut self,
+        storage: &mut SqliteStorage,
+        mapping: &Mapping,
+        record: &Record,
+    ) -> Result<RowResult> {
+        let mut row = mapping.row(&record.fields)?;
+        let mut result = RowResult {
+            line: record.line,
+            status: "exists",
+            id: None,
+            title: row.args.title.clone(),
+            issue: None,
+            error: None,
+        };
+
+        if let Some(external_ref) = &row.args.external_ref {
+            if let Some(id) = self.refs.get(external_ref) {
+                result.id = Some(id.clone());
+                return Ok(result);
+            }
+            if let Some(existing) = storage.find_by_external_ref(external_ref)? {
+                debug!(line = record.line, id = %existing.id, "Row already imported");
+                result.id = Some(existing.id);
+                return Ok(result);
+            }
+        }
+        if let Some(parent) = &row.args.parent {
+            row.args.parent = Some(resolve_id(&self.resolver, storage, parent)?);
+        }
+
+        let mut issue = create::build_issue(storage, &row.args, &self.config, &self.reserved)?;
+        if row.has_text_fields() {
+            issue.design = row.design;
+            issue.acceptance_criteria = row.acceptance_criteria;
+            issue.notes = row.notes;
+            issue.content_hash = Some(issue.compute_content_hash());
+            IssueValidator::validate(&issue).map_err(BeadsError::from_validation_errors)?;
+        }
+        let id = issue.id.clone();
+        let external_ref = issue.external_ref.clone();
+
+        if self.dry_run {
+            self.reserved.insert(id.clone());
+            result.status = "would_create";
+            result.issue = Some(Box::new(issue));
+        } else {
+            storage.create_issue(&issue, &self.config.actor)?;
+            result.status = "created";
+        }
+        if let Some(external_ref) = external_ref {
+            self.refs.insert(external_ref, id.clone());
+        }
+        result.id = Some(id);
+        Ok(result)
+    }
+}
+
+/// How the columns of a CSV file fill issue fields.
+#[derive(Debug)]
+struct Mapping {
+    /// Field filled by each column, by column index.
+    fields: Vec<Option<&'static str>>,
+    /// Value translations by column index, keyed by lowercased value.
+    values: HashMap<usize, HashMap<String, String>>,
+    label_separator: String,
+}
+
+/// Fields of one row, as `create` arguments plus the text fields `create`
+/// has no flags for.
+#[derive(Debug, Default)]
+struct Row {
+    args: CreateArgs,
+    design: Option<String>,
+    acceptance_criteria: Option<String>,
+    notes: Option<String>,
+}
+
+impl Row {
+    const fn has_text_fields(&self) -> bool {
+        self.design.is_some() || self.acceptance_criteria.is_some() || self.notes.is_some()
+    }
+}
+
+impl Mapping {
+    /// Build the mapping for `header` from `--map` and `--values`.
+    fn new(
+        header: &[String],
+        map: Option<&str>,
+        values: &[String],
+        label_separator: &str,
+    ) -> Result<Self> {
+        let column = |name: &str, arg: &str| {
+            header
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(name.trim()))
+                .ok_or_else(|| {
+                    BeadsError::validation(
+                        arg,
+                        format!("no column '{}' in the header", name.trim()),
+                    )
+                })
+        };
+
+        let mut fields: Vec<Option<&'static str>> = header
+            .iter()
+            .map(|name| field_name(name.trim()).ok())
+            .collect();
+        for pair in map.unwrap_or_default().split(',') {
+            if pair.trim().is_empty() {
+                continue;
+            }
+            let Some((name, field)) = pair.rsplit_once('=') else {
+                return Err(BeadsError::validation(
+                    "map",
+                    format!("expected COLUMN=FIELD, got '{}'", pair.trim()),
+                ));
+            };
+            let index = column(name, "map")?;
+            // A listed column takes the field from any column named after it.
+            let field = field_name(field.trim())?;
+            for other in fields.iter_mut().filter(|other| **other == Some(field)) {
+                *other = None;
+            }
+            fields[index] = Some(field);
+        }
+        if !fields.contains(&Some("title")) {
+            return Err(BeadsError::validation(
+                "map",
+                "no column maps to title; use --map \"COLUMN=title\"",
+            ));
+        }
+
+        let mut tables = HashMap::new();
+        for table in values {
+            let Some((name, entries)) = table.split_once(':') else {
+                return Err(BeadsError::validation(
+                    "values",
+                    format!("expected \"COLUMN: FROM=TO,...\", got '{table}'"),
+                ));
+            };
+            let translations: &mut HashMap<String, String> =
+                tables.entry(column(name, "values")?).or_default();
+            for entry in entries.split(',').filter(|entry| !entry.trim().is_empty()) {
+                let Some((from, to)) = entry.split_once('=') else {
+                    return Err(BeadsError::validation(
+                        "values",
+                        format!("expected FROM=TO, got '{}'", entry.trim()),
+                    ));
+                };
+                translations.insert(from.trim().to_lowercase(), to.trim().to_string());
+            }
+        }
+
+        Ok(Self {
+            fields,
+            values: tables,
+            label_separator: label_separator.to_string(),
+        })
+    }
+
+    /// The translated, trimmed value of each mapped column that is not empty.
+    fn cells<'a>(
+        &'a self,
+        record: &'a [String],
+    ) -> impl Iterator<Item = (&'static str, String)> + 'a {
+        self.fields
+            .iter()
+            .enumerate()
+            .filter_map(move |(index, field)| {
+                let field = (*field)?;
+                let value = record.get(index)?.trim();
+                let value = self
+                    .values
+                    .get(&index)
+                    .and_then(|table| table.get(&value.to_lowercase()))
+                    .map_or(value, String::as_str);
+                (!value.is_empty()).then(|| (field, value.to_string()))
+            })
+    }
+
+    /// Title of a row, for reporting rows that failed.
+    fn title(&self, record: &[String]) -> Option<String> {
+        self.cells(record)
+            .find(|(field, _)| *field == "title")
+            .map(|(_, value)| value)
+    }
+
+    fn row(&self, record: &[String]) -> Result<Row> {
+        let mut row = Row::default();
+        let args = &mut row.args;
+        for (field, value) in self.cells(record) {
+            match field {
+                "title" => args.title = Some(value),
+                "description" => args.description = Some(value),
+                "design" => row.design = Some(value),
+                "acceptance_criteria" => row.acceptance_criteria = Some(value),
+                "notes" => row.notes = Some(value),
+                "type" => args.type_ = Some(value),
+                "priority" => args.priority = Some(value),
+                "status" => args.status = Some(value),
+                "assignee" => args.assignee = Some(value),
+                "owner" => args.owner = Some(value),
+                "labels" => args.labels.extend(
+                    value
+                        .split(self.label_separator.as_str())
+                        .map(str::trim)
+                        .filter(|label| !label.is_empty())
+                        .map(str::to_string),
+                ),
+                "external_ref" => args.external_ref = Some(value),
+                "due" => args.due = Some(value),
+                "defer" => args.defer = Some(value),
+                "estimate" => {
+                    args.estimate = Some(value.parse().map_err(|_| {
+                        BeadsError::validation(
+                            "estimate",
+                            format!("'{value}' is not a number of minutes"),
+                        )
+                    })?);
+                }
+                "parent" => args.parent = Some(value),
+                _ => unreachable!("field_name only returns known fields"),
+            }
+        }
+        Ok(row)
+    }
+}
+
+/// The field a `--map` target or column header names.
+fn field_name(name: &str) -> Result<&'static str> {
+    let name = name.to_lowercase().replace([' ', '-'], "_");
+    let name = match name.as_str() {
+        "issue_type" => "type",
+        "label" => "labels",
+        other => other,
+    };
+    FIELDS
+        .iter()
+        .find(|field| **field == name)
+        .copied()
+        .ok_or_else(|| {
+            BeadsError::validation(
+                "map",
+                format!("unknown field '{name}'; fields: {}", FIELDS.join(", ")),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn mapping_translates_values_and_splits_labels() {
+        let mapping = Mapping::new(
+            &header(&["Summary", "Sev", "Tags", "Notes", "Ignored"]),
+            Some("Summary=title, Sev=priority,Tags=labels"),
+            &["Sev: Blocker=0, Major=1".to_string()],
+            ";",
+        )
+        .unwrap();
+        let row = mapping
+            .row(&header(&[
+                " Crash on save ",
+                "major",
+                "ui; ;crash",
+                "",
+                "x",
+            ]))
+            .unwrap();
+        assert_eq!(row.args.title.as_deref(), Some("Crash on save"));
+        assert_eq!(row.args.priority.as_deref(), Some("1"));
+        assert_eq!(row.args.labels, vec!["ui", "crash"]);
+        // Notes maps by name, but empty cells are left unset.
+        assert!(row.notes.is_none());
+        assert!(!row.has_text_fields());
+
+        // Untranslated values pass through to the usual parsing.
+        let row = mapping.row(&header(&["Hang", "P3"])).unwrap();
+        assert_eq!(row.args.priority.as_deref(), Some("P3"));
+    }
+
+    #[test]
+    fn mapping_rejects_unknown_columns_and_fields() {
+        let names = header(&["Summary", "Owner"]);
+        assert!(Mapping::new(&names, Some("Summary=title"), &[], ",").is_ok());
+        assert!(Mapping::new(&names, Some("Missing=title"), &[], ",").is_err());
+        assert!(Mapping::new(&names, Some("Summary=headline"), &[], ",").is_err());
+        assert!(Mapping::new(&names, Some("Owner=owner"), &[], ",").is_err());
+        assert!(
+            Mapping::new(
+                &names,
+                Some("Summary=title"),
+                &["Sev: A=0".to_string()],
+                ","
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn mapping_reads_field_named_columns() {
+        let mapping = Mapping::new(
+            &header(&["Title", "Issue Type", "estimate"]),
+            None,
+            &[],
+            ",",
+        )
+        .unwrap();
+        let row = mapping.row(&header(&["Login", "bug", "90"])).unwrap();
+        assert_eq!(row.args.title.as_deref(), Some("Login"));
+        assert_eq!(row.args.type_.as_deref(), Some("bug"));
+        assert_eq!(row.args.estimate, Some(90));
+        assert!(mapping.row(&header(&["Login", "bug", "soon"])).is_err());
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::cli::InfoArgs;
 use crate::config;
+use crate::daemon::{self, client::DaemonClient};
 use crate::error::Result;
 use crate::output::OutputContext;
 use crate::storage::SqliteStorage;
@@ -24,6 +25,9 @@ const SCHEMA_TABLES: &[&str] = &[
     "export_hashes",
     "blocked_issues_cache",
     "child_counters",
+    "operations",
+    "leases",
+    "org_extras",
 ];
 
 #[derive(Serialize)]
@@ -62,6 +66,50 @@ struct InfoOutput {
     jsonl_size: Option<u64>,
 }
 
+struct DaemonStatus {
+    mode: &'static str,
+    connected: bool,
+    fallback_reason: Option<String>,
+    detail: String,
+}
+
+/// Whether commands would be forwarded to a daemon on the default socket.
+fn daemon_status(beads_dir: &Path, cli: &config::CliOverrides) -> DaemonStatus {
+    let socket_path = daemon::default_socket_path(beads_dir);
+    let direct = |reason: &str, detail: String| DaemonStatus {
+        mode: "direct",
+        connected: false,
+        fallback_reason: Some(reason.to_string()),
+        detail,
+    };
+
+    if cli.no_daemon.unwrap_or(false) {
+        return direct(
+            "flag-no-daemon",
+            "--no-daemon forces direct mode".to_string(),
+        );
+    }
+    let pong = DaemonClient::connect(&socket_path)
+        .ok()
+        .and_then(|mut client| client.call("ping", serde_json::json!({})).ok());
+    match pong {
+        Some(pong) => DaemonStatus {
+            mode: "daemon",
+            connected: true,
+            fallback_reason: None,
+            detail: format!(
+                "pid {} on {}",
+                pong["pid"].as_u64().unwrap_or_default(),
+                socket_path.display()
+            ),
+        },
+        None => direct(
+            "no-daemon",
+            format!("no daemon listening on {}", socket_path.display()),
+        ),
+    }
+}
+
 /// Execute the info command.
 ///
 /// # Errors
@@ -103,13 +151,15 @@ pub fn execute(args: &InfoArgs, cli: &config::CliOverrides, ctx: &OutputContext)
         .map(|m| m.len())
         .ok();
 
+    let daemon = daemon_status(&beads_dir, cli);
+
     let output = InfoOutput {
         database_path: db_path.display().to_string(),
         beads_dir: canonicalize_lossy(&beads_dir).display().to_string(),
-        mode: "direct".to_string(),
-        daemon_connected: false,
-        daemon_fallback_reason: Some("no-daemon".to_string()),
-        daemon_detail: Some("br runs in direct mode only".to_string()),
+        mode: daemon.mode.to_string(),
+        daemon_connected: daemon.connected,
+        daemon_fallback_reason: daemon.fallback_reason,
+        daemon_detail: Some(daemon.detail),
         issue_count,
         config: config_map,
         schema,
@@ -168,7 +218,10 @@ fn print_human(info: &InfoOutput) {
     println!("Mode: {}", info.mode);
 
     if info.daemon_connected {
-        println!("Daemon: connected");
+        match &info.daemon_detail {
+            Some(detail) => println!("Daemon: connected ({detail})"),
+            None => println!("Daemon: connected"),
+        }
     } else if let Some(reason) = &info.daemon_fallback_reason {
         println!("Daemon: not connected ({reason})");
         if let Some(detail) = &info.daemon_detail {
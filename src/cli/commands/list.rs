@@ -8,9 +8,9 @@ use crate::config;
 use crate::error::{BeadsError, Result};
 use crate::format::csv;
 use crate::format::{IssueWithCounts, TextFormatOptions, format_issue_line_with, terminal_width};
-use crate::model::{IssueType, Priority, Status};
+use crate::model::{Issue, IssueType, Priority, Status};
 use crate::output::{IssueTable, IssueTableColumns, OutputContext, OutputMode};
-use crate::storage::{ListFilters, SqliteStorage};
+use crate::storage::{FilterExpr, ListFilters, SqliteStorage};
 use chrono::Utc;
 use std::collections::HashSet;
 use std::io::IsTerminal;
@@ -44,31 +44,7 @@ pub fn execute(
         wrap: args.wrap,
     };
 
-    // Build filter from args
-    let mut filters = build_filters(args)?;
-    let client_filters = needs_client_filters(args);
-    let limit = if client_filters {
-        filters.limit.take()
-    } else {
-        None
-    };
-
-    // Validate sort key before query
-    validate_sort_key(args.sort.as_deref())?;
-
-    // Query issues
-    let issues = storage.list_issues(&filters)?;
-    let mut issues = if client_filters {
-        apply_client_filters(storage, issues, args)?
-    } else {
-        issues
-    };
-
-    if let Some(limit) = limit {
-        if limit > 0 && issues.len() > limit {
-            issues.truncate(limit);
-        }
-    }
+    let issues = query_issues(storage, args)?;
 
     // Determine output format: --json flag overrides --format
     let output_format = resolve_output_format(args.format, outer_ctx.is_json(), false);
@@ -81,32 +57,7 @@ pub fn execute(
     // Output
     match output_format {
         OutputFormat::Json | OutputFormat::Toon => {
-            // Fetch relations for all issues
-            let issue_ids: Vec<String> = issues.iter().map(|i| i.id.clone()).collect();
-            let mut labels_map = storage.get_labels_for_issues(&issue_ids)?;
-
-            // Use batch counting
-            let dependency_counts = storage.count_dependencies_for_issues(&issue_ids)?;
-            let dependent_counts = storage.count_dependents_for_issues(&issue_ids)?;
-
-            // Convert to IssueWithCounts
-            let issues_with_counts: Vec<IssueWithCounts> = issues
-                .into_iter()
-                .map(|mut issue| {
-                    if let Some(labels) = labels_map.remove(&issue.id) {
-                        issue.labels = labels;
-                    }
-
-                    let dependency_count = *dependency_counts.get(&issue.id).unwrap_or(&0);
-                    let dependent_count = *dependent_counts.get(&issue.id).unwrap_or(&0);
-
-                    IssueWithCounts {
-                        issue,
-                        dependency_count,
-                        dependent_count,
-                    }
-                })
-                .collect();
+            let issues_with_counts = with_counts(storage, issues)?;
 
             if matches!(output_format, OutputFormat::Toon) {
                 ctx.toon_with_stats(&issues_with_counts, args.stats);
@@ -165,6 +116,74 @@ pub fn execute(
     Ok(())
 }
 
+/// Run the list query described by `args` (filters, sort and limit).
+///
+/// # Errors
+///
+/// Returns an error if a filter is invalid or the query fails.
+pub fn query_issues(storage: &SqliteStorage, args: &ListArgs) -> Result<Vec<Issue>> {
+    // Build filter from args
+    let mut filters = build_filters(args)?;
+    let client_filters = needs_client_filters(args);
+    let limit = if client_filters {
+        filters.limit.take()
+    } else {
+        None
+    };
+
+    // Validate sort key before query
+    validate_sort_key(args.sort.as_deref())?;
+
+    // Query issues
+    let issues = storage.list_issues(&filters)?;
+    let mut issues = if client_filters {
+        apply_client_filters(storage, issues, args)?
+    } else {
+        issues
+    };
+
+    if let Some(limit) = limit {
+        if limit > 0 && issues.len() > limit {
+            issues.truncate(limit);
+        }
+    }
+    Ok(issues)
+}
+
+/// Attach labels and dependency counts, as in `list --json`.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn with_counts(storage: &SqliteStorage, issues: Vec<Issue>) -> Result<Vec<IssueWithCounts>> {
+    // Fetch relations for all issues
+    let issue_ids: Vec<String> = issues.iter().map(|i| i.id.clone()).collect();
+    let mut labels_map = storage.get_labels_for_issues(&issue_ids)?;
+
+    // Use batch counting
+    let dependency_counts = storage.count_dependencies_for_issues(&issue_ids)?;
+    let dependent_counts = storage.count_dependents_for_issues(&issue_ids)?;
+
+    // Convert to IssueWithCounts
+    Ok(issues
+        .into_iter()
+        .map(|mut issue| {
+            if let Some(labels) = labels_map.remove(&issue.id) {
+                issue.labels = labels;
+            }
+
+            let dependency_count = *dependency_counts.get(&issue.id).unwrap_or(&0);
+            let dependent_count = *dependent_counts.get(&issue.id).unwrap_or(&0);
+
+            IssueWithCounts {
+                issue,
+                dependency_count,
+                dependent_count,
+            }
+        })
+        .collect())
+}
+
 /// Convert CLI args to storage filter.
 fn build_filters(args: &ListArgs) -> Result<ListFilters> {
     // Parse status strings to Status enums
@@ -203,7 +222,14 @@ fn build_filters(args: &ListArgs) -> Result<ListFilters> {
         )
     };
 
+    let filter_expr = args.where_.as_deref().map(FilterExpr::parse).transpose()?;
+    // An expression that constrains status decides closed/deferred visibility itself.
+    let where_status = filter_expr
+        .as_ref()
+        .is_some_and(FilterExpr::references_status);
+
     let include_closed = args.all
+        || where_status
         || statuses
             .as_ref()
             .is_some_and(|parsed| parsed.iter().any(Status::is_terminal));
@@ -212,6 +238,7 @@ fn build_filters(args: &ListArgs) -> Result<ListFilters> {
     // They are only excluded when explicitly filtering by status that doesn't include deferred.
     let include_deferred = args.deferred
         || args.all
+        || where_status
         || statuses.is_none()
         || statuses
             .as_ref()
@@ -242,6 +269,7 @@ fn build_filters(args: &ListArgs) -> Result<ListFilters> {
         },
         updated_before: None,
         updated_after: None,
+        filter_expr,
     })
 }
 
@@ -0,0 +1,20 @@
+//! MCP command implementation.
+//!
+//! Runs the stdio Model Context Protocol server until the client closes
+//! stdin. Stdout carries protocol messages only; logs go to stderr.
+
+use crate::config;
+use crate::error::Result;
+use crate::mcp::McpServer;
+use std::io;
+
+/// Execute the mcp command.
+///
+/// # Errors
+///
+/// Returns an error if the workspace cannot be opened or stdio fails.
+pub fn execute(allow_stale: bool, cli: &config::CliOverrides) -> Result<()> {
+    let mut server = McpServer::open(cli, allow_stale)?;
+    tracing::info!("MCP server listening on stdio");
+    server.serve(io::stdin().lock(), io::stdout().lock())
+}
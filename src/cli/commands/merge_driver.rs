@@ -0,0 +1,208 @@
+//! Merge-driver command implementation.
+//!
+//! Lets git merge the issue export per issue instead of per line. Git calls
+//! the driver with the ancestor (`%O`), current (`%A`) and other (`%B`)
+//! versions; the merged export replaces `%A`. Configure it yourself:
+//!
+//! ```text
+//! # .gitattributes
+//! .beads/issues.org merge=beads
+//! .beads/issues.jsonl merge=beads
+//!
+//! # .git/config
+//! [merge "beads"]
+//!     name = issue-aware merge of the beads export
+//!     driver = br merge-driver %O %A %B %P
+//! ```
+//!
+//! An Org export is written back nested if the current version nests child
+//! issues, so the merge does not reshape the file.
+//!
+//! Fields changed differently on both sides are settled by the newer side,
+//! exactly as `sync --merge` does, and an issue deleted on one side and
+//! modified on the other keeps the modified version. Either way the merged
+//! export is written, but the driver exits non-zero so git reports the file
+//! as conflicted (without conflict markers in it) and the dropped edit can
+//! be checked. `--keep-newer` accepts the newer values instead; only
+//! delete-vs-modify then fails the merge.
+
+use crate::cli::MergeDriverArgs;
+use crate::error::{BeadsError, Result};
+use crate::model::Issue;
+use crate::output::OutputContext;
+use crate::sync::{
+    ConflictResolution, FieldConflict, MergeContext, ensure_no_conflict_markers, org_bridge,
+    read_issues_from_jsonl, three_way_merge,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use tracing::{debug, info};
+
+/// JSON output for `merge-driver`.
+#[derive(Debug, Serialize)]
+struct MergeDriverOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    issues: usize,
+    deleted: Vec<String>,
+    /// Issues deleted on one side and modified on the other.
+    conflicts: Vec<String>,
+    field_conflicts: Vec<FieldConflict>,
+}
+
+/// Execute the merge-driver command.
+///
+/// # Errors
+///
+/// Returns an error if a version cannot be read or parsed, if the merged
+/// export cannot be written, or (after writing it) if some issues were
+/// deleted on one side and modified on the other, or a field was changed on
+/// both sides without `--keep-newer`.
+pub fn execute(args: &MergeDriverArgs, ctx: &OutputContext) -> Result<()> {
+    let org = args
+        .path
+        .as_deref()
+        .is_some_and(|path| Path::new(path).extension().is_some_and(|ext| ext == "org"));
+
+    let base = read_version(&args.ancestor, org)?;
+    let current = read_version(&args.current, org)?;
+    let other = read_version(&args.other, org)?;
+    let nested = org && org_bridge::is_nested_org_text(&fs::read_to_string(&args.current)?);
+    debug!(
+        base = base.len(),
+        current = current.len(),
+        other = other.len(),
+        "Loaded merge versions"
+    );
+
+    let context = MergeContext::new(base, current, other);
+    // Manual turns delete-vs-modify into a conflict; same-field edits are
+    // still settled by the newer side.
+    let report = three_way_merge(&context, ConflictResolution::Manual, None);
+
+    let mut merged = report.kept;
+    let mut conflicts = Vec::with_capacity(report.conflicts.len());
+    for (id, _) in report.conflicts {
+        // Keep whichever side still has the issue so nothing is lost.
+        if let Some(issue) = context.left.get(&id).or_else(|| context.right.get(&id)) {
+            merged.push(issue.clone());
+        }
+        conflicts.push(id);
+    }
+    merged.sort_by(|a, b| a.id.cmp(&b.id));
+    conflicts.sort();
+    let mut deleted = report.deleted;
+    deleted.sort();
+
+    fs::write(&args.current, render(&merged, org, nested)?)?;
+    info!(
+        issues = merged.len(),
+        deleted = deleted.len(),
+        conflicts = conflicts.len(),
+        "Merged export written"
+    );
+
+    let output = MergeDriverOutput {
+        path: args.path.clone(),
+        issues: merged.len(),
+        deleted,
+        conflicts,
+        field_conflicts: report.field_conflicts,
+    };
+
+    if ctx.is_json() {
+        ctx.json_pretty(&output);
+    } else if args.keep_newer {
+        // Git shows the driver's output during the merge; keep stdout clean.
+        for conflict in &output.field_conflicts {
+            eprintln!(
+                "{}: {} changed on both sides, kept the newer value",
+                conflict.issue_id, conflict.field
+            );
+        }
+    }
+
+    let field_conflicts: &[FieldConflict] = if args.keep_newer {
+        &[]
+    } else {
+        &output.field_conflicts
+    };
+    if output.conflicts.is_empty() && field_conflicts.is_empty() {
+        return Ok(());
+    }
+    let mut msg = String::new();
+    if !output.conflicts.is_empty() {
+        msg.push_str("Issues deleted on one side and modified on the other:\n");
+        for id in &output.conflicts {
+            let _ = writeln!(msg, "  - {id}");
+        }
+        msg.push_str("The modified versions were kept; delete them again if intended.\n");
+    }
+    if !field_conflicts.is_empty() {
+        msg.push_str("Fields changed on both sides:\n");
+        for conflict in field_conflicts {
+            let _ = writeln!(msg, "  - {} {}", conflict.issue_id, conflict.field);
+        }
+        msg.push_str("The newer values were kept; restore the other side's edit if needed.\n");
+    }
+    Err(BeadsError::Config(msg.trim_end().to_string()))
+}
+
+fn read_version(path: &Path, org: bool) -> Result<HashMap<String, Issue>> {
+    ensure_no_conflict_markers(path)?;
+    let issues = if org {
+        org_bridge::org_text_to_issues(&fs::read_to_string(path)?)?
+    } else {
+        read_issues_from_jsonl(path)?
+    };
+    Ok(issues
+        .into_iter()
+        .map(|issue| (issue.id.clone(), issue))
+        .collect())
+}
+
+fn render(issues: &[Issue], org: bool, nested: bool) -> Result<String> {
+    if nested {
+        return Ok(org_bridge::issues_to_nested_org_text(issues));
+    }
+    if org {
+        return Ok(org_bridge::issues_to_org_text(issues));
+    }
+    let mut out = String::new();
+    for issue in issues {
+        let json = serde_json::to_string(issue).map_err(|e| {
+            BeadsError::Config(format!("Failed to serialize issue {}: {e}", issue.id))
+        })?;
+        out.push_str(&json);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(id: &str, title: &str) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: title.to_string(),
+            ..Issue::default()
+        }
+    }
+
+    #[test]
+    fn render_round_trips_through_reader() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("merged");
+        let issues = vec![issue("bd-1", "One"), issue("bd-2", "Two")];
+        fs::write(&path, render(&issues, false, false).unwrap()).unwrap();
+
+        let read = read_version(&path, false).unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read["bd-2"].title, "Two");
+    }
+}
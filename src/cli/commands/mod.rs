@@ -1,9 +1,12 @@
 pub mod agents;
 pub mod audit;
+pub mod batch;
 pub mod blocked;
 pub mod changelog;
+pub mod claim;
 pub mod close;
 pub mod comments;
+pub mod compact;
 pub mod completions;
 pub mod config;
 pub mod count;
@@ -13,24 +16,35 @@ pub mod delete;
 pub mod dep;
 pub mod doctor;
 pub mod epic;
+pub mod gc;
 pub mod graph;
 pub mod history;
+pub mod import;
 pub mod info;
 pub mod init;
 pub mod label;
 pub mod lint;
 pub mod list;
+pub mod mcp;
+pub mod merge_driver;
+pub mod next;
 pub mod orphans;
+pub mod plan;
 pub mod q;
 pub mod query;
 pub mod ready;
+pub mod recur;
 pub mod reopen;
 pub mod schema;
 pub mod search;
+pub mod serve;
 pub mod show;
 pub mod stale;
 pub mod stats;
 pub mod sync;
+pub mod template;
+pub mod tui;
+pub mod undo;
 pub mod update;
 pub mod version;
 pub mod r#where;
@@ -0,0 +1,92 @@
+//! Next command implementation.
+//!
+//! `next` answers "what should I work on?" with the top unassigned ready
+//! issue. With `--claim` the choice and the claim happen in one IMMEDIATE
+//! transaction, so agents running `next --claim` side by side never get
+//! the same issue (unlike `ready --limit 1` followed by `update --claim`).
+
+use super::ready::{external_blocker_ids, ready_filters};
+use super::show::print_issue_details;
+use crate::cli::{NextArgs, ReadyArgs};
+use crate::config;
+use crate::config::workflow::Workflow;
+use crate::error::{BeadsError, Result};
+use crate::model::{Issue, Status};
+use crate::output::{IssuePanel, OutputContext, OutputMode};
+
+/// Execute the next command.
+///
+/// # Errors
+///
+/// Returns `NoReadyWork` (exit code 9) if no issue qualifies, or an error
+/// if a filter is invalid or the database fails.
+pub fn execute(args: &NextArgs, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let config_layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let actor = config::resolve_actor(&config_layer);
+    let workflow = Workflow::from_layer(&config_layer)?;
+    let external_db_paths = config::external_project_db_paths(&config_layer, &beads_dir);
+
+    let ready_args = ReadyArgs {
+        unassigned: true,
+        label: args.label.clone(),
+        type_: args.type_.clone(),
+        priority: args.priority.clone(),
+        sort: args.sort,
+        ..ReadyArgs::default()
+    };
+    let weights = config::score_weights_from_layer(&config_layer)?;
+    let (filters, sort) = ready_filters(&ready_args, &actor, &weights)?;
+    let storage = &mut storage_ctx.storage;
+    let external_blockers = external_blocker_ids(storage, &external_db_paths)?;
+    let accept = |issue: &Issue| {
+        !external_blockers.contains(&issue.id)
+            && (!args.claim
+                || workflow
+                    .check_transition(issue, &Status::InProgress)
+                    .is_ok())
+    };
+
+    let picked = if args.claim {
+        storage.claim_next_ready(&filters, sort, &actor, accept)?
+    } else {
+        storage.expire_leases(&actor, chrono::Utc::now())?;
+        storage
+            .get_ready_issues(&filters, sort)?
+            .into_iter()
+            .find(|issue| accept(issue))
+    };
+    let Some(picked) = picked else {
+        // Keep any leases released above even when nothing is picked.
+        storage_ctx.flush_no_db_if_dirty()?;
+        return Err(BeadsError::NoReadyWork {
+            reason: "no unassigned ready issue matches".to_string(),
+        });
+    };
+    tracing::info!(id = %picked.id, claimed = args.claim, "Picked next issue");
+    crate::util::set_last_touched_id(&beads_dir, &picked.id);
+
+    let details = storage
+        .get_issue_details(&picked.id, true, false, 10)?
+        .ok_or_else(|| BeadsError::IssueNotFound {
+            id: picked.id.clone(),
+        })?;
+    if ctx.is_json() {
+        ctx.json_pretty(&details);
+    } else if args.robot {
+        OutputContext::from_flags(true, false, true).json_pretty(&details);
+    } else if !ctx.is_quiet() {
+        if args.claim {
+            println!("\u{2713} Claimed {}", details.issue.id);
+        }
+        if matches!(ctx.mode(), OutputMode::Rich) {
+            IssuePanel::from_details(&details, ctx.theme()).print(ctx, false);
+        } else {
+            print_issue_details(&details, config::should_use_color(&config_layer));
+        }
+    }
+
+    storage_ctx.flush_no_db_if_dirty()?;
+    Ok(())
+}
@@ -0,0 +1,825 @@
+//! Plan command implementation.
+//!
+//! Critical-path scheduling over blocking dependencies:
+//!
+//! - Durations come from `estimated_minutes` (or `--default-estimate`).
+//! - A forward and backward pass give earliest/latest start and slack;
+//!   zero-slack issues form the critical chain.
+//! - With `--capacity N`, issues are list-scheduled onto N workers, least
+//!   slack first, to predict start and finish times.
+//! - Work time becomes calendar time at `--hours-per-day`, and predicted
+//!   finishes are checked against `due_at`.
+//!
+//! Epics are containers, not work, so they are never scheduled themselves.
+//! Blockers outside the plan (closed, or outside `--epic`) count as done.
+
+use crate::cli::PlanArgs;
+use crate::config;
+use crate::error::{BeadsError, Result};
+use crate::format::{terminal_width, truncate_title};
+use crate::model::{DependencyType, Issue, IssueType};
+use crate::output::OutputContext;
+use crate::storage::{ListFilters, SqliteStorage};
+use crate::util::id::{IdResolver, ResolverConfig, find_matching_ids};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// One issue to schedule, with indices of the issues it waits on.
+#[derive(Debug, Clone)]
+struct Task {
+    duration: i64,
+    /// Earliest work minute the task may start (from `defer_until`).
+    release: i64,
+    priority: i32,
+    preds: Vec<usize>,
+}
+
+/// Result of scheduling, indexed like the input tasks. Times are work
+/// minutes from now.
+#[derive(Debug, Default)]
+struct Schedule {
+    earliest_start: Vec<i64>,
+    earliest_finish: Vec<i64>,
+    latest_start: Vec<i64>,
+    latest_finish: Vec<i64>,
+    start: Vec<i64>,
+    finish: Vec<i64>,
+    worker: Vec<Option<usize>>,
+    critical_path: Vec<usize>,
+}
+
+impl Schedule {
+    fn slack(&self, i: usize) -> i64 {
+        self.latest_start[i] - self.earliest_start[i]
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct PlanOutput {
+    generated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    epic: Option<String>,
+    capacity: Option<usize>,
+    hours_per_day: i64,
+    total_work_minutes: i64,
+    makespan_minutes: i64,
+    predicted_completion: DateTime<Utc>,
+    critical_path: Vec<String>,
+    tasks: Vec<PlannedTask>,
+    at_risk: Vec<DueRisk>,
+    unestimated: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct PlannedTask {
+    id: String,
+    title: String,
+    status: String,
+    priority: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    assignee: Option<String>,
+    estimate_minutes: i64,
+    estimated: bool,
+    depends_on: Vec<String>,
+    earliest_start: i64,
+    earliest_finish: i64,
+    latest_start: i64,
+    latest_finish: i64,
+    slack: i64,
+    critical: bool,
+    start: i64,
+    finish: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    worker: Option<usize>,
+    predicted_start: DateTime<Utc>,
+    predicted_finish: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    due_at: Option<DateTime<Utc>>,
+}
+
+/// An issue predicted to finish after its due date.
+#[derive(Debug, Serialize)]
+struct DueRisk {
+    id: String,
+    title: String,
+    due_at: DateTime<Utc>,
+    predicted_finish: DateTime<Utc>,
+    late_minutes: i64,
+    critical: bool,
+    /// `dependencies` if even an unlimited team would be late,
+    /// `capacity` if reordering or more people could still make it.
+    cause: &'static str,
+}
+
+/// Execute the plan command.
+///
+/// # Errors
+///
+/// Returns an error if the options are invalid, the epic doesn't exist,
+/// blocking dependencies form a cycle, or the database query fails.
+pub fn execute(args: &PlanArgs, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let format = if ctx.is_json() {
+        "json".to_string()
+    } else {
+        args.format.to_lowercase()
+    };
+    if !matches!(format.as_str(), "text" | "json" | "mermaid") {
+        return Err(BeadsError::validation(
+            "format",
+            format!(
+                "unknown format '{}' (expected text, json or mermaid)",
+                args.format
+            ),
+        ));
+    }
+    if args.capacity == Some(0) {
+        return Err(BeadsError::validation("capacity", "must be at least 1"));
+    }
+    if !(1..=24).contains(&args.hours_per_day) {
+        return Err(BeadsError::validation(
+            "hours-per-day",
+            "must be between 1 and 24",
+        ));
+    }
+    if args.default_estimate < 0 {
+        return Err(BeadsError::validation("default-estimate", "must be >= 0"));
+    }
+
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let storage = &storage_ctx.storage;
+
+    let epic = match &args.epic {
+        Some(input) => {
+            let config_layer = config::load_config(&beads_dir, Some(storage), cli)?;
+            let id_config = config::id_config_from_layer(&config_layer);
+            let resolver = IdResolver::new(ResolverConfig::with_prefix(id_config.prefix));
+            let all_ids = storage.get_all_ids()?;
+            let resolved = resolver.resolve(
+                input,
+                |id| storage.id_exists(id).unwrap_or(false),
+                |hash| find_matching_ids(&all_ids, hash),
+            )?;
+            Some(resolved.id)
+        }
+        None => None,
+    };
+
+    let output = build_plan(storage, args, epic, Utc::now())?;
+    match format.as_str() {
+        "json" => ctx.json_pretty(&output),
+        "mermaid" => print!("{}", render_mermaid(&output)),
+        _ => print!("{}", render_text(&output, terminal_width())),
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_lines)]
+fn build_plan(
+    storage: &SqliteStorage,
+    args: &PlanArgs,
+    epic: Option<String>,
+    now: DateTime<Utc>,
+) -> Result<PlanOutput> {
+    let dependencies = storage.get_all_dependency_records()?;
+    let filters = ListFilters {
+        include_deferred: true,
+        ..ListFilters::default()
+    };
+    let mut issues: Vec<Issue> = storage
+        .list_issues(&filters)?
+        .into_iter()
+        .filter(|issue| !issue.status.is_terminal() && issue.issue_type != IssueType::Epic)
+        .collect();
+
+    if let Some(epic_id) = &epic {
+        let descendants = descendants(&dependencies, epic_id);
+        issues.retain(|issue| descendants.contains(issue.id.as_str()));
+    }
+    issues.sort_by(|a, b| {
+        a.priority
+            .cmp(&b.priority)
+            .then_with(|| a.created_at.cmp(&b.created_at))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    let index: HashMap<&str, usize> = issues
+        .iter()
+        .enumerate()
+        .map(|(i, issue)| (issue.id.as_str(), i))
+        .collect();
+    let hours_per_day = args.hours_per_day;
+    let tasks: Vec<Task> = issues
+        .iter()
+        .map(|issue| {
+            let mut preds: Vec<usize> = dependencies
+                .get(&issue.id)
+                .into_iter()
+                .flatten()
+                .filter(|dep| is_sequencing(&dep.dep_type))
+                .filter_map(|dep| index.get(dep.depends_on_id.as_str()).copied())
+                .collect();
+            preds.sort_unstable();
+            preds.dedup();
+            Task {
+                duration: issue
+                    .estimated_minutes
+                    .map_or(args.default_estimate, i64::from)
+                    .max(0),
+                release: issue
+                    .defer_until
+                    .map_or(0, |at| work_minutes_until(now, at, hours_per_day).max(0)),
+                priority: issue.priority.0,
+                preds,
+            }
+        })
+        .collect();
+
+    let schedule =
+        schedule(&tasks, args.capacity).map_err(|cycle| BeadsError::DependencyCycle {
+            path: cycle
+                .iter()
+                .map(|&i| issues[i].id.as_str())
+                .collect::<Vec<_>>()
+                .join(" -> "),
+        })?;
+
+    let makespan = schedule.finish.iter().copied().max().unwrap_or(0);
+    let to_time = |minutes: i64| work_time(now, minutes, hours_per_day);
+
+    let mut planned: Vec<PlannedTask> = issues
+        .iter()
+        .zip(&tasks)
+        .enumerate()
+        .map(|(i, (issue, task))| PlannedTask {
+            id: issue.id.clone(),
+            title: issue.title.clone(),
+            status: issue.status.as_str().to_string(),
+            priority: issue.priority.0,
+            assignee: issue.assignee.clone(),
+            estimate_minutes: task.duration,
+            estimated: issue.estimated_minutes.is_some(),
+            depends_on: task.preds.iter().map(|&p| issues[p].id.clone()).collect(),
+            earliest_start: schedule.earliest_start[i],
+            earliest_finish: schedule.earliest_finish[i],
+            latest_start: schedule.latest_start[i],
+            latest_finish: schedule.latest_finish[i],
+            slack: schedule.slack(i),
+            critical: schedule.critical_path.contains(&i),
+            start: schedule.start[i],
+            finish: schedule.finish[i],
+            worker: schedule.worker[i],
+            predicted_start: to_time(schedule.start[i]),
+            predicted_finish: to_time(schedule.finish[i]),
+            due_at: issue.due_at,
+        })
+        .collect();
+
+    let mut at_risk: Vec<DueRisk> = planned
+        .iter()
+        .filter_map(|task| {
+            let due_at = task.due_at?;
+            (task.predicted_finish > due_at).then(|| DueRisk {
+                id: task.id.clone(),
+                title: task.title.clone(),
+                due_at,
+                predicted_finish: task.predicted_finish,
+                late_minutes: (task.predicted_finish - due_at).num_minutes(),
+                critical: task.critical,
+                cause: if to_time(task.earliest_finish) > due_at {
+                    "dependencies"
+                } else {
+                    "capacity"
+                },
+            })
+        })
+        .collect();
+    // Risks on the critical chain first: those can't be fixed by reordering.
+    at_risk.sort_by(|a, b| {
+        b.critical
+            .cmp(&a.critical)
+            .then_with(|| b.late_minutes.cmp(&a.late_minutes))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    planned.sort_by(|a, b| {
+        a.start
+            .cmp(&b.start)
+            .then_with(|| a.finish.cmp(&b.finish))
+            .then_with(|| a.priority.cmp(&b.priority))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    Ok(PlanOutput {
+        generated_at: now,
+        epic,
+        capacity: args.capacity,
+        hours_per_day,
+        total_work_minutes: tasks.iter().map(|t| t.duration).sum(),
+        makespan_minutes: makespan,
+        predicted_completion: to_time(makespan),
+        critical_path: schedule
+            .critical_path
+            .iter()
+            .map(|&i| issues[i].id.clone())
+            .collect(),
+        unestimated: issues
+            .iter()
+            .filter(|issue| issue.estimated_minutes.is_none())
+            .map(|issue| issue.id.clone())
+            .collect(),
+        tasks: planned,
+        at_risk,
+    })
+}
+
+/// Dependency types that force one issue to finish before another starts.
+///
+/// Parent-child is hierarchy, not sequencing: an epic's children are
+/// scheduled on their own merits.
+const fn is_sequencing(dep_type: &DependencyType) -> bool {
+    dep_type.is_blocking() && !matches!(dep_type, DependencyType::ParentChild)
+}
+
+/// All issues below `root` through parent-child links.
+fn descendants<'a>(
+    dependencies: &'a HashMap<String, Vec<crate::model::Dependency>>,
+    root: &str,
+) -> HashSet<&'a str> {
+    let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+    for deps in dependencies.values() {
+        for dep in deps {
+            if dep.dep_type == DependencyType::ParentChild {
+                children
+                    .entry(dep.depends_on_id.as_str())
+                    .or_default()
+                    .push(dep.issue_id.as_str());
+            }
+        }
+    }
+
+    let mut found = HashSet::new();
+    let mut queue: VecDeque<&str> = children.get(root).cloned().unwrap_or_default().into();
+    while let Some(id) = queue.pop_front() {
+        if found.insert(id) {
+            queue.extend(children.get(id).into_iter().flatten());
+        }
+    }
+    found
+}
+
+/// Calendar time after `minutes` of work at `hours_per_day`.
+fn work_time(now: DateTime<Utc>, minutes: i64, hours_per_day: i64) -> DateTime<Utc> {
+    now + Duration::minutes(minutes * 24 / hours_per_day)
+}
+
+/// Work minutes available between now and `at`.
+fn work_minutes_until(now: DateTime<Utc>, at: DateTime<Utc>, hours_per_day: i64) -> i64 {
+    (at - now).num_minutes() * hours_per_day / 24
+}
+
+/// Critical-path analysis plus, with a capacity, list scheduling.
+///
+/// Returns the tasks of a dependency cycle if there is one.
+fn schedule(tasks: &[Task], capacity: Option<usize>) -> std::result::Result<Schedule, Vec<usize>> {
+    let count = tasks.len();
+    let mut succs: Vec<Vec<usize>> = vec![Vec::new(); count];
+    for (i, task) in tasks.iter().enumerate() {
+        for &p in &task.preds {
+            succs[p].push(i);
+        }
+    }
+    let order = topological_order(tasks, &succs)?;
+
+    let mut sched = Schedule {
+        earliest_start: vec![0; count],
+        earliest_finish: vec![0; count],
+        latest_start: vec![0; count],
+        latest_finish: vec![0; count],
+        ..Schedule::default()
+    };
+    for &i in &order {
+        let ready = tasks[i]
+            .preds
+            .iter()
+            .map(|&p| sched.earliest_finish[p])
+            .max()
+            .unwrap_or(0);
+        sched.earliest_start[i] = ready.max(tasks[i].release);
+        sched.earliest_finish[i] = sched.earliest_start[i] + tasks[i].duration;
+    }
+
+    let makespan = sched.earliest_finish.iter().copied().max().unwrap_or(0);
+    for &i in order.iter().rev() {
+        sched.latest_finish[i] = succs[i]
+            .iter()
+            .map(|&s| sched.latest_start[s])
+            .min()
+            .unwrap_or(makespan);
+        sched.latest_start[i] = sched.latest_finish[i] - tasks[i].duration;
+    }
+    sched.critical_path = critical_path(tasks, &sched, makespan);
+
+    match capacity {
+        Some(workers) => list_schedule(tasks, &succs, &mut sched, workers),
+        None => {
+            sched.start.clone_from(&sched.earliest_start);
+            sched.finish.clone_from(&sched.earliest_finish);
+            sched.worker = vec![None; count];
+        }
+    }
+    Ok(sched)
+}
+
+fn topological_order(
+    tasks: &[Task],
+    succs: &[Vec<usize>],
+) -> std::result::Result<Vec<usize>, Vec<usize>> {
+    let mut waiting: Vec<usize> = tasks.iter().map(|t| t.preds.len()).collect();
+    let mut queue: VecDeque<usize> = (0..tasks.len()).filter(|&i| waiting[i] == 0).collect();
+    let mut order = Vec::with_capacity(tasks.len());
+    while let Some(i) = queue.pop_front() {
+        order.push(i);
+        for &s in &succs[i] {
+            waiting[s] -= 1;
+            if waiting[s] == 0 {
+                queue.push_back(s);
+            }
+        }
+    }
+    if order.len() == tasks.len() {
+        return Ok(order);
+    }
+
+    // Every unordered task waits on another unordered one, so walking
+    // predecessors must revisit a task.
+    let mut path: Vec<usize> = Vec::new();
+    let mut current = (0..tasks.len())
+        .find(|&i| waiting[i] > 0)
+        .unwrap_or_default();
+    while !path.contains(&current) {
+        path.push(current);
+        current = tasks[current]
+            .preds
+            .iter()
+            .copied()
+            .find(|&p| waiting[p] > 0)
+            .unwrap_or(current);
+    }
+    let start = path.iter().position(|&i| i == current).unwrap_or_default();
+    let mut cycle = path.split_off(start);
+    cycle.push(current);
+    Err(cycle)
+}
+
+/// A zero-slack chain from the start of the plan to its end.
+fn critical_path(tasks: &[Task], sched: &Schedule, makespan: i64) -> Vec<usize> {
+    let pick = |candidates: &mut dyn Iterator<Item = usize>| {
+        candidates.min_by_key(|&i| (tasks[i].priority, i))
+    };
+    let mut current = pick(
+        &mut (0..tasks.len())
+            .filter(|&i| sched.slack(i) == 0 && sched.earliest_finish[i] == makespan),
+    );
+    let mut chain = Vec::new();
+    while let Some(i) = current {
+        chain.push(i);
+        current = pick(&mut tasks[i].preds.iter().copied().filter(|&p| {
+            sched.slack(p) == 0 && sched.earliest_finish[p] == sched.earliest_start[i]
+        }));
+    }
+    chain.reverse();
+    chain
+}
+
+/// Serial list scheduling onto `workers`, least slack first.
+fn list_schedule(tasks: &[Task], succs: &[Vec<usize>], sched: &mut Schedule, workers: usize) {
+    let count = tasks.len();
+    sched.start = vec![0; count];
+    sched.finish = vec![0; count];
+    sched.worker = vec![None; count];
+
+    let mut free_at = vec![0_i64; workers];
+    let mut waiting: Vec<usize> = tasks.iter().map(|t| t.preds.len()).collect();
+    let mut eligible: Vec<usize> = (0..count).filter(|&i| waiting[i] == 0).collect();
+
+    while !eligible.is_empty() {
+        let pos = (0..eligible.len())
+            .min_by_key(|&k| {
+                let i = eligible[k];
+                (sched.latest_start[i], tasks[i].priority, i)
+            })
+            .unwrap_or_default();
+        let i = eligible.swap_remove(pos);
+
+        let ready = tasks[i]
+            .preds
+            .iter()
+            .map(|&p| sched.finish[p])
+            .max()
+            .unwrap_or(0)
+            .max(tasks[i].release);
+        let worker = (0..workers)
+            .min_by_key(|&w| (free_at[w], w))
+            .unwrap_or_default();
+        let start = ready.max(free_at[worker]);
+        sched.start[i] = start;
+        sched.finish[i] = start + tasks[i].duration;
+        sched.worker[i] = Some(worker + 1);
+        free_at[worker] = sched.finish[i];
+
+        for &s in &succs[i] {
+            waiting[s] -= 1;
+            if waiting[s] == 0 {
+                eligible.push(s);
+            }
+        }
+    }
+}
+
+/// Work duration in days of `hours_per_day`, hours and minutes.
+fn format_work(minutes: i64, hours_per_day: i64) -> String {
+    let day = hours_per_day * 60;
+    let (days, rest) = (minutes / day, minutes % day);
+    let (hours, mins) = (rest / 60, rest % 60);
+    let parts: Vec<String> = [(days, "d"), (hours, "h"), (mins, "m")]
+        .iter()
+        .filter(|(value, _)| *value != 0)
+        .map(|(value, unit)| format!("{value}{unit}"))
+        .collect();
+    if parts.is_empty() {
+        "0m".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+fn render_text(plan: &PlanOutput, width: usize) -> String {
+    use std::fmt::Write as _;
+
+    let hpd = plan.hours_per_day;
+    let mut out = String::new();
+    let workers = plan.capacity.map_or_else(
+        || "unlimited workers".to_string(),
+        |n| format!("{n} worker(s)"),
+    );
+    let scope = plan
+        .epic
+        .as_ref()
+        .map_or_else(String::new, |epic| format!(" under {epic}"));
+    let _ = writeln!(
+        out,
+        "Plan: {} issue(s){scope} · {workers} · {hpd}h work days",
+        plan.tasks.len()
+    );
+    if plan.tasks.is_empty() {
+        out.push_str("Nothing to schedule.\n");
+        return out;
+    }
+    let _ = writeln!(
+        out,
+        "Work: {} total, {} elapsed · predicted completion {}",
+        format_work(plan.total_work_minutes, hpd),
+        format_work(plan.makespan_minutes, hpd),
+        plan.predicted_completion.format("%Y-%m-%d %H:%M UTC")
+    );
+    out.push('\n');
+
+    let id_width = plan
+        .tasks
+        .iter()
+        .map(|t| t.id.len())
+        .max()
+        .unwrap_or(2)
+        .max(2);
+    let title_width = 24;
+    let fixed = id_width + title_width + 3 * 9 + 4;
+    let chart_width = width.saturating_sub(fixed).max(10);
+    let span = plan.makespan_minutes.max(1);
+    let column = |minute: i64| {
+        let scaled = i128::from(minute) * i128::try_from(chart_width).unwrap_or(i128::MAX)
+            / i128::from(span);
+        usize::try_from(scaled)
+            .unwrap_or(chart_width)
+            .min(chart_width)
+    };
+
+    let _ = writeln!(
+        out,
+        "{:id_width$} {:title_width$} {:>8} {:>8} {:>8}  GANTT",
+        "ID", "TITLE", "START", "DUR", "SLACK"
+    );
+    for task in &plan.tasks {
+        let from = column(task.start);
+        let to = column(task.finish)
+            .max(from + 1)
+            .min(chart_width.max(from + 1));
+        let mut bar = " ".repeat(from);
+        bar.push_str(&(if task.critical { "#" } else { "=" }).repeat(to - from));
+        if plan.capacity.is_none() && task.slack > 0 {
+            let until = column(task.finish + task.slack).max(to);
+            bar.push_str(&".".repeat(until - to));
+        }
+        let _ = writeln!(
+            out,
+            "{:id_width$} {:title_width$} {:>8} {:>8} {:>8}  {}",
+            task.id,
+            truncate_title(&task.title, title_width),
+            format_work(task.start, hpd),
+            format_work(task.estimate_minutes, hpd),
+            format_work(task.slack, hpd),
+            bar.trim_end()
+        );
+    }
+
+    out.push('\n');
+    let _ = writeln!(out, "Critical chain: {}", plan.critical_path.join(" → "));
+    if !plan.at_risk.is_empty() {
+        let _ = writeln!(out, "At risk ({}):", plan.at_risk.len());
+        for risk in &plan.at_risk {
+            let _ = writeln!(
+                out,
+                "  {}{} {}: due {}, predicted {} ({} late, cause: {})",
+                risk.id,
+                if risk.critical { " [critical]" } else { "" },
+                risk.title,
+                risk.due_at.format("%Y-%m-%d %H:%M"),
+                risk.predicted_finish.format("%Y-%m-%d %H:%M"),
+                format_work(risk.late_minutes, 24),
+                risk.cause
+            );
+        }
+    }
+    if !plan.unestimated.is_empty() {
+        let _ = writeln!(
+            out,
+            "Unestimated (default estimate used): {}",
+            plan.unestimated.join(", ")
+        );
+    }
+    out
+}
+
+fn render_mermaid(plan: &PlanOutput) -> String {
+    use std::fmt::Write as _;
+
+    const DATE: &str = "%Y-%m-%d %H:%M";
+    let mut out = String::from("gantt\n");
+    let title = plan
+        .epic
+        .as_ref()
+        .map_or_else(|| "Plan".to_string(), |epic| format!("Plan for {epic}"));
+    let _ = writeln!(out, "    title {title}");
+    out.push_str("    dateFormat YYYY-MM-DD HH:mm\n");
+    out.push_str("    axisFormat %m-%d\n");
+
+    let sections: [(&str, bool); 2] = [("Critical chain", true), ("Other work", false)];
+    for (name, critical) in sections {
+        let tasks: Vec<&PlannedTask> = plan
+            .tasks
+            .iter()
+            .filter(|task| task.critical == critical)
+            .collect();
+        if tasks.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out, "    section {name}");
+        for task in tasks {
+            let tag = if critical { "crit, " } else { "" };
+            let _ = writeln!(
+                out,
+                "    {} ({}) :{tag}{}, {}, {}",
+                mermaid_text(&task.title),
+                task.id,
+                mermaid_id(&task.id),
+                task.predicted_start.format(DATE),
+                task.predicted_finish.format(DATE)
+            );
+        }
+    }
+
+    if !plan.at_risk.is_empty() {
+        out.push_str("    section Due dates at risk\n");
+        for risk in &plan.at_risk {
+            let _ = writeln!(
+                out,
+                "    Due {} :crit, milestone, due_{}, {}, 0d",
+                risk.id,
+                mermaid_id(&risk.id),
+                risk.due_at.format(DATE)
+            );
+        }
+    }
+    out
+}
+
+/// Mermaid task ids may only contain word characters.
+fn mermaid_id(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// `:` separates task text from metadata and `#`/`;` end it.
+fn mermaid_text(text: &str) -> String {
+    text.replace([':', ';', '#'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(duration: i64, preds: &[usize]) -> Task {
+        Task {
+            duration,
+            release: 0,
+            priority: 2,
+            preds: preds.to_vec(),
+        }
+    }
+
+    #[test]
+    fn computes_slack_and_critical_chain() {
+        // 0 -> 1 -> 3 (long), 0 -> 2 -> 3 (short)
+        let tasks = vec![
+            task(60, &[]),
+            task(240, &[0]),
+            task(30, &[0]),
+            task(60, &[1, 2]),
+        ];
+        let sched = schedule(&tasks, None).expect("acyclic");
+        assert_eq!(sched.earliest_start, [0, 60, 60, 300]);
+        assert_eq!(sched.latest_start, [0, 60, 270, 300]);
+        assert_eq!(sched.slack(2), 210);
+        assert_eq!(sched.critical_path, [0, 1, 3]);
+        assert_eq!(sched.finish[3], 360);
+        assert!(sched.worker.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn capacity_serializes_parallel_work() {
+        let tasks = vec![task(60, &[]), task(60, &[]), task(60, &[])];
+        let unlimited = schedule(&tasks, None).expect("acyclic");
+        assert_eq!(unlimited.finish.iter().max(), Some(&60));
+
+        let two = schedule(&tasks, Some(2)).expect("acyclic");
+        assert_eq!(two.finish.iter().max(), Some(&120));
+        assert!(two.worker.iter().all(|w| matches!(w, Some(1 | 2))));
+    }
+
+    #[test]
+    fn capacity_prefers_least_slack() {
+        // Task 1 gates a long chain, so it should start before task 0.
+        let tasks = vec![task(60, &[]), task(60, &[]), task(300, &[1])];
+        let sched = schedule(&tasks, Some(1)).expect("acyclic");
+        assert_eq!(sched.start[1], 0);
+        assert_eq!(sched.start[2], 60);
+        assert_eq!(sched.start[0], 360);
+    }
+
+    #[test]
+    fn release_dates_delay_start() {
+        let mut deferred = task(60, &[]);
+        deferred.release = 120;
+        let sched = schedule(&[deferred], None).expect("acyclic");
+        assert_eq!(sched.earliest_start, [120]);
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let tasks = vec![
+            task(10, &[2]),
+            task(10, &[0]),
+            task(10, &[1]),
+            task(10, &[]),
+        ];
+        let cycle = schedule(&tasks, None).expect_err("cycle");
+        assert_eq!(cycle.first(), cycle.last());
+        assert_eq!(cycle.len(), 4);
+    }
+
+    #[test]
+    fn formats_work_in_work_days() {
+        assert_eq!(format_work(0, 8), "0m");
+        assert_eq!(format_work(90, 8), "1h 30m");
+        assert_eq!(format_work(8 * 60 + 60, 8), "1d 1h");
+    }
+
+    #[test]
+    fn work_time_spreads_over_work_days() {
+        let now = Utc::now();
+        assert_eq!(work_time(now, 8 * 60, 8) - now, Duration::hours(24));
+        assert_eq!(
+            work_minutes_until(now, now + Duration::hours(24), 8),
+            8 * 60
+        );
+    }
+
+    #[test]
+    fn mermaid_escapes_ids_and_titles() {
+        assert_eq!(mermaid_id("bd-a1.2"), "bd_a1_2");
+        assert_eq!(mermaid_text("Fix: this; #1"), "Fix  this   1");
+    }
+}
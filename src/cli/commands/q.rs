@@ -101,6 +101,7 @@ pub fn execute(args: QuickArgs, cli: &config::CliOverrides, ctx: &OutputContext)
         labels: vec![],
         dependencies: vec![],
         comments: vec![],
+        org_extras: None,
     };
 
     // Resolve actor and set created_by
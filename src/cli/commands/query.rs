@@ -6,6 +6,7 @@ use crate::cli::{ListArgs, QueryCommands, QueryDeleteArgs, QueryRunArgs, QuerySa
 use crate::config;
 use crate::error::{BeadsError, Result};
 use crate::output::{OutputContext, OutputMode};
+use crate::storage::FilterExpr;
 use chrono::{DateTime, Utc};
 use rich_rust::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -72,6 +73,8 @@ pub struct SavedFilters {
     pub deferred: bool,
     #[serde(default, skip_serializing_if = "is_false")]
     pub overdue: bool,
+    #[serde(default, rename = "where", skip_serializing_if = "Option::is_none")]
+    pub where_: Option<String>,
 }
 
 /// Helper for serde `skip_serializing_if` (requires reference signature).
@@ -102,6 +105,7 @@ impl From<&ListArgs> for SavedFilters {
             reverse: args.reverse,
             deferred: args.deferred,
             overdue: args.overdue,
+            where_: args.where_.clone(),
         }
     }
 }
@@ -130,6 +134,7 @@ impl SavedFilters {
             reverse: self.reverse,
             deferred: self.deferred,
             overdue: self.overdue,
+            where_: self.where_.clone(),
             // Output-related fields use defaults
             long: false,
             pretty: false,
@@ -191,6 +196,11 @@ impl SavedFilters {
             reverse: cli.reverse || base.reverse,
             deferred: cli.deferred || base.deferred,
             overdue: cli.overdue || base.overdue,
+            // Expressions: saved and CLI expressions must both hold
+            where_: match (base.where_, &cli.where_) {
+                (Some(saved), Some(extra)) => Some(format!("({saved}) AND ({extra})")),
+                (saved, extra) => extra.clone().or(saved),
+            },
             // Output fields from CLI only
             long: cli.long,
             pretty: cli.pretty,
@@ -266,6 +276,11 @@ fn query_save(
         ));
     }
 
+    // Reject malformed expressions now rather than when the query is run
+    if let Some(expr) = args.filters.where_.as_deref() {
+        FilterExpr::parse(expr)?;
+    }
+
     let key = format!("{QUERY_KEY_PREFIX}{name}");
 
     // Check if query already exists
@@ -312,18 +327,15 @@ fn query_run(
     ctx: &OutputContext,
 ) -> Result<()> {
     let name = args.name.trim();
-    let key = format!("{QUERY_KEY_PREFIX}{name}");
-
-    let value = storage
-        .get_config(&key)?
-        .ok_or_else(|| BeadsError::validation("query", format!("Query '{name}' not found")))?;
-
-    let saved_query: SavedQuery = serde_json::from_str(&value).map_err(|e| {
-        BeadsError::validation("saved_query", format!("Invalid saved query format: {e}"))
-    })?;
+    let saved_query = load_saved_query(storage, name)?;
 
     debug!(name, "Loaded saved query");
 
+    // Validate the CLI expression on its own so error columns match what was typed
+    if let Some(expr) = args.filters.where_.as_deref() {
+        FilterExpr::parse(expr)?;
+    }
+
     // Merge saved filters with CLI overrides
     let merged_args = saved_query.filters.merge_with_cli(&args.filters);
 
@@ -334,21 +346,39 @@ fn query_run(
     super::list::execute(&merged_args, ctx.is_json(), cli, ctx)
 }
 
-fn query_list(storage: &crate::storage::SqliteStorage, ctx: &OutputContext) -> Result<()> {
+/// Load a saved query by name.
+///
+/// # Errors
+///
+/// Returns an error if the query does not exist or is malformed.
+pub fn load_saved_query(storage: &crate::storage::SqliteStorage, name: &str) -> Result<SavedQuery> {
+    let key = format!("{QUERY_KEY_PREFIX}{name}");
+
+    let value = storage
+        .get_config(&key)?
+        .ok_or_else(|| BeadsError::validation("query", format!("Query '{name}' not found")))?;
+
+    serde_json::from_str(&value).map_err(|e| {
+        BeadsError::validation("saved_query", format!("Invalid saved query format: {e}"))
+    })
+}
+
+/// All well-formed saved queries, sorted by name.
+///
+/// # Errors
+///
+/// Returns an error if the config table cannot be read.
+pub fn saved_queries(storage: &crate::storage::SqliteStorage) -> Result<Vec<SavedQuery>> {
     let all_config = storage.get_all_config()?;
 
-    let mut queries: Vec<QueryListItem> = Vec::new();
+    let mut queries = Vec::new();
 
     for (key, value) in &all_config {
         if let Some(name) = key.strip_prefix(QUERY_KEY_PREFIX) {
             match serde_json::from_str::<SavedQuery>(value) {
-                Ok(saved) => {
-                    queries.push(QueryListItem {
-                        name: name.to_string(),
-                        description: saved.description,
-                        created_at: saved.created_at.to_rfc3339(),
-                        filters: saved.filters,
-                    });
+                Ok(mut saved) => {
+                    saved.name = name.to_string();
+                    queries.push(saved);
                 }
                 Err(e) => {
                     debug!(name, error = %e, "Skipping malformed saved query");
@@ -359,6 +389,19 @@ fn query_list(storage: &crate::storage::SqliteStorage, ctx: &OutputContext) -> R
 
     // Sort by name
     queries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(queries)
+}
+
+fn query_list(storage: &crate::storage::SqliteStorage, ctx: &OutputContext) -> Result<()> {
+    let queries: Vec<QueryListItem> = saved_queries(storage)?
+        .into_iter()
+        .map(|saved| QueryListItem {
+            name: saved.name,
+            description: saved.description,
+            created_at: saved.created_at.to_rfc3339(),
+            filters: saved.filters,
+        })
+        .collect();
 
     if ctx.is_json() {
         let output = QueryListOutput {
@@ -845,9 +888,11 @@ mod tests {
             reverse: true,
             deferred: true,
             overdue: true,
+            where_: Some("label:backend OR priority<=1".to_string()),
         };
 
         let json = serde_json::to_string(&filters).unwrap();
+        assert!(json.contains("\"where\""));
         let parsed: SavedFilters = serde_json::from_str(&json).unwrap();
 
         assert_eq!(parsed.status, filters.status);
@@ -869,6 +914,33 @@ mod tests {
         assert_eq!(parsed.reverse, filters.reverse);
         assert_eq!(parsed.deferred, filters.deferred);
         assert_eq!(parsed.overdue, filters.overdue);
+        assert_eq!(parsed.where_, filters.where_);
+    }
+
+    #[test]
+    fn test_merge_where_expressions_are_anded() {
+        let saved = SavedFilters {
+            where_: Some("label:backend OR priority<=1".to_string()),
+            ..Default::default()
+        };
+
+        let merged = saved.merge_with_cli(&ListArgs::default());
+        assert_eq!(
+            merged.where_.as_deref(),
+            Some("label:backend OR priority<=1")
+        );
+
+        let cli = ListArgs {
+            where_: Some("NOT assignee:bot".to_string()),
+            ..Default::default()
+        };
+        let merged = saved.merge_with_cli(&cli);
+        let combined = merged.where_.expect("combined expression");
+        assert_eq!(
+            combined,
+            "(label:backend OR priority<=1) AND (NOT assignee:bot)"
+        );
+        assert!(FilterExpr::parse(&combined).is_ok());
     }
 
     #[test]
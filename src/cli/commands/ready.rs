@@ -6,10 +6,14 @@ use crate::cli::{OutputFormat, ReadyArgs, SortPolicy, resolve_output_format_basi
 use crate::config;
 use crate::error::Result;
 use crate::format::{ReadyIssue, format_priority_badge, terminal_width, truncate_title};
-use crate::model::{IssueType, Priority};
+use crate::model::{Issue, IssueType, Priority};
 use crate::output::{IssueTable, IssueTableColumns, OutputContext, OutputMode};
-use crate::storage::{ReadyFilters, ReadySortPolicy};
+use crate::storage::scoring::{ScoreBreakdown, ScoreWeights};
+use crate::storage::{FilterExpr, ReadyFilters, ReadySortPolicy, SqliteStorage};
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
 use std::io::IsTerminal;
+use std::path::PathBuf;
 use std::str::FromStr;
 use tracing::{debug, info, trace};
 use unicode_width::UnicodeWidthStr;
@@ -27,10 +31,8 @@ pub fn execute(
 ) -> Result<()> {
     // Open storage
     let beads_dir = config::discover_beads_dir_with_cli(cli)?;
-    let storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
-    let storage = &storage_ctx.storage;
-
-    let config_layer = config::load_config(&beads_dir, Some(storage), cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let config_layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
     let external_db_paths = config::external_project_db_paths(&config_layer, &beads_dir);
     let use_color = config::should_use_color(&config_layer);
     let max_width = if std::io::stdout().is_terminal() {
@@ -42,44 +44,19 @@ pub fn execute(
     let quiet = cli.quiet.unwrap_or(false);
     let ctx = OutputContext::from_output_format(output_format, quiet, !use_color);
 
-    let filters = ReadyFilters {
-        assignee: args.assignee.clone(),
-        unassigned: args.unassigned,
-        labels_and: args.label.clone(),
-        labels_or: args.label_any.clone(),
-        types: parse_types(&args.type_)?,
-        priorities: parse_priorities(&args.priority)?,
-        include_deferred: args.include_deferred,
-        // Fetch all candidates to allow post-filtering of external blockers
-        limit: None,
-        parent: args.parent.clone(),
-        recursive: args.recursive,
-    };
-
-    let sort_policy = match args.sort {
-        SortPolicy::Hybrid => ReadySortPolicy::Hybrid,
-        SortPolicy::Priority => ReadySortPolicy::Priority,
-        SortPolicy::Oldest => ReadySortPolicy::Oldest,
+    let actor = config::resolve_actor(&config_layer);
+    // Hand lapsed claims back first so they show up as ready again.
+    storage_ctx.storage.expire_leases(&actor, Utc::now())?;
+    storage_ctx.flush_no_db_if_dirty()?;
+    let storage = &storage_ctx.storage;
+    let weights = config::score_weights_from_layer(&config_layer)?;
+    let ready_issues = query_ready(storage, args, &actor, &weights, &external_db_paths)?;
+    let scores = if args.explain {
+        storage.score_issues(&ready_issues, &weights, Utc::now())?
+    } else {
+        HashMap::new()
     };
 
-    info!("Fetching ready issues");
-    debug!(filters = ?filters, sort = ?sort_policy, "Applied ready filters");
-
-    // Get ready issues from storage (blocked cache only)
-    let mut ready_issues = storage.get_ready_issues(&filters, sort_policy)?;
-
-    let external_statuses =
-        storage.resolve_external_dependency_statuses(&external_db_paths, true)?;
-    let external_blockers = storage.external_blockers(&external_statuses)?;
-    if !external_blockers.is_empty() {
-        ready_issues.retain(|issue| !external_blockers.contains_key(&issue.id));
-    }
-
-    // Apply limit after external filtering
-    if args.limit > 0 && ready_issues.len() > args.limit {
-        ready_issues.truncate(args.limit);
-    }
-
     info!(count = ready_issues.len(), "Found ready issues");
     for issue in ready_issues.iter().take(5) {
         trace!(id = %issue.id, priority = issue.priority.0, "Ready issue");
@@ -91,18 +68,16 @@ pub fn execute(
     }
     match output_format {
         OutputFormat::Json => {
-            let ready_output: Vec<ReadyIssue> = ready_issues.iter().map(ReadyIssue::from).collect();
-            ctx.json_pretty(&ready_output);
+            ctx.json_pretty(&ready_output(&ready_issues, &scores));
         }
         OutputFormat::Toon => {
-            let ready_output: Vec<ReadyIssue> = ready_issues.iter().map(ReadyIssue::from).collect();
-            ctx.toon_with_stats(&ready_output, args.stats);
+            ctx.toon_with_stats(&ready_output(&ready_issues, &scores), args.stats);
         }
         OutputFormat::Text | OutputFormat::Csv => {
             if ready_issues.is_empty() {
                 // Match bd empty output format
                 println!("✨ No open issues");
-            } else if matches!(ctx.mode(), OutputMode::Rich) {
+            } else if matches!(ctx.mode(), OutputMode::Rich) && !args.explain {
                 let columns = IssueTableColumns {
                     id: true,
                     priority: true,
@@ -134,6 +109,9 @@ pub fn execute(
                 for (i, issue) in ready_issues.iter().enumerate() {
                     let line = format_ready_line(i + 1, issue, use_color, max_width, args.wrap);
                     println!("{line}");
+                    if let Some(score) = scores.get(&issue.id) {
+                        println!("    score {}", score.explain());
+                    }
                 }
             }
         }
@@ -142,6 +120,104 @@ pub fn execute(
     Ok(())
 }
 
+fn ready_output(issues: &[Issue], scores: &HashMap<String, ScoreBreakdown>) -> Vec<ReadyIssue> {
+    issues
+        .iter()
+        .map(|issue| ReadyIssue {
+            score: scores.get(&issue.id).cloned(),
+            ..ReadyIssue::from(issue)
+        })
+        .collect()
+}
+
+/// Run the ready query described by `args`, dropping issues blocked by
+/// external dependencies and applying the limit.
+///
+/// # Errors
+///
+/// Returns an error if a filter is invalid or the query fails.
+pub fn query_ready(
+    storage: &SqliteStorage,
+    args: &ReadyArgs,
+    actor: &str,
+    weights: &ScoreWeights,
+    external_db_paths: &HashMap<String, PathBuf>,
+) -> Result<Vec<Issue>> {
+    let (filters, sort_policy) = ready_filters(args, actor, weights)?;
+
+    info!("Fetching ready issues");
+    debug!(filters = ?filters, sort = ?sort_policy, "Applied ready filters");
+
+    // Get ready issues from storage (blocked cache only)
+    let mut ready_issues = storage.get_ready_issues(&filters, sort_policy)?;
+
+    let external_blockers = external_blocker_ids(storage, external_db_paths)?;
+    if !external_blockers.is_empty() {
+        ready_issues.retain(|issue| !external_blockers.contains(&issue.id));
+    }
+
+    // Apply limit after external filtering
+    if args.limit > 0 && ready_issues.len() > args.limit {
+        ready_issues.truncate(args.limit);
+    }
+
+    Ok(ready_issues)
+}
+
+/// Translate `args` into storage filters and a sort policy. The limit is
+/// left to the caller so external blockers can be dropped first.
+///
+/// # Errors
+///
+/// Returns an error if a type, priority or `--where` filter is invalid.
+pub fn ready_filters(
+    args: &ReadyArgs,
+    actor: &str,
+    weights: &ScoreWeights,
+) -> Result<(ReadyFilters, ReadySortPolicy)> {
+    let filters = ReadyFilters {
+        assignee: args.assignee.clone(),
+        unassigned: args.unassigned,
+        labels_and: args.label.clone(),
+        labels_or: args.label_any.clone(),
+        types: parse_types(&args.type_)?,
+        priorities: parse_priorities(&args.priority)?,
+        include_deferred: args.include_deferred,
+        // Fetch all candidates to allow post-filtering of external blockers
+        limit: None,
+        parent: args.parent.clone(),
+        recursive: args.recursive,
+        filter_expr: args.where_.as_deref().map(FilterExpr::parse).transpose()?,
+        lease_actor: Some(actor.to_string()),
+        score_weights: weights.clone(),
+    };
+
+    let sort_policy = match args.sort {
+        SortPolicy::Hybrid => ReadySortPolicy::Hybrid,
+        SortPolicy::Priority => ReadySortPolicy::Priority,
+        SortPolicy::Oldest => ReadySortPolicy::Oldest,
+        SortPolicy::Score => ReadySortPolicy::Score,
+    };
+    Ok((filters, sort_policy))
+}
+
+/// IDs of issues blocked by dependencies in external projects.
+///
+/// # Errors
+///
+/// Returns an error if an external database cannot be queried.
+pub fn external_blocker_ids(
+    storage: &SqliteStorage,
+    external_db_paths: &HashMap<String, PathBuf>,
+) -> Result<HashSet<String>> {
+    let external_statuses =
+        storage.resolve_external_dependency_statuses(external_db_paths, true)?;
+    Ok(storage
+        .external_blockers(&external_statuses)?
+        .into_keys()
+        .collect())
+}
+
 fn format_ready_line(
     index: usize,
     issue: &crate::model::Issue,
@@ -0,0 +1,470 @@
+//! Recur command implementation.
+//!
+//! A series attaches a recurrence rule (see [`parse_recurrence`]) to a
+//! database template. `recur run` makes sure the next occurrence of every
+//! series exists: it instantiates the template with `due_at` set to the
+//! occurrence and `defer_until` set `--lead` earlier, tags the new root with
+//! the external ref `recur:<template>:<time>` and links it back to the
+//! template with a `discovered-from` dependency. External refs are unique,
+//! so running it again, from any scheduler or two machines at once, never
+//! creates an occurrence twice.
+//!
+//! Series live in the config table under `recur:<template-id>`.
+
+use crate::cli::commands::create::{CreateConfig, create_config, resolve_id};
+use crate::cli::commands::template::{self, TemplateNode, bind_vars, tree_from_db};
+use crate::cli::{RecurAddArgs, RecurCommands, RecurRemoveArgs, RecurRunArgs};
+use crate::config;
+use crate::error::{BeadsError, Result, StructuredError};
+use crate::model::{Dependency, DependencyType};
+use crate::output::OutputContext;
+use crate::storage::SqliteStorage;
+use crate::storage::batch::BatchStep;
+use crate::util::id::{IdResolver, ResolverConfig};
+use crate::util::time::{Recurrence, parse_duration, parse_flexible_timestamp, parse_recurrence};
+use chrono::{DateTime, Duration, Local, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use tracing::{debug, info};
+
+/// Prefix for series keys in the config table.
+const SERIES_KEY_PREFIX: &str = "recur:";
+
+/// Variable filled with the occurrence date when the template uses it.
+const DATE_VAR: &str = "date";
+
+/// A recurrence rule attached to a template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Series {
+    /// Template root issue ID.
+    pub template: String,
+    /// Rule text, e.g. `every 2w on mon`.
+    pub rule: String,
+    /// How long before each occurrence it becomes ready, e.g. `2d`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lead: Option<String>,
+    /// Template variables as `name=value`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vars: Vec<String>,
+    /// Anchor for intervals and for rules that name no weekday or day.
+    pub start: DateTime<Utc>,
+}
+
+impl Series {
+    fn recurrence(&self) -> Result<Recurrence> {
+        parse_recurrence(&self.rule, "rule")
+    }
+
+    fn lead(&self) -> Result<Duration> {
+        self.lead
+            .as_deref()
+            .map_or(Ok(Duration::zero()), |lead| parse_duration(lead, "lead"))
+    }
+
+    fn next_after(&self, after: DateTime<Utc>) -> Result<Option<DateTime<Utc>>> {
+        Ok(self.recurrence()?.next_after(self.start, after))
+    }
+
+    fn external_ref(&self, occurrence: DateTime<Utc>) -> String {
+        format!(
+            "{SERIES_KEY_PREFIX}{}:{}",
+            self.template,
+            occurrence.format("%Y-%m-%dT%H:%MZ")
+        )
+    }
+
+    /// Stored variables plus `date` when the template uses it.
+    fn vars_for(&self, tree: &TemplateNode, occurrence: DateTime<Utc>) -> Vec<String> {
+        let mut used = BTreeSet::new();
+        tree.placeholders(&mut used);
+        let mut vars = self.vars.clone();
+        let date_set = vars.iter().any(|pair| {
+            pair.split_once('=')
+                .is_some_and(|(name, _)| name.trim() == DATE_VAR)
+        });
+        if used.contains(DATE_VAR) && !date_set {
+            let date = occurrence.with_timezone(&Local).format("%Y-%m-%d");
+            vars.push(format!("{DATE_VAR}={date}"));
+        }
+        vars
+    }
+}
+
+/// JSON output for `recur list`.
+#[derive(Debug, Serialize)]
+struct SeriesListItem {
+    #[serde(flatten)]
+    series: Series,
+    title: Option<String>,
+    next: Option<DateTime<Utc>>,
+}
+
+/// What `recur run` did for one series.
+#[derive(Debug, Serialize)]
+struct RunResult {
+    template: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    occurrence: Option<DateTime<Utc>>,
+    /// `created`, `exists`, `would_create`, `finished` or `failed`.
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<Value>,
+}
+
+/// Execute the recur command.
+///
+/// # Errors
+///
+/// Returns an error if a rule or template is invalid, or a database
+/// operation fails. `run` reports every series before returning the first
+/// failure.
+pub fn execute(
+    command: &RecurCommands,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    match command {
+        RecurCommands::Add(args) => execute_add(args, cli, ctx),
+        RecurCommands::List => execute_list(cli, ctx),
+        RecurCommands::Remove(args) => execute_remove(args, cli, ctx),
+        RecurCommands::Run(args) => execute_run(args, cli, ctx),
+    }
+}
+
+fn execute_add(args: &RecurAddArgs, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let id_config = config::id_config_from_layer(&layer);
+    let resolver = IdResolver::new(ResolverConfig::with_prefix(id_config.prefix));
+
+    let storage = &storage_ctx.storage;
+    let id = resolve_id(&resolver, storage, &args.template)?;
+    if !storage
+        .get_issue(&id)?
+        .is_some_and(|issue| issue.is_template)
+    {
+        return Err(BeadsError::validation(
+            "template",
+            format!("{id} is not a template"),
+        ));
+    }
+    let key = format!("{SERIES_KEY_PREFIX}{id}");
+    if storage.get_config(&key)?.is_some() {
+        return Err(BeadsError::validation(
+            "template",
+            format!("{id} already recurs; remove the series first to replace it"),
+        ));
+    }
+
+    let series = Series {
+        template: id.clone(),
+        rule: args.rule.trim().to_string(),
+        lead: args.lead.clone(),
+        vars: args.vars.clone(),
+        start: args.start.as_deref().map_or_else(
+            || Ok(Utc::now()),
+            |start| parse_flexible_timestamp(start, "start"),
+        )?,
+    };
+    series.lead()?;
+    let next = series.next_after(Utc::now())?;
+    // Reject variables the template cannot fill now rather than at run time.
+    let tree = tree_from_db(storage, &id)?;
+    bind_vars(&tree, &series.vars_for(&tree, next.unwrap_or(series.start)))?;
+
+    storage_ctx
+        .storage
+        .set_config(&key, &serde_json::to_string(&series)?)?;
+    info!(template = %id, rule = %series.rule, "Recurring series added");
+
+    if ctx.is_json() {
+        ctx.json_pretty(&SeriesListItem {
+            series,
+            title: Some(tree.title),
+            next,
+        });
+    } else {
+        let next = next.map_or_else(
+            || "none".to_string(),
+            |next| {
+                next.with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            },
+        );
+        ctx.success(&format!("{id} recurs {} (next: {next})", series.rule));
+    }
+    Ok(())
+}
+
+fn execute_list(cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let storage = &storage_ctx.storage;
+
+    let now = Utc::now();
+    let mut items = Vec::new();
+    for series in all_series(storage)? {
+        items.push(SeriesListItem {
+            title: storage
+                .get_issue(&series.template)?
+                .map(|issue| issue.title),
+            next: series.next_after(now).ok().flatten(),
+            series,
+        });
+    }
+
+    if ctx.is_json() {
+        ctx.json_pretty(&items);
+        return Ok(());
+    }
+    if items.is_empty() {
+        println!("No recurring series");
+        return Ok(());
+    }
+    for item in &items {
+        let next = item.next.map_or_else(
+            || "none".to_string(),
+            |next| {
+                next.with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            },
+        );
+        let lead = item
+            .series
+            .lead
+            .as_deref()
+            .map_or_else(String::new, |lead| format!(", lead {lead}"));
+        println!(
+            "{} {}: {} (next: {next}{lead})",
+            item.series.template,
+            item.title.as_deref().unwrap_or("<missing template>"),
+            item.series.rule
+        );
+    }
+    Ok(())
+}
+
+fn execute_remove(
+    args: &RecurRemoveArgs,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let id_config = config::id_config_from_layer(&layer);
+    let resolver = IdResolver::new(ResolverConfig::with_prefix(id_config.prefix));
+
+    // A series can outlive its template, so fall back to the literal ID.
+    let id = resolve_id(&resolver, &storage_ctx.storage, &args.template)
+        .unwrap_or_else(|_| args.template.trim().to_string());
+    if !storage_ctx
+        .storage
+        .delete_config(&format!("{SERIES_KEY_PREFIX}{id}"))?
+    {
+        return Err(BeadsError::validation(
+            "template",
+            format!("{id} has no recurring series"),
+        ));
+    }
+    info!(template = %id, "Recurring series removed");
+
+    if ctx.is_json() {
+        ctx.json_pretty(&serde_json::json!({ "template": id, "removed": true }));
+    } else {
+        ctx.success(&format!("{id} no longer recurs"));
+    }
+    Ok(())
+}
+
+fn execute_run(args: &RecurRunArgs, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let create_config = create_config(&layer)?;
+
+    let now = Utc::now();
+    let mut results = Vec::new();
+    let mut first_error = None;
+    for series in all_series(&storage_ctx.storage)? {
+        match run_series(
+            &mut storage_ctx.storage,
+            &series,
+            now,
+            args.dry_run,
+            &create_config,
+        ) {
+            Ok(result) => results.push(result),
+            Err(error) => {
+                results.push(RunResult {
+                    template: series.template,
+                    occurrence: None,
+                    status: "failed",
+                    id: None,
+                    error: Some(StructuredError::from_error(&error).to_json()["error"].clone()),
+                });
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+
+    if ctx.is_json() {
+        ctx.json_pretty(&results);
+    } else if results.is_empty() {
+        println!("No recurring series");
+    } else {
+        for result in &results {
+            let when = result.occurrence.map_or_else(String::new, |at| {
+                format!(" {}", at.with_timezone(&Local).format("%Y-%m-%d %H:%M"))
+            });
+            let id = result.id.as_deref().unwrap_or("-");
+            println!("{:<12} {}{when} -> {id}", result.status, result.template);
+        }
+    }
+
+    if !args.dry_run {
+        storage_ctx.flush_no_db_if_dirty()?;
+    }
+    first_error.map_or(Ok(()), Err)
+}
+
+/// Make sure the first occurrence after `now` exists.
+fn run_series(
+    storage: &mut SqliteStorage,
+    series: &Series,
+    now: DateTime<Utc>,
+    dry_run: bool,
+    config: &CreateConfig,
+) -> Result<RunResult> {
+    let mut result = RunResult {
+        template: series.template.clone(),
+        occurrence: None,
+        status: "finished",
+        id: None,
+        error: None,
+    };
+    let Some(occurrence) = series.next_after(now)? else {
+        return Ok(result);
+    };
+    result.occurrence = Some(occurrence);
+
+    let external_ref = series.external_ref(occurrence);
+    if let Some(existing) = storage.find_by_external_ref(&external_ref)? {
+        debug!(template = %series.template, id = %existing.id, "Occurrence already exists");
+        result.status = "exists";
+        result.id = Some(existing.id);
+        return Ok(result);
+    }
+
+    if !storage
+        .get_issue(&series.template)?
+        .is_some_and(|issue| issue.is_template)
+    {
+        return Err(BeadsError::validation(
+            "template",
+            format!("{} is missing or no longer a template", series.template),
+        ));
+    }
+    let tree = tree_from_db(storage, &series.template)?;
+    let vars = bind_vars(&tree, &series.vars_for(&tree, occurrence))?;
+    let (mut steps, issues) = template::plan(storage, &tree, None, &vars, config)?;
+    let defer_until = occurrence - series.lead()?;
+    for step in &mut steps {
+        if let BatchStep::Create(issue) = step {
+            issue.due_at = Some(occurrence);
+            issue.defer_until = Some(defer_until);
+            if issue.id == issues[0].id {
+                issue.external_ref = Some(external_ref.clone());
+                issue.dependencies.push(Dependency {
+                    issue_id: issue.id.clone(),
+                    depends_on_id: series.template.clone(),
+                    dep_type: DependencyType::DiscoveredFrom,
+                    created_at: Utc::now(),
+                    created_by: Some(config.actor.clone()),
+                    metadata: None,
+                    thread_id: None,
+                });
+            }
+            issue.content_hash = Some(issue.compute_content_hash());
+        }
+    }
+    result.id = Some(issues[0].id.clone());
+
+    if dry_run {
+        result.status = "would_create";
+        return Ok(result);
+    }
+    if let Err(failure) = storage.apply_batch(&steps, &config.actor, |_, _| Ok(())) {
+        // Another run may have created it since the check above.
+        if let Some(existing) = storage.find_by_external_ref(&external_ref)? {
+            result.status = "exists";
+            result.id = Some(existing.id);
+            return Ok(result);
+        }
+        return Err(failure.error);
+    }
+    info!(template = %series.template, id = ?result.id, %occurrence, "Created occurrence");
+    result.status = "created";
+    Ok(result)
+}
+
+/// All well-formed series, sorted by template ID.
+fn all_series(storage: &SqliteStorage) -> Result<Vec<Series>> {
+    let mut series: Vec<Series> = storage
+        .get_all_config()?
+        .iter()
+        .filter(|(key, _)| key.starts_with(SERIES_KEY_PREFIX))
+        .filter_map(|(key, value)| {
+            serde_json::from_str(value)
+                .map_err(|e| debug!(key, error = %e, "Skipping malformed series"))
+                .ok()
+        })
+        .collect();
+    series.sort_by(|a, b| a.template.cmp(&b.template));
+    Ok(series)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(rule: &str, lead: Option<&str>) -> Series {
+        Series {
+            template: "bd-tpl".to_string(),
+            rule: rule.to_string(),
+            lead: lead.map(str::to_string),
+            vars: vec!["team=ops".to_string()],
+            start: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn external_ref_names_series_and_minute() {
+        let at = DateTime::parse_from_rfc3339("2025-03-03T09:00:30Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            series("every week", None).external_ref(at),
+            "recur:bd-tpl:2025-03-03T09:00Z"
+        );
+    }
+
+    #[test]
+    fn vars_add_date_only_when_used() {
+        let with_date: TemplateNode =
+            serde_yaml::from_str("title: 'Audit {{date}} for {{team}}'").unwrap();
+        let without: TemplateNode = serde_yaml::from_str("title: 'Audit for {{team}}'").unwrap();
+        let s = series("every week", Some("2d"));
+        let at = Utc::now();
+        assert_eq!(s.vars_for(&with_date, at).len(), 2);
+        assert_eq!(s.vars_for(&without, at), vec!["team=ops".to_string()]);
+        assert_eq!(s.lead().unwrap(), Duration::days(2));
+        assert!(series("every week", Some("soon")).lead().is_err());
+    }
+}
@@ -58,6 +58,7 @@ pub fn execute(
     let actor = config::resolve_actor(&config_layer);
     let id_config = config::id_config_from_layer(&config_layer);
     let resolver = IdResolver::new(ResolverConfig::with_prefix(id_config.prefix));
+    let workflow = config::workflow::Workflow::from_layer(&config_layer)?;
     let all_ids = storage_ctx.storage.get_all_ids()?;
     let storage = &mut storage_ctx.storage;
 
@@ -109,6 +110,8 @@ pub fn execute(
 
         tracing::debug!(previous_status = ?issue.status, "Issue was previously {:?}", issue.status);
 
+        workflow.check_transition(&issue, &Status::Open)?;
+
         // Build update: set status=open, clear closed_at, clear tombstone fields
         let update = IssueUpdate {
             status: Some(Status::Open),
@@ -1,17 +1,21 @@
 //! Search command implementation.
 //!
-//! Classic bd-style LIKE search across title/description/id with list-like filters.
+//! Ranked full-text search (SQLite FTS5, bm25) across title, description,
+//! design, acceptance criteria, notes, and comments, with list-like filters.
+//! Classic substring matches on title, description, and ID are kept as an
+//! unranked fallback.
 
 use crate::cli::{ListArgs, OutputFormat, SearchArgs, resolve_output_format};
 use crate::config;
 use crate::error::{BeadsError, Result};
 use crate::format::{
-    IssueWithCounts, TextFormatOptions, csv, format_issue_line_with, terminal_width,
+    IssueWithCounts, SearchResult, TextFormatOptions, csv, format_issue_line_with, terminal_width,
 };
 use crate::model::{IssueType, Priority, Status};
 use crate::output::{IssueTable, IssueTableColumns, OutputContext, OutputMode};
-use crate::storage::{ListFilters, SqliteStorage};
+use crate::storage::{FilterExpr, ListFilters, SqliteStorage};
 use chrono::Utc;
+use crossterm::style::Stylize;
 use regex::{Regex, RegexBuilder};
 use std::collections::{HashMap, HashSet};
 use std::io::IsTerminal;
@@ -62,7 +66,15 @@ pub fn execute(
         None
     };
 
-    let issues = storage.search_issues(query, &filters)?;
+    let hits = storage.search_issues_ranked(query, &filters)?;
+    let mut ranking: HashMap<String, (Option<f64>, Option<String>)> = HashMap::new();
+    let issues: Vec<_> = hits
+        .into_iter()
+        .map(|hit| {
+            ranking.insert(hit.issue.id.clone(), (hit.score, hit.snippet));
+            hit.issue
+        })
+        .collect();
     let issues = if client_filters {
         apply_client_filters(storage, issues, &args.filters)?
     } else {
@@ -115,11 +127,12 @@ pub fn execute(
 
     match output_format {
         OutputFormat::Json => {
-            ctx.json_pretty(&issues_with_counts);
+            ctx.json_pretty(&to_search_results(issues_with_counts, &mut ranking));
             return Ok(());
         }
         OutputFormat::Toon => {
-            ctx.toon_with_stats(&issues_with_counts, args.filters.stats);
+            let results = to_search_results(issues_with_counts, &mut ranking);
+            ctx.toon_with_stats(&results, args.filters.stats);
             return Ok(());
         }
         OutputFormat::Csv => {
@@ -140,7 +153,12 @@ pub fn execute(
             .iter()
             .map(|iwc| iwc.issue.clone())
             .collect();
-        let context_snippets = build_context_snippets(&issues, query);
+        let mut context_snippets = build_context_snippets(&issues, query);
+        for (id, (_, snippet)) in &ranking {
+            if let Some(snippet) = snippet {
+                context_snippets.insert(id.clone(), snippet.replace("**", ""));
+            }
+        }
         let show_context = !context_snippets.is_empty();
         let columns = IssueTableColumns {
             id: true,
@@ -180,11 +198,58 @@ pub fn execute(
     for iwc in &issues_with_counts {
         let line = format_issue_line_with(&iwc.issue, format_options);
         ctx.print(&line);
+        // Title-only hits are already visible on the issue line.
+        if let Some((_, Some(snippet))) = ranking.get(&iwc.issue.id) {
+            if normalize_whitespace(&snippet.replace("**", "")) != iwc.issue.title {
+                ctx.print(&format!("    {}", render_snippet(snippet, use_color)));
+            }
+        }
     }
 
     Ok(())
 }
 
+fn to_search_results(
+    issues: Vec<IssueWithCounts>,
+    ranking: &mut HashMap<String, (Option<f64>, Option<String>)>,
+) -> Vec<SearchResult> {
+    issues
+        .into_iter()
+        .map(|iwc| {
+            let (score, snippet) = ranking.remove(&iwc.issue.id).unwrap_or_default();
+            SearchResult {
+                issue: iwc.issue,
+                dependency_count: iwc.dependency_count,
+                dependent_count: iwc.dependent_count,
+                score,
+                snippet,
+            }
+        })
+        .collect()
+}
+
+/// Render a `**`-delimited FTS snippet for the terminal.
+///
+/// Matches are shown bold when color is enabled and keep their `**` markers
+/// otherwise, so plain output stays greppable.
+fn render_snippet(snippet: &str, use_color: bool) -> String {
+    let snippet = normalize_whitespace(snippet);
+    if !use_color {
+        return snippet;
+    }
+    snippet
+        .split("**")
+        .enumerate()
+        .map(|(idx, part)| {
+            if idx % 2 == 1 {
+                part.yellow().bold().to_string()
+            } else {
+                part.to_string()
+            }
+        })
+        .collect()
+}
+
 fn build_context_snippets(issues: &[crate::model::Issue], query: &str) -> HashMap<String, String> {
     let Some(regex) = build_highlight_regex(query) else {
         return HashMap::new();
@@ -292,7 +357,14 @@ fn build_filters(args: &ListArgs) -> Result<ListFilters> {
         Some(parsed)
     };
 
+    let filter_expr = args.where_.as_deref().map(FilterExpr::parse).transpose()?;
+    // An expression that constrains status decides closed/deferred visibility itself.
+    let where_status = filter_expr
+        .as_ref()
+        .is_some_and(FilterExpr::references_status);
+
     let include_closed = args.all
+        || where_status
         || statuses
             .as_ref()
             .is_some_and(|parsed| parsed.iter().any(Status::is_terminal));
@@ -300,6 +372,7 @@ fn build_filters(args: &ListArgs) -> Result<ListFilters> {
     // Deferred issues are included by default (consistent with "open" status semantics).
     let include_deferred = args.deferred
         || args.all
+        || where_status
         || statuses.is_none()
         || statuses
             .as_ref()
@@ -326,6 +399,7 @@ fn build_filters(args: &ListArgs) -> Result<ListFilters> {
         labels_or: None,
         updated_before: None,
         updated_after: None,
+        filter_expr,
     })
 }
 
@@ -522,6 +596,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         }
     }
 
@@ -603,4 +678,13 @@ mod tests {
         apply_sort(&mut items, Some("created_at")).expect("sort");
         assert_eq!(items[0].issue.id, "bd-new");
     }
+
+    #[test]
+    fn test_render_snippet_marks_matches() {
+        let snippet = "...the **login**\n  flow...";
+        assert_eq!(render_snippet(snippet, false), "...the **login** flow...");
+        let colored = render_snippet(snippet, true);
+        assert!(!colored.contains("**"));
+        assert!(colored.contains("login"));
+    }
 }
@@ -0,0 +1,35 @@
+//! Serve command implementation.
+//!
+//! Runs the JSON-RPC daemon in the foreground until a client sends
+//! `shutdown`. While it listens on the default socket, supported `--json`
+//! commands are forwarded to it.
+
+use crate::cli::ServeArgs;
+use crate::config;
+use crate::daemon;
+use crate::error::Result;
+use crate::mcp::McpServer;
+use crate::output::OutputContext;
+
+/// Execute the serve command.
+///
+/// # Errors
+///
+/// Returns an error if the workspace cannot be opened or another daemon
+/// already listens on the socket.
+pub fn execute(
+    args: &ServeArgs,
+    allow_stale: bool,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let server = McpServer::open(cli, allow_stale)?;
+    let socket_path = args
+        .socket
+        .clone()
+        .unwrap_or_else(|| daemon::default_socket_path(server.beads_dir()));
+    if !ctx.is_quiet() && !ctx.is_json() {
+        eprintln!("Listening on {}", socket_path.display());
+    }
+    daemon::serve(server, &socket_path)
+}
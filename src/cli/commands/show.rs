@@ -1,11 +1,14 @@
 //! Show command implementation.
 
+use crate::cli::commands::compact;
 use crate::cli::{ShowArgs, resolve_output_format_basic};
 use crate::config;
 use crate::error::{BeadsError, Result};
 use crate::format::{format_priority_label, format_status_icon_colored};
+use crate::model::OrgExtras;
 use crate::output::{IssuePanel, OutputContext, OutputMode};
 use crate::util::id::{IdResolver, ResolverConfig};
+use crate::util::time::{format_duration, parse_past_timestamp};
 use std::fmt::Write as FmtWrite;
 
 /// Execute the show command.
@@ -43,6 +46,12 @@ pub fn execute(
     let quiet = cli.quiet.unwrap_or(false);
     let ctx = OutputContext::from_output_format(output_format, quiet, !use_color);
 
+    let as_of = args
+        .as_of
+        .as_deref()
+        .map(|value| parse_past_timestamp(value, "as-of"))
+        .transpose()?;
+
     let mut details_list = Vec::new();
     for id_input in target_ids {
         let resolution = resolver.resolve(
@@ -51,6 +60,19 @@ pub fn execute(
             |hash| storage.find_ids_by_hash(hash).unwrap_or_default(),
         )?;
 
+        if let Some(as_of) = as_of {
+            let details = storage
+                .get_issue_details_as_of(&resolution.id, as_of)?
+                .ok_or_else(|| {
+                    BeadsError::validation(
+                        "as-of",
+                        format!("{} did not exist at {}", resolution.id, as_of.to_rfc3339()),
+                    )
+                })?;
+            details_list.push(details);
+            continue;
+        }
+
         // Fetch full details including comments and events
         if let Some(details) = storage.get_issue_details(&resolution.id, true, false, 10)? {
             details_list.push(details);
@@ -74,6 +96,9 @@ pub fn execute(
                 if i > 0 {
                     println!(); // Separate multiple issues
                 }
+                if let Some(as_of) = as_of {
+                    println!("As of {}:", as_of.format("%Y-%m-%d %H:%M:%S UTC"));
+                }
                 if matches!(ctx.mode(), OutputMode::Rich) {
                     let panel = IssuePanel::from_details(details, ctx.theme());
                     panel.print(&ctx, args.wrap);
@@ -87,7 +112,7 @@ pub fn execute(
     Ok(())
 }
 
-fn print_issue_details(details: &crate::format::IssueDetails, use_color: bool) {
+pub(crate) fn print_issue_details(details: &crate::format::IssueDetails, use_color: bool) {
     let output = format_issue_details(details, use_color);
     print!("{output}");
 }
@@ -163,6 +188,18 @@ fn format_issue_details(details: &crate::format::IssueDetails, use_color: bool)
         }
     }
 
+    let spent = issue
+        .org_extras
+        .as_ref()
+        .map_or(0, OrgExtras::clocked_minutes);
+    if spent > 0 {
+        let _ = writeln!(
+            output,
+            "Time spent: {}",
+            format_duration(chrono::Duration::minutes(spent))
+        );
+    }
+
     if let Some(closed) = &issue.closed_at {
         let reason_str = issue.close_reason.as_deref().unwrap_or("closed");
         let _ = writeln!(
@@ -173,6 +210,18 @@ fn format_issue_details(details: &crate::format::IssueDetails, use_color: bool)
         );
     }
 
+    if let Some(level) = issue.compaction_level.filter(|level| *level > 0) {
+        let when = issue
+            .compacted_at
+            .map_or_else(String::new, |at| format!(" on {}", at.format("%Y-%m-%d")));
+        let _ = writeln!(
+            output,
+            "Compacted: level {level}{when} (original in .beads/{}/{}.jsonl)",
+            compact::HISTORY_DIR,
+            issue.id
+        );
+    }
+
     if let Some(desc) = &issue.description {
         output.push('\n');
         let _ = writeln!(output, "{desc}");
@@ -290,6 +339,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         }
     }
 
@@ -490,4 +540,30 @@ mod tests {
         assert!(output.contains("alice: Looks good"));
         info!("test_show_text_includes_dependencies_and_comments: assertions passed");
     }
+
+    #[test]
+    fn test_show_text_marks_compacted_issues() {
+        init_logging();
+        let mut issue = make_test_issue("bd-001", "Test Issue");
+        let details = |issue: Issue| IssueDetails {
+            issue,
+            labels: Vec::new(),
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+            comments: Vec::new(),
+            events: Vec::new(),
+            parent: None,
+        };
+        assert!(!format_issue_details(&details(issue.clone()), false).contains("Compacted"));
+
+        issue.compaction_level = Some(1);
+        issue.compacted_at = Some(Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap());
+        let output = format_issue_details(&details(issue), false);
+        assert!(
+            output.contains(
+                "Compacted: level 1 on 2025-03-01 (original in .beads/history/bd-001.jsonl)"
+            ),
+            "{output}"
+        );
+    }
 }
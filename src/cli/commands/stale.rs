@@ -218,6 +218,7 @@ mod tests {
             dependencies: vec![],
             comments: vec![],
             content_hash: None,
+            org_extras: None,
         }
     }
 
@@ -155,9 +155,7 @@ fn compute_summary(
         }
 
         // Track epics for eligible-for-closure calculation
-        if issue.issue_type == IssueType::Epic
-            && !matches!(issue.status, Status::Closed | Status::Tombstone)
-        {
+        if issue.issue_type == IssueType::Epic && !issue.status.is_terminal() {
             epics.push(issue.id.clone());
         }
     }
@@ -231,9 +229,7 @@ fn count_epics_eligible_for_closure(storage: &SqliteStorage, epic_ids: &[String]
         }
 
         // Check if all children are closed
-        let all_closed = parent_child_children
-            .iter()
-            .all(|c| matches!(c.status, Status::Closed | Status::Tombstone));
+        let all_closed = parent_child_children.iter().all(|c| c.status.is_terminal());
 
         if all_closed {
             eligible += 1;
@@ -747,6 +743,7 @@ mod tests {
             dependencies: vec![],
             comments: vec![],
             content_hash: None,
+            org_extras: None,
         }
     }
 
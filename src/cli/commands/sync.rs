@@ -12,9 +12,9 @@ use crate::sync::{
     ConflictResolution, ExportConfig, ExportEntityType, ExportError, ExportErrorPolicy,
     ImportConfig, METADATA_JSONL_CONTENT_HASH, METADATA_LAST_EXPORT_TIME,
     METADATA_LAST_IMPORT_TIME, MergeContext, OrphanMode, compute_jsonl_hash, count_issues_in_jsonl,
-    export_to_jsonl_with_policy, finalize_export, get_issue_ids_from_jsonl, import_from_jsonl,
-    load_base_snapshot, read_issues_from_jsonl, require_safe_sync_overwrite_path,
-    save_base_snapshot, three_way_merge,
+    export_mtime, export_to_jsonl_with_policy, finalize_export, get_issue_ids_from_jsonl,
+    import_from_jsonl, load_base_snapshot, markdown_bridge, read_issues_from_jsonl,
+    require_safe_sync_overwrite_path, save_base_snapshot, three_way_merge,
 };
 use rich_rust::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -94,6 +94,7 @@ pub fn execute(
 
     let jsonl_path = paths.jsonl_path;
     let retention_days = paths.metadata.deletions_retention_days;
+    let org_nested = paths.metadata.org_nested;
     let use_json = ctx.is_json() || args.robot;
     let quiet = cli.quiet.unwrap_or(false);
     let show_progress = should_show_progress(use_json, quiet);
@@ -129,6 +130,7 @@ pub fn execute(
             json,
             show_progress,
             retention_days,
+            org_nested,
             ctx,
         )
     } else if args.merge {
@@ -139,6 +141,7 @@ pub fn execute(
             json,
             show_progress,
             retention_days,
+            org_nested,
             cli,
             ctx,
         )
@@ -202,9 +205,11 @@ fn validate_sync_paths(
         .extension()
         .and_then(|ext| ext.to_str())
         .map(str::to_ascii_lowercase);
-    if !matches!(extension.as_deref(), Some("jsonl" | "org")) {
+    if !matches!(extension.as_deref(), Some("jsonl" | "org"))
+        && !markdown_bridge::is_markdown_export(&jsonl_path)
+    {
         return Err(BeadsError::Config(format!(
-            "Sync path must end with .jsonl or .org: {}",
+            "Sync path must end with .jsonl or .org, or name a markdown directory: {}",
             jsonl_path.display()
         )));
     }
@@ -291,8 +296,9 @@ fn execute_status(
     let (jsonl_newer, db_newer) = if jsonl_exists {
         // Use symlink_metadata (Lstat) instead of metadata (stat) to get the mtime
         // of the symlink itself, not the target. This is important for detecting
-        // when the JSONL file has been updated via a symlink.
-        let jsonl_mtime = fs::symlink_metadata(jsonl_path)?.modified()?;
+        // when the JSONL file has been updated via a symlink. A markdown export
+        // directory counts as modified when its newest file is.
+        let jsonl_mtime = export_mtime(jsonl_path)?;
 
         // JSONL is newer if it was modified after last import
         let mtime_newer = last_import_time.as_ref().is_none_or(|import_time| {
@@ -474,6 +480,7 @@ fn execute_flush(
     use_json: bool,
     show_progress: bool,
     retention_days: Option<u64>,
+    org_nested: bool,
     ctx: &OutputContext,
 ) -> Result<()> {
     info!("Starting JSONL export");
@@ -582,6 +589,7 @@ fn execute_flush(
         allow_external_jsonl: args.allow_external_jsonl,
         show_progress,
         history: HistoryConfig::default(),
+        org_nested,
     };
 
     // Execute export
@@ -1059,6 +1067,17 @@ fn detect_prefix_from_jsonl(jsonl_path: &Path) -> Option<String> {
         status: Option<String>,
     }
 
+    if markdown_bridge::is_markdown_export(jsonl_path) {
+        return markdown_bridge::read_issues(jsonl_path)
+            .ok()?
+            .into_iter()
+            .filter(|issue| issue.status != crate::model::Status::Tombstone)
+            .find_map(|issue| {
+                let (prefix, _) = issue.id.split_once('-')?;
+                (!prefix.is_empty()).then(|| prefix.to_string())
+            });
+    }
+
     let file = File::open(jsonl_path).ok()?;
     let reader = BufReader::new(file);
 
@@ -1105,6 +1124,7 @@ fn execute_merge(
     use_json: bool,
     show_progress: bool,
     retention_days: Option<u64>,
+    org_nested: bool,
     cli: &config::CliOverrides,
     ctx: &OutputContext,
 ) -> Result<()> {
@@ -1217,6 +1237,7 @@ fn execute_merge(
         allow_external_jsonl: args.allow_external_jsonl,
         show_progress,
         history: HistoryConfig::default(),
+        org_nested,
     };
 
     let (export_result, _) = export_to_jsonl_with_policy(storage, jsonl_path, &export_config)?;
@@ -1230,6 +1251,7 @@ fn execute_merge(
             "deleted_issues": report.deleted.len(),
             "conflicts": report.conflicts.len(),
             "notes": report.notes,
+            "field_conflicts": report.field_conflicts,
         });
         ctx.json_pretty(&output);
     } else if ctx.is_rich() {
@@ -1244,6 +1266,19 @@ fn execute_merge(
                 println!("    - {id}: {note}");
             }
         }
+        if !report.field_conflicts.is_empty() {
+            println!("  Field conflicts:");
+            for conflict in &report.field_conflicts {
+                println!(
+                    "    - {}.{}: local={} external={} (kept {})",
+                    conflict.issue_id,
+                    conflict.field,
+                    conflict.local,
+                    conflict.external,
+                    merge_side_label(conflict.kept)
+                );
+            }
+        }
         println!("  Base snapshot updated.");
         println!("  JSONL exported.");
     }
@@ -1290,6 +1325,14 @@ fn render_merge_conflicts_rich(
     console.print_renderable(&panel);
 }
 
+/// Lowercase label for the side a merged value came from.
+const fn merge_side_label(side: crate::sync::MergeSide) -> &'static str {
+    match side {
+        crate::sync::MergeSide::Local => "local",
+        crate::sync::MergeSide::External => "external",
+    }
+}
+
 /// Render merge result with rich formatting.
 fn render_merge_result_rich(report: &crate::sync::MergeReport, ctx: &OutputContext) {
     let console = Console::default();
@@ -1337,6 +1380,33 @@ fn render_merge_result_rich(report: &crate::sync::MergeReport, ctx: &OutputConte
         }
     }
 
+    // Field conflicts section
+    if !report.field_conflicts.is_empty() {
+        text.append("\n");
+        text.append_styled("Field conflicts:\n", theme.dimmed.clone());
+        for (i, conflict) in report.field_conflicts.iter().enumerate() {
+            let prefix = if i == report.field_conflicts.len() - 1 {
+                "└──"
+            } else {
+                "├──"
+            };
+            text.append_styled(prefix, theme.muted.clone());
+            text.append(" ");
+            text.append_styled(&conflict.issue_id, theme.issue_id.clone());
+            text.append(" ");
+            text.append_styled(&conflict.field, theme.warning.clone());
+            text.append(": local=");
+            text.append(&conflict.local.to_string());
+            text.append(" external=");
+            text.append(&conflict.external.to_string());
+            text.append_styled(
+                &format!(" (kept {})", merge_side_label(conflict.kept)),
+                theme.muted.clone(),
+            );
+            text.append("\n");
+        }
+    }
+
     // Final status
     text.append("\n");
     text.append_styled("✓ ", theme.success.clone());
@@ -1398,6 +1468,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         }
     }
 
@@ -0,0 +1,765 @@
+//! Template command implementation.
+//!
+//! A template is a tree of issues kept out of normal work. It lives either
+//! in the database, as an issue flagged `is_template` plus its parent-child
+//! subtree, or in `.beads/templates/<name>.yaml`:
+//!
+//! ```yaml
+//! vars:
+//!   version:          # required
+//!   channel: stable   # default
+//! title: "Release {{version}}"
+//! type: epic
+//! children:
+//!   - key: build
+//!     title: "Build {{version}} ({{channel}})"
+//!   - key: publish
+//!     title: "Publish {{version}}"
+//!     deps: [build]
+//! ```
+//!
+//! `instantiate` copies the tree into fresh issues in one transaction,
+//! recreating the parent-child links and the blocking dependencies between
+//! template issues, with `{{var}}` placeholders in titles and bodies filled
+//! from `--var name=value`.
+
+use crate::cli::commands::batch::{PriorityValue, parse_dep_type};
+use crate::cli::commands::create::{self, CreateConfig, create_config, resolve_id};
+use crate::cli::{CreateArgs, TemplateCommands, TemplateCreateArgs, TemplateInstantiateArgs};
+use crate::config;
+use crate::error::{BeadsError, Result};
+use crate::model::{DependencyType, Issue};
+use crate::output::OutputContext;
+use crate::storage::batch::BatchStep;
+use crate::storage::{ListFilters, SqliteStorage};
+use crate::util::id::{IdResolver, ResolverConfig};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Directory under `.beads` holding YAML templates.
+const TEMPLATES_DIR: &str = "templates";
+
+static PLACEHOLDER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_-]*)\s*\}\}").unwrap());
+
+/// One issue in a template tree.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct TemplateNode {
+    /// Name `deps` refer to; database templates use the issue ID.
+    #[serde(default)]
+    key: Option<String>,
+    pub(crate) title: String,
+    #[serde(default, rename = "type")]
+    issue_type: Option<String>,
+    #[serde(default)]
+    priority: Option<PriorityValue>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    design: Option<String>,
+    #[serde(default)]
+    acceptance_criteria: Option<String>,
+    #[serde(default)]
+    notes: Option<String>,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    estimate: Option<i32>,
+    /// Blocking dependencies on other nodes: `key` or `type:key`.
+    #[serde(default)]
+    deps: Vec<String>,
+    #[serde(default)]
+    children: Vec<TemplateNode>,
+    /// Declared variables and their defaults (`~` for required). Root only.
+    #[serde(default)]
+    vars: BTreeMap<String, Option<String>>,
+}
+
+impl TemplateNode {
+    fn count(&self) -> usize {
+        1 + self.children.iter().map(Self::count).sum::<usize>()
+    }
+
+    fn texts(&self) -> impl Iterator<Item = &str> {
+        [
+            Some(self.title.as_str()),
+            self.description.as_deref(),
+            self.design.as_deref(),
+            self.acceptance_criteria.as_deref(),
+            self.notes.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Every placeholder name used in the tree.
+    pub(crate) fn placeholders(&self, out: &mut BTreeSet<String>) {
+        for text in self.texts() {
+            for capture in PLACEHOLDER.captures_iter(text) {
+                out.insert(capture[1].to_string());
+            }
+        }
+        for child in &self.children {
+            child.placeholders(out);
+        }
+    }
+}
+
+/// A template and where it came from.
+struct Template {
+    name: String,
+    /// `database` or `file`.
+    source: &'static str,
+    root: TemplateNode,
+}
+
+/// JSON output for `template list`.
+#[derive(Debug, Serialize)]
+struct TemplateSummary {
+    name: String,
+    source: &'static str,
+    title: String,
+    issues: usize,
+    vars: Vec<String>,
+}
+
+/// One issue created (or planned) by `template instantiate`.
+#[derive(Debug, Serialize)]
+pub(crate) struct InstantiatedIssue {
+    pub(crate) id: String,
+    pub(crate) title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent: Option<String>,
+}
+
+/// JSON output for `template instantiate`.
+#[derive(Debug, Serialize)]
+struct InstantiateOutput {
+    template: String,
+    root: String,
+    dry_run: bool,
+    issues: Vec<InstantiatedIssue>,
+}
+
+/// Execute the template command.
+///
+/// # Errors
+///
+/// Returns an error if a template cannot be found or parsed, a variable is
+/// missing, or a database operation fails.
+pub fn execute(
+    command: &TemplateCommands,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    match command {
+        TemplateCommands::Create(args) => execute_create(args, cli, ctx),
+        TemplateCommands::List => execute_list(cli, ctx),
+        TemplateCommands::Instantiate(args) => execute_instantiate(args, cli, ctx),
+    }
+}
+
+fn execute_create(
+    args: &TemplateCreateArgs,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let create_config = create_config(&layer)?;
+    let resolver = IdResolver::new(ResolverConfig::with_prefix(
+        create_config.id_config.prefix.clone(),
+    ));
+
+    let storage = &storage_ctx.storage;
+    let parent = match &args.parent {
+        Some(parent) => {
+            let id = resolve_id(&resolver, storage, parent)?;
+            if !storage
+                .get_issue(&id)?
+                .is_some_and(|issue| issue.is_template)
+            {
+                return Err(BeadsError::validation(
+                    "parent",
+                    format!("{id} is not a template"),
+                ));
+            }
+            Some(id)
+        }
+        None => None,
+    };
+    let deps = args
+        .deps
+        .iter()
+        .map(|dep| match dep.split_once(':') {
+            Some((dep_type, id)) => Ok(format!(
+                "{dep_type}:{}",
+                resolve_id(&resolver, storage, id)?
+            )),
+            None => resolve_id(&resolver, storage, dep),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let create_args = CreateArgs {
+        title: Some(args.title.clone()),
+        type_: args.type_.clone(),
+        priority: args.priority.clone(),
+        description: args.description.clone(),
+        labels: args.labels.clone(),
+        parent,
+        deps,
+        estimate: args.estimate,
+        ..CreateArgs::default()
+    };
+    let mut issue = create::build_issue(storage, &create_args, &create_config, &HashSet::new())?;
+    issue.is_template = true;
+    issue.content_hash = Some(issue.compute_content_hash());
+
+    storage_ctx
+        .storage
+        .create_issue(&issue, &create_config.actor)?;
+    crate::util::set_last_touched_id(&beads_dir, &issue.id);
+
+    if ctx.is_json() {
+        ctx.json_pretty(&issue);
+    } else {
+        ctx.success(&format!("Created template {}: {}", issue.id, issue.title));
+    }
+    storage_ctx.flush_no_db_if_dirty()?;
+    Ok(())
+}
+
+fn execute_list(cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let storage = &storage_ctx.storage;
+
+    let mut templates = Vec::new();
+    for issue in template_roots(storage)? {
+        templates.push(Template {
+            name: issue.id.clone(),
+            source: "database",
+            root: tree_from_db(storage, &issue.id)?,
+        });
+    }
+    templates.extend(load_file_templates(&beads_dir)?);
+
+    let summaries: Vec<TemplateSummary> = templates
+        .into_iter()
+        .map(|template| {
+            let mut vars = BTreeSet::new();
+            template.root.placeholders(&mut vars);
+            vars.extend(template.root.vars.keys().cloned());
+            TemplateSummary {
+                issues: template.root.count(),
+                title: template.root.title,
+                name: template.name,
+                source: template.source,
+                vars: vars.into_iter().collect(),
+            }
+        })
+        .collect();
+
+    if ctx.is_json() {
+        ctx.json_pretty(&summaries);
+        return Ok(());
+    }
+    if summaries.is_empty() {
+        println!("No templates found");
+        return Ok(());
+    }
+    for summary in &summaries {
+        let vars = if summary.vars.is_empty() {
+            String::new()
+        } else {
+            format!(" [vars: {}]", summary.vars.join(", "))
+        };
+        println!(
+            "{} ({}, {} issue{}): {}{vars}",
+            summary.name,
+            summary.source,
+            summary.issues,
+            if summary.issues == 1 { "" } else { "s" },
+            summary.title
+        );
+    }
+    Ok(())
+}
+
+fn execute_instantiate(
+    args: &TemplateInstantiateArgs,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let create_config = create_config(&layer)?;
+    let resolver = IdResolver::new(ResolverConfig::with_prefix(
+        create_config.id_config.prefix.clone(),
+    ));
+
+    let storage = &storage_ctx.storage;
+    let template = find_template(storage, &beads_dir, &resolver, &args.template)?;
+    let vars = bind_vars(&template.root, &args.vars)?;
+    let parent = args
+        .parent
+        .as_deref()
+        .map(|parent| resolve_id(&resolver, storage, parent))
+        .transpose()?;
+    let (steps, issues) = plan(storage, &template.root, parent, &vars, &create_config)?;
+    let root = issues[0].id.clone();
+
+    if !args.dry_run {
+        storage_ctx
+            .storage
+            .apply_batch(&steps, &create_config.actor, |_, _| Ok(()))
+            .map_err(|failure| failure.error)?;
+        crate::util::set_last_touched_id(&beads_dir, &root);
+        tracing::info!(template = %template.name, root = %root, count = issues.len(), "Instantiated template");
+    }
+
+    if ctx.is_json() {
+        ctx.json_pretty(&InstantiateOutput {
+            template: template.name,
+            root,
+            dry_run: args.dry_run,
+            issues,
+        });
+    } else {
+        let verb = if args.dry_run {
+            "Would instantiate"
+        } else {
+            "Instantiated"
+        };
+        ctx.success(&format!(
+            "{verb} {} as {root} ({} issue{})",
+            template.name,
+            issues.len(),
+            if issues.len() == 1 { "" } else { "s" }
+        ));
+        for issue in &issues {
+            println!("  {}: {}", issue.id, issue.title);
+        }
+    }
+
+    if !args.dry_run {
+        storage_ctx.flush_no_db_if_dirty()?;
+    }
+    Ok(())
+}
+
+/// Template issues whose parent (if any) is not a template.
+fn template_roots(storage: &SqliteStorage) -> Result<Vec<Issue>> {
+    let filters = ListFilters {
+        include_templates: true,
+        include_deferred: true,
+        sort: Some("created_at".to_string()),
+        ..ListFilters::default()
+    };
+    let templates: Vec<Issue> = storage
+        .list_issues(&filters)?
+        .into_iter()
+        .filter(|issue| issue.is_template)
+        .collect();
+    let ids: HashSet<&str> = templates.iter().map(|issue| issue.id.as_str()).collect();
+
+    let mut roots = Vec::new();
+    for issue in &templates {
+        let has_template_parent = storage
+            .get_dependencies_with_metadata(&issue.id)?
+            .iter()
+            .any(|dep| dep.dep_type == "parent-child" && ids.contains(dep.id.as_str()));
+        if !has_template_parent {
+            roots.push(issue.clone());
+        }
+    }
+    Ok(roots)
+}
+
+/// A `.beads/templates/<name>.yaml` template or a database template ID.
+fn find_template(
+    storage: &SqliteStorage,
+    beads_dir: &Path,
+    resolver: &IdResolver,
+    name: &str,
+) -> Result<Template> {
+    let dir = beads_dir.join(TEMPLATES_DIR);
+    for extension in ["yaml", "yml"] {
+        let path = dir.join(format!("{name}.{extension}"));
+        if path.is_file() {
+            return load_file_template(&path);
+        }
+    }
+
+    let id = resolve_id(resolver, storage, name).map_err(|_| {
+        BeadsError::validation(
+            "template",
+            format!("no template '{name}' in the database or {TEMPLATES_DIR}/"),
+        )
+    })?;
+    if !storage
+        .get_issue(&id)?
+        .is_some_and(|issue| issue.is_template)
+    {
+        return Err(BeadsError::validation(
+            "template",
+            format!("{id} is not a template"),
+        ));
+    }
+    Ok(Template {
+        name: id.clone(),
+        source: "database",
+        root: tree_from_db(storage, &id)?,
+    })
+}
+
+fn load_file_templates(beads_dir: &Path) -> Result<Vec<Template>> {
+    let dir = beads_dir.join(TEMPLATES_DIR);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut paths: Vec<_> = std::fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == "yaml" || ext == "yml")
+        })
+        .collect();
+    paths.sort();
+    paths.iter().map(|path| load_file_template(path)).collect()
+}
+
+fn load_file_template(path: &Path) -> Result<Template> {
+    let contents = std::fs::read_to_string(path)?;
+    let root = parse_template(&contents)
+        .map_err(|e| BeadsError::Config(format!("{}: {e}", path.display())))?;
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    Ok(Template {
+        name,
+        source: "file",
+        root,
+    })
+}
+
+fn parse_template(contents: &str) -> std::result::Result<TemplateNode, String> {
+    let root: TemplateNode = serde_yaml::from_str(contents).map_err(|e| e.to_string())?;
+    check_nested_vars(&root)?;
+    Ok(root)
+}
+
+fn check_nested_vars(node: &TemplateNode) -> std::result::Result<(), String> {
+    for child in &node.children {
+        if !child.vars.is_empty() {
+            return Err(format!(
+                "'{}': vars are only allowed at the top level",
+                child.title
+            ));
+        }
+        check_nested_vars(child)?;
+    }
+    Ok(())
+}
+
+/// Load a database template: the root, its template children, and the
+/// blocking dependencies that stay inside the tree.
+pub(crate) fn tree_from_db(storage: &SqliteStorage, root_id: &str) -> Result<TemplateNode> {
+    let mut seen = HashSet::new();
+    let mut root = node_from_db(storage, root_id, &mut seen)?;
+    prune_external_deps(&mut root, &seen);
+    Ok(root)
+}
+
+fn node_from_db(
+    storage: &SqliteStorage,
+    id: &str,
+    seen: &mut HashSet<String>,
+) -> Result<TemplateNode> {
+    let issue = storage
+        .get_issue(id)?
+        .ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() })?;
+    seen.insert(issue.id.clone());
+
+    let deps = storage
+        .get_dependencies_with_metadata(id)?
+        .into_iter()
+        .filter(|dep| {
+            dep.dep_type != "parent-child"
+                && dep
+                    .dep_type
+                    .parse::<DependencyType>()
+                    .is_ok_and(|t| t.is_blocking())
+        })
+        .map(|dep| format!("{}:{}", dep.dep_type, dep.id))
+        .collect();
+
+    let child_ids: Vec<String> = storage
+        .get_dependents_with_metadata(id)?
+        .into_iter()
+        .filter(|dep| dep.dep_type == "parent-child")
+        .map(|dep| dep.id)
+        .collect();
+    let mut child_issues: Vec<Issue> = storage
+        .get_issues_by_ids(&child_ids)?
+        .into_iter()
+        .filter(|child| child.is_template && !seen.contains(&child.id))
+        .collect();
+    child_issues.sort_by(|a, b| {
+        a.created_at
+            .cmp(&b.created_at)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    let mut children = Vec::with_capacity(child_issues.len());
+    for child in child_issues {
+        children.push(node_from_db(storage, &child.id, seen)?);
+    }
+
+    Ok(TemplateNode {
+        key: Some(issue.id.clone()),
+        title: issue.title,
+        issue_type: Some(issue.issue_type.as_str().to_string()),
+        priority: Some(PriorityValue::Number(issue.priority.0)),
+        description: issue.description,
+        design: issue.design,
+        acceptance_criteria: issue.acceptance_criteria,
+        notes: issue.notes,
+        labels: storage.get_labels(id)?,
+        estimate: issue.estimated_minutes,
+        deps,
+        children,
+        vars: BTreeMap::new(),
+    })
+}
+
+fn prune_external_deps(node: &mut TemplateNode, inside: &HashSet<String>) {
+    node.deps.retain(|dep| {
+        let key = dep.split_once(':').map_or(dep.as_str(), |(_, key)| key);
+        inside.contains(key)
+    });
+    for child in &mut node.children {
+        prune_external_deps(child, inside);
+    }
+}
+
+/// Combine `name=value` arguments with declared defaults, requiring a value
+/// for every placeholder and rejecting names the template never uses.
+pub(crate) fn bind_vars(
+    root: &TemplateNode,
+    supplied: &[String],
+) -> Result<BTreeMap<String, String>> {
+    let mut used = BTreeSet::new();
+    root.placeholders(&mut used);
+
+    let mut vars: BTreeMap<String, String> = root
+        .vars
+        .iter()
+        .filter_map(|(name, default)| default.clone().map(|value| (name.clone(), value)))
+        .collect();
+    for pair in supplied {
+        let (name, value) = pair.split_once('=').ok_or_else(|| {
+            BeadsError::validation("var", format!("expected name=value, got '{pair}'"))
+        })?;
+        let name = name.trim();
+        if !used.contains(name) && !root.vars.contains_key(name) {
+            return Err(BeadsError::validation(
+                "var",
+                format!("template has no variable '{name}'"),
+            ));
+        }
+        vars.insert(name.to_string(), value.to_string());
+    }
+
+    let missing: Vec<&str> = used
+        .iter()
+        .chain(root.vars.keys())
+        .filter(|name| !vars.contains_key(*name))
+        .map(String::as_str)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    if !missing.is_empty() {
+        return Err(BeadsError::validation(
+            "var",
+            format!("missing value for {}", missing.join(", ")),
+        ));
+    }
+    Ok(vars)
+}
+
+fn substitute(text: &str, vars: &BTreeMap<String, String>) -> String {
+    PLACEHOLDER
+        .replace_all(text, |captures: &regex::Captures<'_>| {
+            vars.get(&captures[1]).cloned().unwrap_or_default()
+        })
+        .into_owned()
+}
+
+/// Build the creates for every node (parents first) followed by the
+/// dependencies between them.
+pub(crate) fn plan(
+    storage: &SqliteStorage,
+    root: &TemplateNode,
+    parent: Option<String>,
+    vars: &BTreeMap<String, String>,
+    config: &CreateConfig,
+) -> Result<(Vec<BatchStep>, Vec<InstantiatedIssue>)> {
+    let mut planner = Planner {
+        storage,
+        vars,
+        config,
+        reserved: HashSet::new(),
+        keys: HashMap::new(),
+        steps: Vec::new(),
+        deps: Vec::new(),
+        issues: Vec::new(),
+    };
+    planner.create(root, parent)?;
+
+    let mut steps = planner.steps;
+    for (id, dep) in planner.deps {
+        let (dep_type, key) = dep.split_once(':').unwrap_or(("blocks", dep.as_str()));
+        let depends_on = planner.keys.get(key).ok_or_else(|| {
+            BeadsError::validation("deps", format!("no template issue with key '{key}'"))
+        })?;
+        steps.push(BatchStep::DepAdd {
+            id,
+            depends_on: depends_on.clone(),
+            dep_type: parse_dep_type(dep_type)?,
+        });
+    }
+    Ok((steps, planner.issues))
+}
+
+struct Planner<'a> {
+    storage: &'a SqliteStorage,
+    vars: &'a BTreeMap<String, String>,
+    config: &'a CreateConfig,
+    reserved: HashSet<String>,
+    /// Template key to new issue ID.
+    keys: HashMap<String, String>,
+    steps: Vec<BatchStep>,
+    /// (new issue ID, `type:key` it depends on)
+    deps: Vec<(String, String)>,
+    issues: Vec<InstantiatedIssue>,
+}
+
+impl Planner<'_> {
+    fn create(&mut self, node: &TemplateNode, parent: Option<String>) -> Result<()> {
+        let fill = |text: Option<&str>| text.map(|t| substitute(t, self.vars));
+        let args = CreateArgs {
+            title: Some(substitute(&node.title, self.vars)),
+            type_: node.issue_type.clone(),
+            priority: node.priority.as_ref().map(PriorityValue::to_arg),
+            description: fill(node.description.as_deref()),
+            labels: node.labels.clone(),
+            parent: parent.clone(),
+            estimate: node.estimate,
+            ..CreateArgs::default()
+        };
+        let mut issue = create::build_issue(self.storage, &args, self.config, &self.reserved)?;
+        issue.design = fill(node.design.as_deref());
+        issue.acceptance_criteria = fill(node.acceptance_criteria.as_deref());
+        issue.notes = fill(node.notes.as_deref());
+        issue.content_hash = Some(issue.compute_content_hash());
+
+        if let Some(key) = &node.key {
+            if self.keys.insert(key.clone(), issue.id.clone()).is_some() {
+                return Err(BeadsError::validation(
+                    "key",
+                    format!("duplicate template key '{key}'"),
+                ));
+            }
+        }
+        for dep in &node.deps {
+            self.deps.push((issue.id.clone(), dep.clone()));
+        }
+        self.reserved.insert(issue.id.clone());
+        self.issues.push(InstantiatedIssue {
+            id: issue.id.clone(),
+            title: issue.title.clone(),
+            parent,
+        });
+        let id = issue.id.clone();
+        self.steps.push(BatchStep::Create(Box::new(issue)));
+
+        for child in &node.children {
+            self.create(child, Some(id.clone()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RELEASE: &str = r#"
+vars:
+  version:
+  channel: stable
+title: "Release {{version}}"
+type: epic
+children:
+  - key: build
+    title: "Build {{ version }} for {{channel}}"
+  - key: publish
+    title: "Publish {{version}}"
+    deps: [build]
+"#;
+
+    #[test]
+    fn parse_and_bind_vars() {
+        let root = parse_template(RELEASE).unwrap();
+        assert_eq!(root.count(), 3);
+
+        let vars = bind_vars(&root, &["version=1.2".to_string()]).unwrap();
+        assert_eq!(vars["channel"], "stable");
+        assert_eq!(
+            substitute(&root.children[0].title, &vars),
+            "Build 1.2 for stable"
+        );
+
+        let missing = bind_vars(&root, &[]).unwrap_err();
+        assert!(missing.to_string().contains("version"), "{missing}");
+        let unknown = bind_vars(&root, &["verison=1".to_string()]).unwrap_err();
+        assert!(unknown.to_string().contains("verison"), "{unknown}");
+    }
+
+    #[test]
+    fn parse_rejects_unknown_fields_and_nested_vars() {
+        assert!(parse_template("title: A\nassignee: me\n").is_err());
+        let nested = "title: A\nchildren:\n  - title: B\n    vars:\n      x: y\n";
+        assert!(parse_template(nested).unwrap_err().contains("top level"));
+    }
+
+    #[test]
+    fn plan_creates_tree_then_internal_deps() {
+        let storage = SqliteStorage::open_memory().unwrap();
+        let root = parse_template(RELEASE).unwrap();
+        let vars = bind_vars(&root, &["version=2.0".to_string()]).unwrap();
+        let config = CreateConfig {
+            id_config: crate::util::id::IdConfig::with_prefix("bd"),
+            default_priority: crate::model::Priority::MEDIUM,
+            default_issue_type: crate::model::IssueType::Task,
+            actor: "tester".to_string(),
+        };
+
+        let (steps, issues) = plan(&storage, &root, None, &vars, &config).unwrap();
+        assert_eq!(steps.len(), 4);
+        assert_eq!(issues[0].title, "Release 2.0");
+        assert_eq!(issues[1].parent.as_deref(), Some(issues[0].id.as_str()));
+        assert_eq!(issues[1].id, format!("{}.1", issues[0].id));
+        assert!(matches!(
+            &steps[3],
+            BatchStep::DepAdd { id, depends_on, .. }
+                if *id == issues[2].id && *depends_on == issues[1].id
+        ));
+    }
+}
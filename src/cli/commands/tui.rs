@@ -0,0 +1,18 @@
+//! Tui command implementation.
+
+use crate::config;
+use crate::error::Result;
+use crate::mcp::McpServer;
+use crate::output::OutputContext;
+use crate::tui;
+
+/// Execute the tui command.
+///
+/// # Errors
+///
+/// Returns an error if the workspace cannot be opened or stdout is not a
+/// terminal.
+pub fn execute(allow_stale: bool, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let server = McpServer::open(cli, allow_stale)?;
+    tui::run(server, ctx.is_rich())
+}
@@ -0,0 +1,124 @@
+//! Undo command implementation.
+//!
+//! Every command invocation that changes issues is recorded as one
+//! operation. `undo` reverts the most recent ones (or specific ids) in a
+//! single transaction and refuses if later edits touched the same data.
+
+use crate::cli::UndoArgs;
+use crate::config;
+use crate::error::Result;
+use crate::output::OutputContext;
+use crate::storage::undo::{OperationSummary, UndoReport};
+use std::fmt::Write as _;
+
+/// Execute the undo command.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be opened, an operation id is
+/// unknown or already undone, or later edits conflict with the undo.
+pub fn execute(
+    args: &UndoArgs,
+    json: bool,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let use_json = json || args.robot;
+    let json_ctx = OutputContext::from_flags(true, false, true);
+    let out = if use_json && !ctx.is_json() {
+        &json_ctx
+    } else {
+        ctx
+    };
+
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+
+    if args.list {
+        let operations = storage_ctx.storage.list_operations(args.limit)?;
+        if use_json {
+            out.json_pretty(&operations);
+        } else {
+            print_operations(&operations);
+        }
+        return Ok(());
+    }
+
+    let config_layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let actor = config::resolve_actor(&config_layer);
+
+    let op_ids = if args.op_id.is_empty() {
+        storage_ctx.storage.undoable_operations(args.steps.max(1))?
+    } else {
+        args.op_id.clone()
+    };
+
+    if op_ids.is_empty() {
+        if use_json {
+            out.json_pretty(&serde_json::json!({
+                "undone": [],
+                "issues": [],
+                "changes": 0,
+            }));
+        } else {
+            println!("Nothing to undo.");
+        }
+        return Ok(());
+    }
+
+    tracing::info!(operations = ?op_ids, "Undoing operations");
+    let report = storage_ctx.storage.undo_operations(&op_ids, &actor)?;
+
+    if use_json {
+        out.json_pretty(&report);
+    } else {
+        print_report(&report);
+    }
+
+    storage_ctx.flush_no_db_if_dirty()?;
+    Ok(())
+}
+
+fn print_report(report: &UndoReport) {
+    println!(
+        "\u{2713} Undid {} operation(s): {}",
+        report.undone.len(),
+        report.undone.join(", ")
+    );
+    if !report.issues.is_empty() {
+        println!(
+            "  {} change(s) on {}",
+            report.changes,
+            report.issues.join(", ")
+        );
+    }
+    println!("  Redo with: br undo --op-id {}", report.op_id);
+}
+
+fn print_operations(operations: &[OperationSummary]) {
+    if operations.is_empty() {
+        println!("No recorded operations.");
+        return;
+    }
+    for op in operations {
+        let mut line = format!(
+            "{}  {}  {:<24} {:<12} {} event(s)",
+            op.id,
+            op.created_at.format("%Y-%m-%d %H:%M:%S"),
+            op.name,
+            op.actor,
+            op.events
+        );
+        if !op.issues.is_empty() {
+            line.push_str("  ");
+            line.push_str(&op.issues.join(", "));
+        }
+        if let Some(undone_by) = &op.undone_by {
+            let _ = write!(line, "  [undone by {undone_by}]");
+        }
+        if let Some(undoes) = &op.undoes {
+            let _ = write!(line, "  [undoes {}]", undoes.join(", "));
+        }
+        println!("{line}");
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::cli::UpdateArgs;
 use crate::config;
+use crate::config::workflow::Workflow;
 use crate::error::{BeadsError, Result};
 use crate::model::{DependencyType, Issue, Status};
 use crate::output::OutputContext;
@@ -14,7 +15,7 @@ use serde::Serialize;
 
 /// JSON output structure for updated issues.
 #[derive(Serialize)]
-struct UpdatedIssueOutput {
+pub(crate) struct UpdatedIssueOutput {
     id: String,
     title: String,
     status: String,
@@ -51,6 +52,11 @@ pub fn execute(args: &UpdateArgs, cli: &config::CliOverrides, ctx: &OutputContex
 
     let claim_exclusive = config::claim_exclusive_from_layer(&config_layer);
     let update = build_update(args, &actor, claim_exclusive)?;
+    let update_config = UpdateConfig {
+        actor,
+        resolver,
+        workflow: Workflow::from_layer(&config_layer)?,
+    };
     let has_updates = !update.is_empty()
         || !args.add_label.is_empty()
         || !args.remove_label.is_empty()
@@ -65,61 +71,7 @@ pub fn execute(args: &UpdateArgs, cli: &config::CliOverrides, ctx: &OutputContex
         // Get issue before update for change tracking
         let issue_before = storage.get_issue(id)?;
 
-        // Claim guard is now inside the IMMEDIATE transaction (see IssueUpdate.expect_unassigned)
-        // to prevent TOCTOU races between concurrent agents.
-
-        // Check if transitioning to in_progress (via --claim or --status in_progress)
-        // and if so, validate that the issue is not blocked
-        let transitioning_to_in_progress = args.claim
-            || args
-                .status
-                .as_ref()
-                .is_some_and(|s| s.eq_ignore_ascii_case("in_progress"));
-
-        if transitioning_to_in_progress && !args.force && storage.is_blocked(id)? {
-            let blockers = storage.get_blockers(id)?;
-            let blocker_list = if blockers.is_empty() {
-                "blocking dependencies".to_string()
-            } else {
-                blockers.join(", ")
-            };
-            return Err(BeadsError::validation(
-                "claim",
-                format!("cannot claim blocked issue: {blocker_list}"),
-            ));
-        }
-
-        // Apply basic field updates
-        if !update.is_empty() {
-            storage.update_issue(id, &update, &actor)?;
-        }
-
-        // Apply labels
-        for label in &args.add_label {
-            LabelValidator::validate(label)
-                .map_err(|e| BeadsError::validation("label", e.message))?;
-            storage.add_label(id, label, &actor)?;
-        }
-        for label in &args.remove_label {
-            storage.remove_label(id, label, &actor)?;
-        }
-        if !args.set_labels.is_empty() {
-            // Remove all then add new
-            storage.remove_all_labels(id, &actor)?;
-            // Join all flag values, then split by comma (handles both --set-labels a,b and --set-labels a --set-labels b)
-            let combined = args.set_labels.join(",");
-            for label in combined.split(',') {
-                let label = label.trim();
-                if !label.is_empty() {
-                    LabelValidator::validate(label)
-                        .map_err(|e| BeadsError::validation("label", e.message))?;
-                    storage.add_label(id, label, &actor)?;
-                }
-            }
-        }
-
-        // Apply parent
-        apply_parent_update(storage, id, args.parent.as_deref(), &resolver, &actor)?;
+        update_issue_impl(storage, id, args, &update, &update_config)?;
 
         // Update last touched
         crate::util::set_last_touched_id(&beads_dir, id);
@@ -146,6 +98,118 @@ pub fn execute(args: &UpdateArgs, cli: &config::CliOverrides, ctx: &OutputContex
     Ok(())
 }
 
+/// Settings shared by every issue an update touches.
+pub struct UpdateConfig {
+    pub actor: String,
+    pub resolver: IdResolver,
+    pub workflow: Workflow,
+}
+
+/// Core logic for updating one resolved issue.
+///
+/// Checks the workflow transition and the claim guard, then applies field
+/// updates, label changes and the parent link. Prints nothing.
+///
+/// # Errors
+///
+/// Returns an error if the transition is not allowed, a blocked issue is
+/// claimed without `--force`, a label is invalid, or a storage write fails.
+pub fn update_issue_impl(
+    storage: &mut SqliteStorage,
+    id: &str,
+    args: &UpdateArgs,
+    update: &IssueUpdate,
+    config: &UpdateConfig,
+) -> Result<()> {
+    check_update(storage, id, args, update, &config.workflow)?;
+
+    // Apply basic field updates
+    if !update.is_empty() {
+        storage.update_issue(id, update, &config.actor)?;
+    }
+
+    // Apply labels
+    for label in &args.add_label {
+        LabelValidator::validate(label).map_err(|e| BeadsError::validation("label", e.message))?;
+        storage.add_label(id, label, &config.actor)?;
+    }
+    for label in &args.remove_label {
+        storage.remove_label(id, label, &config.actor)?;
+    }
+    if !args.set_labels.is_empty() {
+        // Remove all then add new
+        storage.remove_all_labels(id, &config.actor)?;
+        // Join all flag values, then split by comma (handles both --set-labels a,b and --set-labels a --set-labels b)
+        let combined = args.set_labels.join(",");
+        for label in combined.split(',') {
+            let label = label.trim();
+            if !label.is_empty() {
+                LabelValidator::validate(label)
+                    .map_err(|e| BeadsError::validation("label", e.message))?;
+                storage.add_label(id, label, &config.actor)?;
+            }
+        }
+    }
+
+    // Apply parent
+    apply_parent_update(
+        storage,
+        id,
+        args.parent.as_deref(),
+        &config.resolver,
+        &config.actor,
+    )?;
+
+    Ok(())
+}
+
+/// Check the workflow transition and refuse to claim a blocked issue
+/// unless `--force` is set.
+///
+/// # Errors
+///
+/// Returns an error if the transition is not allowed or the issue is
+/// blocked.
+pub fn check_update(
+    storage: &SqliteStorage,
+    id: &str,
+    args: &UpdateArgs,
+    update: &IssueUpdate,
+    workflow: &Workflow,
+) -> Result<()> {
+    let issue_before = storage.get_issue(id)?;
+
+    if let (Some(issue), Some(status)) = (issue_before.as_ref(), update.status.as_ref()) {
+        workflow.check_transition(issue, status)?;
+    }
+
+    // Claim guard is now inside the IMMEDIATE transaction (see IssueUpdate.expect_unassigned)
+    // to prevent TOCTOU races between concurrent agents.
+
+    // Check if transitioning to in_progress (via --claim or --status in_progress)
+    // and if so, validate that the issue is not blocked
+    let transitioning_to_in_progress = args.claim
+        || args
+            .status
+            .as_ref()
+            .is_some_and(|s| s.eq_ignore_ascii_case("in_progress"));
+
+    if transitioning_to_in_progress && !args.force && storage.is_blocked(id)? {
+        let blockers = storage.get_blockers(id)?;
+        let blocker_list = if blockers.is_empty() {
+            "blocking dependencies".to_string()
+        } else {
+            blockers.join(", ")
+        };
+        return Err(BeadsError::validation(
+            "claim",
+            format!("cannot claim blocked issue: {blocker_list}"),
+        ));
+    }
+
+    Ok(())
+}
+
 /// Print a summary of what changed for the issue.
 fn print_update_summary(id: &str, title: &str, before: Option<&Issue>, after: &Issue) {
     println!("Updated {id}: {title}");
@@ -218,7 +282,12 @@ fn resolve_target_ids(
     Ok(resolved_ids.into_iter().map(|r| r.id).collect())
 }
 
-fn build_update(args: &UpdateArgs, actor: &str, claim_exclusive: bool) -> Result<IssueUpdate> {
+/// Build the field update described by `args` (`--claim` assigns `actor`).
+///
+/// # Errors
+///
+/// Returns an error if a status, priority, type or date is invalid.
+pub fn build_update(args: &UpdateArgs, actor: &str, claim_exclusive: bool) -> Result<IssueUpdate> {
     let status = if args.claim {
         Some(Status::InProgress)
     } else {
@@ -241,7 +310,8 @@ fn build_update(args: &UpdateArgs, actor: &str, claim_exclusive: bool) -> Result
 
     let closed_at = match &status {
         Some(Status::Closed | Status::Tombstone) => Some(Some(Utc::now())),
-        Some(Status::Open | Status::InProgress) => Some(None),
+        // Custom statuses (even terminal ones) must not carry closed_at.
+        Some(Status::Open | Status::InProgress | Status::Custom(_)) => Some(None),
         _ => None,
     };
 
@@ -146,6 +146,20 @@ const SORT_KEY_CANDIDATES: &[(&str, &str)] = &[
 const DEP_TREE_FORMAT_CANDIDATES: &[(&str, &str)] =
     &[("text", "Text output"), ("mermaid", "Mermaid graph")];
 
+const GRAPH_FORMAT_CANDIDATES: &[(&str, &str)] = &[
+    ("text", "Text output"),
+    ("dot", "Graphviz DOT"),
+    ("graphml", "GraphML"),
+    ("d2", "D2 diagram"),
+    ("mermaid", "Mermaid flowchart"),
+];
+
+const PLAN_FORMAT_CANDIDATES: &[(&str, &str)] = &[
+    ("text", "Text Gantt chart"),
+    ("json", "JSON schedule"),
+    ("mermaid", "Mermaid gantt chart"),
+];
+
 const CSV_FIELD_CANDIDATES: &[(&str, &str)] = &[
     ("id", "Issue ID"),
     ("title", "Title"),
@@ -590,6 +604,20 @@ fn dep_tree_format_completer(current: &OsStr) -> Vec<CompletionCandidate> {
     static_candidates(prefix, DEP_TREE_FORMAT_CANDIDATES)
 }
 
+fn graph_format_completer(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some(prefix) = current.to_str() else {
+        return Vec::new();
+    };
+    static_candidates(prefix, GRAPH_FORMAT_CANDIDATES)
+}
+
+fn plan_format_completer(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some(prefix) = current.to_str() else {
+        return Vec::new();
+    };
+    static_candidates(prefix, PLAN_FORMAT_CANDIDATES)
+}
+
 fn saved_query_completer(current: &OsStr) -> Vec<CompletionCandidate> {
     let Some(prefix) = current.to_str() else {
         return Vec::new();
@@ -667,7 +695,7 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub json: bool,
 
-    /// Force direct mode (no daemon) - effectively no-op in br v1
+    /// Force direct mode: never forward commands to a running daemon
     #[arg(long, global = true)]
     pub no_daemon: bool,
 
@@ -745,9 +773,33 @@ pub enum Commands {
     /// Delete an issue (creates tombstone)
     Delete(DeleteArgs),
 
+    /// Undo recent changes, one command invocation at a time
+    Undo(UndoArgs),
+
+    /// Apply JSONL operations from stdin in one all-or-nothing transaction
+    Batch(BatchArgs),
+
+    /// Create issues from files exported by other tools
+    Import {
+        #[command(subcommand)]
+        command: ImportCommands,
+    },
+
     /// List ready issues (unblocked, not deferred)
     Ready(ReadyArgs),
 
+    /// Pick the top ready issue, optionally claiming it atomically
+    Next(NextArgs),
+
+    /// Claim an issue, optionally under an expiring lease
+    Claim(ClaimArgs),
+
+    /// Renew the lease on a claimed issue
+    Heartbeat(HeartbeatArgs),
+
+    /// List leases and release expired ones
+    Leases(LeasesArgs),
+
     /// List blocked issues
     Blocked(BlockedArgs),
 
@@ -841,6 +893,15 @@ EXAMPLES:
   br sync --status               Show current sync status")]
     Sync(SyncArgs),
 
+    /// Purge expired tombstones with their history
+    Gc(GcArgs),
+
+    /// Summarize old closed issues to shrink the export
+    Compact(CompactArgs),
+
+    /// Merge three versions of the issue export (git merge driver)
+    MergeDriver(MergeDriverArgs),
+
     /// Run read-only diagnostics
     Doctor,
 
@@ -883,11 +944,43 @@ EXAMPLES:
         command: QueryCommands,
     },
 
+    /// Create, list and instantiate issue templates
+    Template {
+        #[command(subcommand)]
+        command: TemplateCommands,
+    },
+
+    /// Generate recurring issues from templates on a schedule
+    Recur {
+        #[command(subcommand)]
+        command: RecurCommands,
+    },
+
     /// Visualize dependency graph
     Graph(GraphArgs),
 
+    /// Critical-path schedule from estimates and dependencies
+    Plan(PlanArgs),
+
     /// Manage AGENTS.md workflow instructions
     Agents(AgentsArgs),
+
+    /// Serve issue tools to agents over MCP (stdio)
+    Mcp,
+
+    /// Run a JSON-RPC daemon on a Unix socket
+    Serve(ServeArgs),
+
+    /// Interactive terminal board
+    Tui,
+}
+
+/// Arguments for the serve command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct ServeArgs {
+    /// Socket path (default: .beads/obr.sock)
+    #[arg(long)]
+    pub socket: Option<std::path::PathBuf>,
 }
 
 /// Arguments for the completions command.
@@ -1179,8 +1272,10 @@ pub enum SchemaTarget {
     All,
     /// Core Issue object (used by many commands)
     Issue,
-    /// List/search row: Issue + dependency/dependent counts
+    /// List row: Issue + dependency/dependent counts
     IssueWithCounts,
+    /// Search row: Issue + counts + relevance score and snippet
+    SearchResult,
     /// Show view: Issue + relations/comments/events
     IssueDetails,
     /// Ready list row
@@ -1373,6 +1468,10 @@ pub struct ListArgs {
     #[arg(long)]
     pub overdue: bool,
 
+    /// Filter expression, e.g. `status:open AND (label:backend OR priority<=1)`
+    #[arg(long = "where", value_name = "EXPR")]
+    pub where_: Option<String>,
+
     /// Use long output format
     #[arg(long)]
     pub long: bool,
@@ -1432,6 +1531,11 @@ pub struct ShowArgs {
     /// Show token savings stats when using TOON output
     #[arg(long)]
     pub stats: bool,
+
+    /// Show the issue as it was at a past moment, rebuilt from events
+    /// (RFC 3339, YYYY-MM-DD, -7d, 3h ago)
+    #[arg(long, value_name = "WHEN")]
+    pub as_of: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -1806,6 +1910,10 @@ pub struct CountArgs {
     /// Title contains substring
     #[arg(long)]
     pub title_contains: Option<String>,
+
+    /// Filter expression, e.g. `type:bug AND updated>-7d`
+    #[arg(long = "where", value_name = "EXPR")]
+    pub where_: Option<String>,
 }
 
 #[derive(ValueEnum, Debug, Clone, Copy, Eq, PartialEq)]
@@ -1871,6 +1979,70 @@ pub struct UndeferArgs {
     pub robot: bool,
 }
 
+/// Arguments for the next command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct NextArgs {
+    /// Claim the issue (assignee=actor + `status=in_progress`) in the same transaction
+    #[arg(long)]
+    pub claim: bool,
+
+    /// Filter by label (AND logic, can be repeated)
+    #[arg(long, short = 'l', add = ArgValueCompleter::new(label_completer))]
+    pub label: Vec<String>,
+
+    /// Filter by issue type (can be repeated)
+    #[arg(long = "type", short = 't', add = ArgValueCompleter::new(issue_type_completer))]
+    pub type_: Vec<String>,
+
+    /// Filter by priority (can be repeated, 0-4 or P0-P4)
+    #[arg(long, short = 'p', add = ArgValueCompleter::new(priority_completer))]
+    pub priority: Vec<String>,
+
+    /// Sort policy: hybrid (default), priority, oldest, score
+    #[arg(long, default_value = "hybrid", value_enum)]
+    pub sort: SortPolicy,
+
+    /// Machine-readable output (alias for --json)
+    #[arg(long)]
+    pub robot: bool,
+}
+
+/// Arguments for the claim command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct ClaimArgs {
+    /// Issue ID to claim
+    #[arg(add = ArgValueCompleter::new(open_issue_id_completer))]
+    pub id: String,
+
+    /// Lease duration (e.g. 30m, 2h, 1h30m); the claim lapses unless renewed
+    #[arg(long)]
+    pub ttl: Option<String>,
+
+    /// Claim even if the issue is blocked
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// Arguments for the heartbeat command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct HeartbeatArgs {
+    /// Issue ID whose lease to renew
+    #[arg(add = ArgValueCompleter::new(open_issue_id_completer))]
+    pub id: String,
+
+    /// New lease duration (default: keep the current TTL)
+    #[arg(long)]
+    pub ttl: Option<String>,
+}
+
+/// Arguments for the leases command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct LeasesArgs {
+    /// Only show leases held by this actor
+    #[arg(long, add = ArgValueCompleter::new(assignee_completer))]
+    pub actor: Option<String>,
+}
+
 /// Arguments for the ready command.
 #[derive(Args, Debug, Clone, Default)]
 #[allow(clippy::struct_excessive_bools)]
@@ -1903,7 +2075,7 @@ pub struct ReadyArgs {
     #[arg(long, short = 'p', add = ArgValueCompleter::new(priority_completer))]
     pub priority: Vec<String>,
 
-    /// Sort policy: hybrid (default), priority, oldest
+    /// Sort policy: hybrid (default), priority, oldest, score
     #[arg(long, default_value = "hybrid", value_enum)]
     pub sort: SortPolicy,
 
@@ -1919,10 +2091,18 @@ pub struct ReadyArgs {
     #[arg(long, short = 'r')]
     pub recursive: bool,
 
+    /// Filter expression, e.g. `label:backend AND NOT assignee:bot`
+    #[arg(long = "where", value_name = "EXPR")]
+    pub where_: Option<String>,
+
     /// Wrap long lines instead of truncating in text output
     #[arg(long)]
     pub wrap: bool,
 
+    /// Show each issue's score breakdown (weights from `ready.score.*` config)
+    #[arg(long)]
+    pub explain: bool,
+
     /// Output format (text, json, toon). Env: BR_OUTPUT_FORMAT, TOON_DEFAULT_FORMAT.
     #[arg(long, value_enum)]
     pub format: Option<OutputFormatBasic>,
@@ -2021,6 +2201,75 @@ pub struct ReopenArgs {
     pub robot: bool,
 }
 
+/// Arguments for the undo command.
+#[derive(Args, Debug, Clone)]
+pub struct UndoArgs {
+    /// Number of most recent operations to undo
+    #[arg(long, short = 'n', default_value_t = 1)]
+    pub steps: usize,
+
+    /// Undo specific operations by id (repeatable; see --list)
+    #[arg(long = "op-id", value_name = "ID", conflicts_with = "steps")]
+    pub op_id: Vec<String>,
+
+    /// List recent operations instead of undoing
+    #[arg(long, conflicts_with_all = ["steps", "op_id"])]
+    pub list: bool,
+
+    /// Maximum operations to list (0 = all)
+    #[arg(long, default_value_t = 20, requires = "list")]
+    pub limit: usize,
+
+    /// Machine-readable output (alias for --json)
+    #[arg(long)]
+    pub robot: bool,
+}
+
+/// Arguments for the batch command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct BatchArgs {
+    /// Read operations from this file instead of stdin
+    #[arg(long, short = 'f')]
+    pub file: Option<PathBuf>,
+
+    /// Machine-readable output (alias for --json)
+    #[arg(long)]
+    pub robot: bool,
+}
+
+/// Subcommands for the import command.
+#[derive(Subcommand, Debug)]
+pub enum ImportCommands {
+    /// Create issues from the rows of a CSV file with a header row
+    Csv(ImportCsvArgs),
+}
+
+/// Arguments for the import csv command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct ImportCsvArgs {
+    /// CSV file; the first row names the columns
+    pub file: PathBuf,
+
+    /// Issue field for each column: "Summary=title,Sev=priority,Owner=assignee"
+    ///
+    /// Columns named after a field map to it without being listed; other
+    /// columns are ignored.
+    #[arg(long, short = 'm')]
+    pub map: Option<String>,
+
+    /// Translate a column's values (repeatable): "Sev: Blocker=0,Major=1"
+    #[arg(long = "values", value_name = "COLUMN: FROM=TO,...")]
+    pub values: Vec<String>,
+
+    /// Separator between labels in the labels column
+    #[arg(long, default_value = ",")]
+    pub label_separator: String,
+
+    /// Show the issues that would be created without creating them
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
 /// Sort policy for ready command.
 #[derive(ValueEnum, Debug, Clone, Copy, Default, Eq, PartialEq)]
 pub enum SortPolicy {
@@ -2031,6 +2280,65 @@ pub enum SortPolicy {
     Priority,
     /// Sort by `created_at` ASC only
     Oldest,
+    /// Weighted score from `ready.score.*` config, highest first
+    Score,
+}
+
+/// Arguments for the gc command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct GcArgs {
+    /// Purge tombstones deleted longer ago than this (e.g. 30d, 12w)
+    ///
+    /// Defaults to `deletions_retention_days` from .beads/metadata.json.
+    #[arg(long)]
+    pub older_than: Option<String>,
+
+    /// Preview only, no changes
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Arguments for the compact command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct CompactArgs {
+    /// Compact issues closed before this (e.g. 90d, 2025-01-01)
+    #[arg(long)]
+    pub closed_before: String,
+
+    /// 1: summarize description, design and notes; 2: keep only title and close reason
+    #[arg(long, default_value_t = 1)]
+    pub level: u8,
+
+    /// Shell command that reads a field on stdin and prints its summary (level 1)
+    ///
+    /// Overrides the `compact.summarizer` config key.
+    #[arg(long)]
+    pub summarizer: Option<String>,
+
+    /// Preview only, no changes
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Arguments for the merge-driver command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct MergeDriverArgs {
+    /// Common ancestor version (git's %O)
+    pub ancestor: PathBuf,
+
+    /// Current branch version, replaced by the merge result (git's %A)
+    pub current: PathBuf,
+
+    /// Other branch version (git's %B)
+    pub other: PathBuf,
+
+    /// Path of the file in the repository (git's %P); an `.org` path merges Org exports
+    pub path: Option<String>,
+
+    /// Settle fields changed on both sides with the newer value and exit 0
+    /// (by default the merge is reported as conflicted)
+    #[arg(long)]
+    pub keep_newer: bool,
 }
 
 /// Arguments for the sync command.
@@ -2227,6 +2535,14 @@ pub enum HistoryCommands {
         #[arg(long)]
         older_than: Option<u32>,
     },
+    /// Show who changed one field of an issue, and when
+    Field {
+        /// Issue ID
+        #[arg(add = ArgValueCompleter::new(issue_id_completer))]
+        id: String,
+        /// Field name (title, status, priority, labels, dependencies, comments, ...)
+        field: String,
+    },
 }
 
 /// Arguments for the version command.
@@ -2346,6 +2662,123 @@ pub struct QueryDeleteArgs {
     pub name: String,
 }
 
+/// Subcommands for the template command.
+#[derive(Subcommand, Debug)]
+pub enum TemplateCommands {
+    /// Create a template issue (use --parent to add to a template tree)
+    Create(TemplateCreateArgs),
+    /// List database and .beads/templates templates
+    List,
+    /// Copy a template tree into new issues, filling in {{var}} placeholders
+    Instantiate(TemplateInstantiateArgs),
+}
+
+/// Arguments for the template create command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct TemplateCreateArgs {
+    /// Template title (may contain {{var}} placeholders)
+    pub title: String,
+
+    /// Issue type (task, bug, feature, etc.)
+    #[arg(long = "type", short = 't', add = ArgValueCompleter::new(issue_type_completer))]
+    pub type_: Option<String>,
+
+    /// Priority (0-4 or P0-P4)
+    #[arg(long, short = 'p', add = ArgValueCompleter::new(priority_completer))]
+    pub priority: Option<String>,
+
+    /// Description (may contain {{var}} placeholders)
+    #[arg(long, short = 'd')]
+    pub description: Option<String>,
+
+    /// Labels (comma-separated)
+    #[arg(long, short = 'l', value_delimiter = ',', add = ArgValueCompleter::new(label_completer_delimited))]
+    pub labels: Vec<String>,
+
+    /// Parent template ID
+    #[arg(long, add = ArgValueCompleter::new(issue_id_completer))]
+    pub parent: Option<String>,
+
+    /// Dependencies on other template issues (format: type:id,type:id)
+    #[arg(long, value_delimiter = ',', add = ArgValueCompleter::new(deps_completer))]
+    pub deps: Vec<String>,
+
+    /// Time estimate in minutes
+    #[arg(long, short = 'e')]
+    pub estimate: Option<i32>,
+}
+
+/// Arguments for the template instantiate command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct TemplateInstantiateArgs {
+    /// Template ID, or the name of a .beads/templates/<name>.yaml file
+    pub template: String,
+
+    /// Placeholder value (repeatable)
+    #[arg(long = "var", value_name = "NAME=VALUE")]
+    pub vars: Vec<String>,
+
+    /// Attach the new tree under this issue
+    #[arg(long, add = ArgValueCompleter::new(issue_id_completer))]
+    pub parent: Option<String>,
+
+    /// Show the issues that would be created without creating them
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Subcommands for the recur command.
+#[derive(Subcommand, Debug)]
+pub enum RecurCommands {
+    /// Attach a recurrence rule to a template
+    Add(RecurAddArgs),
+    /// List recurring series and their next occurrence
+    List,
+    /// Stop a series (existing occurrences are kept)
+    Remove(RecurRemoveArgs),
+    /// Create the next occurrence of each series if missing (idempotent)
+    Run(RecurRunArgs),
+}
+
+/// Arguments for the recur add command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct RecurAddArgs {
+    /// Template ID
+    #[arg(add = ArgValueCompleter::new(issue_id_completer))]
+    pub template: String,
+
+    /// Rule: "every 2w on mon", "every month on 1 at 10:00", or cron "0 9 * * 1"
+    pub rule: String,
+
+    /// Make each occurrence ready this long before it is due (e.g. 2d)
+    #[arg(long)]
+    pub lead: Option<String>,
+
+    /// Series start; anchors intervals (default: now)
+    #[arg(long)]
+    pub start: Option<String>,
+
+    /// Template placeholder value (repeatable); {{date}} is filled automatically
+    #[arg(long = "var", value_name = "NAME=VALUE")]
+    pub vars: Vec<String>,
+}
+
+/// Arguments for the recur remove command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct RecurRemoveArgs {
+    /// Template ID
+    #[arg(add = ArgValueCompleter::new(issue_id_completer))]
+    pub template: String,
+}
+
+/// Arguments for the recur run command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct RecurRunArgs {
+    /// Report what would be created without creating it
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
 /// Arguments for the graph command.
 #[derive(Args, Debug, Clone, Default)]
 pub struct GraphArgs {
@@ -2360,6 +2793,34 @@ pub struct GraphArgs {
     /// One line per issue (compact output)
     #[arg(long)]
     pub compact: bool,
+
+    /// Output format: text, dot, graphml, d2, mermaid
+    #[arg(long, default_value = "text", add = ArgValueCompleter::new(graph_format_completer))]
+    pub format: String,
+}
+
+/// Arguments for the plan command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct PlanArgs {
+    /// Only schedule open work under this epic
+    #[arg(long, add = ArgValueCompleter::new(issue_id_completer))]
+    pub epic: Option<String>,
+
+    /// Number of people working in parallel (default: unlimited)
+    #[arg(long, value_name = "N")]
+    pub capacity: Option<usize>,
+
+    /// Minutes assumed for issues without an estimate
+    #[arg(long, default_value_t = 60, value_name = "MINUTES")]
+    pub default_estimate: i64,
+
+    /// Working hours per calendar day, for predicted dates
+    #[arg(long, default_value_t = 8, value_name = "HOURS")]
+    pub hours_per_day: i64,
+
+    /// Output format: text, json, mermaid
+    #[arg(long, default_value = "text", add = ArgValueCompleter::new(plan_format_completer))]
+    pub format: String,
 }
 
 /// Arguments for the agents command.
@@ -10,10 +10,12 @@
 //! 7. Defaults
 
 pub mod routing;
+pub mod workflow;
 
 use crate::error::{BeadsError, Result};
 use crate::model::{IssueType, Priority};
 use crate::storage::SqliteStorage;
+use crate::storage::scoring::ScoreWeights;
 use crate::sync::{
     ExportConfig, ImportConfig, export_to_jsonl_with_policy, finalize_export, import_from_jsonl,
 };
@@ -56,6 +58,9 @@ pub struct Metadata {
     pub backend: Option<String>,
     #[serde(default)]
     pub deletions_retention_days: Option<u64>,
+    /// Nest child issues under their parent in the Org export.
+    #[serde(default)]
+    pub org_nested: bool,
 }
 
 impl Default for Metadata {
@@ -65,6 +70,7 @@ impl Default for Metadata {
             jsonl_export: DEFAULT_JSONL_FILENAME.to_string(),
             backend: None,
             deletions_retention_days: None,
+            org_nested: false,
         }
     }
 }
@@ -98,11 +104,12 @@ impl Metadata {
 ///
 /// Selection rules:
 /// 1. Prefer `issues.org` if present (new default).
-/// 2. Fall back to `issues.jsonl` if present (legacy).
-/// 3. Fall back to `beads.jsonl` (older legacy) if present.
-/// 4. Never use merge artifacts (`beads.base.jsonl`, `beads.left.jsonl`, `beads.right.jsonl`).
-/// 5. Never use deletion logs (`deletions.jsonl`) or interaction logs (`interactions.jsonl`).
-/// 6. If no valid file exists, return `None` (caller should use default for writing).
+/// 2. Fall back to the `issues/` per-issue markdown directory if present.
+/// 3. Fall back to `issues.jsonl` if present (legacy).
+/// 4. Fall back to `beads.jsonl` (older legacy) if present.
+/// 5. Never use merge artifacts (`beads.base.jsonl`, `beads.left.jsonl`, `beads.right.jsonl`).
+/// 6. Never use deletion logs (`deletions.jsonl`) or interaction logs (`interactions.jsonl`).
+/// 7. If no valid file exists, return `None` (caller should use default for writing).
 #[must_use]
 pub fn discover_jsonl(beads_dir: &Path) -> Option<PathBuf> {
     // Check preferred file first (issues.org)
@@ -111,6 +118,12 @@ pub fn discover_jsonl(beads_dir: &Path) -> Option<PathBuf> {
         return Some(issues_path);
     }
 
+    // Check per-issue markdown directory (issues/)
+    let markdown_path = beads_dir.join(crate::sync::markdown_bridge::MARKDOWN_EXPORT_DIR);
+    if markdown_path.is_dir() {
+        return Some(markdown_path);
+    }
+
     // Check legacy file (issues.jsonl)
     let legacy_path = beads_dir.join(LEGACY_JSONL_FILENAME);
     if legacy_path.is_file() {
@@ -305,6 +318,7 @@ pub fn open_storage(
     lock_timeout: Option<u64>,
 ) -> Result<(SqliteStorage, ConfigPaths)> {
     let startup_layer = load_startup_config(beads_dir)?;
+    workflow::Workflow::from_layer(&startup_layer)?.install()?;
     let resolved_db_override = db_override
         .cloned()
         .or_else(|| db_override_from_layer(&startup_layer));
@@ -345,6 +359,7 @@ impl OpenStorageResult {
             beads_dir: Some(self.paths.beads_dir.clone()),
             allow_external_jsonl: false,
             show_progress: false,
+            org_nested: self.paths.metadata.org_nested,
             ..Default::default()
         };
 
@@ -369,6 +384,7 @@ pub fn open_storage_with_cli(beads_dir: &Path, cli: &CliOverrides) -> Result<Ope
     let startup_layer = load_startup_config(beads_dir)?;
     let cli_layer = cli.as_layer();
     let merged_layer = ConfigLayer::merge_layers(&[startup_layer, cli_layer]);
+    workflow::Workflow::from_layer(&merged_layer)?.install()?;
 
     let no_db = no_db_from_layer(&merged_layer).unwrap_or(false);
 
@@ -449,18 +465,15 @@ fn resolve_no_db_prefix(beads_dir: &Path, jsonl_path: &Path) -> Result<String> {
 }
 
 fn common_prefix_from_jsonl(jsonl_path: &Path) -> Result<Option<String>> {
-    if !jsonl_path.is_file() {
+    if !jsonl_path.exists() {
         return Ok(None);
     }
 
-    // For Org-mode files, parse issues via org_bridge and extract prefixes
-    let is_org = jsonl_path
-        .extension()
-        .and_then(|e| e.to_str()) == Some("org");
+    // For Org-mode files and markdown directories, parse issues and extract prefixes
+    let is_org = jsonl_path.extension().and_then(|e| e.to_str()) == Some("org");
 
-    if is_org {
-        let content = std::fs::read_to_string(jsonl_path)?;
-        let issues = crate::sync::org_bridge::org_text_to_issues(&content)?;
+    if is_org || jsonl_path.is_dir() {
+        let issues = crate::sync::read_issues_from_jsonl(jsonl_path)?;
         let mut prefixes: HashSet<String> = HashSet::new();
         for issue in &issues {
             if let Some((prefix, _)) = issue.id.split_once('-') {
@@ -889,6 +902,24 @@ pub fn display_color_from_layer(layer: &ConfigLayer) -> Option<bool> {
         .and_then(|value| parse_bool(value))
 }
 
+/// Resolve the external summarizer command for `compact`.
+///
+/// Accepts keys: `compact.summarizer`, `compact-summarizer`, `compact_summarizer`.
+#[must_use]
+pub fn compact_summarizer_from_layer(layer: &ConfigLayer) -> Option<String> {
+    get_value(
+        layer,
+        &[
+            "compact.summarizer",
+            "compact-summarizer",
+            "compact_summarizer",
+        ],
+    )
+    .map(|value| value.trim())
+    .filter(|value| !value.is_empty())
+    .map(str::to_string)
+}
+
 /// Determine whether human-readable output should use ANSI color.
 ///
 /// Precedence:
@@ -1012,6 +1043,74 @@ pub fn resolve_actor(layer: &ConfigLayer) -> String {
         .unwrap_or_else(|| "unknown".to_string())
 }
 
+/// Read the `ready.score.*` weights for `ready --sort score`.
+///
+/// ```yaml
+/// ready:
+///   score:
+///     priority: 10        # per level above P4
+///     age: 0.5            # per day waiting, up to age-cap-days
+///     age-cap-days: 30
+///     due: 20             # full weight once due, fading over due-window-days
+///     due-window-days: 14
+///     unblocks: 5         # per dependent issue
+///     estimate: -1        # per estimated hour
+///     labels:
+///       quick-win: 15
+/// ```
+///
+/// Unset keys keep their defaults.
+///
+/// # Errors
+///
+/// Returns a config error for unknown keys or values that are not numbers.
+pub fn score_weights_from_layer(layer: &ConfigLayer) -> Result<ScoreWeights> {
+    const PREFIX: &str = "ready.score.";
+
+    let mut weights = ScoreWeights::default();
+    // Sort keys so errors are reported deterministically.
+    let mut entries: Vec<(&str, &String)> = layer
+        .runtime
+        .iter()
+        .map(|(key, value)| (key.trim(), value))
+        .collect();
+    entries.sort();
+
+    for (key, value) in entries {
+        let normalized = normalize_key(key);
+        if !normalized.starts_with(PREFIX) {
+            continue;
+        }
+        // Label names keep their spelling; the rest is case-insensitive.
+        let name = normalized[PREFIX.len()..].to_string();
+        let raw_name = &key[PREFIX.len()..];
+        let number: f64 = value
+            .trim()
+            .parse()
+            .map_err(|_| BeadsError::Config(format!("{key}: expected a number, got '{value}'")))?;
+        if !number.is_finite() {
+            return Err(BeadsError::Config(format!("{key}: must be finite")));
+        }
+        let slot = match name.as_str() {
+            "priority" => &mut weights.priority,
+            "age" => &mut weights.age,
+            "age-cap-days" => &mut weights.age_cap_days,
+            "due" => &mut weights.due,
+            "due-window-days" => &mut weights.due_window_days,
+            "unblocks" => &mut weights.unblocks,
+            "estimate" => &mut weights.estimate,
+            _ if name.starts_with("labels.") => {
+                let label = &raw_name["labels.".len()..];
+                weights.labels.insert(label.to_string(), number);
+                continue;
+            }
+            _ => return Err(BeadsError::Config(format!("{key}: unknown score term"))),
+        };
+        *slot = number;
+    }
+    Ok(weights)
+}
+
 /// Read the `claim-exclusive` config key.
 ///
 /// When true, `--claim` rejects re-claims even by the same actor.
@@ -1036,6 +1135,8 @@ pub fn is_startup_key(key: &str) -> bool {
         || normalized.starts_with("directory.")
         || normalized.starts_with("sync.")
         || normalized.starts_with("external-projects.")
+        || normalized.starts_with("statuses.")
+        || normalized.starts_with("workflow.")
     {
         return true;
     }
@@ -1333,6 +1434,28 @@ labels:
         assert_eq!(issue_type, IssueType::Feature);
     }
 
+    #[test]
+    fn score_weights_from_yaml() {
+        let yaml = "ready:\n  score:\n    unblocks: 8\n    age_cap_days: 10\n    labels:\n      Quick_Win: 15\n";
+        let value: serde_yaml::Value = serde_yaml::from_str(yaml).expect("parse yaml");
+        let layer = layer_from_yaml_value(&value);
+
+        let weights = score_weights_from_layer(&layer).expect("weights");
+        assert!((weights.unblocks - 8.0).abs() < f64::EPSILON);
+        assert!((weights.age_cap_days - 10.0).abs() < f64::EPSILON);
+        assert_eq!(weights.labels.get("Quick_Win"), Some(&15.0));
+        assert_eq!(weights.priority, ScoreWeights::default().priority);
+
+        let mut bad = ConfigLayer::default();
+        bad.runtime
+            .insert("ready.score.speed".to_string(), "1".to_string());
+        assert!(score_weights_from_layer(&bad).is_err());
+        bad.runtime.clear();
+        bad.runtime
+            .insert("ready.score.age".to_string(), "lots".to_string());
+        assert!(score_weights_from_layer(&bad).is_err());
+    }
+
     #[test]
     fn db_layer_skips_startup_keys() {
         let mut storage = SqliteStorage::open_memory().expect("storage");
@@ -1686,6 +1809,7 @@ labels:
             jsonl_export: DEFAULT_JSONL_FILENAME.to_string(),
             backend: None,
             deletions_retention_days: None,
+            org_nested: false,
         };
 
         let resolved = resolve_db_path(&beads_dir, &metadata, None);
@@ -1703,6 +1827,7 @@ labels:
             jsonl_export: DEFAULT_JSONL_FILENAME.to_string(),
             backend: None,
             deletions_retention_days: None,
+            org_nested: false,
         };
 
         let resolved = resolve_db_path(&beads_dir, &metadata, None);
@@ -1734,6 +1859,7 @@ labels:
             jsonl_export: absolute_path.to_string(),
             backend: None,
             deletions_retention_days: None,
+            org_nested: false,
         };
 
         let resolved = resolve_jsonl_path(&beads_dir, &metadata, None);
@@ -1751,6 +1877,7 @@ labels:
             jsonl_export: "relative.jsonl".to_string(),
             backend: None,
             deletions_retention_days: None,
+            org_nested: false,
         };
 
         let resolved = resolve_jsonl_path(&beads_dir, &metadata, None);
@@ -2008,6 +2135,21 @@ routing:
         assert_eq!(discovered, beads_dir.join("beads.jsonl"));
     }
 
+    #[test]
+    fn discover_jsonl_prefers_markdown_dir_over_legacy() {
+        let temp = TempDir::new().expect("tempdir");
+        let beads_dir = temp.path().join(".beads");
+        fs::create_dir_all(beads_dir.join("issues")).expect("create markdown dir");
+        fs::write(beads_dir.join("issues.jsonl"), "{}").expect("write issues");
+
+        let discovered = discover_jsonl(&beads_dir).expect("should discover");
+        assert_eq!(discovered, beads_dir.join("issues"));
+
+        fs::write(beads_dir.join("issues.org"), "").expect("write org");
+        let discovered = discover_jsonl(&beads_dir).expect("should discover");
+        assert_eq!(discovered, beads_dir.join("issues.org"));
+    }
+
     #[test]
     fn discover_jsonl_returns_none_when_empty() {
         let temp = TempDir::new().expect("tempdir");
@@ -2089,6 +2231,7 @@ routing:
             jsonl_export: "custom.jsonl".to_string(),
             backend: None,
             deletions_retention_days: None,
+            org_nested: false,
         };
 
         let resolved = resolve_jsonl_path(&beads_dir, &metadata, None);
@@ -2110,6 +2253,7 @@ routing:
             jsonl_export: "deletions.jsonl".to_string(),
             backend: None,
             deletions_retention_days: None,
+            org_nested: false,
         };
 
         let resolved = resolve_jsonl_path(&beads_dir, &metadata, None);
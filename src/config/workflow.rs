@@ -0,0 +1,291 @@
+//! Custom statuses and workflow transition graphs.
+//!
+//! Both are declared in `config.yaml`:
+//!
+//! ```yaml
+//! statuses:
+//!   in_review: active
+//!   qa: waiting
+//!   wontfix: terminal
+//!
+//! workflow:
+//!   default:
+//!     open: [in_progress, closed]
+//!     in_progress: [in_review, open]
+//!     in_review: [qa, in_progress]
+//!     qa: [closed, in_progress]
+//!   bug:
+//!     open: [in_progress, wontfix]
+//! ```
+//!
+//! # Semantics
+//!
+//! - Each custom status has a category (`active`, `waiting`, `terminal`) that
+//!   drives ready/blocked computation just like the built-in statuses.
+//! - A graph for the issue's type replaces the `default` graph entirely.
+//! - A status with no entry in the applicable graph may move anywhere; an
+//!   empty list (`closed: []`) makes it final.
+//! - Moving to the same status and deleting (tombstone) are always allowed.
+
+use super::ConfigLayer;
+use crate::error::{BeadsError, Result};
+use crate::model::{
+    Issue, IssueType, Status, StatusCategory, register_custom_status, validate_custom_status_name,
+};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Key naming the graph used for issue types without their own graph.
+const DEFAULT_GRAPH: &str = "default";
+
+/// Allowed targets keyed by source status name.
+type TransitionGraph = BTreeMap<String, Vec<String>>;
+
+/// Parsed `statuses:` and `workflow:` configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Workflow {
+    statuses: BTreeMap<String, StatusCategory>,
+    graphs: BTreeMap<String, TransitionGraph>,
+}
+
+impl Workflow {
+    /// Parse custom statuses and transition graphs from a merged config layer.
+    ///
+    /// # Errors
+    ///
+    /// Returns a config error for unknown categories, invalid status names, or
+    /// transitions that mention a status that is neither built-in nor declared.
+    pub fn from_layer(layer: &ConfigLayer) -> Result<Self> {
+        let mut workflow = Self::default();
+
+        // Sort keys so errors are reported deterministically.
+        let entries: BTreeMap<String, &String> = layer
+            .startup
+            .iter()
+            .map(|(key, value)| (key.trim().to_lowercase(), value))
+            .collect();
+
+        for (key, value) in &entries {
+            let Some(name) = key.strip_prefix("statuses.") else {
+                continue;
+            };
+            if name.contains('.') {
+                continue;
+            }
+            validate_custom_status_name(name).map_err(|err| in_key(key, err))?;
+            let category = StatusCategory::from_str(value).map_err(|err| in_key(key, err))?;
+            workflow.statuses.insert(name.to_string(), category);
+        }
+
+        for (key, value) in &entries {
+            let Some(rest) = key.strip_prefix("workflow.") else {
+                continue;
+            };
+            let Some((graph, from)) = rest.split_once('.') else {
+                continue;
+            };
+            if from.contains('.') {
+                continue;
+            }
+            let from = workflow.canonical_name(key, from)?;
+            let mut targets = Vec::new();
+            for target in value.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+                let target = workflow.canonical_name(key, target)?;
+                if !targets.contains(&target) {
+                    targets.push(target);
+                }
+            }
+            workflow
+                .graphs
+                .entry(graph.to_string())
+                .or_default()
+                .insert(from, targets);
+        }
+
+        Ok(workflow)
+    }
+
+    /// Register the declared custom statuses so they parse process-wide.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a status name is invalid or shadows a built-in.
+    pub fn install(&self) -> Result<()> {
+        for (name, category) in &self.statuses {
+            register_custom_status(name, *category)?;
+        }
+        Ok(())
+    }
+
+    /// Declared custom statuses and their categories.
+    #[must_use]
+    pub const fn statuses(&self) -> &BTreeMap<String, StatusCategory> {
+        &self.statuses
+    }
+
+    /// Returns true if no transition graph is configured.
+    #[must_use]
+    pub fn is_unrestricted(&self) -> bool {
+        self.graphs.is_empty()
+    }
+
+    /// Allowed targets from `from` for an issue type, or `None` if unrestricted.
+    #[must_use]
+    pub fn allowed_targets(&self, issue_type: &IssueType, from: &Status) -> Option<&[String]> {
+        self.graphs
+            .get(issue_type.as_str())
+            .or_else(|| self.graphs.get(DEFAULT_GRAPH))
+            .and_then(|graph| graph.get(from.as_str()))
+            .map(Vec::as_slice)
+    }
+
+    /// Check that `issue` may move to `to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BeadsError::InvalidTransition` when the configured graph for the
+    /// issue's type does not allow the move.
+    pub fn check_transition(&self, issue: &Issue, to: &Status) -> Result<()> {
+        if issue.status == *to || matches!(to, Status::Tombstone) {
+            return Ok(());
+        }
+        match self.allowed_targets(&issue.issue_type, &issue.status) {
+            Some(allowed) if !allowed.iter().any(|target| target == to.as_str()) => {
+                Err(BeadsError::InvalidTransition {
+                    id: issue.id.clone(),
+                    issue_type: issue.issue_type.as_str().to_string(),
+                    from: issue.status.as_str().to_string(),
+                    to: to.as_str().to_string(),
+                    allowed: allowed.to_vec(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Resolve a status name used in a transition to its canonical spelling.
+    fn canonical_name(&self, key: &str, name: &str) -> Result<String> {
+        let name = name.to_lowercase();
+        match Status::from_str(&name) {
+            Ok(status) if status.is_builtin() => Ok(status.as_str().to_string()),
+            _ if self.statuses.contains_key(&name) => Ok(name),
+            _ => Err(BeadsError::Config(format!(
+                "{key}: unknown status '{name}' (declare it under statuses:)"
+            ))),
+        }
+    }
+}
+
+/// Prefix a config error with the key it came from.
+fn in_key(key: &str, err: BeadsError) -> BeadsError {
+    match err {
+        BeadsError::Config(message) => BeadsError::Config(format!("{key}: {message}")),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(pairs: &[(&str, &str)]) -> ConfigLayer {
+        let mut layer = ConfigLayer::default();
+        for (key, value) in pairs {
+            layer
+                .startup
+                .insert((*key).to_string(), (*value).to_string());
+        }
+        layer
+    }
+
+    fn issue(status: Status, issue_type: IssueType) -> Issue {
+        Issue {
+            id: "bd-1".to_string(),
+            status,
+            issue_type,
+            ..Issue::default()
+        }
+    }
+
+    #[test]
+    fn parses_statuses_and_graphs() {
+        let workflow = Workflow::from_layer(&layer(&[
+            ("statuses.wf_review", "active"),
+            ("statuses.wf_qa", "Waiting"),
+            ("workflow.default.open", "in_progress, closed"),
+            ("workflow.bug.open", "in_progress"),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            workflow.statuses().get("wf_review"),
+            Some(&StatusCategory::Active)
+        );
+        assert_eq!(
+            workflow.statuses().get("wf_qa"),
+            Some(&StatusCategory::Waiting)
+        );
+        assert_eq!(
+            workflow.allowed_targets(&IssueType::Task, &Status::Open),
+            Some(&["in_progress".to_string(), "closed".to_string()][..])
+        );
+        assert_eq!(
+            workflow.allowed_targets(&IssueType::Bug, &Status::Open),
+            Some(&["in_progress".to_string()][..])
+        );
+        assert_eq!(
+            workflow.allowed_targets(&IssueType::Task, &Status::Closed),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_bad_configuration() {
+        assert!(Workflow::from_layer(&layer(&[("statuses.wf_x", "done")])).is_err());
+        assert!(Workflow::from_layer(&layer(&[("statuses.open", "active")])).is_err());
+        assert!(Workflow::from_layer(&layer(&[("workflow.default.open", "nowhere")])).is_err());
+    }
+
+    #[test]
+    fn check_transition_enforces_type_graph() {
+        let workflow = Workflow::from_layer(&layer(&[
+            ("workflow.default.open", "in_progress,closed"),
+            ("workflow.bug.open", "in_progress"),
+            ("workflow.default.closed", ""),
+        ]))
+        .unwrap();
+
+        let task = issue(Status::Open, IssueType::Task);
+        assert!(workflow.check_transition(&task, &Status::Closed).is_ok());
+        assert!(workflow.check_transition(&task, &Status::Open).is_ok());
+
+        let bug = issue(Status::Open, IssueType::Bug);
+        let err = workflow
+            .check_transition(&bug, &Status::Closed)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            BeadsError::InvalidTransition { ref allowed, .. } if allowed == &["in_progress"]
+        ));
+        // The bug graph has no entry for in_progress, so it is unrestricted.
+        let started = issue(Status::InProgress, IssueType::Bug);
+        assert!(workflow.check_transition(&started, &Status::Closed).is_ok());
+
+        // An empty list makes a status final, but deletion is always allowed.
+        let closed = issue(Status::Closed, IssueType::Task);
+        assert!(workflow.check_transition(&closed, &Status::Open).is_err());
+        assert!(
+            workflow
+                .check_transition(&closed, &Status::Tombstone)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn unconfigured_workflow_allows_everything() {
+        let workflow = Workflow::from_layer(&ConfigLayer::default()).unwrap();
+        assert!(workflow.is_unrestricted());
+        let closed = issue(Status::Closed, IssueType::Task);
+        assert!(workflow.check_transition(&closed, &Status::Open).is_ok());
+    }
+}
@@ -0,0 +1,77 @@
+//! Client side of the daemon protocol.
+
+use crate::error::{BeadsError, ErrorCode, Result, StructuredError};
+use serde_json::{Value, json};
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+
+/// A connection to a running `br serve` daemon.
+pub struct DaemonClient {
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+    next_id: u64,
+}
+
+impl DaemonClient {
+    /// Connect to the daemon listening on `socket_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if nothing is listening (e.g. a stale socket).
+    pub fn connect(socket_path: &Path) -> io::Result<Self> {
+        let writer = UnixStream::connect(socket_path)?;
+        let reader = BufReader::new(writer.try_clone()?);
+        Ok(Self {
+            reader,
+            writer,
+            next_id: 1,
+        })
+    }
+
+    /// Call `method` and wait for its result.
+    ///
+    /// # Errors
+    ///
+    /// Returns the daemon's structured error as [`BeadsError::Daemon`], or
+    /// an I/O error if the connection drops mid-request.
+    pub fn call(&mut self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let request = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        serde_json::to_writer(&mut self.writer, &request)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()?;
+
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(BeadsError::Io(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "daemon closed the connection without responding",
+            )));
+        }
+        let mut response: Value = serde_json::from_str(&line)?;
+
+        if let Some(error) = response.get("error") {
+            let structured = error
+                .get("data")
+                .and_then(StructuredError::from_json)
+                .unwrap_or_else(|| StructuredError {
+                    code: ErrorCode::InternalError,
+                    message: error
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .unwrap_or("daemon request failed")
+                        .to_string(),
+                    hint: None,
+                    retryable: false,
+                    context: Some(error.clone()),
+                });
+            return Err(BeadsError::Daemon(Box::new(structured)));
+        }
+        Ok(response
+            .get_mut("result")
+            .map(Value::take)
+            .unwrap_or(Value::Null))
+    }
+}
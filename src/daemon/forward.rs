@@ -0,0 +1,357 @@
+//! Transparent forwarding of CLI commands to a running daemon.
+//!
+//! Only `--json` invocations whose output the daemon's tools reproduce
+//! exactly are forwarded: `ready`, `show`, `create`, single-issue `update`
+//! and single-issue `close`. Everything else, and every command when no
+//! daemon answers on `.beads/obr.sock`, runs directly against the database.
+
+use super::client::DaemonClient;
+use super::default_socket_path;
+use crate::cli::commands::close::CloseResult;
+use crate::cli::commands::update::UpdatedIssueOutput;
+use crate::cli::{
+    Commands, CreateArgs, OutputFormat, OutputFormatBasic, ReadyArgs, SortPolicy, UpdateArgs,
+    resolve_output_format_basic,
+};
+use crate::config::{self, CliOverrides};
+use crate::error::{BeadsError, Result};
+use crate::format::{IssueDetails, ReadyIssue};
+use crate::model::Issue;
+use crate::output::OutputContext;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use tracing::debug;
+
+/// How to print the daemon's answers so they match direct output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Render {
+    Ready,
+    Show,
+    Created,
+    Updated,
+    Closed,
+}
+
+/// The daemon calls a forwarded command makes.
+#[derive(Debug)]
+struct Plan {
+    calls: Vec<(&'static str, Value)>,
+    render: Render,
+}
+
+/// Run `command` through the daemon if one is listening and the command
+/// can be forwarded.
+///
+/// Returns `None` when the caller should execute the command directly.
+/// Once a request has been sent, failures are returned rather than retried
+/// directly, so a mutation is never applied twice.
+pub fn forward(command: &Commands, cli: &CliOverrides, ctx: &OutputContext) -> Option<Result<()>> {
+    if cli.no_daemon.unwrap_or(false)
+        || cli.no_db.unwrap_or(false)
+        || cli.db.is_some()
+        || ctx.is_quiet()
+    {
+        return None;
+    }
+    let plan = plan(command, ctx.is_json())?;
+
+    let beads_dir = config::discover_beads_dir(None).ok()?;
+    let socket_path = default_socket_path(&beads_dir);
+    if !socket_path.exists() {
+        return None;
+    }
+    let mut client = match DaemonClient::connect(&socket_path) {
+        Ok(client) => client,
+        Err(e) => {
+            debug!(?e, socket = %socket_path.display(), "Daemon unreachable, using direct access");
+            return None;
+        }
+    };
+
+    // Attribute changes to this caller, not to whoever started the daemon.
+    let actor = config::load_config(&beads_dir, None, cli)
+        .ok()
+        .and_then(|layer| config::actor_from_layer(&layer));
+    debug!(socket = %socket_path.display(), render = ?plan.render, "Forwarding command to daemon");
+    Some(run(&mut client, plan, actor.as_deref()))
+}
+
+fn plan(command: &Commands, json: bool) -> Option<Plan> {
+    let single = |method, params| Plan {
+        calls: vec![(method, params)],
+        render: Render::Created,
+    };
+    match command {
+        Commands::Ready(args) if is_json(args.format, json, args.robot) && !args.explain => {
+            Some(Plan {
+                render: Render::Ready,
+                ..single("ready", ready_params(args))
+            })
+        }
+        Commands::Show(args)
+            if is_json(args.format, json, false)
+                && args.as_of.is_none()
+                && !args.ids.is_empty() =>
+        {
+            Some(Plan {
+                calls: args
+                    .ids
+                    .iter()
+                    .map(|id| ("show", json!({ "id": id })))
+                    .collect(),
+                render: Render::Show,
+            })
+        }
+        Commands::Create(args)
+            if json && args.file.is_none() && !args.dry_run && !args.silent && !args.ephemeral =>
+        {
+            create_params(args).map(|params| single("create", params))
+        }
+        Commands::Update(args) if json && args.ids.len() == 1 => {
+            update_call(args).map(|(method, params)| Plan {
+                render: Render::Updated,
+                ..single(method, params)
+            })
+        }
+        Commands::Close(args)
+            if (json || args.robot) && args.ids.len() == 1 && !args.suggest_next =>
+        {
+            Some(Plan {
+                render: Render::Closed,
+                ..single(
+                    "close",
+                    json!({
+                        "id": args.ids[0],
+                        "reason": args.reason,
+                        "force": args.force,
+                        "session": args.session,
+                    }),
+                )
+            })
+        }
+        _ => None,
+    }
+}
+
+fn is_json(format: Option<OutputFormatBasic>, json: bool, robot: bool) -> bool {
+    resolve_output_format_basic(format, json, robot) == OutputFormat::Json
+}
+
+fn ready_params(args: &ReadyArgs) -> Value {
+    let sort = match args.sort {
+        SortPolicy::Hybrid => "hybrid",
+        SortPolicy::Priority => "priority",
+        SortPolicy::Oldest => "oldest",
+        SortPolicy::Score => "score",
+    };
+    json!({
+        "limit": args.limit,
+        "assignee": args.assignee,
+        "unassigned": args.unassigned,
+        "labels": args.label,
+        "labels_any": args.label_any,
+        "types": args.type_,
+        "priorities": args.priority,
+        "include_deferred": args.include_deferred,
+        "parent": args.parent,
+        "recursive": args.recursive,
+        "where": args.where_,
+        "sort": sort,
+    })
+}
+
+fn create_params(args: &CreateArgs) -> Option<Value> {
+    let title = args.title.as_ref().or(args.title_flag.as_ref())?;
+    Some(json!({
+        "title": title,
+        "type": args.type_,
+        "priority": args.priority,
+        "description": args.description,
+        "assignee": args.assignee,
+        "owner": args.owner,
+        "labels": args.labels,
+        "parent": args.parent,
+        "deps": args.deps,
+        "estimate": args.estimate,
+        "due": args.due,
+        "defer": args.defer,
+        "external_ref": args.external_ref,
+        "status": args.status,
+    }))
+}
+
+/// `--claim` alone maps to the `claim` tool; anything else to `update`.
+fn update_call(args: &UpdateArgs) -> Option<(&'static str, Value)> {
+    let id = &args.ids[0];
+    let has_field_changes = args.title.is_some()
+        || args.description.is_some()
+        || args.design.is_some()
+        || args.acceptance_criteria.is_some()
+        || args.notes.is_some()
+        || args.status.is_some()
+        || args.priority.is_some()
+        || args.type_.is_some()
+        || args.assignee.is_some()
+        || args.owner.is_some()
+        || args.due.is_some()
+        || args.defer.is_some()
+        || args.estimate.is_some()
+        || !args.add_label.is_empty()
+        || !args.remove_label.is_empty()
+        || !args.set_labels.is_empty()
+        || args.parent.is_some()
+        || args.external_ref.is_some()
+        || args.session.is_some();
+
+    if args.claim {
+        return (!has_field_changes).then(|| ("claim", json!({ "id": id, "force": args.force })));
+    }
+    Some((
+        "update",
+        json!({
+            "id": id,
+            "title": args.title,
+            "description": args.description,
+            "design": args.design,
+            "acceptance_criteria": args.acceptance_criteria,
+            "notes": args.notes,
+            "status": args.status,
+            "priority": args.priority,
+            "type": args.type_,
+            "assignee": args.assignee,
+            "owner": args.owner,
+            "due": args.due,
+            "defer": args.defer,
+            "estimate": args.estimate,
+            "add_labels": args.add_label,
+            "remove_labels": args.remove_label,
+            "set_labels": args.set_labels,
+            "parent": args.parent,
+            "external_ref": args.external_ref,
+            "force": args.force,
+            "session": args.session,
+        }),
+    ))
+}
+
+fn run(client: &mut DaemonClient, plan: Plan, actor: Option<&str>) -> Result<()> {
+    let mut results = Vec::with_capacity(plan.calls.len());
+    for (method, mut params) in plan.calls {
+        if let (Some(actor), Some(object)) = (actor, params.as_object_mut()) {
+            object.insert("actor".to_string(), json!(actor));
+        }
+        results.push(client.call(method, params)?);
+    }
+
+    // Re-serialize through the typed models so field order matches direct output.
+    let out = OutputContext::from_output_format(OutputFormat::Json, false, true);
+    match plan.render {
+        Render::Ready => {
+            let mut result = results.pop().unwrap_or_default();
+            let issues: Vec<ReadyIssue> = parse(result["issues"].take())?;
+            out.json_pretty(&issues);
+        }
+        Render::Show => {
+            let details = results
+                .into_iter()
+                .map(parse)
+                .collect::<Result<Vec<IssueDetails>>>()?;
+            out.json_pretty(&details);
+        }
+        Render::Created => {
+            let issue: Issue = parse(results.pop().unwrap_or_default())?;
+            out.json_pretty(&issue);
+        }
+        Render::Updated => {
+            let issue: Issue = parse(results.pop().unwrap_or_default())?;
+            out.json_pretty(&[UpdatedIssueOutput::from(&issue)]);
+        }
+        Render::Closed => {
+            let result: CloseResult = parse(results.pop().unwrap_or_default())?;
+            out.json_pretty(&result.closed);
+            if result.closed.is_empty() && !result.skipped.is_empty() {
+                return Err(BeadsError::NothingToDo {
+                    reason: format!("all {} issue(s) skipped", result.skipped.len()),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse<T: DeserializeOwned>(value: Value) -> Result<T> {
+    Ok(serde_json::from_value(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{CloseArgs, ShowArgs};
+
+    #[test]
+    fn only_json_invocations_are_forwarded() {
+        let ready = Commands::Ready(ReadyArgs::default());
+        assert!(plan(&ready, false).is_none());
+        let plan_json = plan(&ready, true).expect("ready plan");
+        assert_eq!(plan_json.calls[0].0, "ready");
+        assert_eq!(plan_json.calls[0].1["sort"], "hybrid");
+    }
+
+    #[test]
+    fn show_makes_one_call_per_id_and_skips_as_of() {
+        let show = Commands::Show(ShowArgs {
+            ids: vec!["bd-1".to_string(), "bd-2".to_string()],
+            ..ShowArgs::default()
+        });
+        let plan_json = plan(&show, true).expect("show plan");
+        assert_eq!(plan_json.calls.len(), 2);
+        assert_eq!(plan_json.render, Render::Show);
+
+        let as_of = Commands::Show(ShowArgs {
+            ids: vec!["bd-1".to_string()],
+            as_of: Some("1d".to_string()),
+            ..ShowArgs::default()
+        });
+        assert!(plan(&as_of, true).is_none());
+    }
+
+    #[test]
+    fn update_claim_alone_uses_claim_tool() {
+        let claim = UpdateArgs {
+            ids: vec!["bd-1".to_string()],
+            claim: true,
+            ..UpdateArgs::default()
+        };
+        assert_eq!(update_call(&claim).expect("claim").0, "claim");
+
+        let claim_and_edit = UpdateArgs {
+            title: Some("New".to_string()),
+            ..claim
+        };
+        assert!(update_call(&claim_and_edit).is_none());
+
+        let edit = UpdateArgs {
+            claim: false,
+            ..claim_and_edit
+        };
+        let (method, params) = update_call(&edit).expect("update");
+        assert_eq!(method, "update");
+        assert_eq!(params["title"], "New");
+    }
+
+    #[test]
+    fn multi_issue_mutations_run_directly() {
+        let close = Commands::Close(CloseArgs {
+            ids: vec!["bd-1".to_string(), "bd-2".to_string()],
+            ..CloseArgs::default()
+        });
+        assert!(plan(&close, true).is_none());
+
+        let suggest = Commands::Close(CloseArgs {
+            ids: vec!["bd-1".to_string()],
+            suggest_next: true,
+            ..CloseArgs::default()
+        });
+        assert!(plan(&suggest, true).is_none());
+    }
+}
@@ -0,0 +1,284 @@
+//! JSON-RPC daemon on a Unix socket (`br serve`).
+//!
+//! The daemon keeps one database connection open and answers
+//! newline-delimited JSON-RPC 2.0 requests, so a busy agent swarm stops
+//! paying for `SQLite` setup, migration checks and staleness checks on every
+//! call. Methods are the MCP tool names (`ready`, `show`, `create`,
+//! `update`, `claim`, `close`, `dep_add`, `comment`) with the same argument
+//! schemas, plus `ping` and `shutdown`. Any method also accepts an `actor`
+//! param naming who is making the change.
+//!
+//! Requests from all connections go through one lock, so writes are
+//! serialized and each request is its own undoable operation.
+//!
+//! The CLI forwards supported `--json` commands to the daemon when the
+//! default socket exists (see [`forward`]) and falls back to direct access
+//! when it doesn't.
+
+pub mod client;
+pub mod forward;
+
+use crate::error::{BeadsError, Result, StructuredError};
+use crate::mcp::{INVALID_REQUEST, METHOD_NOT_FOUND, McpServer, PARSE_ERROR, RpcError, Tool};
+use serde_json::{Value, json};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use tracing::{debug, info, warn};
+
+/// Socket file name inside `.beads/`.
+pub const SOCKET_FILE_NAME: &str = "obr.sock";
+
+/// JSON-RPC error code for failed commands; `data` holds the structured error.
+const COMMAND_FAILED: i64 = -32000;
+
+/// Default socket path for a beads directory.
+#[must_use]
+pub fn default_socket_path(beads_dir: &Path) -> PathBuf {
+    beads_dir.join(SOCKET_FILE_NAME)
+}
+
+struct Shared {
+    server: Mutex<McpServer>,
+    shutdown: AtomicBool,
+    socket_path: PathBuf,
+}
+
+/// Removes the socket file when the daemon stops.
+struct SocketGuard<'a>(&'a Path);
+
+impl Drop for SocketGuard<'_> {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(self.0);
+    }
+}
+
+/// Serve `server` on `socket_path` until a `shutdown` request arrives.
+///
+/// # Errors
+///
+/// Returns an error if another daemon is already listening on the socket
+/// or the socket cannot be created.
+pub fn serve(server: McpServer, socket_path: &Path) -> Result<()> {
+    let listener = bind(socket_path)?;
+    let _guard = SocketGuard(socket_path);
+    info!(socket = %socket_path.display(), "Daemon listening");
+
+    let shared = Arc::new(Shared {
+        server: Mutex::new(server),
+        shutdown: AtomicBool::new(false),
+        socket_path: socket_path.to_path_buf(),
+    });
+
+    for stream in listener.incoming() {
+        if shared.shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+        match stream {
+            Ok(stream) => {
+                let shared = Arc::clone(&shared);
+                thread::spawn(move || {
+                    if let Err(e) = handle_connection(&shared, stream) {
+                        debug!(?e, "Daemon connection closed with error");
+                    }
+                });
+            }
+            Err(e) => warn!(?e, "Daemon failed to accept a connection"),
+        }
+    }
+
+    info!("Daemon stopped");
+    Ok(())
+}
+
+/// Bind the socket, replacing a stale file left by a daemon that died.
+fn bind(socket_path: &Path) -> Result<UnixListener> {
+    if socket_path.exists() {
+        if UnixStream::connect(socket_path).is_ok() {
+            return Err(BeadsError::validation(
+                "socket",
+                format!("a daemon is already listening on {}", socket_path.display()),
+            ));
+        }
+        debug!(socket = %socket_path.display(), "Removing stale daemon socket");
+        std::fs::remove_file(socket_path)?;
+    }
+
+    let listener = UnixListener::bind(socket_path)?;
+    // Only the owner may drive the database through the socket.
+    std::fs::set_permissions(socket_path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+fn handle_connection(shared: &Shared, stream: UnixStream) -> Result<()> {
+    let reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_line(shared, &line) {
+            serde_json::to_writer(&mut writer, &response)?;
+            writer.write_all(b"\n")?;
+            writer.flush()?;
+        }
+        if shared.shutdown.load(Ordering::SeqCst) {
+            // Wake the accept loop, now that the caller has its answer.
+            let _ = UnixStream::connect(&shared.socket_path);
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Handle one request line; notifications (no `id`) get no response.
+fn handle_line(shared: &Shared, line: &str) -> Option<Value> {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(value) => value,
+        Err(e) => {
+            return Some(
+                RpcError::new(PARSE_ERROR, format!("Parse error: {e}")).to_response(Value::Null),
+            );
+        }
+    };
+    let id = request.get("id").cloned()?;
+    let Some(method) = request.get("method").and_then(Value::as_str) else {
+        return Some(RpcError::new(INVALID_REQUEST, "Missing method").to_response(id));
+    };
+
+    let mut params = request.get("params").cloned().unwrap_or_else(|| json!({}));
+    let actor = params
+        .as_object_mut()
+        .and_then(|object| object.remove("actor"))
+        .and_then(|actor| actor.as_str().map(str::to_string));
+
+    debug!(method, "Daemon request");
+    Some(match dispatch(shared, method, params, actor.as_deref()) {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(err) => err.to_response(id),
+    })
+}
+
+fn dispatch(
+    shared: &Shared,
+    method: &str,
+    params: Value,
+    actor: Option<&str>,
+) -> std::result::Result<Value, RpcError> {
+    match method {
+        "ping" => Ok(json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "pid": std::process::id(),
+        })),
+        "shutdown" => {
+            shared.shutdown.store(true, Ordering::SeqCst);
+            Ok(json!({}))
+        }
+        _ => {
+            let tool = Tool::from_name(method).ok_or_else(|| {
+                RpcError::new(METHOD_NOT_FOUND, format!("Method not found: {method}"))
+            })?;
+            let mut server = shared.server.lock().unwrap_or_else(PoisonError::into_inner);
+            server.call(tool, params, actor).map_err(|e| {
+                RpcError::new(COMMAND_FAILED, e.to_string())
+                    .with_data(StructuredError::from_error(&e).to_json())
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CliOverrides;
+    use crate::error::ErrorCode;
+    use client::DaemonClient;
+    use tempfile::TempDir;
+
+    fn start_daemon() -> (TempDir, PathBuf, thread::JoinHandle<Result<()>>) {
+        let dir = TempDir::new().expect("tempdir");
+        let beads_dir = dir.path().join(".beads");
+        std::fs::create_dir_all(&beads_dir).expect("beads dir");
+        let cli = CliOverrides {
+            db: Some(beads_dir.join("beads.db")),
+            actor: Some("daemon".to_string()),
+            no_auto_flush: Some(true),
+            no_auto_import: Some(true),
+            ..CliOverrides::default()
+        };
+        let server = McpServer::open(&cli, true).expect("server");
+
+        let socket_path = default_socket_path(&beads_dir);
+        let handle = {
+            let socket_path = socket_path.clone();
+            thread::spawn(move || serve(server, &socket_path))
+        };
+        for _ in 0..100 {
+            if socket_path.exists() {
+                break;
+            }
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+        (dir, socket_path, handle)
+    }
+
+    #[test]
+    fn serves_tools_and_shuts_down() {
+        let (_dir, socket_path, handle) = start_daemon();
+        let mut client = DaemonClient::connect(&socket_path).expect("connect");
+
+        let pong = client.call("ping", json!({})).expect("ping");
+        assert_eq!(pong["pid"], std::process::id());
+
+        let created = client
+            .call("create", json!({ "title": "Via socket", "actor": "alice" }))
+            .expect("create");
+        assert_eq!(created["created_by"], "alice");
+        let id = created["id"].as_str().expect("id").to_string();
+
+        let claimed = client
+            .call("claim", json!({ "id": id.clone() }))
+            .expect("claim");
+        assert_eq!(claimed["assignee"], "daemon");
+
+        let err = client
+            .call("show", json!({ "id": "bd-missing" }))
+            .expect_err("missing issue");
+        let structured = StructuredError::from_error(&err);
+        assert_eq!(structured.code, ErrorCode::IssueNotFound);
+
+        let err = client.call("bogus", json!({})).expect_err("unknown method");
+        assert!(err.to_string().contains("Method not found"));
+
+        client.call("shutdown", json!({})).expect("shutdown");
+        handle.join().expect("join").expect("serve");
+        assert!(!socket_path.exists());
+    }
+
+    #[test]
+    fn refuses_to_start_twice() {
+        let (_dir, socket_path, handle) = start_daemon();
+        let err = bind(&socket_path).expect_err("second daemon");
+        assert!(err.to_string().contains("already listening"));
+
+        DaemonClient::connect(&socket_path)
+            .expect("connect")
+            .call("shutdown", json!({}))
+            .expect("shutdown");
+        handle.join().expect("join").expect("serve");
+    }
+
+    #[test]
+    fn replaces_stale_socket_file() {
+        let dir = TempDir::new().expect("tempdir");
+        let socket_path = dir.path().join(SOCKET_FILE_NAME);
+        std::fs::write(&socket_path, "").expect("stale file");
+        let listener = bind(&socket_path).expect("bind");
+        drop(listener);
+    }
+}
@@ -84,6 +84,31 @@ pub enum BeadsError {
     #[error("Priority must be 0-4, got: {priority}")]
     InvalidPriority { priority: i32 },
 
+    /// Malformed filter expression (`--where`, saved queries).
+    #[error("Invalid filter expression at column {column}: {reason}")]
+    FilterParse {
+        expression: String,
+        column: usize,
+        reason: String,
+    },
+
+    /// Status change not allowed by the configured workflow.
+    #[error("Transition not allowed for {id} ({issue_type}): {from} -> {to}")]
+    InvalidTransition {
+        id: String,
+        issue_type: String,
+        from: String,
+        to: String,
+        allowed: Vec<String>,
+    },
+
+    /// Undo refused because later edits changed what the operation touched.
+    #[error("Cannot undo {op_id}: {} conflicting change(s)", conflicts.len())]
+    UndoConflict {
+        op_id: String,
+        conflicts: Vec<String>,
+    },
+
     // === JSONL Errors ===
     /// Failed to parse a line in the JSONL file.
     #[error("JSONL parse error at line {line}: {reason}")]
@@ -158,6 +183,14 @@ pub enum BeadsError {
     #[error("Nothing to do: {reason}")]
     NothingToDo { reason: String },
 
+    /// No ready issue matched (`next`).
+    #[error("No ready work: {reason}")]
+    NoReadyWork { reason: String },
+
+    /// Error reported by a `br serve` daemon on behalf of a forwarded command.
+    #[error("{}", .0.message)]
+    Daemon(Box<StructuredError>),
+
     /// Wrapped anyhow error for gradual migration.
     #[error(transparent)]
     Other(#[from] anyhow::Error),
@@ -204,6 +237,8 @@ impl BeadsError {
                 | Self::InvalidStatus { .. }
                 | Self::InvalidType { .. }
                 | Self::InvalidPriority { .. }
+                | Self::FilterParse { .. }
+                | Self::InvalidTransition { .. }
                 | Self::PrefixMismatch { .. }
                 | Self::AmbiguousId { .. }
         )
@@ -247,10 +282,12 @@ impl BeadsError {
     ///
     /// Legacy bd typically uses exit code 1 for most errors.
     /// `NothingToDo` uses exit code 3 (issue errors category).
+    /// `NoReadyWork` uses exit code 9 so agents can poll on it.
     #[must_use]
     pub const fn exit_code(&self) -> i32 {
         match self {
             Self::NothingToDo { .. } => 3,
+            Self::NoReadyWork { .. } => 9,
             _ => 1,
         }
     }
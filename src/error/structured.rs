@@ -29,6 +29,7 @@ use std::sync::LazyLock;
 /// These codes are stable and can be used for programmatic error handling.
 /// Format: `SCREAMING_SNAKE_CASE` for easy parsing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ErrorCode {
     // === Database Errors (exit code 2) ===
     /// Database file not found
@@ -53,6 +54,8 @@ pub enum ErrorCode {
     IdCollision,
     /// Invalid issue ID format
     InvalidId,
+    /// Undo refused because of later conflicting edits
+    UndoConflict,
 
     // === Validation Errors (exit code 4) ===
     /// Field validation failed
@@ -65,6 +68,10 @@ pub enum ErrorCode {
     InvalidPriority,
     /// Required field missing
     RequiredField,
+    /// Malformed filter expression
+    FilterParseError,
+    /// Status change not allowed by the workflow
+    InvalidTransition,
 
     // === Dependency Errors (exit code 5) ===
     /// Dependency cycle detected
@@ -110,6 +117,10 @@ pub enum ErrorCode {
     /// All requested items were skipped; nothing to do
     NothingToDo,
 
+    // === No Work (exit code 9) ===
+    /// No ready issue matched
+    NoReadyWork,
+
     // === Internal Errors (exit code 1) ===
     /// Unexpected internal error
     InternalError,
@@ -132,12 +143,15 @@ impl ErrorCode {
             Self::AmbiguousId => "AMBIGUOUS_ID",
             Self::IdCollision => "ID_COLLISION",
             Self::InvalidId => "INVALID_ID",
+            Self::UndoConflict => "UNDO_CONFLICT",
             // Validation
             Self::ValidationFailed => "VALIDATION_FAILED",
             Self::InvalidStatus => "INVALID_STATUS",
             Self::InvalidType => "INVALID_TYPE",
             Self::InvalidPriority => "INVALID_PRIORITY",
             Self::RequiredField => "REQUIRED_FIELD",
+            Self::FilterParseError => "FILTER_PARSE_ERROR",
+            Self::InvalidTransition => "INVALID_TRANSITION",
             // Dependency
             Self::CycleDetected => "CYCLE_DETECTED",
             Self::DependencyNotFound => "DEPENDENCY_NOT_FOUND",
@@ -160,6 +174,8 @@ impl ErrorCode {
             Self::YamlError => "YAML_ERROR",
             // Operational
             Self::NothingToDo => "NOTHING_TO_DO",
+            // No work
+            Self::NoReadyWork => "NO_READY_WORK",
             // Internal
             Self::InternalError => "INTERNAL_ERROR",
         }
@@ -180,7 +196,10 @@ impl ErrorCode {
                 | Self::InvalidType
                 | Self::InvalidPriority
                 | Self::RequiredField
+                | Self::FilterParseError
+                | Self::InvalidTransition
                 | Self::AmbiguousId
+                | Self::NoReadyWork
         )
     }
 
@@ -195,6 +214,7 @@ impl ErrorCode {
     /// - 6: Sync/JSONL errors
     /// - 7: Config errors
     /// - 8: I/O errors
+    /// - 9: No ready work (`next`)
     #[must_use]
     pub const fn exit_code(&self) -> i32 {
         match self {
@@ -210,13 +230,16 @@ impl ErrorCode {
             | Self::AmbiguousId
             | Self::IdCollision
             | Self::InvalidId
+            | Self::UndoConflict
             | Self::NothingToDo => 3,
             // Validation (4)
             Self::ValidationFailed
             | Self::InvalidStatus
             | Self::InvalidType
             | Self::InvalidPriority
-            | Self::RequiredField => 4,
+            | Self::RequiredField
+            | Self::FilterParseError
+            | Self::InvalidTransition => 4,
             // Dependency (5)
             Self::CycleDetected
             | Self::DependencyNotFound
@@ -233,6 +256,8 @@ impl ErrorCode {
             Self::ConfigError | Self::ConfigNotFound | Self::ConfigParseError => 7,
             // I/O (8)
             Self::IoError | Self::JsonError | Self::YamlError => 8,
+            // No work (9)
+            Self::NoReadyWork => 9,
             // Internal (1)
             Self::InternalError => 1,
         }
@@ -267,6 +292,10 @@ impl StructuredError {
     /// Create a new structured error from a `BeadsError`.
     #[must_use]
     pub fn from_error(err: &BeadsError) -> Self {
+        if let BeadsError::Daemon(structured) = err {
+            return (**structured).clone();
+        }
+
         let (code, context) = Self::extract_code_and_context(err);
         let hint = Self::generate_hint(err, context.as_ref());
 
@@ -445,6 +474,12 @@ impl StructuredError {
         }
     }
 
+    /// Parse the `{"error": {...}}` object produced by [`Self::to_json`].
+    #[must_use]
+    pub fn from_json(value: &Value) -> Option<Self> {
+        serde_json::from_value(value.get("error")?.clone()).ok()
+    }
+
     /// Serialize to JSON value.
     #[must_use]
     pub fn to_json(&self) -> Value {
@@ -568,6 +603,38 @@ impl StructuredError {
                     })),
                 )
             }
+            BeadsError::FilterParse {
+                expression,
+                column,
+                reason,
+            } => (
+                ErrorCode::FilterParseError,
+                Some(json!({
+                    "expression": expression,
+                    "column": column,
+                    "reason": reason,
+                })),
+            ),
+            BeadsError::InvalidTransition {
+                id,
+                issue_type,
+                from,
+                to,
+                allowed,
+            } => (
+                ErrorCode::InvalidTransition,
+                Some(json!({
+                    "id": id,
+                    "issue_type": issue_type,
+                    "from": from,
+                    "to": to,
+                    "allowed": allowed,
+                })),
+            ),
+            BeadsError::UndoConflict { op_id, conflicts } => (
+                ErrorCode::UndoConflict,
+                Some(json!({"op_id": op_id, "conflicts": conflicts})),
+            ),
             BeadsError::JsonlParse { line, reason } => (
                 ErrorCode::JsonlParseError,
                 Some(json!({"line": line, "reason": reason})),
@@ -600,6 +667,9 @@ impl StructuredError {
             BeadsError::NothingToDo { reason } => {
                 (ErrorCode::NothingToDo, Some(json!({"reason": reason})))
             }
+            BeadsError::NoReadyWork { reason } => {
+                (ErrorCode::NoReadyWork, Some(json!({"reason": reason})))
+            }
             BeadsError::Config(_) => (ErrorCode::ConfigError, None),
             BeadsError::Io(_) => (ErrorCode::IoError, None),
             BeadsError::Json(_) => (ErrorCode::JsonError, None),
@@ -607,6 +677,7 @@ impl StructuredError {
             BeadsError::WithContext { context, .. } => {
                 (ErrorCode::InternalError, Some(json!({"context": context})))
             }
+            BeadsError::Daemon(structured) => (structured.code, structured.context.clone()),
             BeadsError::Other(_) => (ErrorCode::InternalError, None),
         }
     }
@@ -657,9 +728,28 @@ impl StructuredError {
             BeadsError::NothingToDo { .. } => {
                 Some("All specified issues were already closed or not found.".to_string())
             }
+            BeadsError::NoReadyWork { .. } => Some(
+                "Retry later, or widen the filters; 'br ready' shows what is available."
+                    .to_string(),
+            ),
             BeadsError::JsonlParse { line, .. } => Some(format!(
                 "Check line {line} of the JSONL file for syntax errors."
             )),
+            BeadsError::FilterParse {
+                expression, column, ..
+            } => Some(format!(
+                "{expression}\n{}^ (column {column})",
+                " ".repeat(column.saturating_sub(1))
+            )),
+            BeadsError::InvalidTransition { from, allowed, .. } => Some(if allowed.is_empty() {
+                format!("'{from}' is a final status in this workflow.")
+            } else {
+                format!("Allowed from '{from}': {}", allowed.join(", "))
+            }),
+            BeadsError::UndoConflict { conflicts, .. } => Some(format!(
+                "{}. Undo the later operations first (see 'br undo --list').",
+                conflicts.join("; ")
+            )),
             _ => None,
         }
     }
@@ -945,6 +1035,7 @@ mod tests {
         assert_eq!(ErrorCode::JsonlParseError.exit_code(), 6);
         assert_eq!(ErrorCode::ConfigError.exit_code(), 7);
         assert_eq!(ErrorCode::IoError.exit_code(), 8);
+        assert_eq!(ErrorCode::NoReadyWork.exit_code(), 9);
         assert_eq!(ErrorCode::InternalError.exit_code(), 1);
     }
 
@@ -1036,6 +1127,55 @@ mod tests {
         assert!(err.hint.as_ref().unwrap().contains("closed"));
     }
 
+    #[test]
+    fn test_structured_error_filter_parse_points_at_column() {
+        let err = StructuredError::from_error(&BeadsError::FilterParse {
+            expression: "status:open AND bogus:1".to_string(),
+            column: 17,
+            reason: "unknown field 'bogus'".to_string(),
+        });
+        assert_eq!(err.code, ErrorCode::FilterParseError);
+        assert_eq!(err.code.exit_code(), 4);
+        assert_eq!(err.context.as_ref().unwrap()["column"], 17);
+        assert!(
+            err.hint
+                .as_ref()
+                .unwrap()
+                .ends_with(&format!("{}^ (column 17)", " ".repeat(16)))
+        );
+    }
+
+    #[test]
+    fn test_structured_error_invalid_transition_lists_allowed() {
+        let err = StructuredError::from_error(&BeadsError::InvalidTransition {
+            id: "bd-abc".to_string(),
+            issue_type: "bug".to_string(),
+            from: "open".to_string(),
+            to: "closed".to_string(),
+            allowed: vec!["in_progress".to_string(), "in_review".to_string()],
+        });
+        assert_eq!(err.code, ErrorCode::InvalidTransition);
+        assert_eq!(err.code.exit_code(), 4);
+        assert_eq!(err.context.as_ref().unwrap()["to"], "closed");
+        assert_eq!(
+            err.hint.as_deref(),
+            Some("Allowed from 'open': in_progress, in_review")
+        );
+    }
+
+    #[test]
+    fn test_structured_error_undo_conflict() {
+        let err = StructuredError::from_error(&BeadsError::UndoConflict {
+            op_id: "op-1".to_string(),
+            conflicts: vec!["bd-abc: title changed since".to_string()],
+        });
+        assert_eq!(err.code, ErrorCode::UndoConflict);
+        assert_eq!(err.code.exit_code(), 3);
+        assert!(!err.retryable);
+        assert_eq!(err.context.as_ref().unwrap()["op_id"], "op-1");
+        assert!(err.hint.as_deref().unwrap().starts_with("bd-abc: title"));
+    }
+
     #[test]
     fn test_structured_error_ambiguous_id() {
         let matches = vec!["bd-abc".to_string(), "bd-abd".to_string()];
@@ -1063,4 +1203,20 @@ mod tests {
         assert!(colored.contains("\x1b[31m")); // Red color code
         assert!(colored.contains("\x1b[33m")); // Yellow color code
     }
+
+    #[test]
+    fn test_from_json_round_trips_daemon_errors() {
+        let original = StructuredError::from_error(&BeadsError::IssueNotFound {
+            id: "bd-abc".to_string(),
+        });
+        let parsed = StructuredError::from_json(&original.to_json()).expect("parse");
+        assert_eq!(parsed.code, ErrorCode::IssueNotFound);
+        assert_eq!(parsed.message, original.message);
+        assert_eq!(parsed.hint, original.hint);
+
+        // A forwarded error reports exactly what the daemon sent.
+        let forwarded = StructuredError::from_error(&BeadsError::Daemon(Box::new(parsed)));
+        assert_eq!(forwarded.to_json(), original.to_json());
+        assert_eq!(forwarded.code.exit_code(), 3);
+    }
 }
@@ -1,8 +1,10 @@
 //! CSV formatting for `beads_rust`.
 //!
 //! Provides CSV output for list/export commands. Handles proper escaping
-//! of fields containing commas, quotes, or newlines.
+//! of fields containing commas, quotes, or newlines, and reads such files
+//! back for `import csv`.
 
+use crate::error::{BeadsError, Result};
 use crate::model::Issue;
 use std::io::{self, Write};
 
@@ -143,6 +145,84 @@ pub fn format_csv(issues: &[Issue], fields: &[&str]) -> String {
     String::from_utf8_lossy(&output).into_owned()
 }
 
+/// One row of a CSV file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Line the row starts on (1-based).
+    pub line: usize,
+    pub fields: Vec<String>,
+}
+
+/// Parse CSV text into records.
+///
+/// Quoted fields may hold commas, doubled quotes and line breaks. Rows
+/// with only empty fields are skipped, and a leading byte order mark (as
+/// spreadsheets write) is ignored.
+///
+/// # Errors
+///
+/// Returns an error if a quoted field is not closed.
+pub fn parse_records(text: &str) -> Result<Vec<Record>> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut start = 1;
+    let mut quoted = false;
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                '\r' if chars.peek() == Some(&'\n') => {}
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => quoted = true,
+            ',' => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                push_record(&mut records, start, std::mem::take(&mut fields));
+                line += 1;
+                start = line;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(BeadsError::validation(
+            "csv",
+            format!("line {start}: quoted field is not closed"),
+        ));
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        push_record(&mut records, start, fields);
+    }
+    Ok(records)
+}
+
+fn push_record(records: &mut Vec<Record>, line: usize, fields: Vec<String>) {
+    if fields.iter().any(|field| !field.trim().is_empty()) {
+        records.push(Record { line, fields });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -190,6 +270,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         }
     }
 
@@ -285,6 +366,34 @@ mod tests {
         assert_eq!(lines[2], "bd-2,Second");
     }
 
+    #[test]
+    fn test_parse_records_quotes_and_line_breaks() {
+        let text = "\u{feff}Summary,Notes\r\n\
+                    \"Crash, on save\",\"says \"\"boom\"\"\r\nthen exits\"\r\n\
+                    ,,\n\
+                    Plain,\n";
+        let records = parse_records(text).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].fields, vec!["Summary", "Notes"]);
+        assert_eq!(records[1].line, 2);
+        assert_eq!(
+            records[1].fields,
+            vec!["Crash, on save", "says \"boom\"\nthen exits"]
+        );
+        // The blank row is skipped; line numbers count the embedded break,
+        // which comes back as a bare newline.
+        assert_eq!(records[2].line, 5);
+        assert_eq!(records[2].fields, vec!["Plain", ""]);
+    }
+
+    #[test]
+    fn test_parse_records_round_trips_format_csv() {
+        let issues = vec![make_test_issue("bd-1", "Fix bug, then \"test\"")];
+        let records = parse_records(&format_csv(&issues, &["id", "title"])).unwrap();
+        assert_eq!(records[1].fields, vec!["bd-1", "Fix bug, then \"test\""]);
+        assert!(parse_records("a,\"open\n").is_err());
+    }
+
     #[test]
     fn test_write_header() {
         let mut output = Vec::new();
@@ -0,0 +1,683 @@
+//! Dependency graph export for `graph --format`.
+//!
+//! Renders a set of issues and their dependencies as Graphviz DOT, GraphML,
+//! D2 or Mermaid. Nodes are styled by status (fill) and priority (border
+//! weight); edges point from the dependent issue to the issue it depends on
+//! and are labeled with the dependency type. Epics with children in the
+//! graph become clusters, nested when epics have parent epics.
+
+use crate::error::{BeadsError, Result};
+use crate::model::{DependencyType, IssueType, Status};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+/// Export formats for the dependency graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    GraphMl,
+    D2,
+    Mermaid,
+}
+
+impl FromStr for GraphFormat {
+    type Err = BeadsError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "dot" | "graphviz" => Ok(Self::Dot),
+            "graphml" => Ok(Self::GraphMl),
+            "d2" => Ok(Self::D2),
+            "mermaid" => Ok(Self::Mermaid),
+            _ => Err(BeadsError::validation(
+                "format",
+                format!("unknown graph format '{s}' (expected text, dot, graphml, d2 or mermaid)"),
+            )),
+        }
+    }
+}
+
+/// An issue in an exported graph.
+#[derive(Debug, Clone)]
+pub struct ExportNode {
+    pub id: String,
+    pub title: String,
+    pub status: Status,
+    pub priority: i32,
+    pub issue_type: IssueType,
+}
+
+/// A dependency in an exported graph: `from` depends on `to`.
+#[derive(Debug, Clone)]
+pub struct ExportEdge {
+    pub from: String,
+    pub to: String,
+    pub dep_type: DependencyType,
+}
+
+/// Issues and dependencies to export. Edges must only reference nodes.
+#[derive(Debug, Clone, Default)]
+pub struct GraphExport {
+    pub nodes: Vec<ExportNode>,
+    pub edges: Vec<ExportEdge>,
+}
+
+impl GraphExport {
+    /// Render the graph in `format`.
+    #[must_use]
+    pub fn render(&self, format: GraphFormat) -> String {
+        let layout = Layout::new(self);
+        match format {
+            GraphFormat::Dot => render_dot(self, &layout),
+            GraphFormat::GraphMl => render_graphml(self, &layout),
+            GraphFormat::D2 => render_d2(self, &layout),
+            GraphFormat::Mermaid => render_mermaid(self, &layout),
+        }
+    }
+}
+
+/// Cluster membership derived from parent-child edges.
+struct Layout<'a> {
+    /// Node index -> index of the epic cluster it sits in.
+    parent: HashMap<usize, usize>,
+    /// Epic index -> member indices, in node order.
+    members: BTreeMap<usize, Vec<usize>>,
+    index: HashMap<&'a str, usize>,
+}
+
+impl<'a> Layout<'a> {
+    fn new(graph: &'a GraphExport) -> Self {
+        let index: HashMap<&str, usize> = graph
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.id.as_str(), i))
+            .collect();
+
+        let mut parent: HashMap<usize, usize> = HashMap::new();
+        for edge in &graph.edges {
+            if edge.dep_type != DependencyType::ParentChild {
+                continue;
+            }
+            let (Some(&child), Some(&epic)) =
+                (index.get(edge.from.as_str()), index.get(edge.to.as_str()))
+            else {
+                continue;
+            };
+            if child != epic && graph.nodes[epic].issue_type == IssueType::Epic {
+                // With several parents, the first listed one wins.
+                parent.entry(child).or_insert(epic);
+            }
+        }
+
+        // A parent-child cycle can't be drawn as nesting; cut it where the
+        // walk from the first node in it closes the loop.
+        let mut starts: Vec<usize> = parent.keys().copied().collect();
+        starts.sort_unstable();
+        for start in starts {
+            let mut seen = HashSet::from([start]);
+            let mut current = start;
+            while let Some(&up) = parent.get(&current) {
+                if !seen.insert(up) {
+                    parent.remove(&current);
+                    break;
+                }
+                current = up;
+            }
+        }
+
+        let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (&child, &epic) in &parent {
+            members.entry(epic).or_default().push(child);
+        }
+        for list in members.values_mut() {
+            list.sort_unstable();
+        }
+        Self {
+            parent,
+            members,
+            index,
+        }
+    }
+
+    /// Nodes outside any cluster, in node order.
+    fn top_level(&self, count: usize) -> Vec<usize> {
+        (0..count)
+            .filter(|i| !self.parent.contains_key(i))
+            .collect()
+    }
+
+    fn is_cluster(&self, node: usize) -> bool {
+        self.members.contains_key(&node)
+    }
+
+    /// Whether an edge is already shown by nesting the child in its epic.
+    fn is_nesting(&self, edge: &ExportEdge) -> bool {
+        edge.dep_type == DependencyType::ParentChild
+            && matches!(
+                (self.index.get(edge.from.as_str()), self.index.get(edge.to.as_str())),
+                (Some(child), Some(epic)) if self.parent.get(child) == Some(epic)
+            )
+    }
+
+    /// Enclosing epics from the outermost down to the node itself.
+    fn path(&self, node: usize) -> Vec<usize> {
+        let mut path = vec![node];
+        let mut current = node;
+        while let Some(&up) = self.parent.get(&current) {
+            path.push(up);
+            current = up;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Fill color for a status.
+fn status_fill(status: &Status) -> &'static str {
+    match status {
+        Status::Open => "#dbeafe",
+        Status::InProgress => "#fef3c7",
+        Status::Blocked => "#fecaca",
+        Status::Deferred | Status::Pinned => "#e5e7eb",
+        Status::Closed | Status::Tombstone => "#d1fae5",
+        Status::Custom(_) => "#f3f4f6",
+    }
+}
+
+/// Border width for a priority: heavier for P0 and P1.
+const fn priority_width(priority: i32) -> u8 {
+    match priority {
+        0 => 3,
+        1 => 2,
+        _ => 1,
+    }
+}
+
+/// Blocking edges are solid; informational ones are dashed.
+const fn is_dashed(dep_type: &DependencyType) -> bool {
+    !dep_type.is_blocking()
+}
+
+fn node_caption(node: &ExportNode) -> String {
+    format!(
+        "{}\n{}\nP{} · {}",
+        node.id,
+        node.title,
+        node.priority,
+        node.status.as_str()
+    )
+}
+
+// ─────────────────────────────────────────────────────────────
+// Graphviz DOT
+// ─────────────────────────────────────────────────────────────
+
+fn dot_quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+fn render_dot(graph: &GraphExport, layout: &Layout) -> String {
+    let mut out = String::from("digraph beads {\n");
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n");
+    out.push_str("  edge [fontname=\"Helvetica\", fontsize=10];\n");
+    for node in layout.top_level(graph.nodes.len()) {
+        dot_node(&mut out, graph, layout, node, 1);
+    }
+    for edge in graph.edges.iter().filter(|e| !layout.is_nesting(e)) {
+        let style = if is_dashed(&edge.dep_type) {
+            ", style=dashed"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            out,
+            "  {} -> {} [label={}{style}];",
+            dot_quote(&edge.from),
+            dot_quote(&edge.to),
+            dot_quote(edge.dep_type.as_str())
+        );
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn dot_node(out: &mut String, graph: &GraphExport, layout: &Layout, index: usize, depth: usize) {
+    let indent = "  ".repeat(depth);
+    let node = &graph.nodes[index];
+    let shape = if node.issue_type == IssueType::Epic {
+        ", shape=folder"
+    } else {
+        ""
+    };
+    let declaration = format!(
+        "{} [label={}, fillcolor=\"{}\", penwidth={}{shape}];",
+        dot_quote(&node.id),
+        dot_quote(&node_caption(node)),
+        status_fill(&node.status),
+        priority_width(node.priority)
+    );
+
+    if let Some(members) = layout.members.get(&index) {
+        let _ = writeln!(
+            out,
+            "{indent}subgraph {} {{",
+            dot_quote(&format!("cluster_{}", node.id))
+        );
+        let _ = writeln!(out, "{indent}  label={};", dot_quote(&node.title));
+        let _ = writeln!(out, "{indent}  style=\"rounded,dashed\";");
+        let _ = writeln!(out, "{indent}  {declaration}");
+        for &member in members {
+            dot_node(out, graph, layout, member, depth + 1);
+        }
+        let _ = writeln!(out, "{indent}}}");
+    } else {
+        let _ = writeln!(out, "{indent}{declaration}");
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// GraphML
+// ─────────────────────────────────────────────────────────────
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn render_graphml(graph: &GraphExport, layout: &Layout) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    for (id, target, name, kind) in [
+        ("title", "node", "title", "string"),
+        ("status", "node", "status", "string"),
+        ("priority", "node", "priority", "int"),
+        ("issue_type", "node", "issue_type", "string"),
+        ("fill", "node", "fill", "string"),
+        ("dep_type", "edge", "dep_type", "string"),
+    ] {
+        let _ = writeln!(
+            out,
+            "  <key id=\"{id}\" for=\"{target}\" attr.name=\"{name}\" attr.type=\"{kind}\"/>"
+        );
+    }
+    out.push_str("  <graph id=\"beads\" edgedefault=\"directed\">\n");
+    for node in layout.top_level(graph.nodes.len()) {
+        graphml_node(&mut out, graph, layout, node, 2);
+    }
+    // GraphML is a data format, so parent-child edges stay alongside nesting.
+    for (i, edge) in graph.edges.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "    <edge id=\"e{i}\" source=\"{}\" target=\"{}\"><data key=\"dep_type\">{}</data></edge>",
+            xml_escape(&edge.from),
+            xml_escape(&edge.to),
+            xml_escape(edge.dep_type.as_str())
+        );
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+fn graphml_node(
+    out: &mut String,
+    graph: &GraphExport,
+    layout: &Layout,
+    index: usize,
+    depth: usize,
+) {
+    let indent = "  ".repeat(depth);
+    let node = &graph.nodes[index];
+    let _ = writeln!(out, "{indent}<node id=\"{}\">", xml_escape(&node.id));
+    for (key, value) in [
+        ("title", xml_escape(&node.title)),
+        ("status", xml_escape(node.status.as_str())),
+        ("priority", node.priority.to_string()),
+        ("issue_type", xml_escape(node.issue_type.as_str())),
+        ("fill", status_fill(&node.status).to_string()),
+    ] {
+        let _ = writeln!(out, "{indent}  <data key=\"{key}\">{value}</data>");
+    }
+    if let Some(members) = layout.members.get(&index) {
+        let _ = writeln!(
+            out,
+            "{indent}  <graph id=\"{}:\" edgedefault=\"directed\">",
+            xml_escape(&node.id)
+        );
+        for &member in members {
+            graphml_node(out, graph, layout, member, depth + 2);
+        }
+        let _ = writeln!(out, "{indent}  </graph>");
+    }
+    let _ = writeln!(out, "{indent}</node>");
+}
+
+// ─────────────────────────────────────────────────────────────
+// D2
+// ─────────────────────────────────────────────────────────────
+
+fn d2_quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+/// D2 addresses nested shapes by their container path.
+fn d2_path(graph: &GraphExport, layout: &Layout, index: usize) -> String {
+    layout
+        .path(index)
+        .iter()
+        .map(|&i| {
+            if layout.is_cluster(i) {
+                d2_quote(&format!("{}_epic", graph.nodes[i].id))
+            } else {
+                d2_quote(&graph.nodes[i].id)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Path of the shape drawn for a node (an epic's own shape sits inside
+/// its container).
+fn d2_shape_path(graph: &GraphExport, layout: &Layout, index: usize) -> String {
+    let path = d2_path(graph, layout, index);
+    if layout.is_cluster(index) {
+        format!("{path}.{}", d2_quote(&graph.nodes[index].id))
+    } else {
+        path
+    }
+}
+
+fn render_d2(graph: &GraphExport, layout: &Layout) -> String {
+    let mut out = String::from("direction: right\n");
+    for node in layout.top_level(graph.nodes.len()) {
+        d2_node(&mut out, graph, layout, node, 0);
+    }
+    for edge in graph.edges.iter().filter(|e| !layout.is_nesting(e)) {
+        let (Some(&from), Some(&to)) = (
+            layout.index.get(edge.from.as_str()),
+            layout.index.get(edge.to.as_str()),
+        ) else {
+            continue;
+        };
+        let style = if is_dashed(&edge.dep_type) {
+            " { style.stroke-dash: 3 }"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            out,
+            "{} -> {}: {}{style}",
+            d2_shape_path(graph, layout, from),
+            d2_shape_path(graph, layout, to),
+            d2_quote(edge.dep_type.as_str())
+        );
+    }
+    out
+}
+
+fn d2_node(out: &mut String, graph: &GraphExport, layout: &Layout, index: usize, depth: usize) {
+    let indent = "  ".repeat(depth);
+    let node = &graph.nodes[index];
+    let mut shape_indent = indent.clone();
+    if layout.is_cluster(index) {
+        let _ = writeln!(
+            out,
+            "{indent}{}: {{",
+            d2_quote(&format!("{}_epic", node.id))
+        );
+        let _ = writeln!(out, "{indent}  label: {}", d2_quote(&node.title));
+        let _ = writeln!(out, "{indent}  style.stroke-dash: 3");
+        shape_indent.push_str("  ");
+    }
+    let _ = writeln!(out, "{shape_indent}{}: {{", d2_quote(&node.id));
+    let _ = writeln!(
+        out,
+        "{shape_indent}  label: {}",
+        d2_quote(&node_caption(node))
+    );
+    if node.issue_type == IssueType::Epic {
+        let _ = writeln!(out, "{shape_indent}  shape: package");
+    }
+    let _ = writeln!(
+        out,
+        "{shape_indent}  style.fill: \"{}\"",
+        status_fill(&node.status)
+    );
+    let _ = writeln!(
+        out,
+        "{shape_indent}  style.stroke-width: {}",
+        priority_width(node.priority)
+    );
+    let _ = writeln!(out, "{shape_indent}}}");
+    if let Some(members) = layout.members.get(&index) {
+        for &member in members {
+            d2_node(out, graph, layout, member, depth + 1);
+        }
+        let _ = writeln!(out, "{indent}}}");
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// Mermaid
+// ─────────────────────────────────────────────────────────────
+
+/// Mermaid ids may only contain word characters.
+fn mermaid_id(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn mermaid_label(text: &str) -> String {
+    text.replace('"', "#quot;").replace('\n', "<br/>")
+}
+
+fn render_mermaid(graph: &GraphExport, layout: &Layout) -> String {
+    let mut out = String::from("graph LR\n");
+    for node in layout.top_level(graph.nodes.len()) {
+        mermaid_node(&mut out, graph, layout, node, 1);
+    }
+    for edge in graph.edges.iter().filter(|e| !layout.is_nesting(e)) {
+        let arrow = if is_dashed(&edge.dep_type) {
+            "-.->"
+        } else {
+            "-->"
+        };
+        let _ = writeln!(
+            out,
+            "    {} {arrow}|{}| {}",
+            mermaid_id(&edge.from),
+            mermaid_label(edge.dep_type.as_str()),
+            mermaid_id(&edge.to)
+        );
+    }
+
+    // One class per status for the fill, one per priority for the border.
+    let mut by_class: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut class_defs: BTreeMap<String, String> = BTreeMap::new();
+    for node in &graph.nodes {
+        let status_class = format!("status_{}", mermaid_id(node.status.as_str()));
+        class_defs
+            .entry(status_class.clone())
+            .or_insert_with(|| format!("fill:{}", status_fill(&node.status)));
+        by_class
+            .entry(status_class)
+            .or_default()
+            .push(mermaid_id(&node.id));
+        let width = priority_width(node.priority);
+        if width > 1 {
+            let priority_class = format!("p{}", node.priority);
+            class_defs
+                .entry(priority_class.clone())
+                .or_insert_with(|| format!("stroke-width:{width}px"));
+            by_class
+                .entry(priority_class)
+                .or_default()
+                .push(mermaid_id(&node.id));
+        }
+    }
+    for (class, style) in &class_defs {
+        let _ = writeln!(out, "    classDef {class} {style}");
+    }
+    for (class, ids) in &by_class {
+        let _ = writeln!(out, "    class {} {class}", ids.join(","));
+    }
+    out
+}
+
+fn mermaid_node(
+    out: &mut String,
+    graph: &GraphExport,
+    layout: &Layout,
+    index: usize,
+    depth: usize,
+) {
+    let indent = "    ".repeat(depth);
+    let node = &graph.nodes[index];
+    let declaration = format!(
+        "{}[\"{}\"]",
+        mermaid_id(&node.id),
+        mermaid_label(&node_caption(node))
+    );
+    if let Some(members) = layout.members.get(&index) {
+        let _ = writeln!(
+            out,
+            "{indent}subgraph {}_epic[\"{}\"]",
+            mermaid_id(&node.id),
+            mermaid_label(&node.title)
+        );
+        let _ = writeln!(out, "{indent}    {declaration}");
+        for &member in members {
+            mermaid_node(out, graph, layout, member, depth + 1);
+        }
+        let _ = writeln!(out, "{indent}end");
+    } else {
+        let _ = writeln!(out, "{indent}{declaration}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, issue_type: IssueType, status: Status, priority: i32) -> ExportNode {
+        ExportNode {
+            id: id.to_string(),
+            title: format!("Title \"{id}\""),
+            status,
+            priority,
+            issue_type,
+        }
+    }
+
+    fn edge(from: &str, to: &str, dep_type: DependencyType) -> ExportEdge {
+        ExportEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            dep_type,
+        }
+    }
+
+    fn sample() -> GraphExport {
+        GraphExport {
+            nodes: vec![
+                node("bd-epic", IssueType::Epic, Status::Open, 1),
+                node("bd-a", IssueType::Task, Status::InProgress, 0),
+                node("bd-b", IssueType::Task, Status::Blocked, 2),
+                node("bd-c", IssueType::Bug, Status::Open, 2),
+            ],
+            edges: vec![
+                edge("bd-a", "bd-epic", DependencyType::ParentChild),
+                edge("bd-b", "bd-epic", DependencyType::ParentChild),
+                edge("bd-b", "bd-a", DependencyType::Blocks),
+                edge("bd-c", "bd-b", DependencyType::Related),
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("DOT".parse::<GraphFormat>().unwrap(), GraphFormat::Dot);
+        assert_eq!(
+            "graphml".parse::<GraphFormat>().unwrap(),
+            GraphFormat::GraphMl
+        );
+        assert!("svg".parse::<GraphFormat>().is_err());
+    }
+
+    #[test]
+    fn dot_clusters_epics_and_labels_edges() {
+        let dot = sample().render(GraphFormat::Dot);
+        assert!(dot.starts_with("digraph beads {"));
+        assert!(dot.contains("subgraph \"cluster_bd-epic\" {"));
+        assert!(dot.contains("\"bd-b\" -> \"bd-a\" [label=\"blocks\"];"));
+        assert!(dot.contains("\"bd-c\" -> \"bd-b\" [label=\"related\", style=dashed];"));
+        assert!(
+            !dot.contains("label=\"parent-child\""),
+            "nesting replaces the edge"
+        );
+        assert!(dot.contains("Title \\\"bd-a\\\""));
+        assert!(dot.contains("penwidth=3"));
+        assert!(dot.contains("#fecaca"));
+    }
+
+    #[test]
+    fn graphml_nests_children_and_keeps_edges() {
+        let xml = sample().render(GraphFormat::GraphMl);
+        assert!(xml.contains("<graph id=\"bd-epic:\" edgedefault=\"directed\">"));
+        assert!(xml.contains("Title &quot;bd-a&quot;"));
+        assert!(xml.contains("<data key=\"dep_type\">parent-child</data>"));
+        assert_eq!(xml.matches("<edge ").count(), 4);
+    }
+
+    #[test]
+    fn d2_uses_container_paths() {
+        let d2 = sample().render(GraphFormat::D2);
+        assert!(d2.contains("\"bd-epic_epic\": {"));
+        assert!(d2.contains("\"bd-epic_epic\".\"bd-b\" -> \"bd-epic_epic\".\"bd-a\": \"blocks\""));
+        assert!(d2.contains(
+            "\"bd-c\" -> \"bd-epic_epic\".\"bd-b\": \"related\" { style.stroke-dash: 3 }"
+        ));
+    }
+
+    #[test]
+    fn mermaid_uses_subgraphs_and_classes() {
+        let mermaid = sample().render(GraphFormat::Mermaid);
+        assert!(mermaid.starts_with("graph LR\n"));
+        assert!(mermaid.contains("subgraph bd_epic_epic[\"Title #quot;bd-epic#quot;\"]"));
+        assert!(mermaid.contains("bd_b -->|blocks| bd_a"));
+        assert!(mermaid.contains("bd_c -.->|related| bd_b"));
+        assert!(mermaid.contains("classDef status_blocked fill:#fecaca"));
+        assert!(mermaid.contains("class bd_a p0"));
+    }
+
+    #[test]
+    fn parent_child_cycles_are_not_nested_forever() {
+        let graph = GraphExport {
+            nodes: vec![
+                node("bd-x", IssueType::Epic, Status::Open, 2),
+                node("bd-y", IssueType::Epic, Status::Open, 2),
+            ],
+            edges: vec![
+                edge("bd-x", "bd-y", DependencyType::ParentChild),
+                edge("bd-y", "bd-x", DependencyType::ParentChild),
+            ],
+        };
+        let dot = graph.render(GraphFormat::Dot);
+        assert_eq!(dot.matches("subgraph").count(), 1);
+        assert!(dot.contains("label=\"parent-child\""));
+    }
+}
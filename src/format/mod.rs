@@ -6,7 +6,8 @@
 //! # Output Types
 //!
 //! These types match the classic bd JSON schemas for CLI compatibility:
-//! - [`IssueWithCounts`] - Issue with dependency/dependent counts (list/search)
+//! - [`IssueWithCounts`] - Issue with dependency/dependent counts (list)
+//! - [`SearchResult`] - Issue with counts, relevance score, and snippet (search)
 //! - [`IssueDetails`] - Issue with full relations (show)
 //! - [`BlockedIssue`] - Issue with blocking info (blocked)
 //! - [`TreeNode`] - Issue in dependency tree (dep tree)
@@ -34,6 +35,7 @@
 
 pub mod context;
 pub mod csv;
+pub mod graph;
 pub mod markdown;
 mod output;
 pub mod rich;
@@ -43,8 +45,8 @@ pub mod theme;
 
 pub use output::{
     BlockedIssue, BlockedIssueOutput, Breakdown, BreakdownEntry, IssueDetails, IssueWithCounts,
-    IssueWithDependencyMetadata, ReadyIssue, RecentActivity, StaleIssue, Statistics, StatsSummary,
-    TreeNode,
+    IssueWithDependencyMetadata, ReadyIssue, RecentActivity, SearchResult, StaleIssue, Statistics,
+    StatsSummary, TreeNode,
 };
 pub use text::{
     TextFormatOptions, format_issue_line, format_issue_line_with, format_priority,
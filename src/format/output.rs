@@ -1,4 +1,5 @@
 use crate::model::{Comment, Event, Issue, IssueType, Priority, Status};
+use crate::storage::scoring::ScoreBreakdown;
 use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -40,6 +41,9 @@ pub struct ReadyIssue {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub owner: Option<String>,
     pub priority: Priority,
+    /// Score breakdown, present with `ready --explain`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<ScoreBreakdown>,
     pub status: Status,
     pub title: String,
     pub updated_at: DateTime<Utc>,
@@ -59,6 +63,7 @@ impl From<&Issue> for ReadyIssue {
             notes: issue.notes.clone(),
             owner: issue.owner.clone(),
             priority: issue.priority,
+            score: None,
             status: issue.status.clone(),
             title: issue.title.clone(),
             updated_at: issue.updated_at,
@@ -110,6 +115,21 @@ pub struct IssueWithCounts {
     pub dependent_count: usize,
 }
 
+/// Issue with counts plus full-text ranking data for search views.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SearchResult {
+    #[serde(flatten)]
+    pub issue: Issue,
+    pub dependency_count: usize,
+    pub dependent_count: usize,
+    /// Relevance score (negated bm25; higher is better). Absent for substring-only matches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    /// Best-matching excerpt with hits wrapped in `**`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
 /// Issue details with full relations for show view.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct IssueDetails {
@@ -257,6 +277,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         }
     }
 
@@ -365,6 +365,7 @@ mod tests {
             is_template: false,
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         }
     }
 
@@ -11,9 +11,12 @@
 //! - [`model`] - Data types (Issue, Dependency, Comment, Event)
 //! - [`storage`] - `SQLite` database layer
 //! - [`sync`] - JSONL import/export operations
+//! - [`mcp`] - Model Context Protocol server for agents
+//! - [`daemon`] - JSON-RPC daemon on a Unix socket and CLI forwarding
 //! - [`config`] - Configuration management
 //! - [`error`] - Error types and handling
 //! - [`format`] - Output formatting (text, JSON)
+//! - [`tui`] - Interactive terminal board
 //! - [`util`] - Utility functions (hashing, time, paths)
 
 #![forbid(unsafe_code)]
@@ -22,13 +25,16 @@
 
 pub mod cli;
 pub mod config;
+pub mod daemon;
 pub mod error;
 pub mod format;
 pub mod logging;
+pub mod mcp;
 pub mod model;
 pub mod output;
 pub mod storage;
 pub mod sync;
+pub mod tui;
 pub mod util;
 pub mod validation;
 
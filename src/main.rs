@@ -1,6 +1,7 @@
 use beads_rust::cli::commands;
 use beads_rust::cli::{Cli, Commands};
 use beads_rust::config;
+use beads_rust::daemon;
 use beads_rust::logging::init_logging;
 use beads_rust::output::OutputContext;
 use beads_rust::sync::{auto_flush, auto_import_if_stale};
@@ -29,6 +30,14 @@ fn main() {
     // Track if this command potentially mutates data (for auto-flush)
     let is_mutating = is_mutating_command(&cli.command);
 
+    // A running daemon already keeps the database fresh and flushed.
+    if let Some(result) = daemon::forward::forward(&cli.command, &overrides, &output_ctx) {
+        if let Err(e) = result {
+            handle_error(&e, cli.json);
+        }
+        return;
+    }
+
     if should_auto_import(&cli.command) && !cli.no_db {
         if let Err(e) = run_auto_import(&overrides, cli.allow_stale, cli.no_auto_import) {
             handle_error(&e, cli.json);
@@ -46,6 +55,7 @@ fn main() {
         Commands::Delete(args) => {
             commands::delete::execute(&args, cli.json, &overrides, &output_ctx)
         }
+        Commands::Undo(args) => commands::undo::execute(&args, cli.json, &overrides, &output_ctx),
         Commands::List(args) => commands::list::execute(&args, cli.json, &overrides, &output_ctx),
         Commands::Comments(args) => {
             commands::comments::execute(&args, cli.json, &overrides, &output_ctx)
@@ -74,10 +84,23 @@ fn main() {
         Commands::Stale(args) => commands::stale::execute(&args, &overrides, &output_ctx),
         Commands::Lint(args) => commands::lint::execute(&args, cli.json, &overrides, &output_ctx),
         Commands::Ready(args) => commands::ready::execute(&args, cli.json, &overrides, &output_ctx),
+        Commands::Next(args) => commands::next::execute(&args, &overrides, &output_ctx),
+        Commands::Batch(args) => commands::batch::execute(&args, &overrides, &output_ctx),
+        Commands::Import { command } => {
+            commands::import::execute(&command, &overrides, &output_ctx)
+        }
+        Commands::Claim(args) => commands::claim::execute_claim(&args, &overrides, &output_ctx),
+        Commands::Heartbeat(args) => {
+            commands::claim::execute_heartbeat(&args, &overrides, &output_ctx)
+        }
+        Commands::Leases(args) => commands::claim::execute_leases(&args, &overrides, &output_ctx),
         Commands::Blocked(args) => {
             commands::blocked::execute(&args, cli.json || args.robot, &overrides, &output_ctx)
         }
         Commands::Sync(args) => commands::sync::execute(&args, cli.json, &overrides, &output_ctx),
+        Commands::Gc(args) => commands::gc::execute(&args, &overrides, &output_ctx),
+        Commands::Compact(args) => commands::compact::execute(&args, &overrides, &output_ctx),
+        Commands::MergeDriver(args) => commands::merge_driver::execute(&args, &output_ctx),
         Commands::Doctor => commands::doctor::execute(&overrides, &output_ctx),
         Commands::Info(args) => commands::info::execute(&args, &overrides, &output_ctx),
         Commands::Schema(args) => commands::schema::execute(&args, &overrides, &output_ctx),
@@ -110,7 +133,12 @@ fn main() {
             commands::changelog::execute(&args, cli.json || args.robot, &overrides, &output_ctx)
         }
         Commands::Query { command } => commands::query::execute(&command, &overrides, &output_ctx),
+        Commands::Template { command } => {
+            commands::template::execute(&command, &overrides, &output_ctx)
+        }
+        Commands::Recur { command } => commands::recur::execute(&command, &overrides, &output_ctx),
         Commands::Graph(args) => commands::graph::execute(&args, &overrides, &output_ctx),
+        Commands::Plan(args) => commands::plan::execute(&args, &overrides, &output_ctx),
         Commands::Agents(args) => {
             let agents_args = commands::agents::AgentsArgs {
                 add: args.add,
@@ -122,6 +150,11 @@ fn main() {
             };
             commands::agents::execute(&agents_args, &output_ctx)
         }
+        Commands::Mcp => commands::mcp::execute(cli.allow_stale, &overrides),
+        Commands::Serve(args) => {
+            commands::serve::execute(&args, cli.allow_stale, &overrides, &output_ctx)
+        }
+        Commands::Tui => commands::tui::execute(cli.allow_stale, &overrides, &output_ctx),
     };
 
     // Handle command result
@@ -148,11 +181,36 @@ const fn is_mutating_command(cmd: &Commands) -> bool {
         | Commands::Label { .. }
         | Commands::Comments(_)
         | Commands::Defer(_)
-        | Commands::Undefer(_) => true,
+        | Commands::Undefer(_)
+        | Commands::Claim(_)
+        | Commands::Heartbeat(_)
+        | Commands::Leases(_)
+        | Commands::Batch(_)
+        // Both hand expired leases back before looking for work.
+        | Commands::Ready(_)
+        | Commands::Next(_) => true,
+        Commands::Gc(args) => !args.dry_run,
+        Commands::Compact(args) => !args.dry_run,
+        Commands::Undo(args) => !args.list,
         Commands::Epic { command } => matches!(
             command,
             beads_rust::cli::EpicCommands::CloseEligible(args) if !args.dry_run
         ),
+        Commands::Template { command } => match command {
+            beads_rust::cli::TemplateCommands::Create(_) => true,
+            beads_rust::cli::TemplateCommands::Instantiate(args) => !args.dry_run,
+            beads_rust::cli::TemplateCommands::List => false,
+        },
+        Commands::Import { command } => match command {
+            beads_rust::cli::ImportCommands::Csv(args) => !args.dry_run,
+        },
+        Commands::Recur { command } => match command {
+            beads_rust::cli::RecurCommands::Add(_) | beads_rust::cli::RecurCommands::Remove(_) => {
+                true
+            }
+            beads_rust::cli::RecurCommands::Run(args) => !args.dry_run,
+            beads_rust::cli::RecurCommands::List => false,
+        },
         _ => false,
     }
 }
@@ -176,6 +234,7 @@ const fn should_auto_import(cmd: &Commands) -> bool {
         | Commands::Orphans(_)
         | Commands::Changelog(_)
         | Commands::Graph(_)
+        | Commands::Plan(_)
         | Commands::Create(_)
         | Commands::Update(_)
         | Commands::Delete(_)
@@ -184,15 +243,28 @@ const fn should_auto_import(cmd: &Commands) -> bool {
         | Commands::Q(_)
         | Commands::Defer(_)
         | Commands::Undefer(_)
+        | Commands::Next(_)
+        | Commands::Claim(_)
+        | Commands::Heartbeat(_)
+        | Commands::Leases(_)
+        | Commands::Undo(_)
+        | Commands::Batch(_)
+        | Commands::Import { .. }
         | Commands::Comments(_)
         | Commands::Dep { .. }
         | Commands::Label { .. }
         | Commands::Epic { .. }
-        | Commands::Query { .. } => true,
+        | Commands::Query { .. }
+        | Commands::Template { .. }
+        | Commands::Recur { .. }
+        | Commands::Gc(_)
+        | Commands::Compact(_) => true,
 
         // Explicitly excluded: init, sync, diagnostic, and config commands
         Commands::Init { .. }
         | Commands::Sync(_)
+        // Git runs the driver on temporary files mid-merge.
+        | Commands::MergeDriver(_)
         | Commands::Doctor
         | Commands::Info(_)
         | Commands::Schema(_)
@@ -202,7 +274,11 @@ const fn should_auto_import(cmd: &Commands) -> bool {
         | Commands::Audit { .. }
         | Commands::Config { .. }
         | Commands::History(_)
-        | Commands::Agents(_) => false,
+        | Commands::Agents(_)
+        // The server imports before each request itself.
+        | Commands::Mcp
+        | Commands::Serve(_)
+        | Commands::Tui => false,
 
         #[cfg(feature = "self_update")]
         Commands::Upgrade(_) => false,
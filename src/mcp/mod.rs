@@ -0,0 +1,604 @@
+//! Model Context Protocol (MCP) server.
+//!
+//! `br mcp` speaks MCP over stdio: newline-delimited JSON-RPC 2.0 messages
+//! on stdin and stdout, with logs on stderr. Tools wrap the same core
+//! functions the CLI commands use, so validation, workflow checks and
+//! event recording behave exactly as they do from the shell.
+//!
+//! Each tool call is one undoable operation. The JSONL file is imported
+//! before a call when it is newer than the database and flushed after
+//! every successful mutation, mirroring a CLI invocation.
+
+mod resources;
+mod tools;
+
+use crate::config::{self, CliOverrides, ConfigLayer, OpenStorageResult};
+use crate::error::{BeadsError, Result, StructuredError};
+use crate::sync::{auto_flush, auto_import_if_stale};
+use crate::util::id::{IdResolver, ResolverConfig};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+pub use tools::Tool;
+
+/// Protocol revision advertised when the client asks for one we don't know.
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+
+const SUPPORTED_PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+// JSON-RPC 2.0 error codes (plus the MCP resource-not-found code).
+pub(crate) const PARSE_ERROR: i64 = -32700;
+pub(crate) const INVALID_REQUEST: i64 = -32600;
+pub(crate) const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+const RESOURCE_NOT_FOUND: i64 = -32002;
+
+/// A JSON-RPC error object.
+#[derive(Debug)]
+pub(crate) struct RpcError {
+    code: i64,
+    message: String,
+    data: Option<Value>,
+}
+
+impl RpcError {
+    pub(crate) fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(INVALID_PARAMS, message)
+    }
+
+    /// Attach structured `data` to the error.
+    pub(crate) fn with_data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Wrap a `BeadsError`, carrying its structured payload as `data`.
+    fn from_beads(err: &BeadsError) -> Self {
+        let code = if matches!(err, BeadsError::IssueNotFound { .. }) {
+            RESOURCE_NOT_FOUND
+        } else {
+            INTERNAL_ERROR
+        };
+        Self {
+            code,
+            message: err.to_string(),
+            data: Some(StructuredError::from_error(err).to_json()),
+        }
+    }
+
+    pub(crate) fn to_response(&self, id: Value) -> Value {
+        let mut error = json!({ "code": self.code, "message": self.message });
+        if let Some(data) = &self.data {
+            error["data"] = data.clone();
+        }
+        json!({ "jsonrpc": "2.0", "id": id, "error": error })
+    }
+}
+
+#[derive(Deserialize)]
+struct CallToolParams {
+    name: String,
+    #[serde(default)]
+    arguments: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct ReadResourceParams {
+    uri: String,
+}
+
+/// A stdio MCP server bound to one beads workspace.
+pub struct McpServer {
+    beads_dir: PathBuf,
+    cli: CliOverrides,
+    storage_ctx: OpenStorageResult,
+    allow_stale: bool,
+}
+
+impl McpServer {
+    /// Open the workspace the CLI overrides point at.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no beads directory is found or the database
+    /// cannot be opened.
+    pub fn open(cli: &CliOverrides, allow_stale: bool) -> Result<Self> {
+        let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+        let storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+        Ok(Self {
+            beads_dir,
+            cli: cli.clone(),
+            storage_ctx,
+            allow_stale,
+        })
+    }
+
+    /// The workspace's `.beads` directory.
+    #[must_use]
+    pub fn beads_dir(&self) -> &Path {
+        &self.beads_dir
+    }
+
+    /// The open database and its paths, for front ends that read directly.
+    pub(crate) const fn workspace(&self) -> &OpenStorageResult {
+        &self.storage_ctx
+    }
+
+    /// Serve newline-delimited JSON-RPC messages until `reader` hits EOF.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading a message or writing a response fails.
+    pub fn serve<R: BufRead, W: Write>(&mut self, reader: R, mut writer: W) -> Result<()> {
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle_message(&line) {
+                serde_json::to_writer(&mut writer, &response)?;
+                writer.write_all(b"\n")?;
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle one JSON-RPC message, returning the response to send (if any).
+    ///
+    /// Notifications and stray responses produce no reply.
+    pub fn handle_message(&mut self, line: &str) -> Option<Value> {
+        let message: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(e) => {
+                return Some(
+                    RpcError::new(PARSE_ERROR, format!("Parse error: {e}"))
+                        .to_response(Value::Null),
+                );
+            }
+        };
+
+        let Some(object) = message.as_object() else {
+            return Some(
+                RpcError::new(INVALID_REQUEST, "Expected a JSON-RPC object")
+                    .to_response(Value::Null),
+            );
+        };
+
+        let id = object.get("id").cloned();
+        let Some(method) = object.get("method").and_then(Value::as_str) else {
+            // Responses to server-initiated requests; we never send any.
+            if object.contains_key("result") || object.contains_key("error") {
+                return None;
+            }
+            return Some(
+                RpcError::new(INVALID_REQUEST, "Missing method")
+                    .to_response(id.unwrap_or(Value::Null)),
+            );
+        };
+
+        let Some(id) = id else {
+            debug!(method, "MCP notification");
+            return None;
+        };
+
+        let params = object.get("params").cloned().unwrap_or(Value::Null);
+        debug!(method, "MCP request");
+        let response = match self.dispatch(method, params) {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(err) => err.to_response(id),
+        };
+        Some(response)
+    }
+
+    fn dispatch(&mut self, method: &str, params: Value) -> std::result::Result<Value, RpcError> {
+        match method {
+            "initialize" => Ok(initialize_result(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tools::definitions() })),
+            "tools/call" => self.call_tool(params),
+            "resources/list" => {
+                self.refresh().map_err(|e| RpcError::from_beads(&e))?;
+                let resources = resources::list(&self.storage_ctx.storage)
+                    .map_err(|e| RpcError::from_beads(&e))?;
+                Ok(json!({ "resources": resources }))
+            }
+            "resources/templates/list" => {
+                Ok(json!({ "resourceTemplates": resources::templates() }))
+            }
+            "resources/read" => self.read_resource(params),
+            other => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Method not found: {other}"),
+            )),
+        }
+    }
+
+    fn call_tool(&mut self, params: Value) -> std::result::Result<Value, RpcError> {
+        let call: CallToolParams = serde_json::from_value(params)
+            .map_err(|e| RpcError::invalid_params(format!("Invalid tools/call params: {e}")))?;
+        let tool = Tool::from_name(&call.name)
+            .ok_or_else(|| RpcError::invalid_params(format!("Unknown tool: {}", call.name)))?;
+        let arguments = call.arguments.unwrap_or_else(|| json!({}));
+
+        Ok(match self.call(tool, arguments, None) {
+            Ok(value) => tool_result(value, false),
+            Err(e) => {
+                debug!(tool = tool.name(), error = %e, "MCP tool failed");
+                tool_result(StructuredError::from_error(&e).to_json(), true)
+            }
+        })
+    }
+
+    /// Run one tool call as its own operation, returning its JSON output.
+    ///
+    /// `actor` overrides the configured actor for this call only; the
+    /// `serve` daemon uses it to attribute forwarded commands to the caller.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the arguments don't match the tool's schema or
+    /// the underlying command fails.
+    pub fn call(&mut self, tool: Tool, arguments: Value, actor: Option<&str>) -> Result<Value> {
+        let configured_actor = self.cli.actor.clone();
+        if let Some(actor) = actor {
+            self.cli.actor = Some(actor.to_string());
+        }
+
+        let outcome = self.refresh().and_then(|()| {
+            if tool.is_mutating() {
+                self.storage_ctx.storage.begin_operation();
+            }
+            let value = tools::call(self, tool, arguments)?;
+            if tool.is_mutating() {
+                self.flush()?;
+            }
+            Ok(value)
+        });
+
+        self.cli.actor = configured_actor;
+        outcome
+    }
+
+    fn read_resource(&mut self, params: Value) -> std::result::Result<Value, RpcError> {
+        let read: ReadResourceParams = serde_json::from_value(params)
+            .map_err(|e| RpcError::invalid_params(format!("Invalid resources/read params: {e}")))?;
+        self.refresh().map_err(|e| RpcError::from_beads(&e))?;
+
+        let value = match resources::read(self, &read.uri) {
+            Ok(Some(value)) => value,
+            Ok(None) => {
+                return Err(RpcError::new(RESOURCE_NOT_FOUND, "Resource not found")
+                    .with_data(json!({ "uri": read.uri })));
+            }
+            Err(e) => return Err(RpcError::from_beads(&e)),
+        };
+
+        let text = serde_json::to_string_pretty(&value)
+            .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+        Ok(json!({
+            "contents": [{
+                "uri": read.uri,
+                "mimeType": "application/json",
+                "text": text,
+            }]
+        }))
+    }
+
+    /// Import the JSONL file first if it changed behind our back.
+    pub(crate) fn refresh(&mut self) -> Result<()> {
+        if self.storage_ctx.no_db {
+            return Ok(());
+        }
+        let expected_prefix = self.storage_ctx.storage.get_config("issue_prefix")?;
+        let outcome = auto_import_if_stale(
+            &mut self.storage_ctx.storage,
+            &self.storage_ctx.paths.beads_dir,
+            &self.storage_ctx.paths.jsonl_path,
+            expected_prefix.as_deref(),
+            self.allow_stale,
+            self.cli.no_auto_import.unwrap_or(false),
+        )?;
+        if outcome.attempted {
+            debug!(
+                imported_count = outcome.imported_count,
+                "Auto-import attempt completed"
+            );
+        }
+        Ok(())
+    }
+
+    /// Export dirty issues after a mutation, like the CLI's auto-flush.
+    fn flush(&mut self) -> Result<()> {
+        if self.storage_ctx.no_db {
+            return self.storage_ctx.flush_no_db_if_dirty();
+        }
+        if self.cli.no_auto_flush.unwrap_or(false) {
+            return Ok(());
+        }
+        // Auto-flush failures are non-fatal, as they are for CLI commands.
+        if let Err(e) = auto_flush(&mut self.storage_ctx.storage, &self.beads_dir) {
+            debug!(?e, "Auto-flush failed (non-fatal)");
+        }
+        Ok(())
+    }
+
+    fn config_layer(&self) -> Result<ConfigLayer> {
+        config::load_config(&self.beads_dir, Some(&self.storage_ctx.storage), &self.cli)
+    }
+
+    /// Resolve a full or partial issue id against the workspace.
+    fn resolve_id(&self, layer: &ConfigLayer, input: &str) -> Result<String> {
+        let resolver = id_resolver(layer);
+        let storage = &self.storage_ctx.storage;
+        let resolution = resolver.resolve(
+            input,
+            |id| storage.id_exists(id).unwrap_or(false),
+            |hash| storage.find_ids_by_hash(hash).unwrap_or_default(),
+        )?;
+        Ok(resolution.id)
+    }
+}
+
+fn id_resolver(layer: &ConfigLayer) -> IdResolver {
+    let id_config = config::id_config_from_layer(layer);
+    IdResolver::new(ResolverConfig::with_prefix(id_config.prefix))
+}
+
+fn initialize_result(params: &Value) -> Value {
+    let version = params
+        .get("protocolVersion")
+        .and_then(Value::as_str)
+        .filter(|requested| SUPPORTED_PROTOCOL_VERSIONS.contains(requested))
+        .unwrap_or(PROTOCOL_VERSION);
+    json!({
+        "protocolVersion": version,
+        "capabilities": {
+            "tools": { "listChanged": false },
+            "resources": { "subscribe": false, "listChanged": false },
+        },
+        "serverInfo": {
+            "name": "obr",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "instructions": "Issue tracker for this repository. Use `ready` to find \
+            unblocked work, `claim` before starting, `comment` to record progress \
+            and `close` when done. Issues are readable as beads://issues/{id}.",
+    })
+}
+
+/// Wrap a tool's JSON output (or error payload) as a `CallToolResult`.
+fn tool_result(value: Value, is_error: bool) -> Value {
+    let text = serde_json::to_string_pretty(&value).unwrap_or_else(|_| value.to_string());
+    json!({
+        "content": [{ "type": "text", "text": text }],
+        "structuredContent": value,
+        "isError": is_error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Issue;
+    use tempfile::TempDir;
+
+    fn server() -> (TempDir, McpServer) {
+        let dir = TempDir::new().expect("tempdir");
+        let beads_dir = dir.path().join(".beads");
+        std::fs::create_dir_all(&beads_dir).expect("beads dir");
+        let db_path = beads_dir.join("beads.db");
+        let cli = CliOverrides {
+            db: Some(db_path),
+            actor: Some("tester".to_string()),
+            no_auto_flush: Some(true),
+            no_auto_import: Some(true),
+            ..CliOverrides::default()
+        };
+        let mut server = McpServer {
+            beads_dir: beads_dir.clone(),
+            storage_ctx: config::open_storage_with_cli(&beads_dir, &cli).expect("open"),
+            cli,
+            allow_stale: true,
+        };
+        server
+            .storage_ctx
+            .storage
+            .set_config("issue_prefix", "bd")
+            .expect("prefix");
+        (dir, server)
+    }
+
+    fn request(server: &mut McpServer, method: &str, params: &Value) -> Value {
+        let line = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        server.handle_message(&line.to_string()).expect("response")
+    }
+
+    fn call(server: &mut McpServer, name: &str, arguments: &Value) -> Value {
+        let response = request(
+            server,
+            "tools/call",
+            &json!({ "name": name, "arguments": arguments }),
+        );
+        response["result"].clone()
+    }
+
+    #[test]
+    fn initialize_negotiates_protocol_version() {
+        let (_dir, mut server) = server();
+        let response = request(
+            &mut server,
+            "initialize",
+            &json!({ "protocolVersion": "2025-03-26", "capabilities": {} }),
+        );
+        assert_eq!(response["result"]["protocolVersion"], "2025-03-26");
+        assert_eq!(response["result"]["serverInfo"]["name"], "obr");
+
+        let response = request(
+            &mut server,
+            "initialize",
+            &json!({ "protocolVersion": "1999-01-01" }),
+        );
+        assert_eq!(response["result"]["protocolVersion"], PROTOCOL_VERSION);
+    }
+
+    #[test]
+    fn notifications_get_no_response() {
+        let (_dir, mut server) = server();
+        let line = r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#;
+        assert!(server.handle_message(line).is_none());
+    }
+
+    #[test]
+    fn malformed_and_unknown_requests_are_rpc_errors() {
+        let (_dir, mut server) = server();
+        let response = server.handle_message("{not json").expect("response");
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+
+        let response = request(&mut server, "bogus/method", &json!({}));
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = request(
+            &mut server,
+            "tools/call",
+            &json!({ "name": "nope", "arguments": {} }),
+        );
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[test]
+    fn tools_list_exposes_schemas() {
+        let (_dir, mut server) = server();
+        let response = request(&mut server, "tools/list", &json!({}));
+        let tools = response["result"]["tools"].as_array().expect("tools");
+        let names: Vec<&str> = tools.iter().filter_map(|t| t["name"].as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "ready", "show", "create", "update", "claim", "close", "dep_add", "comment"
+            ]
+        );
+        for tool in tools {
+            assert_eq!(tool["inputSchema"]["type"], "object", "{}", tool["name"]);
+            assert_eq!(tool["outputSchema"]["type"], "object", "{}", tool["name"]);
+        }
+        let create = &tools[2]["inputSchema"];
+        assert_eq!(create["required"], json!(["title"]));
+    }
+
+    #[test]
+    fn create_claim_comment_close_round_trip() {
+        let (_dir, mut server) = server();
+        let created = call(
+            &mut server,
+            "create",
+            &json!({ "title": "From MCP", "priority": 1, "labels": ["agent"] }),
+        );
+        assert_eq!(created["isError"], false, "{created}");
+        let issue: Issue =
+            serde_json::from_value(created["structuredContent"].clone()).expect("issue");
+        assert_eq!(issue.title, "From MCP");
+        assert_eq!(issue.priority.0, 1);
+
+        let ready = call(&mut server, "ready", &json!({}));
+        assert_eq!(ready["structuredContent"]["issues"][0]["id"], issue.id);
+
+        let claimed = call(&mut server, "claim", &json!({ "id": issue.id }));
+        assert_eq!(claimed["structuredContent"]["status"], "in_progress");
+        assert_eq!(claimed["structuredContent"]["assignee"], "tester");
+
+        let comment = call(
+            &mut server,
+            "comment",
+            &json!({ "id": issue.id, "text": "halfway" }),
+        );
+        assert_eq!(comment["structuredContent"]["text"], "halfway");
+
+        let closed = call(
+            &mut server,
+            "close",
+            &json!({ "id": issue.id, "reason": "shipped" }),
+        );
+        assert_eq!(closed["structuredContent"]["closed"][0]["id"], issue.id);
+
+        let shown = call(&mut server, "show", &json!({ "id": issue.id }));
+        assert_eq!(shown["structuredContent"]["status"], "closed");
+        assert_eq!(shown["structuredContent"]["comments"][0]["text"], "halfway");
+
+        // Each tool call is its own undoable operation.
+        let operations = server.storage_ctx.storage.list_operations(10).expect("ops");
+        assert_eq!(operations.len(), 4);
+    }
+
+    #[test]
+    fn tool_failures_return_structured_errors() {
+        let (_dir, mut server) = server();
+        let result = call(&mut server, "show", &json!({ "id": "bd-missing" }));
+        assert_eq!(result["isError"], true);
+        assert_eq!(
+            result["structuredContent"]["error"]["code"],
+            "ISSUE_NOT_FOUND"
+        );
+
+        let result = call(&mut server, "create", &json!({ "title": "" }));
+        assert_eq!(result["isError"], true);
+        assert_eq!(
+            result["structuredContent"]["error"]["code"],
+            "VALIDATION_FAILED"
+        );
+
+        let result = call(&mut server, "create", &json!({ "bogus": 1 }));
+        assert_eq!(result["isError"], true);
+    }
+
+    #[test]
+    fn resources_read_issue_and_missing_uri() {
+        let (_dir, mut server) = server();
+        let created = call(&mut server, "create", &json!({ "title": "Readable" }));
+        let id = created["structuredContent"]["id"]
+            .as_str()
+            .expect("id")
+            .to_string();
+
+        let response = request(
+            &mut server,
+            "resources/read",
+            &json!({ "uri": format!("beads://issues/{id}") }),
+        );
+        let text = response["result"]["contents"][0]["text"]
+            .as_str()
+            .expect("text");
+        let details: Value = serde_json::from_str(text).expect("json");
+        assert_eq!(details["title"], "Readable");
+
+        let response = request(
+            &mut server,
+            "resources/read",
+            &json!({ "uri": "beads://elsewhere/x" }),
+        );
+        assert_eq!(response["error"]["code"], RESOURCE_NOT_FOUND);
+
+        let response = request(
+            &mut server,
+            "resources/read",
+            &json!({ "uri": "beads://issues/bd-nope" }),
+        );
+        assert_eq!(response["error"]["code"], RESOURCE_NOT_FOUND);
+        assert_eq!(
+            response["error"]["data"]["error"]["code"],
+            "ISSUE_NOT_FOUND"
+        );
+    }
+}
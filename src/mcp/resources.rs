@@ -0,0 +1,86 @@
+//! MCP resources: issues by id and saved queries.
+//!
+//! - `beads://issues/{id}` - one issue with labels, dependencies and comments
+//! - `beads://queries/{name}` - a saved query and the issues it matches
+
+use super::McpServer;
+use crate::cli::commands::{list, query};
+use crate::error::{BeadsError, Result};
+use crate::storage::SqliteStorage;
+use serde_json::{Value, json};
+
+const ISSUE_PREFIX: &str = "beads://issues/";
+const QUERY_PREFIX: &str = "beads://queries/";
+
+/// Concrete resources: one per saved query.
+///
+/// Issues are too numerous to enumerate; clients reach them through the
+/// `beads://issues/{id}` template instead.
+pub fn list(storage: &SqliteStorage) -> Result<Vec<Value>> {
+    Ok(query::saved_queries(storage)?
+        .into_iter()
+        .map(|saved| {
+            let description = saved
+                .description
+                .unwrap_or_else(|| format!("Issues matching saved query '{}'", saved.name));
+            json!({
+                "uri": format!("{QUERY_PREFIX}{}", saved.name),
+                "name": saved.name,
+                "description": description,
+                "mimeType": "application/json",
+            })
+        })
+        .collect())
+}
+
+/// URI templates for parameterised resources.
+pub fn templates() -> Vec<Value> {
+    vec![
+        json!({
+            "uriTemplate": format!("{ISSUE_PREFIX}{{id}}"),
+            "name": "issue",
+            "description": "An issue with its labels, dependencies and comments",
+            "mimeType": "application/json",
+        }),
+        json!({
+            "uriTemplate": format!("{QUERY_PREFIX}{{name}}"),
+            "name": "saved-query",
+            "description": "A saved query and the issues it currently matches",
+            "mimeType": "application/json",
+        }),
+    ]
+}
+
+/// Read a resource, or `None` if the URI names no known resource.
+pub fn read(server: &McpServer, uri: &str) -> Result<Option<Value>> {
+    let storage = &server.storage_ctx.storage;
+
+    if let Some(id) = uri.strip_prefix(ISSUE_PREFIX).filter(|id| !id.is_empty()) {
+        let layer = server.config_layer()?;
+        let id = server.resolve_id(&layer, id)?;
+        let details = storage
+            .get_issue_details(&id, true, false, 10)?
+            .ok_or(BeadsError::IssueNotFound { id })?;
+        return Ok(Some(serde_json::to_value(details)?));
+    }
+
+    if let Some(name) = uri
+        .strip_prefix(QUERY_PREFIX)
+        .filter(|name| !name.is_empty())
+    {
+        let Some(saved) = query::saved_queries(storage)?
+            .into_iter()
+            .find(|saved| saved.name == name)
+        else {
+            return Ok(None);
+        };
+        let issues = list::query_issues(storage, &saved.filters.to_list_args())?;
+        let issues = list::with_counts(storage, issues)?;
+        return Ok(Some(json!({
+            "query": saved,
+            "issues": issues,
+        })));
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,625 @@
+//! MCP tool definitions and handlers.
+//!
+//! Argument structs double as the tools' input schemas and the output
+//! schemas come from the same `schemars` models `br schema` publishes.
+
+use super::McpServer;
+use crate::cli::commands::close::{self, CloseArgs, CloseOutcome, CloseResult};
+use crate::cli::commands::create::{self, CreateConfig};
+use crate::cli::commands::dep::{self, DepActionResult};
+use crate::cli::commands::ready;
+use crate::cli::commands::update::{self, UpdateConfig};
+use crate::cli::{CreateArgs, ReadyArgs, SortPolicy, UpdateArgs};
+use crate::config::workflow::Workflow;
+use crate::config::{self, ConfigLayer};
+use crate::error::{BeadsError, Result};
+use crate::format::{IssueDetails, ReadyIssue};
+use crate::model::{Comment, Issue};
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaSettings;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// Default number of issues returned by the `ready` tool.
+const DEFAULT_READY_LIMIT: usize = 20;
+
+/// The tools this server exposes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    Ready,
+    Show,
+    Create,
+    Update,
+    Claim,
+    Close,
+    DepAdd,
+    Comment,
+}
+
+impl Tool {
+    pub const ALL: [Self; 8] = [
+        Self::Ready,
+        Self::Show,
+        Self::Create,
+        Self::Update,
+        Self::Claim,
+        Self::Close,
+        Self::DepAdd,
+        Self::Comment,
+    ];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Ready => "ready",
+            Self::Show => "show",
+            Self::Create => "create",
+            Self::Update => "update",
+            Self::Claim => "claim",
+            Self::Close => "close",
+            Self::DepAdd => "dep_add",
+            Self::Comment => "comment",
+        }
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|tool| tool.name() == name)
+    }
+
+    /// Whether the tool changes issues (and so starts an undoable operation).
+    #[must_use]
+    pub const fn is_mutating(self) -> bool {
+        !matches!(self, Self::Ready | Self::Show)
+    }
+
+    const fn description(self) -> &'static str {
+        match self {
+            Self::Ready => {
+                "List open issues with no blocking dependencies, highest priority first."
+            }
+            Self::Show => "Show one issue with its labels, dependencies and comments.",
+            Self::Create => "Create an issue and return it.",
+            Self::Update => "Update fields, labels or the parent of an issue.",
+            Self::Claim => {
+                "Assign an issue to yourself and mark it in_progress. Fails if it is \
+                 blocked or already claimed by someone else."
+            }
+            Self::Close => "Close an issue. Blocked issues are skipped unless force is set.",
+            Self::DepAdd => "Record that issue_id depends on depends_on_id.",
+            Self::Comment => "Add a comment to an issue.",
+        }
+    }
+
+    fn input_schema(self) -> Value {
+        match self {
+            Self::Ready => schema_value::<ReadyParams>(),
+            Self::Show => schema_value::<ShowParams>(),
+            Self::Create => schema_value::<CreateParams>(),
+            Self::Update => schema_value::<UpdateParams>(),
+            Self::Claim => schema_value::<ClaimParams>(),
+            Self::Close => schema_value::<CloseParams>(),
+            Self::DepAdd => schema_value::<DepAddParams>(),
+            Self::Comment => schema_value::<CommentParams>(),
+        }
+    }
+
+    fn output_schema(self) -> Value {
+        match self {
+            Self::Ready => schema_value::<ReadyOutput>(),
+            Self::Show => schema_value::<IssueDetails>(),
+            Self::Create | Self::Update | Self::Claim => schema_value::<Issue>(),
+            Self::Close => schema_value::<CloseResult>(),
+            Self::DepAdd => schema_value::<DepActionResult>(),
+            Self::Comment => schema_value::<Comment>(),
+        }
+    }
+}
+
+/// `tools/list` entries for every tool.
+pub fn definitions() -> Vec<Value> {
+    Tool::ALL
+        .into_iter()
+        .map(|tool| {
+            json!({
+                "name": tool.name(),
+                "description": tool.description(),
+                "inputSchema": tool.input_schema(),
+                "outputSchema": tool.output_schema(),
+                "annotations": { "readOnlyHint": !tool.is_mutating() },
+            })
+        })
+        .collect()
+}
+
+/// Run `tool` with raw JSON `arguments`.
+pub fn call(server: &mut McpServer, tool: Tool, arguments: Value) -> Result<Value> {
+    match tool {
+        Tool::Ready => ready_tool(server, parse(arguments)?),
+        Tool::Show => show_tool(server, &parse(arguments)?),
+        Tool::Create => create_tool(server, parse(arguments)?),
+        Tool::Update => update_tool(server, parse(arguments)?),
+        Tool::Claim => claim_tool(server, parse(arguments)?),
+        Tool::Close => close_tool(server, parse(arguments)?),
+        Tool::DepAdd => dep_add_tool(server, parse(arguments)?),
+        Tool::Comment => comment_tool(server, &parse(arguments)?),
+    }
+}
+
+/// A JSON Schema with subschemas inlined, as MCP clients expect.
+fn schema_value<T: JsonSchema>() -> Value {
+    let schema = SchemaSettings::draft07()
+        .with(|settings| {
+            settings.inline_subschemas = true;
+            settings.meta_schema = None;
+        })
+        .into_generator()
+        .into_root_schema_for::<T>();
+    serde_json::to_value(schema).unwrap_or_else(|_| json!({ "type": "object" }))
+}
+
+fn parse<T: DeserializeOwned>(arguments: Value) -> Result<T> {
+    serde_json::from_value(arguments)
+        .map_err(|e| BeadsError::validation("arguments", e.to_string()))
+}
+
+fn to_value<T: Serialize>(value: &T) -> Result<Value> {
+    Ok(serde_json::to_value(value)?)
+}
+
+/// Priority as a number (`1`) or string (`"P1"`).
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum PriorityParam {
+    Number(i32),
+    Text(String),
+}
+
+impl PriorityParam {
+    fn into_arg(self) -> String {
+        match self {
+            Self::Number(n) => n.to_string(),
+            Self::Text(text) => text,
+        }
+    }
+}
+
+/// Ordering for the `ready` tool.
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum ReadySort {
+    /// P0/P1 first by creation time, then the rest by creation time
+    #[default]
+    Hybrid,
+    /// Priority, then creation time
+    Priority,
+    /// Creation time only
+    Oldest,
+    /// Weighted score from the `ready.score.*` config
+    Score,
+}
+
+impl From<ReadySort> for SortPolicy {
+    fn from(sort: ReadySort) -> Self {
+        match sort {
+            ReadySort::Hybrid => Self::Hybrid,
+            ReadySort::Priority => Self::Priority,
+            ReadySort::Oldest => Self::Oldest,
+            ReadySort::Score => Self::Score,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+#[allow(clippy::struct_excessive_bools)]
+struct ReadyParams {
+    /// Maximum number of issues (default 20, 0 for unlimited)
+    limit: Option<usize>,
+    /// Only issues assigned to this person
+    assignee: Option<String>,
+    /// Only unassigned issues
+    unassigned: bool,
+    /// Issues must carry all of these labels
+    labels: Vec<String>,
+    /// Issues must carry at least one of these labels
+    labels_any: Vec<String>,
+    /// Issue types to include (task, bug, feature, ...)
+    types: Vec<String>,
+    /// Priorities to include
+    priorities: Vec<PriorityParam>,
+    /// Include deferred issues
+    include_deferred: bool,
+    /// Only children of this epic
+    parent: Option<String>,
+    /// With `parent`, include all descendants
+    recursive: bool,
+    /// Filter expression, as accepted by `br ready --where`
+    #[serde(rename = "where")]
+    where_: Option<String>,
+    /// Sort order
+    sort: ReadySort,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+struct ReadyOutput {
+    issues: Vec<ReadyIssue>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct ShowParams {
+    /// Issue id (full, prefix-less or a unique hash fragment)
+    id: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct CreateParams {
+    /// Issue title
+    title: String,
+    /// Issue type (task, bug, feature, epic, chore, ...)
+    #[serde(default, rename = "type")]
+    issue_type: Option<String>,
+    /// Priority 0-4 (0 is critical)
+    #[serde(default)]
+    priority: Option<PriorityParam>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    assignee: Option<String>,
+    #[serde(default)]
+    owner: Option<String>,
+    #[serde(default)]
+    labels: Vec<String>,
+    /// Parent epic id
+    #[serde(default)]
+    parent: Option<String>,
+    /// Dependencies as `id` or `type:id`
+    #[serde(default)]
+    deps: Vec<String>,
+    /// Estimate in minutes
+    #[serde(default)]
+    estimate: Option<i32>,
+    /// Due date (RFC3339 or relative, e.g. `+2d`)
+    #[serde(default)]
+    due: Option<String>,
+    /// Defer until (RFC3339 or relative)
+    #[serde(default)]
+    defer: Option<String>,
+    #[serde(default)]
+    external_ref: Option<String>,
+    /// Initial status
+    #[serde(default)]
+    status: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct UpdateParams {
+    /// Issue id
+    id: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    design: Option<String>,
+    #[serde(default)]
+    acceptance_criteria: Option<String>,
+    #[serde(default)]
+    notes: Option<String>,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    priority: Option<PriorityParam>,
+    #[serde(default, rename = "type")]
+    issue_type: Option<String>,
+    /// New assignee (empty string clears)
+    #[serde(default)]
+    assignee: Option<String>,
+    #[serde(default)]
+    owner: Option<String>,
+    /// Due date (empty string clears)
+    #[serde(default)]
+    due: Option<String>,
+    /// Defer until (empty string clears)
+    #[serde(default)]
+    defer: Option<String>,
+    #[serde(default)]
+    estimate: Option<i32>,
+    #[serde(default)]
+    add_labels: Vec<String>,
+    #[serde(default)]
+    remove_labels: Vec<String>,
+    /// Replace all labels with these
+    #[serde(default)]
+    set_labels: Vec<String>,
+    /// New parent id (empty string removes the parent)
+    #[serde(default)]
+    parent: Option<String>,
+    #[serde(default)]
+    external_ref: Option<String>,
+    /// Skip the blocked-issue check when moving to in_progress
+    #[serde(default)]
+    force: bool,
+    /// Session id recorded as `closed_by_session` if this closes the issue
+    #[serde(default)]
+    session: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct ClaimParams {
+    /// Issue id
+    id: String,
+    /// Claim even if the issue is blocked
+    #[serde(default)]
+    force: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct CloseParams {
+    /// Issue id
+    id: String,
+    /// Close reason (default "done")
+    #[serde(default)]
+    reason: Option<String>,
+    /// Close even if blocked
+    #[serde(default)]
+    force: bool,
+    /// Session id recorded as `closed_by_session`
+    #[serde(default)]
+    session: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct DepAddParams {
+    /// The dependent issue
+    issue_id: String,
+    /// The issue it depends on (or `external:<project>:<capability>`)
+    depends_on_id: String,
+    /// Dependency type (default "blocks")
+    #[serde(default, rename = "type")]
+    dep_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct CommentParams {
+    /// Issue id
+    id: String,
+    /// Comment text
+    text: String,
+}
+
+fn ready_tool(server: &McpServer, params: ReadyParams) -> Result<Value> {
+    let layer = server.config_layer()?;
+    let args = ReadyArgs {
+        limit: params.limit.unwrap_or(DEFAULT_READY_LIMIT),
+        assignee: params.assignee,
+        unassigned: params.unassigned,
+        label: params.labels,
+        label_any: params.labels_any,
+        type_: params.types,
+        priority: params
+            .priorities
+            .into_iter()
+            .map(PriorityParam::into_arg)
+            .collect(),
+        sort: params.sort.into(),
+        include_deferred: params.include_deferred,
+        parent: params.parent,
+        recursive: params.recursive,
+        where_: params.where_,
+        ..ReadyArgs::default()
+    };
+    let external_db_paths = config::external_project_db_paths(&layer, &server.beads_dir);
+    let actor = config::resolve_actor(&layer);
+    let weights = config::score_weights_from_layer(&layer)?;
+    let issues = ready::query_ready(
+        &server.storage_ctx.storage,
+        &args,
+        &actor,
+        &weights,
+        &external_db_paths,
+    )?;
+    to_value(&ReadyOutput {
+        issues: issues.iter().map(ReadyIssue::from).collect(),
+    })
+}
+
+fn show_tool(server: &McpServer, params: &ShowParams) -> Result<Value> {
+    let layer = server.config_layer()?;
+    let id = server.resolve_id(&layer, &params.id)?;
+    let details = server
+        .storage_ctx
+        .storage
+        .get_issue_details(&id, true, false, 10)?
+        .ok_or(BeadsError::IssueNotFound { id })?;
+    to_value(&details)
+}
+
+fn create_tool(server: &mut McpServer, params: CreateParams) -> Result<Value> {
+    let layer = server.config_layer()?;
+    let config = CreateConfig {
+        id_config: config::id_config_from_layer(&layer),
+        default_priority: config::default_priority_from_layer(&layer)?,
+        default_issue_type: config::default_issue_type_from_layer(&layer)?,
+        actor: config::resolve_actor(&layer),
+    };
+    let args = CreateArgs {
+        title: Some(params.title),
+        type_: params.issue_type,
+        priority: params.priority.map(PriorityParam::into_arg),
+        description: params.description,
+        assignee: params.assignee,
+        owner: params.owner,
+        labels: params.labels,
+        parent: params.parent,
+        deps: params.deps,
+        estimate: params.estimate,
+        due: params.due,
+        defer: params.defer,
+        external_ref: params.external_ref,
+        status: params.status,
+        ..CreateArgs::default()
+    };
+    let issue = create::create_issue_impl(&mut server.storage_ctx.storage, &args, &config)?;
+    exported_issue(server, &issue.id)
+}
+
+fn update_tool(server: &mut McpServer, params: UpdateParams) -> Result<Value> {
+    let layer = server.config_layer()?;
+    let id = server.resolve_id(&layer, &params.id)?;
+    let args = UpdateArgs {
+        ids: vec![id.clone()],
+        title: params.title,
+        description: params.description,
+        design: params.design,
+        acceptance_criteria: params.acceptance_criteria,
+        notes: params.notes,
+        status: params.status,
+        priority: params.priority.map(PriorityParam::into_arg),
+        type_: params.issue_type,
+        assignee: params.assignee,
+        owner: params.owner,
+        due: params.due,
+        defer: params.defer,
+        estimate: params.estimate,
+        add_label: params.add_labels,
+        remove_label: params.remove_labels,
+        set_labels: params.set_labels,
+        parent: params.parent,
+        external_ref: params.external_ref,
+        force: params.force,
+        session: params.session,
+        ..UpdateArgs::default()
+    };
+    apply_update(server, &layer, &id, &args)
+}
+
+fn claim_tool(server: &mut McpServer, params: ClaimParams) -> Result<Value> {
+    let layer = server.config_layer()?;
+    let id = server.resolve_id(&layer, &params.id)?;
+    let args = UpdateArgs {
+        ids: vec![id.clone()],
+        claim: true,
+        force: params.force,
+        ..UpdateArgs::default()
+    };
+    apply_update(server, &layer, &id, &args)
+}
+
+fn apply_update(
+    server: &mut McpServer,
+    layer: &ConfigLayer,
+    id: &str,
+    args: &UpdateArgs,
+) -> Result<Value> {
+    let actor = config::resolve_actor(layer);
+    let update = update::build_update(args, &actor, config::claim_exclusive_from_layer(layer))?;
+    let update_config = UpdateConfig {
+        actor,
+        resolver: super::id_resolver(layer),
+        workflow: Workflow::from_layer(layer)?,
+    };
+    update::update_issue_impl(
+        &mut server.storage_ctx.storage,
+        id,
+        args,
+        &update,
+        &update_config,
+    )?;
+    crate::util::set_last_touched_id(&server.beads_dir, id);
+    exported_issue(server, id)
+}
+
+fn close_tool(server: &mut McpServer, params: CloseParams) -> Result<Value> {
+    let layer = server.config_layer()?;
+    let id = server.resolve_id(&layer, &params.id)?;
+    let args = CloseArgs {
+        ids: vec![id.clone()],
+        reason: params.reason,
+        force: params.force,
+        session: params.session,
+        suggest_next: false,
+    };
+    let workflow = Workflow::from_layer(&layer)?;
+    let actor = config::resolve_actor(&layer);
+    let mut result = CloseResult {
+        closed: Vec::new(),
+        skipped: Vec::new(),
+    };
+    match close::close_issue_impl(
+        &mut server.storage_ctx.storage,
+        &id,
+        &args,
+        &workflow,
+        &actor,
+    )? {
+        CloseOutcome::Closed(closed) => {
+            crate::util::set_last_touched_id(&server.beads_dir, &id);
+            result.closed.push(closed);
+        }
+        CloseOutcome::Skipped(skipped) => result.skipped.push(skipped),
+    }
+    to_value(&result)
+}
+
+fn dep_add_tool(server: &mut McpServer, params: DepAddParams) -> Result<Value> {
+    let layer = server.config_layer()?;
+    let issue_id = server.resolve_id(&layer, &params.issue_id)?;
+    // External dependencies don't need resolution
+    let depends_on_id = if params.depends_on_id.starts_with("external:") {
+        params.depends_on_id
+    } else {
+        server.resolve_id(&layer, &params.depends_on_id)?
+    };
+    let actor = config::resolve_actor(&layer);
+    let dep_type_str = params.dep_type.as_deref().unwrap_or("blocks");
+    let (dep_type, added) = dep::add_dependency_impl(
+        &mut server.storage_ctx.storage,
+        &issue_id,
+        &depends_on_id,
+        dep_type_str,
+        &actor,
+    )?;
+    to_value(&DepActionResult {
+        status: if added { "ok" } else { "exists" }.to_string(),
+        issue_id,
+        depends_on_id,
+        dep_type: dep_type.as_str().to_string(),
+        action: if added { "added" } else { "already_exists" }.to_string(),
+    })
+}
+
+fn comment_tool(server: &mut McpServer, params: &CommentParams) -> Result<Value> {
+    if params.text.trim().is_empty() {
+        return Err(BeadsError::validation(
+            "text",
+            "comment text cannot be empty",
+        ));
+    }
+    let layer = server.config_layer()?;
+    let id = server.resolve_id(&layer, &params.id)?;
+    let author = config::resolve_actor(&layer);
+    let comment = server
+        .storage_ctx
+        .storage
+        .add_comment(&id, &author, &params.text)?;
+    to_value(&comment)
+}
+
+/// The issue as `br show --json`-style export (labels and dependencies included).
+fn exported_issue(server: &McpServer, id: &str) -> Result<Value> {
+    let issue = server
+        .storage_ctx
+        .storage
+        .get_issue_for_export(id)?
+        .ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() })?;
+    to_value(&issue)
+}
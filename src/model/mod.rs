@@ -11,8 +11,10 @@
 use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::str::FromStr;
+use std::sync::{LazyLock, PoisonError, RwLock};
 
 #[allow(clippy::trivially_copy_pass_by_ref)]
 const fn is_false(b: &bool) -> bool {
@@ -61,14 +63,182 @@ impl Status {
         }
     }
 
+    /// Workflow category of this status.
+    ///
+    /// Custom statuses take the category declared in config; unknown custom
+    /// values (e.g. imported from another workspace) are treated as waiting.
+    #[must_use]
+    pub fn category(&self) -> StatusCategory {
+        match self {
+            Self::Open | Self::InProgress => StatusCategory::Active,
+            Self::Blocked | Self::Deferred | Self::Pinned => StatusCategory::Waiting,
+            Self::Closed | Self::Tombstone => StatusCategory::Terminal,
+            Self::Custom(name) => custom_status_category(name).unwrap_or(StatusCategory::Waiting),
+        }
+    }
+
+    #[must_use]
+    pub fn is_terminal(&self) -> bool {
+        self.category() == StatusCategory::Terminal
+    }
+
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.category() == StatusCategory::Active
+    }
+
+    /// Returns true if this is a built-in (non-custom) status.
     #[must_use]
-    pub const fn is_terminal(&self) -> bool {
-        matches!(self, Self::Closed | Self::Tombstone)
+    pub const fn is_builtin(&self) -> bool {
+        !matches!(self, Self::Custom(_))
+    }
+
+    /// Names of every known status (built-in and registered) in a category.
+    #[must_use]
+    pub fn names_in_category(category: StatusCategory) -> Vec<String> {
+        let builtin = [
+            Self::Open,
+            Self::InProgress,
+            Self::Blocked,
+            Self::Deferred,
+            Self::Closed,
+            Self::Tombstone,
+            Self::Pinned,
+        ];
+        let mut names: Vec<String> = builtin
+            .iter()
+            .filter(|status| status.category() == category)
+            .map(|status| status.as_str().to_string())
+            .collect();
+        names.extend(
+            custom_statuses()
+                .into_iter()
+                .filter(|(_, c)| *c == category)
+                .map(|(name, _)| name),
+        );
+        names
     }
+}
+
+/// Workflow category of a status.
+///
+/// The category decides how a status behaves in ready/blocked computation:
+/// active issues are workable, waiting issues are open but not ready, and
+/// terminal issues no longer block anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusCategory {
+    Active,
+    Waiting,
+    Terminal,
+}
 
+impl StatusCategory {
     #[must_use]
-    pub const fn is_active(&self) -> bool {
-        matches!(self, Self::Open | Self::InProgress)
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Waiting => "waiting",
+            Self::Terminal => "terminal",
+        }
+    }
+}
+
+impl fmt::Display for StatusCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for StatusCategory {
+    type Err = crate::error::BeadsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "active" => Ok(Self::Active),
+            "waiting" => Ok(Self::Waiting),
+            "terminal" => Ok(Self::Terminal),
+            other => Err(crate::error::BeadsError::Config(format!(
+                "invalid status category '{other}' (expected active, waiting or terminal)"
+            ))),
+        }
+    }
+}
+
+/// Custom statuses declared in config, keyed by lowercase name.
+static CUSTOM_STATUSES: LazyLock<RwLock<BTreeMap<String, StatusCategory>>> =
+    LazyLock::new(|| RwLock::new(BTreeMap::new()));
+
+/// Check that a custom status name is usable.
+///
+/// # Errors
+///
+/// Returns an error if the name shadows a built-in status or contains
+/// characters other than `a-z`, `0-9`, `_` and `-`.
+pub fn validate_custom_status_name(name: &str) -> crate::error::Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+    {
+        return Err(crate::error::BeadsError::Config(format!(
+            "invalid custom status name '{name}' (use a-z, 0-9, '_' or '-')"
+        )));
+    }
+    if builtin_status(name).is_some() {
+        return Err(crate::error::BeadsError::Config(format!(
+            "custom status '{name}' shadows a built-in status"
+        )));
+    }
+    Ok(())
+}
+
+/// Register a custom status so it parses and is categorized process-wide.
+///
+/// Registering an existing name updates its category.
+///
+/// # Errors
+///
+/// Returns an error if the name is invalid (see [`validate_custom_status_name`]).
+pub fn register_custom_status(name: &str, category: StatusCategory) -> crate::error::Result<()> {
+    let name = name.trim().to_lowercase();
+    validate_custom_status_name(&name)?;
+    CUSTOM_STATUSES
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(name, category);
+    Ok(())
+}
+
+/// All registered custom statuses with their categories, sorted by name.
+#[must_use]
+pub fn custom_statuses() -> Vec<(String, StatusCategory)> {
+    CUSTOM_STATUSES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .map(|(name, category)| (name.clone(), *category))
+        .collect()
+}
+
+fn custom_status_category(name: &str) -> Option<StatusCategory> {
+    CUSTOM_STATUSES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(name)
+        .copied()
+}
+
+fn builtin_status(name: &str) -> Option<Status> {
+    match name {
+        "open" => Some(Status::Open),
+        "in_progress" | "inprogress" => Some(Status::InProgress),
+        "blocked" => Some(Status::Blocked),
+        "deferred" => Some(Status::Deferred),
+        "closed" => Some(Status::Closed),
+        "tombstone" => Some(Status::Tombstone),
+        "pinned" => Some(Status::Pinned),
+        _ => None,
     }
 }
 
@@ -82,18 +252,14 @@ impl FromStr for Status {
     type Err = crate::error::BeadsError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "open" => Ok(Self::Open),
-            "in_progress" | "inprogress" => Ok(Self::InProgress),
-            "blocked" => Ok(Self::Blocked),
-            "deferred" => Ok(Self::Deferred),
-            "closed" => Ok(Self::Closed),
-            "tombstone" => Ok(Self::Tombstone),
-            "pinned" => Ok(Self::Pinned),
-            other => Err(crate::error::BeadsError::InvalidStatus {
-                status: other.to_string(),
-            }),
+        let lower = s.to_lowercase();
+        if let Some(status) = builtin_status(&lower) {
+            return Ok(status);
         }
+        if custom_status_category(&lower).is_some() {
+            return Ok(Self::Custom(lower));
+        }
+        Err(crate::error::BeadsError::InvalidStatus { status: lower })
     }
 }
 
@@ -500,6 +666,10 @@ pub struct Issue {
     pub dependencies: Vec<Dependency>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub comments: Vec<Comment>,
+
+    /// Org-mode content with no issue field, kept so hand edits survive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org_extras: Option<OrgExtras>,
 }
 
 impl Default for Issue {
@@ -544,6 +714,7 @@ impl Default for Issue {
             labels: Vec::new(),
             dependencies: Vec::new(),
             comments: Vec::new(),
+            org_extras: None,
         }
     }
 }
@@ -635,6 +806,78 @@ pub struct Comment {
     pub created_at: DateTime<Utc>,
 }
 
+/// Org-mode content of an issue heading that has no issue field.
+///
+/// Emacs users edit `issues.org` directly; what they add there (clocked
+/// time, their own properties, drawers and subheadings) is carried through
+/// import and re-export instead of being dropped.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct OrgExtras {
+    /// `:LOGBOOK:` lines (`CLOCK:` entries, state notes), in file order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logbook: Vec<String>,
+    /// Property drawer entries with no issue field, in file order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<(String, String)>,
+    /// Other drawers before the description, verbatim.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drawers: Option<String>,
+    /// Subheadings other than the issue sections, verbatim.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtrees: Option<String>,
+}
+
+impl OrgExtras {
+    /// Whether there is nothing to keep.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.logbook.is_empty()
+            && self.properties.is_empty()
+            && self.drawers.is_none()
+            && self.subtrees.is_none()
+    }
+
+    /// Total clocked time in minutes, summed over closed `CLOCK:` entries.
+    ///
+    /// Uses the `=> H:MM` total Org writes, falling back to the interval
+    /// itself; running clocks count as zero.
+    #[must_use]
+    pub fn clocked_minutes(&self) -> i64 {
+        self.logbook
+            .iter()
+            .filter_map(|line| line.trim().strip_prefix("CLOCK:"))
+            .filter_map(clock_entry_minutes)
+            .sum()
+    }
+}
+
+fn clock_entry_minutes(entry: &str) -> Option<i64> {
+    let (range, total) = entry
+        .split_once("=>")
+        .map_or((entry, None), |(range, total)| (range, Some(total)));
+    let total = total.and_then(|total| {
+        let (hours, minutes) = total.trim().split_once(':')?;
+        Some(hours.parse::<i64>().ok()? * 60 + minutes.parse::<i64>().ok()?)
+    });
+    total.or_else(|| {
+        let (start, end) = range.trim().split_once("--")?;
+        Some(
+            (parse_clock_stamp(end)? - parse_clock_stamp(start)?)
+                .num_minutes()
+                .max(0),
+        )
+    })
+}
+
+/// Parse an inactive Org timestamp such as `[2026-01-05 Mon 09:00]`.
+fn parse_clock_stamp(stamp: &str) -> Option<chrono::NaiveDateTime> {
+    let inner = stamp.trim().strip_prefix('[')?.strip_suffix(']')?;
+    let mut parts = inner.split_whitespace();
+    let date = parts.next()?;
+    let time = parts.last()?;
+    chrono::NaiveDateTime::parse_from_str(&format!("{date} {time}"), "%Y-%m-%d %H:%M").ok()
+}
+
 /// An event in the issue's history (audit log).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 pub struct Event {
@@ -649,6 +892,12 @@ pub struct Event {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// Issue field this event changed, when the change is replayable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    /// Operation (one CLI invocation) that recorded this event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub op_id: Option<String>,
 }
 
 #[cfg(test)]
@@ -731,6 +980,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         };
 
         let json = serde_json::to_string(&issue).unwrap();
@@ -865,6 +1115,61 @@ mod tests {
         assert!(!Status::Custom("custom".to_string()).is_active());
     }
 
+    #[test]
+    fn test_status_category_builtin() {
+        assert_eq!(Status::Open.category(), StatusCategory::Active);
+        assert_eq!(Status::InProgress.category(), StatusCategory::Active);
+        assert_eq!(Status::Blocked.category(), StatusCategory::Waiting);
+        assert_eq!(Status::Deferred.category(), StatusCategory::Waiting);
+        assert_eq!(Status::Pinned.category(), StatusCategory::Waiting);
+        assert_eq!(Status::Closed.category(), StatusCategory::Terminal);
+        assert_eq!(Status::Tombstone.category(), StatusCategory::Terminal);
+        assert_eq!(
+            Status::Custom("unregistered".to_string()).category(),
+            StatusCategory::Waiting
+        );
+    }
+
+    #[test]
+    fn test_registered_custom_status_parses_and_categorizes() {
+        register_custom_status("model_test_review", StatusCategory::Active).unwrap();
+        register_custom_status("model_test_wontfix", StatusCategory::Terminal).unwrap();
+
+        let review = Status::from_str("Model_Test_Review").unwrap();
+        assert_eq!(review, Status::Custom("model_test_review".to_string()));
+        assert!(review.is_active());
+        assert!(!review.is_terminal());
+
+        let wontfix = Status::from_str("model_test_wontfix").unwrap();
+        assert!(wontfix.is_terminal());
+        assert!(
+            Status::names_in_category(StatusCategory::Terminal)
+                .contains(&"model_test_wontfix".to_string())
+        );
+        assert!(Status::names_in_category(StatusCategory::Active).contains(&"open".to_string()));
+    }
+
+    #[test]
+    fn test_register_custom_status_rejects_bad_names() {
+        assert!(register_custom_status("closed", StatusCategory::Terminal).is_err());
+        assert!(register_custom_status("in review", StatusCategory::Active).is_err());
+        assert!(register_custom_status("qa'; --", StatusCategory::Waiting).is_err());
+        assert!(register_custom_status("", StatusCategory::Waiting).is_err());
+    }
+
+    #[test]
+    fn test_status_category_from_str() {
+        assert_eq!(
+            StatusCategory::from_str("Active").unwrap(),
+            StatusCategory::Active
+        );
+        assert_eq!(
+            StatusCategory::from_str("terminal").unwrap(),
+            StatusCategory::Terminal
+        );
+        assert!(StatusCategory::from_str("done").is_err());
+    }
+
     #[test]
     fn test_status_as_str() {
         assert_eq!(Status::Open.as_str(), "open");
@@ -1186,6 +1491,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         }
     }
 
@@ -1465,6 +1771,8 @@ mod tests {
             new_value: Some("closed".to_string()),
             comment: None,
             created_at: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+            field: Some("status".to_string()),
+            op_id: Some("op-1".to_string()),
         };
 
         let json = serde_json::to_string(&event).unwrap();
@@ -1491,4 +1799,24 @@ mod tests {
         assert!(json.contains("\"closed_children\":7"));
         assert!(json.contains("\"eligible_for_close\":false"));
     }
+
+    // ========================================================================
+    // ORG EXTRAS TESTS
+    // ========================================================================
+
+    #[test]
+    fn test_org_extras_clocked_minutes() {
+        let extras = OrgExtras {
+            logbook: vec![
+                "CLOCK: [2026-01-05 Mon 09:00]--[2026-01-05 Mon 10:30] =>  1:30".to_string(),
+                "CLOCK: [2026-01-06 Tue 14:00]--[2026-01-06 Tue 14:45]".to_string(),
+                "CLOCK: [2026-01-07 Wed 08:00]".to_string(),
+                "- State \"DONE\"       from \"TODO\"       [2026-01-07 Wed 09:00]".to_string(),
+            ],
+            ..OrgExtras::default()
+        };
+        assert_eq!(extras.clocked_minutes(), 135);
+        assert!(!extras.is_empty());
+        assert!(OrgExtras::default().is_empty());
+    }
 }
@@ -1,6 +1,8 @@
 use crate::format::{IssueDetails, IssueWithDependencyMetadata};
-use crate::model::{Comment, Dependency, Issue};
+use crate::model::{Comment, Dependency, Issue, OrgExtras};
 use crate::output::{OutputContext, Theme};
+use crate::util::time::format_duration;
+use chrono::Duration;
 use rich_rust::prelude::*;
 
 /// Renders a single issue with full details in a styled panel.
@@ -125,6 +127,30 @@ impl<'a> IssuePanel<'a> {
             self.theme.timestamp.clone(),
         );
 
+        let spent = self
+            .issue
+            .org_extras
+            .as_ref()
+            .map_or(0, OrgExtras::clocked_minutes);
+        if spent > 0 {
+            content.append_styled("Spent:    ", self.theme.dimmed.clone());
+            content.append_styled(
+                &format!("{}\n", format_duration(Duration::minutes(spent))),
+                self.theme.timestamp.clone(),
+            );
+        }
+
+        if let Some(level) = self.issue.compaction_level.filter(|level| *level > 0) {
+            content.append_styled("Compacted: ", self.theme.dimmed.clone());
+            content.append_styled(
+                &format!(
+                    "level {level} (original in .beads/history/{}.jsonl)\n",
+                    self.issue.id
+                ),
+                self.theme.muted.clone(),
+            );
+        }
+
         // Dependencies / Dependents
         if self.show_dependencies {
             if let Some(details) = self.details {
@@ -0,0 +1,283 @@
+//! All-or-nothing batches of issue mutations (`batch`).
+//!
+//! A batch is a list of resolved [`BatchStep`]s applied inside one
+//! mutation: one transaction, one operation for `undo`. Each step sees the
+//! effects of the steps before it, including a blocked cache rebuilt on
+//! demand, so a batch can create an issue, link it and close its blocker.
+
+use crate::error::{BeadsError, Result};
+use crate::model::{DependencyType, Issue, Status};
+use crate::storage::sqlite::{IssueUpdate, MutationContext, SqliteStorage};
+use chrono::Utc;
+use rusqlite::Transaction;
+use serde::Serialize;
+
+/// One mutation with every ID already resolved.
+#[derive(Debug, Clone)]
+pub enum BatchStep {
+    Create(Box<Issue>),
+    Update {
+        id: String,
+        update: Box<IssueUpdate>,
+        /// Allow moving a blocked issue to `in_progress`.
+        force: bool,
+    },
+    DepAdd {
+        id: String,
+        depends_on: String,
+        dep_type: DependencyType,
+    },
+    Label {
+        id: String,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    Comment {
+        id: String,
+        author: String,
+        text: String,
+    },
+    Close {
+        id: String,
+        reason: String,
+        session: Option<String>,
+        /// Close even if open blockers remain.
+        force: bool,
+    },
+}
+
+impl BatchStep {
+    /// The issue the step creates or changes.
+    #[must_use]
+    pub fn issue_id(&self) -> &str {
+        match self {
+            Self::Create(issue) => &issue.id,
+            Self::Update { id, .. }
+            | Self::DepAdd { id, .. }
+            | Self::Label { id, .. }
+            | Self::Comment { id, .. }
+            | Self::Close { id, .. } => id,
+        }
+    }
+}
+
+/// What one step did.
+#[derive(Debug, Clone, Serialize)]
+pub struct StepOutcome {
+    pub id: String,
+    /// False when the step was already satisfied (existing label or
+    /// dependency, issue already closed).
+    pub changed: bool,
+}
+
+/// Why a batch was rolled back.
+#[derive(Debug)]
+pub struct BatchFailure {
+    /// Index of the failing step; `None` if the commit itself failed.
+    pub step: Option<usize>,
+    pub error: BeadsError,
+}
+
+/// Apply one step inside an open mutation.
+///
+/// `check_transition` vets every status change against the caller's
+/// workflow before it is written.
+///
+/// # Errors
+///
+/// Returns an error if an issue is missing, a transition is refused, a
+/// blocked issue would be started or closed without `force`, or a write
+/// fails.
+pub fn apply_in_tx(
+    tx: &Transaction<'_>,
+    ctx: &mut MutationContext,
+    step: &BatchStep,
+    check_transition: &impl Fn(&Issue, &Status) -> Result<()>,
+) -> Result<StepOutcome> {
+    let actor = ctx.actor.clone();
+    let changed = match step {
+        BatchStep::Create(issue) => {
+            SqliteStorage::create_issue_in_tx(tx, ctx, issue, &actor)?;
+            true
+        }
+        BatchStep::Update { id, update, force } => {
+            let issue = existing(tx, id)?;
+            if let Some(status) = &update.status {
+                check_transition(&issue, status)?;
+                if *status == Status::InProgress && !force {
+                    refuse_if_blocked(tx, ctx, id, "claim", "cannot claim blocked issue")?;
+                }
+            }
+            SqliteStorage::update_issue_in_tx(tx, ctx, id, update)?;
+            !update.is_empty()
+        }
+        BatchStep::DepAdd {
+            id,
+            depends_on,
+            dep_type,
+        } => {
+            if id == depends_on {
+                return Err(BeadsError::SelfDependency { id: id.clone() });
+            }
+            existing(tx, id)?;
+            if SqliteStorage::fetch_issue(tx, depends_on)?.is_none() {
+                return Err(BeadsError::DependencyNotFound {
+                    id: depends_on.clone(),
+                });
+            }
+            SqliteStorage::add_dependency_in_tx(tx, ctx, id, depends_on, dep_type.as_str(), &actor)?
+        }
+        BatchStep::Label { id, add, remove } => {
+            existing(tx, id)?;
+            let mut changed = false;
+            for label in remove {
+                changed |= SqliteStorage::remove_label_in_tx(tx, ctx, id, label)?;
+            }
+            for label in add {
+                changed |= SqliteStorage::add_label_in_tx(tx, ctx, id, label)?;
+            }
+            changed
+        }
+        BatchStep::Comment { id, author, text } => {
+            existing(tx, id)?;
+            SqliteStorage::add_comment_in_tx(tx, ctx, id, author, text)?;
+            true
+        }
+        BatchStep::Close {
+            id,
+            reason,
+            session,
+            force,
+        } => {
+            let issue = existing(tx, id)?;
+            if issue.status.is_terminal() {
+                false
+            } else {
+                check_transition(&issue, &Status::Closed)?;
+                if !force {
+                    refuse_if_blocked(tx, ctx, id, "close", "cannot close blocked issue")?;
+                }
+                let update = IssueUpdate {
+                    status: Some(Status::Closed),
+                    closed_at: Some(Some(Utc::now())),
+                    close_reason: Some(Some(reason.clone())),
+                    closed_by_session: session.clone().map(Some),
+                    ..IssueUpdate::default()
+                };
+                SqliteStorage::update_issue_in_tx(tx, ctx, id, &update)?;
+                true
+            }
+        }
+    };
+    Ok(StepOutcome {
+        id: step.issue_id().to_string(),
+        changed,
+    })
+}
+
+fn existing(tx: &Transaction<'_>, id: &str) -> Result<Issue> {
+    SqliteStorage::fetch_issue(tx, id)?
+        .ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() })
+}
+
+fn refuse_if_blocked(
+    tx: &Transaction<'_>,
+    ctx: &mut MutationContext,
+    id: &str,
+    field: &str,
+    message: &str,
+) -> Result<()> {
+    let blockers = SqliteStorage::blockers_in_tx(tx, ctx, id)?;
+    if blockers.is_empty() {
+        return Ok(());
+    }
+    Err(BeadsError::validation(
+        field,
+        format!("{message}: {}", blockers.join(", ")),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::SqliteStorage;
+
+    fn issue(id: &str) -> BatchStep {
+        BatchStep::Create(Box::new(Issue {
+            id: id.to_string(),
+            title: format!("Issue {id}"),
+            ..Issue::default()
+        }))
+    }
+
+    fn allow_all(_: &Issue, _: &Status) -> Result<()> {
+        Ok(())
+    }
+
+    #[test]
+    fn batch_applies_in_order() {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let steps = vec![
+            issue("bd-1"),
+            issue("bd-2"),
+            BatchStep::DepAdd {
+                id: "bd-2".to_string(),
+                depends_on: "bd-1".to_string(),
+                dep_type: DependencyType::Blocks,
+            },
+            BatchStep::Close {
+                id: "bd-1".to_string(),
+                reason: "done".to_string(),
+                session: None,
+                force: false,
+            },
+            BatchStep::Update {
+                id: "bd-2".to_string(),
+                update: Box::new(IssueUpdate {
+                    status: Some(Status::InProgress),
+                    ..IssueUpdate::default()
+                }),
+                force: false,
+            },
+        ];
+
+        let outcomes = storage.apply_batch(&steps, "tester", allow_all).unwrap();
+        assert_eq!(outcomes.len(), 5);
+        assert!(outcomes.iter().all(|outcome| outcome.changed));
+        let second = storage.get_issue("bd-2").unwrap().unwrap();
+        assert_eq!(second.status, Status::InProgress);
+        assert_eq!(storage.get_dependencies("bd-2").unwrap(), vec!["bd-1"]);
+        assert_eq!(storage.undoable_operations(10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn failing_step_rolls_back_everything() {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let steps = vec![
+            issue("bd-1"),
+            issue("bd-2"),
+            BatchStep::DepAdd {
+                id: "bd-2".to_string(),
+                depends_on: "bd-1".to_string(),
+                dep_type: DependencyType::Blocks,
+            },
+            BatchStep::Close {
+                id: "bd-2".to_string(),
+                reason: "done".to_string(),
+                session: None,
+                force: false,
+            },
+        ];
+
+        let failure = storage
+            .apply_batch(&steps, "tester", allow_all)
+            .unwrap_err();
+        assert_eq!(failure.step, Some(3));
+        assert!(
+            failure.error.to_string().contains("bd-1"),
+            "{}",
+            failure.error
+        );
+        assert_eq!(storage.count_issues().unwrap(), 0);
+    }
+}
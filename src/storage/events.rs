@@ -26,6 +26,8 @@ CREATE TABLE IF NOT EXISTS events (
     new_value TEXT,
     comment TEXT,
     created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    op_id TEXT,
+    field TEXT,
     FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
 );
 
@@ -355,7 +357,8 @@ pub fn insert_restored_event(
 pub fn get_events(conn: &Connection, issue_id: &str, limit: usize) -> Result<Vec<Event>> {
     let query = if limit > 0 {
         r"
-            SELECT id, issue_id, event_type, actor, old_value, new_value, comment, created_at
+            SELECT id, issue_id, event_type, actor, old_value, new_value, comment, created_at,
+                   field, op_id
             FROM events
             WHERE issue_id = ?1
             ORDER BY created_at DESC, id DESC
@@ -363,7 +366,8 @@ pub fn get_events(conn: &Connection, issue_id: &str, limit: usize) -> Result<Vec
             "
     } else {
         r"
-            SELECT id, issue_id, event_type, actor, old_value, new_value, comment, created_at
+            SELECT id, issue_id, event_type, actor, old_value, new_value, comment, created_at,
+                   field, op_id
             FROM events
             WHERE issue_id = ?1
             ORDER BY created_at DESC, id DESC
@@ -391,6 +395,8 @@ fn event_from_row(row: &rusqlite::Row) -> rusqlite::Result<Event> {
     let new_value: Option<String> = row.get(5)?;
     let comment: Option<String> = row.get(6)?;
     let created_at_str: String = row.get(7)?;
+    let field: Option<String> = row.get(8)?;
+    let op_id: Option<String> = row.get(9)?;
 
     // Parse event type
     let event_type = parse_event_type(&event_type_str);
@@ -407,10 +413,12 @@ fn event_from_row(row: &rusqlite::Row) -> rusqlite::Result<Event> {
         new_value,
         comment,
         created_at,
+        field,
+        op_id,
     })
 }
 
-fn parse_event_timestamp(value: &str) -> DateTime<Utc> {
+pub(crate) fn parse_event_timestamp(value: &str) -> DateTime<Utc> {
     if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
         return dt.with_timezone(&Utc);
     }
@@ -422,6 +430,60 @@ fn parse_event_timestamp(value: &str) -> DateTime<Utc> {
     Utc::now()
 }
 
+/// Get the events recorded by one operation, in insertion order.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn get_operation_events(conn: &Connection, op_id: &str) -> Result<Vec<Event>> {
+    let mut stmt = conn.prepare(
+        r"
+        SELECT id, issue_id, event_type, actor, old_value, new_value, comment, created_at,
+               field, op_id
+        FROM events
+        WHERE op_id = ?1
+        ORDER BY id ASC
+        ",
+    )?;
+    let events = stmt
+        .query_map(params![op_id], event_from_row)?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(events)
+}
+
+/// Get `dependencies` events on other issues whose link may point at
+/// `depends_on_id`, newest first.
+///
+/// The match is a substring test on the encoded link, so callers must
+/// decode the value and compare the target exactly.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn get_dependency_events_targeting(
+    conn: &Connection,
+    depends_on_id: &str,
+) -> Result<Vec<Event>> {
+    let pattern = format!(
+        "%\"depends_on_id\":{}%",
+        serde_json::to_string(depends_on_id)?
+    );
+    let mut stmt = conn.prepare(
+        r"
+        SELECT id, issue_id, event_type, actor, old_value, new_value, comment, created_at,
+               field, op_id
+        FROM events
+        WHERE field = 'dependencies' AND issue_id != ?1
+          AND (old_value LIKE ?2 OR new_value LIKE ?2)
+        ORDER BY created_at DESC, id DESC
+        ",
+    )?;
+    let events = stmt
+        .query_map(params![depends_on_id, pattern], event_from_row)?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(events)
+}
+
 /// Get all events across all issues, ordered by `created_at` DESC.
 ///
 /// Useful for audit trails and debugging.
@@ -432,14 +494,16 @@ fn parse_event_timestamp(value: &str) -> DateTime<Utc> {
 pub fn get_all_events(conn: &Connection, limit: usize) -> Result<Vec<Event>> {
     let query = if limit > 0 {
         r"
-            SELECT id, issue_id, event_type, actor, old_value, new_value, comment, created_at
+            SELECT id, issue_id, event_type, actor, old_value, new_value, comment, created_at,
+                   field, op_id
             FROM events
             ORDER BY created_at DESC, id DESC
             LIMIT ?1
             "
     } else {
         r"
-            SELECT id, issue_id, event_type, actor, old_value, new_value, comment, created_at
+            SELECT id, issue_id, event_type, actor, old_value, new_value, comment, created_at,
+                   field, op_id
             FROM events
             ORDER BY created_at DESC, id DESC
             "
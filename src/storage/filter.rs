@@ -0,0 +1,780 @@
+//! Filter expression language for issue queries.
+//!
+//! A filter expression combines field predicates with `AND`, `OR`, `NOT`
+//! and parentheses, for example:
+//!
+//! ```text
+//! status:open AND (label:backend OR priority<=1) AND updated>-7d AND NOT assignee:bot
+//! ```
+//!
+//! Adjacent predicates without an operator are joined with `AND`. Keywords are
+//! case-insensitive. Values containing spaces or `)` must be double-quoted.
+//!
+//! | Field | Operators | Values |
+//! |-------|-----------|--------|
+//! | `status` | `:` `=` `!=` | status name |
+//! | `type` | `:` `=` `!=` | issue type |
+//! | `priority` | all | `0`-`4` or `P0`-`P4` |
+//! | `assignee`, `owner` | `:` `=` `!=` | exact name, or `none` |
+//! | `label` | `:` `=` `!=` | label name, or `none` |
+//! | `id` | `:` `=` `!=` | exact ID, or a prefix ending in `*` |
+//! | `title`, `description`, `design`, `acceptance`, `notes` | `:` `=` `!=` | `:` is a case-insensitive substring match |
+//! | `created`, `updated`, `closed`, `due`, `defer` | all | `YYYY-MM-DD`, RFC3339, `-7d`/`+2w`/`-12h`, `tomorrow`, or `none` |
+//!
+//! Expressions compile to parameterized SQL over the `issues` table; values
+//! are never interpolated into the SQL text. Every compiled predicate is
+//! two-valued (never `NULL`), so `NOT` behaves as expected on optional
+//! columns: `NOT assignee:bot` includes unassigned issues.
+
+use crate::error::{BeadsError, Result};
+use crate::model::{IssueType, Priority, Status};
+use crate::util::time::parse_flexible_timestamp;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::fmt::Write as _;
+
+use super::sqlite::escape_like_pattern;
+
+/// A parsed filter expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterExpr {
+    source: String,
+    root: Node,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    And(Vec<Node>),
+    Or(Vec<Node>),
+    Not(Box<Node>),
+    Predicate(Predicate),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Predicate {
+    field: Field,
+    op: CmpOp,
+    operand: Operand,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Status,
+    Type,
+    Priority,
+    Assignee,
+    Owner,
+    Label,
+    Id,
+    Title,
+    Description,
+    Design,
+    Acceptance,
+    Notes,
+    Created,
+    Updated,
+    Closed,
+    Due,
+    Defer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    Enum,
+    Ordered,
+    Name,
+    Label,
+    Id,
+    Text,
+    Date,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    /// `:` - field-specific match (equality, substring, or has-label).
+    Match,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Text(String),
+    Prefix(String),
+    Priority(i32),
+    Day(NaiveDate),
+    Instant(DateTime<Utc>),
+    None,
+}
+
+/// Field names accepted in expressions, used in error messages.
+const FIELD_NAMES: &str = "status, type, priority, assignee, owner, label, id, title, \
+                           description, design, acceptance, notes, created, updated, closed, due, defer";
+
+impl Field {
+    fn from_name(name: &str) -> Option<Self> {
+        let field = match name.to_lowercase().as_str() {
+            "status" => Self::Status,
+            "type" | "issue_type" => Self::Type,
+            "priority" | "p" => Self::Priority,
+            "assignee" => Self::Assignee,
+            "owner" => Self::Owner,
+            "label" | "labels" => Self::Label,
+            "id" => Self::Id,
+            "title" => Self::Title,
+            "description" | "desc" => Self::Description,
+            "design" => Self::Design,
+            "acceptance" | "acceptance_criteria" => Self::Acceptance,
+            "notes" => Self::Notes,
+            "created" | "created_at" => Self::Created,
+            "updated" | "updated_at" => Self::Updated,
+            "closed" | "closed_at" => Self::Closed,
+            "due" | "due_at" => Self::Due,
+            "defer" | "defer_until" => Self::Defer,
+            _ => return None,
+        };
+        Some(field)
+    }
+
+    const fn kind(self) -> FieldKind {
+        match self {
+            Self::Status | Self::Type => FieldKind::Enum,
+            Self::Priority => FieldKind::Ordered,
+            Self::Assignee | Self::Owner => FieldKind::Name,
+            Self::Label => FieldKind::Label,
+            Self::Id => FieldKind::Id,
+            Self::Title | Self::Description | Self::Design | Self::Acceptance | Self::Notes => {
+                FieldKind::Text
+            }
+            Self::Created | Self::Updated | Self::Closed | Self::Due | Self::Defer => {
+                FieldKind::Date
+            }
+        }
+    }
+
+    const fn column(self) -> &'static str {
+        match self {
+            Self::Status => "status",
+            Self::Type => "issue_type",
+            Self::Priority => "priority",
+            Self::Assignee => "assignee",
+            Self::Owner => "owner",
+            Self::Label => "label",
+            Self::Id => "issues.id",
+            Self::Title => "title",
+            Self::Description => "description",
+            Self::Design => "design",
+            Self::Acceptance => "acceptance_criteria",
+            Self::Notes => "notes",
+            Self::Created => "created_at",
+            Self::Updated => "updated_at",
+            Self::Closed => "closed_at",
+            Self::Due => "due_at",
+            Self::Defer => "defer_until",
+        }
+    }
+
+    /// Convert a raw value into an operand, or explain why it is invalid.
+    fn operand(self, op: CmpOp, raw: &str) -> std::result::Result<Operand, String> {
+        let is_none = raw.eq_ignore_ascii_case("none");
+        match self.kind() {
+            FieldKind::Enum => {
+                let value = if self == Self::Status {
+                    raw.parse::<Status>()
+                        .map_err(|e| e.to_string())?
+                        .as_str()
+                        .to_string()
+                } else {
+                    raw.parse::<IssueType>()
+                        .map_err(|e| e.to_string())?
+                        .as_str()
+                        .to_string()
+                };
+                Ok(Operand::Text(value))
+            }
+            FieldKind::Ordered => raw
+                .parse::<Priority>()
+                .map(|p| Operand::Priority(p.0))
+                .map_err(|_| format!("invalid priority '{raw}' (use 0-4 or P0-P4)")),
+            FieldKind::Name | FieldKind::Label if is_none => Ok(Operand::None),
+            FieldKind::Name | FieldKind::Label | FieldKind::Text => {
+                Ok(Operand::Text(raw.to_string()))
+            }
+            FieldKind::Id => match raw.strip_suffix('*') {
+                Some(prefix) if op != CmpOp::Ne => Ok(Operand::Prefix(prefix.to_string())),
+                _ => Ok(Operand::Text(raw.to_string())),
+            },
+            FieldKind::Date if is_none => {
+                if matches!(op, CmpOp::Match | CmpOp::Eq | CmpOp::Ne) {
+                    Ok(Operand::None)
+                } else {
+                    Err("'none' can only be compared with ':', '=' or '!='".to_string())
+                }
+            }
+            FieldKind::Date => {
+                if let Ok(day) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+                    return Ok(Operand::Day(day));
+                }
+                parse_flexible_timestamp(raw, "where")
+                    .map(Operand::Instant)
+                    .map_err(|_| {
+                        format!(
+                            "invalid date '{raw}' (try 2025-01-15, -7d, +2w, tomorrow, or RFC3339)"
+                        )
+                    })
+            }
+        }
+    }
+
+    const fn supports(self, op: CmpOp) -> bool {
+        match op {
+            CmpOp::Match | CmpOp::Eq | CmpOp::Ne => true,
+            CmpOp::Lt | CmpOp::Le | CmpOp::Gt | CmpOp::Ge => {
+                matches!(self.kind(), FieldKind::Ordered | FieldKind::Date)
+            }
+        }
+    }
+}
+
+impl CmpOp {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Match => ":",
+            Self::Eq => "=",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        }
+    }
+
+    /// SQL comparison operator (`:` compares for equality).
+    const fn sql(self) -> &'static str {
+        match self {
+            Self::Match | Self::Eq => "=",
+            other => other.as_str(),
+        }
+    }
+}
+
+impl FilterExpr {
+    /// Parse a filter expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BeadsError::FilterParse` with the 1-based column of the
+    /// offending token if the expression is malformed.
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut parser = Parser::new(input);
+        parser.skip_ws();
+        if parser.at_end() {
+            return Err(parser.error(parser.pos, "expression is empty"));
+        }
+        let root = parser.parse_or()?;
+        parser.skip_ws();
+        if !parser.at_end() {
+            let reason = if parser.peek() == Some(')') {
+                "unexpected ')'"
+            } else {
+                "unexpected input"
+            };
+            return Err(parser.error(parser.pos, reason));
+        }
+        Ok(Self {
+            source: input.to_string(),
+            root,
+        })
+    }
+
+    /// The expression text as originally written.
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether any predicate constrains `status`.
+    ///
+    /// Callers use this to lift their default closed/deferred exclusion so an
+    /// expression such as `status:closed` can match.
+    #[must_use]
+    pub fn references_status(&self) -> bool {
+        fn walk(node: &Node) -> bool {
+            match node {
+                Node::And(nodes) | Node::Or(nodes) => nodes.iter().any(walk),
+                Node::Not(inner) => walk(inner),
+                Node::Predicate(p) => p.field == Field::Status,
+            }
+        }
+        walk(&self.root)
+    }
+
+    /// Append the expression as ` AND (...)` to a query over `issues`.
+    pub(crate) fn push_sql(&self, sql: &mut String, params: &mut Vec<Box<dyn rusqlite::ToSql>>) {
+        sql.push_str(" AND ");
+        write_node(&self.root, sql, params);
+    }
+}
+
+fn write_node(node: &Node, sql: &mut String, params: &mut Vec<Box<dyn rusqlite::ToSql>>) {
+    match node {
+        Node::And(nodes) | Node::Or(nodes) => {
+            let joiner = if matches!(node, Node::And(_)) {
+                " AND "
+            } else {
+                " OR "
+            };
+            sql.push('(');
+            for (i, child) in nodes.iter().enumerate() {
+                if i > 0 {
+                    sql.push_str(joiner);
+                }
+                write_node(child, sql, params);
+            }
+            sql.push(')');
+        }
+        Node::Not(inner) => {
+            sql.push_str("NOT ");
+            write_node(inner, sql, params);
+        }
+        Node::Predicate(predicate) => write_predicate(predicate, sql, params),
+    }
+}
+
+fn write_predicate(
+    predicate: &Predicate,
+    sql: &mut String,
+    params: &mut Vec<Box<dyn rusqlite::ToSql>>,
+) {
+    let Predicate { field, op, operand } = predicate;
+    let column = field.column();
+    let negate = *op == CmpOp::Ne;
+
+    match (field.kind(), operand) {
+        (FieldKind::Label, Operand::None) => {
+            let not = if negate { "" } else { "NOT " };
+            let _ = write!(
+                sql,
+                "({not}EXISTS (SELECT 1 FROM labels WHERE labels.issue_id = issues.id))"
+            );
+        }
+        (FieldKind::Label, Operand::Text(label)) => {
+            let not = if negate { "NOT " } else { "" };
+            let _ = write!(
+                sql,
+                "({not}EXISTS (SELECT 1 FROM labels WHERE labels.issue_id = issues.id AND labels.label = ?))"
+            );
+            params.push(Box::new(label.clone()));
+        }
+        (FieldKind::Name, Operand::None) => {
+            if negate {
+                let _ = write!(sql, "({column} IS NOT NULL AND {column} != '')");
+            } else {
+                let _ = write!(sql, "({column} IS NULL OR {column} = '')");
+            }
+        }
+        (FieldKind::Name, Operand::Text(value)) => {
+            let is = if negate { "IS NOT" } else { "IS" };
+            let _ = write!(sql, "({column} {is} ?)");
+            params.push(Box::new(value.clone()));
+        }
+        (FieldKind::Id, Operand::Prefix(prefix)) => {
+            let _ = write!(sql, "({column} LIKE ? ESCAPE '\\')");
+            params.push(Box::new(format!("{}%", escape_like_pattern(prefix))));
+        }
+        (FieldKind::Text, Operand::Text(value)) if *op == CmpOp::Match => {
+            let _ = write!(sql, "(COALESCE({column}, '') LIKE ? ESCAPE '\\')");
+            params.push(Box::new(format!("%{}%", escape_like_pattern(value))));
+        }
+        (FieldKind::Text, Operand::Text(value)) => {
+            let _ = write!(sql, "(COALESCE({column}, '') {} ?)", op.sql());
+            params.push(Box::new(value.clone()));
+        }
+        (FieldKind::Date, Operand::None) => {
+            let is = if negate { "IS NOT" } else { "IS" };
+            let _ = write!(sql, "({column} {is} NULL)");
+        }
+        (FieldKind::Date, Operand::Day(day)) => {
+            let _ = write!(
+                sql,
+                "({column} IS NOT NULL AND date({column}) {} ?)",
+                op.sql()
+            );
+            params.push(Box::new(day.format("%Y-%m-%d").to_string()));
+        }
+        (FieldKind::Date, Operand::Instant(ts)) => {
+            let _ = write!(
+                sql,
+                "({column} IS NOT NULL AND datetime({column}) {} datetime(?))",
+                op.sql()
+            );
+            params.push(Box::new(ts.to_rfc3339()));
+        }
+        (_, Operand::Priority(p)) => {
+            let _ = write!(sql, "({column} {} ?)", op.sql());
+            params.push(Box::new(*p));
+        }
+        (_, Operand::Text(value)) => {
+            let _ = write!(sql, "({column} {} ?)", op.sql());
+            params.push(Box::new(value.clone()));
+        }
+        // Remaining combinations are rejected by `Field::operand`.
+        _ => sql.push_str("(0)"),
+    }
+}
+
+/// Recursive-descent parser over the characters of an expression.
+struct Parser<'a> {
+    input: &'a str,
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            chars: input.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    fn error(&self, pos: usize, reason: impl Into<String>) -> BeadsError {
+        BeadsError::FilterParse {
+            expression: self.input.to_string(),
+            column: pos + 1,
+            reason: reason.into(),
+        }
+    }
+
+    const fn at_end(&self) -> bool {
+        self.pos >= self.chars.len()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consume `keyword` (case-insensitive) if it appears as a whole word.
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let end = self.pos + keyword.len();
+        if end > self.chars.len() {
+            return false;
+        }
+        let matches = self.chars[self.pos..end]
+            .iter()
+            .zip(keyword.chars())
+            .all(|(c, k)| c.eq_ignore_ascii_case(&k));
+        let boundary = self
+            .chars
+            .get(end)
+            .is_none_or(|c| c.is_whitespace() || *c == '(' || *c == ')');
+        if matches && boundary {
+            self.pos = end;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Node> {
+        let mut nodes = vec![self.parse_and()?];
+        loop {
+            self.skip_ws();
+            if !self.eat_keyword("OR") {
+                break;
+            }
+            nodes.push(self.parse_and()?);
+        }
+        Ok(if nodes.len() == 1 {
+            nodes.remove(0)
+        } else {
+            Node::Or(nodes)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Node> {
+        let mut nodes = vec![self.parse_unary()?];
+        loop {
+            self.skip_ws();
+            if self.at_end() || self.peek() == Some(')') {
+                break;
+            }
+            let before_or = self.pos;
+            if self.eat_keyword("OR") {
+                self.pos = before_or;
+                break;
+            }
+            self.eat_keyword("AND");
+            nodes.push(self.parse_unary()?);
+        }
+        Ok(if nodes.len() == 1 {
+            nodes.remove(0)
+        } else {
+            Node::And(nodes)
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<Node> {
+        self.skip_ws();
+        if self.eat_keyword("NOT") {
+            return Ok(Node::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Node> {
+        self.skip_ws();
+        match self.peek() {
+            None => Err(self.error(self.pos, "expected a condition")),
+            Some('(') => {
+                let open = self.pos;
+                self.pos += 1;
+                self.skip_ws();
+                if self.peek() == Some(')') {
+                    return Err(self.error(self.pos, "empty parentheses"));
+                }
+                let node = self.parse_or()?;
+                self.skip_ws();
+                if self.peek() == Some(')') {
+                    self.pos += 1;
+                    Ok(node)
+                } else if self.at_end() {
+                    Err(self.error(open, "unclosed '('"))
+                } else {
+                    Err(self.error(self.pos, "expected ')'"))
+                }
+            }
+            Some(')') => Err(self.error(self.pos, "unexpected ')'")),
+            Some(_) => self.parse_predicate(),
+        }
+    }
+
+    fn parse_predicate(&mut self) -> Result<Node> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(self.error(start, "expected a field name"));
+        }
+        let name: String = self.chars[start..self.pos].iter().collect();
+        let field = Field::from_name(&name).ok_or_else(|| {
+            self.error(
+                start,
+                format!("unknown field '{name}' (expected one of: {FIELD_NAMES})"),
+            )
+        })?;
+
+        let op_pos = self.pos;
+        let op = self.parse_op().ok_or_else(|| {
+            self.error(
+                op_pos,
+                format!("expected an operator after '{name}' (:, =, !=, <, <=, >, >=)"),
+            )
+        })?;
+        if !field.supports(op) {
+            return Err(self.error(
+                op_pos,
+                format!("operator '{}' is not supported for '{name}'", op.as_str()),
+            ));
+        }
+
+        let value_pos = self.pos;
+        let raw = self.parse_value()?;
+        if raw.is_empty() {
+            return Err(self.error(value_pos, format!("expected a value for '{name}'")));
+        }
+        let operand = field
+            .operand(op, &raw)
+            .map_err(|reason| self.error(value_pos, reason))?;
+
+        Ok(Node::Predicate(Predicate { field, op, operand }))
+    }
+
+    fn parse_op(&mut self) -> Option<CmpOp> {
+        let first = self.peek()?;
+        let second = self.chars.get(self.pos + 1).copied();
+        let (op, len) = match (first, second) {
+            ('!', Some('=')) => (CmpOp::Ne, 2),
+            ('<', Some('=')) => (CmpOp::Le, 2),
+            ('>', Some('=')) => (CmpOp::Ge, 2),
+            (':', _) => (CmpOp::Match, 1),
+            ('=', _) => (CmpOp::Eq, 1),
+            ('<', _) => (CmpOp::Lt, 1),
+            ('>', _) => (CmpOp::Gt, 1),
+            _ => return None,
+        };
+        self.pos += len;
+        Some(op)
+    }
+
+    fn parse_value(&mut self) -> Result<String> {
+        if self.peek() == Some('"') {
+            let open = self.pos;
+            self.pos += 1;
+            let mut value = String::new();
+            loop {
+                match self.peek() {
+                    None => return Err(self.error(open, "unterminated string")),
+                    Some('"') => {
+                        self.pos += 1;
+                        return Ok(value);
+                    }
+                    Some('\\') if self.chars.get(self.pos + 1).is_some() => {
+                        value.push(self.chars[self.pos + 1]);
+                        self.pos += 2;
+                    }
+                    Some(c) => {
+                        value.push(c);
+                        self.pos += 1;
+                    }
+                }
+            }
+        }
+
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| !c.is_whitespace() && c != '(' && c != ')')
+        {
+            self.pos += 1;
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Issue;
+    use crate::storage::{ListFilters, SqliteStorage};
+
+    fn parse_err(input: &str) -> (usize, String) {
+        match FilterExpr::parse(input) {
+            Err(BeadsError::FilterParse { column, reason, .. }) => (column, reason),
+            other => panic!("expected parse error for {input:?}, got {other:?}"),
+        }
+    }
+
+    fn compile(input: &str) -> (String, usize) {
+        let expr = FilterExpr::parse(input).expect("parse");
+        let mut sql = String::new();
+        let mut params = Vec::new();
+        expr.push_sql(&mut sql, &mut params);
+        (sql, params.len())
+    }
+
+    #[test]
+    fn test_parse_precedence_and_implicit_and() {
+        let expr = FilterExpr::parse("status:open label:a OR NOT priority<=1").unwrap();
+        let Node::Or(branches) = &expr.root else {
+            panic!("expected OR at the root");
+        };
+        assert_eq!(branches.len(), 2);
+        assert!(matches!(&branches[0], Node::And(nodes) if nodes.len() == 2));
+        assert!(matches!(&branches[1], Node::Not(_)));
+        assert!(expr.references_status());
+        assert!(!FilterExpr::parse("label:a").unwrap().references_status());
+    }
+
+    #[test]
+    fn test_compile_is_parameterized() {
+        let (sql, params) = compile(
+            r#"status:open AND (label:backend OR priority<=1) AND updated>-7d AND NOT assignee:bot AND title:"it's""#,
+        );
+        assert!(!sql.contains("backend"));
+        assert!(!sql.contains("bot"));
+        assert!(!sql.contains("it's"));
+        assert_eq!(params, 6);
+        assert!(sql.starts_with(" AND ("));
+        assert!(sql.contains("NOT (assignee IS ?)"));
+    }
+
+    #[test]
+    fn test_parse_errors_report_column() {
+        assert_eq!(parse_err("").0, 1);
+        assert_eq!(
+            parse_err("status:open AND bogus:1"),
+            (
+                17,
+                format!("unknown field 'bogus' (expected one of: {FIELD_NAMES})")
+            )
+        );
+        assert_eq!(parse_err("status open").0, 7);
+        assert_eq!(parse_err("priority:9").0, 10);
+        assert_eq!(parse_err("(status:open").0, 1);
+        assert_eq!(parse_err("status:open)").0, 12);
+        assert_eq!(parse_err("status<open").0, 7);
+        assert_eq!(parse_err("title:\"abc").0, 7);
+        assert_eq!(parse_err("updated>someday").0, 9);
+        assert_eq!(parse_err("status:open AND").0, 16);
+    }
+
+    #[test]
+    fn test_filter_expr_over_storage() {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let mut make = |id: &str, priority: i32, assignee: Option<&str>, status: Status| {
+            let issue = Issue {
+                id: id.to_string(),
+                title: format!("Issue {id}"),
+                priority: Priority(priority),
+                assignee: assignee.map(str::to_string),
+                closed_at: (status == Status::Closed).then(Utc::now),
+                status,
+                ..Issue::default()
+            };
+            storage.create_issue(&issue, "tester").unwrap();
+        };
+        make("bd-1", 0, Some("bot"), Status::Open);
+        make("bd-2", 2, None, Status::Open);
+        make("bd-3", 3, Some("alice"), Status::Open);
+        make("bd-4", 1, None, Status::Closed);
+        storage.add_label("bd-3", "backend", "tester").unwrap();
+
+        let run = |storage: &SqliteStorage, input: &str| {
+            let expr = FilterExpr::parse(input).unwrap();
+            let filters = ListFilters {
+                include_closed: expr.references_status(),
+                filter_expr: Some(expr),
+                ..ListFilters::default()
+            };
+            let mut ids: Vec<String> = storage
+                .list_issues(&filters)
+                .unwrap()
+                .into_iter()
+                .map(|i| i.id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(
+            run(
+                &storage,
+                "(label:backend OR priority<=1) AND NOT assignee:bot"
+            ),
+            vec!["bd-3"]
+        );
+        assert_eq!(run(&storage, "assignee:none"), vec!["bd-2"]);
+        assert_eq!(run(&storage, "NOT assignee:bot"), vec!["bd-2", "bd-3"]);
+        assert_eq!(run(&storage, "status:closed"), vec!["bd-4"]);
+        assert_eq!(
+            run(&storage, "id:bd-* AND label!=backend updated>-1d"),
+            vec!["bd-1", "bd-2"]
+        );
+        assert_eq!(run(&storage, "title:\"issue bd-2\""), vec!["bd-2"]);
+    }
+}
@@ -0,0 +1,398 @@
+//! Work leases: claims that lapse unless renewed.
+//!
+//! `claim --ttl` records a lease next to the usual claim (assignee plus
+//! `in_progress`). Each `heartbeat` pushes the expiry out by the lease's
+//! TTL. Once a lease has expired, [`expire_in_tx`] hands the issue back:
+//! it reverts to `open` and unassigned and gets a `lease_expired` event, so
+//! work held by a crashed agent becomes ready again.
+//!
+//! Leases are local to the database, like `operations`; only the status and
+//! assignee changes they cause reach the JSONL export.
+
+use crate::error::{BeadsError, Result};
+use crate::model::{EventType, Issue, Status};
+use crate::storage::events::parse_event_timestamp;
+use crate::storage::sqlite::{MutationContext, SqliteStorage};
+use crate::storage::undo;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{Connection, OptionalExtension, Row, Transaction};
+use serde::Serialize;
+
+/// Event type recorded when an expired lease releases an issue.
+pub const LEASE_EXPIRED_EVENT: &str = "lease_expired";
+
+/// A lease on one issue.
+#[derive(Debug, Clone, Serialize)]
+pub struct Lease {
+    pub issue_id: String,
+    pub actor: String,
+    pub ttl_seconds: i64,
+    pub acquired_at: DateTime<Utc>,
+    pub renewed_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Lease {
+    #[must_use]
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+
+    #[must_use]
+    pub fn ttl(&self) -> Duration {
+        Duration::seconds(self.ttl_seconds)
+    }
+}
+
+/// What happened to an expired lease.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExpiredLease {
+    #[serde(flatten)]
+    pub lease: Lease,
+    /// Whether the issue was reverted to open and unassigned. False when
+    /// the claim had already ended (closed, reassigned, released).
+    pub released: bool,
+}
+
+const LEASE_COLUMNS: &str = "issue_id, actor, ttl_seconds, acquired_at, renewed_at, expires_at";
+
+fn lease_from_row(row: &Row<'_>) -> rusqlite::Result<Lease> {
+    let timestamp = |index: usize| -> rusqlite::Result<DateTime<Utc>> {
+        let text: String = row.get(index)?;
+        Ok(parse_event_timestamp(&text))
+    };
+    Ok(Lease {
+        issue_id: row.get(0)?,
+        actor: row.get(1)?,
+        ttl_seconds: row.get(2)?,
+        acquired_at: timestamp(3)?,
+        renewed_at: timestamp(4)?,
+        expires_at: timestamp(5)?,
+    })
+}
+
+/// Look up the lease on an issue, live or expired.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn get_lease(conn: &Connection, issue_id: &str) -> Result<Option<Lease>> {
+    let lease = conn
+        .query_row(
+            &format!("SELECT {LEASE_COLUMNS} FROM leases WHERE issue_id = ?"),
+            [issue_id],
+            lease_from_row,
+        )
+        .optional()?;
+    Ok(lease)
+}
+
+/// All leases, soonest expiry first.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn list_leases(conn: &Connection) -> Result<Vec<Lease>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {LEASE_COLUMNS} FROM leases ORDER BY datetime(expires_at), issue_id"
+    ))?;
+    let leases = stmt
+        .query_map([], lease_from_row)?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(leases)
+}
+
+/// Create or replace the lease on an issue.
+///
+/// # Errors
+///
+/// Returns an error if the write fails.
+pub fn acquire_in_tx(
+    tx: &Transaction<'_>,
+    issue_id: &str,
+    actor: &str,
+    ttl: Duration,
+    now: DateTime<Utc>,
+) -> Result<Lease> {
+    let lease = Lease {
+        issue_id: issue_id.to_string(),
+        actor: actor.to_string(),
+        ttl_seconds: ttl.num_seconds(),
+        acquired_at: now,
+        renewed_at: now,
+        expires_at: now + ttl,
+    };
+    tx.execute(
+        &format!("INSERT OR REPLACE INTO leases ({LEASE_COLUMNS}) VALUES (?, ?, ?, ?, ?, ?)"),
+        rusqlite::params![
+            lease.issue_id,
+            lease.actor,
+            lease.ttl_seconds,
+            lease.acquired_at.to_rfc3339(),
+            lease.renewed_at.to_rfc3339(),
+            lease.expires_at.to_rfc3339(),
+        ],
+    )?;
+    Ok(lease)
+}
+
+/// Extend a live lease held by `actor`, optionally with a new TTL.
+///
+/// # Errors
+///
+/// Returns a validation error if the issue has no live lease or another
+/// actor holds it.
+pub fn renew_in_tx(
+    tx: &Transaction<'_>,
+    issue_id: &str,
+    actor: &str,
+    ttl: Option<Duration>,
+    now: DateTime<Utc>,
+) -> Result<Lease> {
+    let mut lease = get_lease(tx, issue_id)?
+        .filter(|lease| !lease.is_expired(now))
+        .ok_or_else(|| {
+            BeadsError::validation(
+                "lease",
+                format!("no active lease on {issue_id} (claim it again with --ttl)"),
+            )
+        })?;
+    if lease.actor != actor {
+        return Err(BeadsError::validation(
+            "lease",
+            format!("lease on {issue_id} is held by {}", lease.actor),
+        ));
+    }
+
+    if let Some(ttl) = ttl {
+        lease.ttl_seconds = ttl.num_seconds();
+    }
+    lease.renewed_at = now;
+    lease.expires_at = now + lease.ttl();
+    tx.execute(
+        "UPDATE leases SET ttl_seconds = ?, renewed_at = ?, expires_at = ? WHERE issue_id = ?",
+        rusqlite::params![
+            lease.ttl_seconds,
+            lease.renewed_at.to_rfc3339(),
+            lease.expires_at.to_rfc3339(),
+            issue_id
+        ],
+    )?;
+    Ok(lease)
+}
+
+/// Remove the lease on an issue, if any.
+///
+/// # Errors
+///
+/// Returns an error if the write fails.
+pub fn release_in_tx(tx: &Transaction<'_>, issue_id: &str) -> Result<bool> {
+    Ok(tx.execute("DELETE FROM leases WHERE issue_id = ?", [issue_id])? > 0)
+}
+
+/// Remove the lease on an issue once its claim has ended: the issue left
+/// `in_progress` or is no longer assigned to the lease holder.
+///
+/// # Errors
+///
+/// Returns an error if the write fails.
+pub fn release_stale_in_tx(tx: &Transaction<'_>, issue: &Issue) -> Result<()> {
+    let holder = if issue.status == Status::InProgress {
+        issue.assignee.as_deref().unwrap_or_default()
+    } else {
+        ""
+    };
+    tx.execute(
+        "DELETE FROM leases WHERE issue_id = ? AND actor != ?",
+        rusqlite::params![issue.id, holder],
+    )?;
+    Ok(())
+}
+
+/// Drop every expired lease, reverting issues whose claim it still covers.
+///
+/// A claim is still covered when the issue is `in_progress` and assigned to
+/// the lease holder; anything else means the claim already ended and only
+/// the lease row is removed.
+///
+/// # Errors
+///
+/// Returns an error if a read or write fails.
+pub fn expire_in_tx(
+    tx: &Transaction<'_>,
+    ctx: &mut MutationContext,
+    now: DateTime<Utc>,
+) -> Result<Vec<ExpiredLease>> {
+    let expired: Vec<Lease> = list_leases(tx)?
+        .into_iter()
+        .filter(|lease| lease.is_expired(now))
+        .collect();
+
+    let mut outcomes = Vec::with_capacity(expired.len());
+    for lease in expired {
+        release_in_tx(tx, &lease.issue_id)?;
+        let Some(before) = SqliteStorage::fetch_issue(tx, &lease.issue_id)? else {
+            continue;
+        };
+        let released = before.status == Status::InProgress
+            && before.assignee.as_deref() == Some(lease.actor.as_str());
+        if released {
+            let mut after = before.clone();
+            after.status = Status::Open;
+            after.assignee = None;
+            after.updated_at = now;
+            tx.execute(
+                "UPDATE issues SET status = 'open', assignee = NULL, updated_at = ?, content_hash = ?
+                 WHERE id = ?",
+                rusqlite::params![
+                    now.to_rfc3339(),
+                    after.compute_content_hash(),
+                    lease.issue_id
+                ],
+            )?;
+            undo::record_field_changes(ctx, &before, &after, &[]);
+            ctx.record_event(
+                EventType::Custom(LEASE_EXPIRED_EVENT.to_string()),
+                &lease.issue_id,
+                Some(format!(
+                    "Lease held by {} expired at {}",
+                    lease.actor,
+                    lease.expires_at.to_rfc3339()
+                )),
+            );
+            ctx.mark_dirty(&lease.issue_id);
+            ctx.invalidate_cache();
+        }
+        outcomes.push(ExpiredLease { lease, released });
+    }
+    Ok(outcomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::IssueUpdate;
+
+    fn storage_with_claim(actor: &str) -> SqliteStorage {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let issue = Issue {
+            id: "bd-1".to_string(),
+            title: "Leased".to_string(),
+            ..Issue::default()
+        };
+        storage.create_issue(&issue, actor).unwrap();
+        let claim = IssueUpdate {
+            status: Some(Status::InProgress),
+            assignee: Some(Some(actor.to_string())),
+            ..IssueUpdate::default()
+        };
+        storage.update_issue("bd-1", &claim, actor).unwrap();
+        storage
+    }
+
+    #[test]
+    fn heartbeat_extends_and_checks_holder() {
+        let mut storage = storage_with_claim("alice");
+        let now = Utc::now();
+        storage
+            .acquire_lease("bd-1", "alice", Duration::minutes(10), now)
+            .unwrap();
+
+        let later = now + Duration::minutes(5);
+        let renewed = storage.renew_lease("bd-1", "alice", None, later).unwrap();
+        assert_eq!(renewed.expires_at, later + Duration::minutes(10));
+        assert_eq!(renewed.acquired_at, now);
+
+        let err = storage.renew_lease("bd-1", "bob", None, later).unwrap_err();
+        assert!(err.to_string().contains("held by alice"));
+
+        let expired = later + Duration::minutes(11);
+        assert!(storage.renew_lease("bd-1", "alice", None, expired).is_err());
+    }
+
+    #[test]
+    fn expiry_releases_the_claim_once() {
+        let mut storage = storage_with_claim("alice");
+        let now = Utc::now();
+        storage
+            .acquire_lease("bd-1", "alice", Duration::minutes(1), now)
+            .unwrap();
+
+        assert!(storage.expire_leases("sweeper", now).unwrap().is_empty());
+
+        let later = now + Duration::minutes(2);
+        let expired = storage.expire_leases("sweeper", later).unwrap();
+        assert_eq!(expired.len(), 1);
+        assert!(expired[0].released);
+
+        let issue = storage.get_issue("bd-1").unwrap().unwrap();
+        assert_eq!(issue.status, Status::Open);
+        assert_eq!(issue.assignee, None);
+        let events = storage.get_events("bd-1", 0).unwrap();
+        assert!(
+            events
+                .iter()
+                .any(|e| e.event_type.as_str() == LEASE_EXPIRED_EVENT)
+        );
+        assert!(storage.list_leases().unwrap().is_empty());
+        assert!(storage.expire_leases("sweeper", later).unwrap().is_empty());
+    }
+
+    #[test]
+    fn refused_claim_writes_no_lease() {
+        let mut storage = storage_with_claim("alice");
+        let claim = IssueUpdate {
+            status: Some(Status::InProgress),
+            assignee: Some(Some("bob".to_string())),
+            expect_unassigned: true,
+            ..IssueUpdate::default()
+        };
+        let now = Utc::now();
+        let ttl = Some(Duration::minutes(1));
+        assert!(
+            storage
+                .claim_with_lease("bd-1", &claim, "bob", ttl, now)
+                .is_err()
+        );
+        assert!(storage.get_lease("bd-1").unwrap().is_none());
+
+        storage
+            .create_issue(
+                &Issue {
+                    id: "bd-2".to_string(),
+                    title: "Free".to_string(),
+                    ..Issue::default()
+                },
+                "bob",
+            )
+            .unwrap();
+        let (issue, lease) = storage
+            .claim_with_lease("bd-2", &claim, "bob", ttl, now)
+            .unwrap();
+        assert_eq!(issue.assignee.as_deref(), Some("bob"));
+        assert_eq!(lease.unwrap().actor, "bob");
+    }
+
+    #[test]
+    fn ending_the_claim_drops_the_lease() {
+        let mut storage = storage_with_claim("alice");
+        let now = Utc::now();
+        storage
+            .acquire_lease("bd-1", "alice", Duration::minutes(1), now)
+            .unwrap();
+        let reassign = IssueUpdate {
+            assignee: Some(Some("bob".to_string())),
+            ..IssueUpdate::default()
+        };
+        storage.update_issue("bd-1", &reassign, "bob").unwrap();
+        assert!(storage.get_lease("bd-1").unwrap().is_none());
+
+        let expired = storage
+            .expire_leases("sweeper", now + Duration::minutes(2))
+            .unwrap();
+        assert!(expired.is_empty());
+        let issue = storage.get_issue("bd-1").unwrap().unwrap();
+        assert_eq!(issue.assignee.as_deref(), Some("bob"));
+        assert_eq!(issue.status, Status::InProgress);
+    }
+}
@@ -8,12 +8,27 @@
 //!
 //! # Submodules
 //!
+//! - [`batch`] - All-or-nothing batches of mutations
 //! - [`events`] - Audit event storage (insertion, retrieval)
+//! - [`filter`] - Filter expression language (`--where`)
+//! - [`lease`] - Expiring work leases on claims
 //! - [`schema`] - Database schema definitions
+//! - [`scoring`] - Weighted ready-work scores
 //! - [`sqlite`] - Main `SQLite` storage implementation
+//! - [`timeline`] - Point-in-time views rebuilt from events
+//! - [`undo`] - Operation-grouped undo
 
+pub mod batch;
 pub mod events;
+pub mod filter;
+pub mod lease;
 pub mod schema;
+pub mod scoring;
 pub mod sqlite;
+pub mod timeline;
+pub mod undo;
 
-pub use sqlite::{IssueUpdate, ListFilters, ReadyFilters, ReadySortPolicy, SqliteStorage};
+pub use filter::FilterExpr;
+pub use sqlite::{
+    IssueUpdate, ListFilters, ReadyFilters, ReadySortPolicy, SearchHit, SqliteStorage,
+};
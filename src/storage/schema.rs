@@ -2,7 +2,7 @@
 
 use rusqlite::{Connection, Result};
 
-pub const CURRENT_SCHEMA_VERSION: i32 = 1;
+pub const CURRENT_SCHEMA_VERSION: i32 = 5;
 
 /// The complete SQL schema for the beads database.
 /// Schema matches classic bd (Go) for interoperability.
@@ -140,12 +140,47 @@ pub const SCHEMA_SQL: &str = r"
         new_value TEXT,
         comment TEXT,
         created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        op_id TEXT,
+        field TEXT,
         FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
     );
     CREATE INDEX IF NOT EXISTS idx_events_issue ON events(issue_id);
     CREATE INDEX IF NOT EXISTS idx_events_type ON events(event_type);
     CREATE INDEX IF NOT EXISTS idx_events_created_at ON events(created_at);
     CREATE INDEX IF NOT EXISTS idx_events_actor ON events(actor) WHERE actor != '';
+    CREATE INDEX IF NOT EXISTS idx_events_op ON events(op_id) WHERE op_id IS NOT NULL;
+
+    -- Mutation operations (local only, used by `undo`)
+    -- One row per storage session that recorded events. Not part of bd's schema.
+    CREATE TABLE IF NOT EXISTS operations (
+        id TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        actor TEXT NOT NULL DEFAULT '',
+        created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        undoes TEXT,
+        undone_by TEXT
+    );
+
+    -- Work leases (local only, used by `claim --ttl`, `heartbeat`, `leases`)
+    -- One row per claimed issue whose claim lapses unless renewed. Not part of bd's schema.
+    CREATE TABLE IF NOT EXISTS leases (
+        issue_id TEXT PRIMARY KEY,
+        actor TEXT NOT NULL,
+        ttl_seconds INTEGER NOT NULL,
+        acquired_at DATETIME NOT NULL,
+        renewed_at DATETIME NOT NULL,
+        expires_at DATETIME NOT NULL,
+        FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
+    );
+    CREATE INDEX IF NOT EXISTS idx_leases_expires_at ON leases(expires_at);
+
+    -- Org extras (local only, filled from and written back to issues.org)
+    -- One row per issue with Org content that has no issue field. Not part of bd's schema.
+    CREATE TABLE IF NOT EXISTS org_extras (
+        issue_id TEXT PRIMARY KEY,
+        data TEXT NOT NULL,
+        FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
+    );
 
     -- Config (Runtime)
     CREATE TABLE IF NOT EXISTS config (
@@ -191,8 +226,48 @@ pub const SCHEMA_SQL: &str = r"
         last_child INTEGER NOT NULL DEFAULT 0,
         FOREIGN KEY (parent_id) REFERENCES issues(id) ON DELETE CASCADE
     );
+
+    -- Full-text search index (FTS5)
+    -- One row per issue (rowid mirrors issues.rowid), refreshed by the storage
+    -- layer whenever an issue or its comments change. Not part of bd's schema.
+    CREATE VIRTUAL TABLE IF NOT EXISTS issues_fts USING fts5(
+        issue_id UNINDEXED,
+        title,
+        description,
+        design,
+        acceptance_criteria,
+        notes,
+        comments,
+        tokenize = 'unicode61 remove_diacritics 2'
+    );
 ";
 
+/// SQL that (re)populates `issues_fts` rows from `issues` and `comments`.
+///
+/// Callers append a `WHERE` clause to restrict the refresh to specific issues.
+pub const FTS_POPULATE_SQL: &str = r"
+    INSERT INTO issues_fts (rowid, issue_id, title, description, design, acceptance_criteria, notes, comments)
+    SELECT i.rowid,
+           i.id,
+           i.title,
+           COALESCE(i.description, ''),
+           COALESCE(i.design, ''),
+           COALESCE(i.acceptance_criteria, ''),
+           COALESCE(i.notes, ''),
+           COALESCE((SELECT group_concat(c.text, char(10)) FROM comments c WHERE c.issue_id = i.id), '')
+    FROM issues i";
+
+/// Rebuild the full-text index from scratch.
+///
+/// # Errors
+///
+/// Returns an error if the index cannot be cleared or repopulated.
+pub fn rebuild_fts_index(conn: &Connection) -> Result<()> {
+    conn.execute("DELETE FROM issues_fts", [])?;
+    conn.execute(FTS_POPULATE_SQL, [])?;
+    Ok(())
+}
+
 /// Apply the schema to the database.
 ///
 /// This uses `execute_batch` to run the entire DDL script.
@@ -305,6 +380,8 @@ const EVENT_COLUMNS: &[(&str, &str)] = &[
     ("new_value", "TEXT"),
     ("comment", "TEXT"),
     ("created_at", "DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP"),
+    ("op_id", "TEXT"),
+    ("field", "TEXT"),
 ];
 
 fn ensure_columns(conn: &Connection, table: &str, columns: &[(&str, &str)]) -> Result<()> {
@@ -513,6 +590,15 @@ fn run_migrations(conn: &Connection) -> Result<()> {
         )?;
     }
 
+    // Migration: backfill the full-text index for databases created before it existed
+    if table_exists(conn, "issues_fts") {
+        let indexed: i64 =
+            conn.query_row("SELECT count(*) FROM issues_fts", [], |row| row.get(0))?;
+        if indexed == 0 {
+            rebuild_fts_index(conn)?;
+        }
+    }
+
     Ok(())
 }
 
@@ -1072,4 +1158,33 @@ mod tests {
             "missing dependency type column"
         );
     }
+
+    #[test]
+    fn test_migration_backfills_fts_index() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            r"
+            CREATE TABLE issues (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                created_at DATETIME NOT NULL,
+                updated_at DATETIME NOT NULL
+            );
+            INSERT INTO issues (id, title, created_at, updated_at)
+            VALUES ('bd-1', 'Legacy searchable title', '2025-01-01T00:00:00Z', '2025-01-01T00:00:00Z');
+        ",
+        )
+        .unwrap();
+
+        apply_schema(&conn).unwrap();
+
+        let hits: Vec<String> = conn
+            .prepare("SELECT issue_id FROM issues_fts WHERE issues_fts MATCH 'searchable'")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(hits, vec!["bd-1".to_string()]);
+    }
 }
@@ -0,0 +1,195 @@
+//! Weighted ready-work scores (`ready --sort score`).
+//!
+//! A score is a sum of independent terms, each a weight from the
+//! `ready.score.*` config keys times one feature of the issue:
+//!
+//! | Term       | Feature                                                      |
+//! |------------|--------------------------------------------------------------|
+//! | `priority` | levels above P4 (P0 = 4, P4 = 0)                             |
+//! | `age`      | days since creation, capped at `age-cap-days`                |
+//! | `due`      | 0 outside `due-window-days` of `due_at`, rising to 1 when due |
+//! | `unblocks` | unfinished issues this one blocks (children do not count)    |
+//! | `estimate` | estimated hours (a negative weight favours quick wins)       |
+//! | `labels`   | sum of the `labels.<name>` boosts for the issue's labels     |
+//!
+//! Higher scores come first; ties keep priority, then creation order.
+
+use crate::model::Issue;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Weights for each score term.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreWeights {
+    pub priority: f64,
+    pub age: f64,
+    pub age_cap_days: f64,
+    pub due: f64,
+    pub due_window_days: f64,
+    pub unblocks: f64,
+    pub estimate: f64,
+    pub labels: BTreeMap<String, f64>,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            priority: 10.0,
+            age: 0.5,
+            age_cap_days: 30.0,
+            due: 20.0,
+            due_window_days: 14.0,
+            unblocks: 5.0,
+            estimate: -1.0,
+            labels: BTreeMap::new(),
+        }
+    }
+}
+
+/// One issue's score, term by term.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ScoreBreakdown {
+    pub total: f64,
+    pub priority: f64,
+    pub age: f64,
+    pub due: f64,
+    pub unblocks: f64,
+    pub estimate: f64,
+    pub labels: f64,
+}
+
+impl ScoreWeights {
+    /// Score `issue`, given its labels and how many issues depend on it.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn score(
+        &self,
+        issue: &Issue,
+        labels: &[String],
+        dependents: usize,
+        now: DateTime<Utc>,
+    ) -> ScoreBreakdown {
+        let days = |seconds: i64| seconds as f64 / 86_400.0;
+
+        let levels = f64::from((4 - issue.priority.0).clamp(0, 4));
+        let age_days = days((now - issue.created_at).num_seconds()).clamp(0.0, self.age_cap_days);
+        let due_closeness = issue.due_at.map_or(0.0, |due| {
+            if self.due_window_days <= 0.0 {
+                return f64::from(u8::from(due <= now));
+            }
+            let days_left = days((due - now).num_seconds()).max(0.0);
+            (1.0 - days_left / self.due_window_days).max(0.0)
+        });
+        let hours = issue
+            .estimated_minutes
+            .map_or(0.0, |minutes| f64::from(minutes.max(0)) / 60.0);
+        let label_boost: f64 = labels
+            .iter()
+            .filter_map(|label| self.labels.get(label))
+            .sum();
+
+        let mut breakdown = ScoreBreakdown {
+            total: 0.0,
+            priority: round(self.priority * levels),
+            age: round(self.age * age_days),
+            due: round(self.due * due_closeness),
+            unblocks: round(self.unblocks * dependents as f64),
+            estimate: round(self.estimate * hours),
+            labels: round(label_boost),
+        };
+        breakdown.total = round(
+            breakdown.priority
+                + breakdown.age
+                + breakdown.due
+                + breakdown.unblocks
+                + breakdown.estimate
+                + breakdown.labels,
+        );
+        breakdown
+    }
+}
+
+impl ScoreBreakdown {
+    /// One-line explanation, e.g. `42.5 = priority 30 + age 2.5 + unblocks 10`.
+    /// Zero terms are left out.
+    #[must_use]
+    pub fn explain(&self) -> String {
+        let terms: Vec<String> = [
+            ("priority", self.priority),
+            ("age", self.age),
+            ("due", self.due),
+            ("unblocks", self.unblocks),
+            ("estimate", self.estimate),
+            ("labels", self.labels),
+        ]
+        .into_iter()
+        .filter(|(_, value)| *value != 0.0)
+        .map(|(name, value)| format!("{name} {value}"))
+        .collect();
+        if terms.is_empty() {
+            format!("{}", self.total)
+        } else {
+            format!("{} = {}", self.total, terms.join(" + "))
+        }
+    }
+}
+
+/// Round to two decimals so JSON output stays readable.
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Priority;
+    use chrono::Duration;
+
+    fn issue(priority: i32, estimate: Option<i32>) -> Issue {
+        Issue {
+            id: "bd-1".to_string(),
+            title: "Scored".to_string(),
+            priority: Priority(priority),
+            estimated_minutes: estimate,
+            created_at: Utc::now(),
+            ..Issue::default()
+        }
+    }
+
+    #[test]
+    fn quick_win_that_unblocks_beats_leaf_task() {
+        let weights = ScoreWeights::default();
+        let now = Utc::now();
+        let quick_win = weights.score(&issue(2, Some(30)), &[], 2, now);
+        let leaf = weights.score(&issue(2, Some(480)), &[], 0, now);
+        assert!(quick_win.total > leaf.total, "{quick_win:?} vs {leaf:?}");
+        assert!((quick_win.unblocks - 10.0).abs() < f64::EPSILON);
+        assert!((leaf.estimate + 8.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn due_age_and_label_terms() {
+        let mut weights = ScoreWeights::default();
+        weights.labels.insert("quick-win".to_string(), 15.0);
+        let now = Utc::now();
+
+        let mut old = issue(4, None);
+        old.created_at = now - Duration::days(90);
+        old.due_at = Some(now - Duration::days(1));
+        let score = weights.score(&old, &["quick-win".to_string()], 0, now);
+        assert!(score.priority.abs() < f64::EPSILON);
+        assert!((score.age - 15.0).abs() < f64::EPSILON, "capped at 30 days");
+        assert!(
+            (score.due - 20.0).abs() < f64::EPSILON,
+            "overdue gets the full weight"
+        );
+        assert!((score.labels - 15.0).abs() < f64::EPSILON);
+        assert_eq!(score.explain(), "50 = age 15 + due 20 + labels 15");
+
+        old.due_at = Some(now + Duration::days(7));
+        let halfway = weights.score(&old, &[], 0, now);
+        assert!((halfway.due - 10.0).abs() < 0.01);
+    }
+}
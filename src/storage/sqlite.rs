@@ -2,25 +2,38 @@
 
 use crate::error::{BeadsError, Result};
 use crate::format::{IssueDetails, IssueWithDependencyMetadata};
-use crate::model::{Comment, DependencyType, Event, EventType, Issue, IssueType, Priority, Status};
+use crate::model::{
+    Comment, DependencyType, Event, EventType, Issue, IssueType, OrgExtras, Priority, Status,
+    StatusCategory,
+};
+use crate::storage::batch::{self, BatchFailure, BatchStep, StepOutcome};
 use crate::storage::events::get_events;
-use crate::storage::schema::{CURRENT_SCHEMA_VERSION, apply_schema};
+use crate::storage::filter::FilterExpr;
+use crate::storage::lease::{self, ExpiredLease, Lease};
+use crate::storage::schema::{CURRENT_SCHEMA_VERSION, FTS_POPULATE_SQL, apply_schema};
+use crate::storage::scoring::{ScoreBreakdown, ScoreWeights};
+use crate::storage::timeline::{self, FieldChange};
+use crate::storage::undo::{self, OperationSummary, UndoReport};
 use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 use rusqlite::{Connection, OpenFlags, OptionalExtension, Transaction};
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write as _;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
 /// SQLite-based storage backend.
 #[derive(Debug)]
 pub struct SqliteStorage {
     conn: Connection,
+    /// Operation id stamped on events; one per storage session unless reset.
+    operation: Option<String>,
 }
 
 /// Context for a mutation operation, tracking side effects.
 pub struct MutationContext {
     pub op_name: String,
+    pub op_id: String,
     pub actor: String,
     pub events: Vec<Event>,
     pub dirty_ids: HashSet<String>,
@@ -32,6 +45,7 @@ impl MutationContext {
     pub fn new(op_name: &str, actor: &str) -> Self {
         Self {
             op_name: op_name.to_string(),
+            op_id: String::new(),
             actor: actor.to_string(),
             events: Vec::new(),
             dirty_ids: HashSet::new(),
@@ -49,6 +63,8 @@ impl MutationContext {
             new_value: None,
             comment: details,
             created_at: Utc::now(),
+            field: None,
+            op_id: None,
         });
     }
 
@@ -70,9 +86,27 @@ impl MutationContext {
             new_value,
             comment,
             created_at: Utc::now(),
+            field: None,
+            op_id: None,
         });
     }
 
+    /// Record a replayable change to a single issue field (used by `undo`).
+    pub fn record_field_event(
+        &mut self,
+        event_type: EventType,
+        issue_id: &str,
+        field: &str,
+        old_value: Option<String>,
+        new_value: Option<String>,
+        comment: Option<String>,
+    ) {
+        self.record_field_change(event_type, issue_id, old_value, new_value, comment);
+        if let Some(event) = self.events.last_mut() {
+            event.field = Some(field.to_string());
+        }
+    }
+
     pub fn mark_dirty(&mut self, issue_id: &str) {
         self.dirty_ids.insert(issue_id.to_string());
     }
@@ -107,7 +141,10 @@ impl SqliteStorage {
         if user_version < CURRENT_SCHEMA_VERSION {
             apply_schema(&conn)?;
         }
-        Ok(Self { conn })
+        Ok(Self {
+            conn,
+            operation: None,
+        })
     }
 
     /// Open an in-memory database for testing.
@@ -118,7 +155,10 @@ impl SqliteStorage {
     pub fn open_memory() -> Result<Self> {
         let conn = Connection::open_in_memory()?;
         apply_schema(&conn)?;
-        Ok(Self { conn })
+        Ok(Self {
+            conn,
+            operation: None,
+        })
     }
 
     /// Get audit events for a specific issue.
@@ -139,6 +179,186 @@ impl SqliteStorage {
         crate::storage::events::get_all_events(&self.conn, limit)
     }
 
+    /// Start a new operation; later mutations are grouped under its id.
+    ///
+    /// Each storage session starts one lazily, so a CLI invocation is a
+    /// single operation. Long-lived callers start one per logical request.
+    pub fn begin_operation(&mut self) -> String {
+        static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+        let micros = Utc::now().timestamp_micros();
+        let seq = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        let id = format!("op-{micros:x}-{:x}-{seq}", std::process::id());
+        self.operation = Some(id.clone());
+        id
+    }
+
+    /// Id of the current operation, starting one if needed.
+    pub fn operation_id(&mut self) -> String {
+        match &self.operation {
+            Some(id) => id.clone(),
+            None => self.begin_operation(),
+        }
+    }
+
+    /// List recorded operations, newest first (0 = no limit).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn list_operations(&self, limit: usize) -> Result<Vec<OperationSummary>> {
+        undo::list_operations(&self.conn, limit)
+    }
+
+    /// Ids of the `steps` most recent operations that can still be undone.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn undoable_operations(&self, steps: usize) -> Result<Vec<String>> {
+        undo::undoable_operations(&self.conn, steps)
+    }
+
+    /// Undo the given operations in a single transaction.
+    ///
+    /// The undo is itself recorded as a new operation, so it can be reverted
+    /// by undoing its id.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BeadsError::UndoConflict` if later edits changed anything the
+    /// operations touched, or a validation error for unknown/undone ids.
+    pub fn undo_operations(&mut self, op_ids: &[String], actor: &str) -> Result<UndoReport> {
+        self.begin_operation();
+        let report = self.mutate("undo", actor, |tx, ctx| undo::undo_in_tx(tx, ctx, op_ids));
+        self.begin_operation();
+        report
+    }
+
+    /// Apply `steps` in one mutation, all or nothing (see [`batch`]).
+    ///
+    /// `check_transition` vets each status change (the caller's workflow).
+    ///
+    /// # Errors
+    ///
+    /// Returns the failing step and its error; nothing is written then.
+    pub fn apply_batch(
+        &mut self,
+        steps: &[BatchStep],
+        actor: &str,
+        check_transition: impl Fn(&Issue, &Status) -> Result<()>,
+    ) -> std::result::Result<Vec<StepOutcome>, BatchFailure> {
+        let mut current = None;
+        let result = self.mutate("batch", actor, |tx, ctx| {
+            let mut outcomes = Vec::with_capacity(steps.len());
+            for (index, step) in steps.iter().enumerate() {
+                current = Some(index);
+                outcomes.push(batch::apply_in_tx(tx, ctx, step, &check_transition)?);
+            }
+            current = None;
+            Ok(outcomes)
+        });
+        result.map_err(|error| BatchFailure {
+            step: current,
+            error,
+        })
+    }
+
+    /// Lease an issue to `actor` for `ttl`, replacing any existing lease.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the write fails.
+    pub fn acquire_lease(
+        &mut self,
+        issue_id: &str,
+        actor: &str,
+        ttl: chrono::Duration,
+        now: DateTime<Utc>,
+    ) -> Result<Lease> {
+        self.mutate("acquire_lease", actor, |tx, _ctx| {
+            lease::acquire_in_tx(tx, issue_id, actor, ttl, now)
+        })
+    }
+
+    /// Apply a claim update and, with a `ttl`, lease the issue to `actor` in
+    /// the same transaction, so a claim never lands without its lease.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the claim guard rejects the update or a write
+    /// fails; nothing is written then.
+    pub fn claim_with_lease(
+        &mut self,
+        id: &str,
+        updates: &IssueUpdate,
+        actor: &str,
+        ttl: Option<chrono::Duration>,
+        now: DateTime<Utc>,
+    ) -> Result<(Issue, Option<Lease>)> {
+        self.mutate("claim", actor, |tx, ctx| {
+            let issue = Self::update_issue_in_tx(tx, ctx, id, updates)?;
+            let lease = ttl
+                .map(|ttl| lease::acquire_in_tx(tx, id, actor, ttl, now))
+                .transpose()?;
+            Ok((issue, lease))
+        })
+    }
+
+    /// Extend `actor`'s live lease on an issue (see [`lease::renew_in_tx`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if there is no live lease or another actor
+    /// holds it.
+    pub fn renew_lease(
+        &mut self,
+        issue_id: &str,
+        actor: &str,
+        ttl: Option<chrono::Duration>,
+        now: DateTime<Utc>,
+    ) -> Result<Lease> {
+        self.mutate("renew_lease", actor, |tx, _ctx| {
+            lease::renew_in_tx(tx, issue_id, actor, ttl, now)
+        })
+    }
+
+    /// Get the lease on an issue, live or expired.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn get_lease(&self, issue_id: &str) -> Result<Option<Lease>> {
+        lease::get_lease(&self.conn, issue_id)
+    }
+
+    /// List all leases, soonest expiry first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn list_leases(&self) -> Result<Vec<Lease>> {
+        lease::list_leases(&self.conn)
+    }
+
+    /// Drop expired leases and hand their issues back (see
+    /// [`lease::expire_in_tx`]). Reverts are recorded as one operation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a read or write fails.
+    pub fn expire_leases(&mut self, actor: &str, now: DateTime<Utc>) -> Result<Vec<ExpiredLease>> {
+        // Read paths call this on every run; skip the write lock when idle.
+        if !lease::list_leases(&self.conn)?
+            .iter()
+            .any(|lease| lease.is_expired(now))
+        {
+            return Ok(Vec::new());
+        }
+        self.mutate("expire_leases", actor, |tx, ctx| {
+            lease::expire_in_tx(tx, ctx, now)
+        })
+    }
+
     /// Execute a mutation with the 4-step transaction protocol.
     ///
     /// # Errors
@@ -149,18 +369,28 @@ impl SqliteStorage {
     where
         F: FnOnce(&Transaction, &mut MutationContext) -> Result<R>,
     {
+        let op_id = self.operation_id();
         let tx = self
             .conn
             .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
         let mut ctx = MutationContext::new(op, actor);
+        ctx.op_id.clone_from(&op_id);
 
         let result = f(&tx, &mut ctx)?;
 
+        // Group events under the current operation so `undo` can find them
+        if !ctx.events.is_empty() {
+            tx.execute(
+                "INSERT OR IGNORE INTO operations (id, name, actor, created_at) VALUES (?, ?, ?, ?)",
+                rusqlite::params![op_id, op, actor, Utc::now().to_rfc3339()],
+            )?;
+        }
+
         // Write events
         for event in ctx.events {
             tx.execute(
-                "INSERT INTO events (issue_id, event_type, actor, old_value, new_value, comment, created_at)
-                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                "INSERT INTO events (issue_id, event_type, actor, old_value, new_value, comment, created_at, op_id, field)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
                 rusqlite::params![
                     event.issue_id,
                     event.event_type.as_str(),
@@ -168,19 +398,26 @@ impl SqliteStorage {
                     event.old_value,
                     event.new_value,
                     event.comment,
-                    event.created_at.to_rfc3339()
+                    event.created_at.to_rfc3339(),
+                    op_id,
+                    event.field
                 ],
             )?;
         }
 
         // Mark dirty
-        for id in ctx.dirty_ids {
+        for id in &ctx.dirty_ids {
             tx.execute(
                 "INSERT OR REPLACE INTO dirty_issues (issue_id, marked_at) VALUES (?, ?)",
                 rusqlite::params![id, Utc::now().to_rfc3339()],
             )?;
         }
 
+        // Keep the full-text index in step with every touched issue
+        for id in &ctx.dirty_ids {
+            Self::refresh_fts_row(&tx, id)?;
+        }
+
         // Track if we need to rebuild cache
         let needs_cache_rebuild = ctx.invalidate_blocked_cache;
 
@@ -194,155 +431,208 @@ impl SqliteStorage {
         Ok(result)
     }
 
+    /// Refresh the full-text index row for a single issue.
+    ///
+    /// The FTS rowid mirrors `issues.rowid`, so stale rows are replaced in
+    /// place. Issues that no longer exist are left untouched.
+    fn refresh_fts_row(conn: &Connection, issue_id: &str) -> Result<()> {
+        let rowid: Option<i64> = conn
+            .query_row("SELECT rowid FROM issues WHERE id = ?", [issue_id], |row| {
+                row.get(0)
+            })
+            .optional()?;
+        let Some(rowid) = rowid else {
+            return Ok(());
+        };
+
+        conn.execute("DELETE FROM issues_fts WHERE rowid = ?", [rowid])?;
+        let mut stmt = conn.prepare_cached(&format!("{FTS_POPULATE_SQL} WHERE i.rowid = ?"))?;
+        stmt.execute([rowid])?;
+        Ok(())
+    }
+
     /// Create a new issue.
     ///
     /// # Errors
     ///
     /// Returns an error if the issue cannot be inserted (e.g. ID collision).
-    #[allow(clippy::too_many_lines)]
     pub fn create_issue(&mut self, issue: &Issue, actor: &str) -> Result<()> {
         self.mutate("create_issue", actor, |tx, ctx| {
-            let status_str = issue.status.as_str();
-            let issue_type_str = issue.issue_type.as_str();
-            let created_at_str = issue.created_at.to_rfc3339();
-            let updated_at_str = issue.updated_at.to_rfc3339();
-            let closed_at_str = issue.closed_at.map(|dt| dt.to_rfc3339());
-            let due_at_str = issue.due_at.map(|dt| dt.to_rfc3339());
-            let defer_until_str = issue.defer_until.map(|dt| dt.to_rfc3339());
-            let deleted_at_str = issue.deleted_at.map(|dt| dt.to_rfc3339());
-            let compacted_at_str = issue.compacted_at.map(|dt| dt.to_rfc3339());
+            Self::create_issue_in_tx(tx, ctx, issue, actor)
+        })
+    }
+
+    /// Insert an issue with its labels, dependencies and comments inside an
+    /// open mutation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the issue cannot be inserted (e.g. ID collision)
+    /// or a dependency would create a cycle.
+    #[allow(clippy::too_many_lines)]
+    pub(crate) fn create_issue_in_tx(
+        tx: &Transaction<'_>,
+        ctx: &mut MutationContext,
+        issue: &Issue,
+        actor: &str,
+    ) -> Result<()> {
+        let status_str = issue.status.as_str();
+        let issue_type_str = issue.issue_type.as_str();
+        let created_at_str = issue.created_at.to_rfc3339();
+        let updated_at_str = issue.updated_at.to_rfc3339();
+        let closed_at_str = issue.closed_at.map(|dt| dt.to_rfc3339());
+        let due_at_str = issue.due_at.map(|dt| dt.to_rfc3339());
+        let defer_until_str = issue.defer_until.map(|dt| dt.to_rfc3339());
+        let deleted_at_str = issue.deleted_at.map(|dt| dt.to_rfc3339());
+        let compacted_at_str = issue.compacted_at.map(|dt| dt.to_rfc3339());
+
+        tx.execute(
+            "INSERT INTO issues (
+                id, content_hash, title, description, design, acceptance_criteria, notes,
+                status, priority, issue_type, assignee, owner, estimated_minutes,
+                created_at, created_by, updated_at, closed_at, close_reason,
+                closed_by_session, due_at, defer_until, external_ref, source_system,
+                source_repo, deleted_at, deleted_by, delete_reason, original_type,
+                compaction_level, compacted_at, compacted_at_commit, original_size,
+                sender, ephemeral, pinned, is_template
+                        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",                rusqlite::params![
+                issue.id,
+                issue.content_hash,
+                issue.title,
+                issue.description.as_deref().unwrap_or(""),
+                issue.design.as_deref().unwrap_or(""),
+                issue.acceptance_criteria.as_deref().unwrap_or(""),
+                issue.notes.as_deref().unwrap_or(""),
+                status_str,
+                issue.priority.0,
+                issue_type_str,
+                issue.assignee,
+                issue.owner.as_deref().unwrap_or(""),
+                issue.estimated_minutes,
+                created_at_str,
+                issue.created_by.as_deref().unwrap_or(""),
+                updated_at_str,
+                closed_at_str,
+                issue.close_reason.as_deref().unwrap_or(""),
+                issue.closed_by_session.as_deref().unwrap_or(""),
+                due_at_str,
+                defer_until_str,
+                issue.external_ref,
+                issue.source_system.as_deref().unwrap_or(""),
+                issue.source_repo.as_deref().unwrap_or("."),
+                deleted_at_str,
+                issue.deleted_by.as_deref().unwrap_or(""),
+                issue.delete_reason.as_deref().unwrap_or(""),
+                issue.original_type.as_deref().unwrap_or(""),
+                issue.compaction_level.unwrap_or(0),
+                compacted_at_str,
+                issue.compacted_at_commit,
+                issue.original_size.unwrap_or(0),
+                issue.sender.as_deref().unwrap_or(""),
+                i32::from(issue.ephemeral),
+                i32::from(issue.pinned),
+                i32::from(issue.is_template),
+            ],
+        )?;
 
+        // Insert Labels
+        for label in &issue.labels {
             tx.execute(
-                "INSERT INTO issues (
-                    id, content_hash, title, description, design, acceptance_criteria, notes,
-                    status, priority, issue_type, assignee, owner, estimated_minutes,
-                    created_at, created_by, updated_at, closed_at, close_reason,
-                    closed_by_session, due_at, defer_until, external_ref, source_system,
-                    source_repo, deleted_at, deleted_by, delete_reason, original_type,
-                    compaction_level, compacted_at, compacted_at_commit, original_size,
-                    sender, ephemeral, pinned, is_template
-                            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",                rusqlite::params![
-                    issue.id,
-                    issue.content_hash,
-                    issue.title,
-                    issue.description.as_deref().unwrap_or(""),
-                    issue.design.as_deref().unwrap_or(""),
-                    issue.acceptance_criteria.as_deref().unwrap_or(""),
-                    issue.notes.as_deref().unwrap_or(""),
-                    status_str,
-                    issue.priority.0,
-                    issue_type_str,
-                    issue.assignee,
-                    issue.owner.as_deref().unwrap_or(""),
-                    issue.estimated_minutes,
-                    created_at_str,
-                    issue.created_by.as_deref().unwrap_or(""),
-                    updated_at_str,
-                    closed_at_str,
-                    issue.close_reason.as_deref().unwrap_or(""),
-                    issue.closed_by_session.as_deref().unwrap_or(""),
-                    due_at_str,
-                    defer_until_str,
-                    issue.external_ref,
-                    issue.source_system.as_deref().unwrap_or(""),
-                    issue.source_repo.as_deref().unwrap_or("."),
-                    deleted_at_str,
-                    issue.deleted_by.as_deref().unwrap_or(""),
-                    issue.delete_reason.as_deref().unwrap_or(""),
-                    issue.original_type.as_deref().unwrap_or(""),
-                    issue.compaction_level.unwrap_or(0),
-                    compacted_at_str,
-                    issue.compacted_at_commit,
-                    issue.original_size.unwrap_or(0),
-                    issue.sender.as_deref().unwrap_or(""),
-                    i32::from(issue.ephemeral),
-                    i32::from(issue.pinned),
-                    i32::from(issue.is_template),
-                ],
+                "INSERT INTO labels (issue_id, label) VALUES (?, ?)",
+                rusqlite::params![issue.id, label],
             )?;
+            ctx.record_field_event(
+                EventType::LabelAdded,
+                &issue.id,
+                "labels",
+                None,
+                Some(label.clone()),
+                Some(format!("Added label {label}")),
+            );
+        }
 
-            // Insert Labels
-            for label in &issue.labels {
-                tx.execute(
-                    "INSERT INTO labels (issue_id, label) VALUES (?, ?)",
-                    rusqlite::params![issue.id, label],
-                )?;
-                ctx.record_event(
-                    EventType::LabelAdded,
-                    &issue.id,
-                    Some(format!("Added label {label}")),
-                );
+        // Insert Dependencies
+        for dep in &issue.dependencies {
+            // Check cycle if blocking
+            if dep.dep_type.is_blocking()
+                && Self::check_cycle(tx, &issue.id, &dep.depends_on_id, true)?
+            {
+                return Err(BeadsError::DependencyCycle {
+                    path: format!(
+                        "Adding dependency {} -> {} would create a cycle",
+                        issue.id, dep.depends_on_id
+                    ),
+                });
             }
 
-            // Insert Dependencies
-            for dep in &issue.dependencies {
-                // Check cycle if blocking
-                if dep.dep_type.is_blocking()
-                    && Self::check_cycle(tx, &issue.id, &dep.depends_on_id, true)?
-                {
-                    return Err(BeadsError::DependencyCycle {
-                        path: format!(
-                            "Adding dependency {} -> {} would create a cycle",
-                            issue.id, dep.depends_on_id
-                        ),
-                    });
-                }
-
-                tx.execute(
-                    "INSERT INTO dependencies (issue_id, depends_on_id, type, created_at, created_by)
-                     VALUES (?, ?, ?, ?, ?)",
-                    rusqlite::params![
-                        issue.id,
-                        dep.depends_on_id,
-                        dep.dep_type.as_str(),
-                        dep.created_at.to_rfc3339(),
-                        dep.created_by.as_deref().unwrap_or(actor)
-                    ],
-                )?;
-
-                ctx.record_event(
-                    EventType::DependencyAdded,
-                    &issue.id,
-                    Some(format!(
-                        "Added dependency on {} ({})",
-                        dep.depends_on_id, dep.dep_type
-                    )),
-                );
-                ctx.invalidate_cache();
-            }
+            let dep_created_at = dep.created_at.to_rfc3339();
+            let dep_created_by = dep.created_by.as_deref().unwrap_or(actor);
+            tx.execute(
+                "INSERT INTO dependencies (issue_id, depends_on_id, type, created_at, created_by)
+                 VALUES (?, ?, ?, ?, ?)",
+                rusqlite::params![
+                    issue.id,
+                    dep.depends_on_id,
+                    dep.dep_type.as_str(),
+                    dep_created_at,
+                    dep_created_by
+                ],
+            )?;
 
-            // Insert Comments
-            for comment in &issue.comments {
-                tx.execute(
-                    "INSERT INTO comments (issue_id, author, text, created_at) VALUES (?, ?, ?, ?)",
-                    rusqlite::params![
-                        issue.id,
-                        comment.author,
-                        comment.body,
-                        comment.created_at.to_rfc3339()
-                    ],
-                )?;
-                ctx.record_event(
-                    EventType::Commented,
-                    &issue.id,
-                    Some(comment.body.clone()),
-                );
-            }
+            ctx.record_field_event(
+                EventType::DependencyAdded,
+                &issue.id,
+                "dependencies",
+                None,
+                Some(undo::dependency_value(
+                    &dep.depends_on_id,
+                    dep.dep_type.as_str(),
+                    &dep_created_at,
+                    dep_created_by,
+                )),
+                Some(format!(
+                    "Added dependency on {} ({})",
+                    dep.depends_on_id, dep.dep_type
+                )),
+            );
+            ctx.invalidate_cache();
+        }
 
-            ctx.record_event(
-                EventType::Created,
+        // Insert Comments
+        for comment in &issue.comments {
+            tx.execute(
+                "INSERT INTO comments (issue_id, author, text, created_at) VALUES (?, ?, ?, ?)",
+                rusqlite::params![
+                    issue.id,
+                    comment.author,
+                    comment.body,
+                    comment.created_at.to_rfc3339()
+                ],
+            )?;
+            let stored = fetch_comment(tx, tx.last_insert_rowid())?;
+            ctx.record_field_event(
+                EventType::Commented,
                 &issue.id,
-                Some(format!("Created issue: {}", issue.title)),
+                "comments",
+                None,
+                Some(undo::comment_value(&stored)),
+                Some(comment.body.clone()),
             );
+        }
 
-            ctx.mark_dirty(&issue.id);
+        ctx.record_event(
+            EventType::Created,
+            &issue.id,
+            Some(format!("Created issue: {}", issue.title)),
+        );
 
-            Ok(())
-        })
+        ctx.mark_dirty(&issue.id);
+
+        Ok(())
     }
 
     // Helper for cycle detection (refactored from would_create_cycle)
-    fn check_cycle(
+    pub(crate) fn check_cycle(
         conn: &Connection,
         issue_id: &str,
         depends_on_id: &str,
@@ -387,252 +677,280 @@ impl SqliteStorage {
     /// # Errors
     ///
     /// Returns an error if the issue doesn't exist or the update fails.
-    #[allow(clippy::too_many_lines)]
     pub fn update_issue(&mut self, id: &str, updates: &IssueUpdate, actor: &str) -> Result<Issue> {
-        let mut issue = self
-            .get_issue(id)?
-            .ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() })?;
+        if updates.is_empty() {
+            return self
+                .get_issue(id)?
+                .ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() });
+        }
+        self.mutate("update_issue", actor, |tx, ctx| {
+            Self::update_issue_in_tx(tx, ctx, id, updates)
+        })
+    }
 
+    /// Apply `updates` to an issue inside an open mutation and return the
+    /// updated issue.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the issue doesn't exist, a claim is refused or
+    /// the update fails.
+    #[allow(clippy::too_many_lines)]
+    pub(crate) fn update_issue_in_tx(
+        tx: &Transaction<'_>,
+        ctx: &mut MutationContext,
+        id: &str,
+        updates: &IssueUpdate,
+    ) -> Result<Issue> {
+        let mut issue = Self::fetch_issue(tx, id)?
+            .ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() })?;
         if updates.is_empty() {
             return Ok(issue);
         }
 
-        self.mutate("update_issue", actor, |tx, ctx| {
-            // Atomic claim guard: check assignee INSIDE the IMMEDIATE transaction
-            // to prevent TOCTOU races where two agents both see "unassigned".
-            if updates.expect_unassigned {
-                let current_assignee: Option<String> =
-                    tx.query_row("SELECT assignee FROM issues WHERE id = ?", [id], |row| {
-                        row.get(0)
-                    })?;
-                let trimmed = current_assignee
-                    .as_deref()
-                    .map(str::trim)
-                    .filter(|s| !s.is_empty());
-                let claim_actor = updates.claim_actor.as_deref().unwrap_or("");
-
-                match trimmed {
-                    None => { /* unassigned, proceed with claim */ }
-                    Some(current) if !updates.claim_exclusive && current == claim_actor => {
-                        /* same actor re-claim, idempotent */
-                    }
-                    Some(current) => {
-                        return Err(BeadsError::validation(
-                            "claim",
-                            format!("issue {id} already assigned to {current}"),
-                        ));
-                    }
+        let before = issue.clone();
+        // Atomic claim guard: check assignee INSIDE the IMMEDIATE transaction
+        // to prevent TOCTOU races where two agents both see "unassigned".
+        if updates.expect_unassigned {
+            let current_assignee: Option<String> =
+                tx.query_row("SELECT assignee FROM issues WHERE id = ?", [id], |row| {
+                    row.get(0)
+                })?;
+            let trimmed = current_assignee
+                .as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty());
+            let claim_actor = updates.claim_actor.as_deref().unwrap_or("");
+
+            match trimmed {
+                None => { /* unassigned, proceed with claim */ }
+                Some(current) if !updates.claim_exclusive && current == claim_actor => {
+                    /* same actor re-claim, idempotent */
+                }
+                Some(current) => {
+                    return Err(BeadsError::validation(
+                        "claim",
+                        format!("issue {id} already assigned to {current}"),
+                    ));
                 }
             }
+        }
 
-            let mut set_clauses: Vec<String> = vec![];
-            let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![];
-
-            // Helper to add update
-            let mut add_update = |field: &str, val: Box<dyn rusqlite::ToSql>| {
-                set_clauses.push(format!("{field} = ?"));
-                params.push(val);
-            };
+        let mut set_clauses: Vec<String> = vec![];
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![];
 
-            // Title
-            if let Some(ref title) = updates.title {
-                let old_title = issue.title.clone();
-                issue.title.clone_from(title);
-                add_update("title", Box::new(title.clone()));
-                ctx.record_field_change(
-                    EventType::Updated,
-                    id,
-                    Some(old_title),
-                    Some(title.clone()),
-                    Some("Title changed".to_string()),
-                );
-            }
+        // Helper to add update
+        let mut add_update = |field: &str, val: Box<dyn rusqlite::ToSql>| {
+            set_clauses.push(format!("{field} = ?"));
+            params.push(val);
+        };
 
-            // Simple text fields - use empty string instead of NULL for bd compatibility
-            if let Some(ref val) = updates.description {
-                issue.description.clone_from(val);
-                add_update(
-                    "description",
-                    Box::new(val.as_deref().unwrap_or("").to_string()),
-                );
-            }
-            if let Some(ref val) = updates.design {
-                issue.design.clone_from(val);
-                add_update("design", Box::new(val.as_deref().unwrap_or("").to_string()));
-            }
-            if let Some(ref val) = updates.acceptance_criteria {
-                issue.acceptance_criteria.clone_from(val);
-                add_update(
-                    "acceptance_criteria",
-                    Box::new(val.as_deref().unwrap_or("").to_string()),
-                );
-            }
-            if let Some(ref val) = updates.notes {
-                issue.notes.clone_from(val);
-                add_update("notes", Box::new(val.as_deref().unwrap_or("").to_string()));
-            }
+        // Title
+        if let Some(ref title) = updates.title {
+            let old_title = issue.title.clone();
+            issue.title.clone_from(title);
+            add_update("title", Box::new(title.clone()));
+            ctx.record_field_event(
+                EventType::Updated,
+                id,
+                "title",
+                Some(old_title),
+                Some(title.clone()),
+                Some("Title changed".to_string()),
+            );
+        }
 
-            // Status
-            if let Some(ref status) = updates.status {
-                let old_status = issue.status.as_str().to_string();
-                issue.status.clone_from(status);
-                add_update("status", Box::new(status.as_str().to_string()));
-                ctx.record_field_change(
-                    EventType::StatusChanged,
-                    id,
-                    Some(old_status),
-                    Some(status.as_str().to_string()),
-                    None,
-                );
+        // Simple text fields - use empty string instead of NULL for bd compatibility
+        if let Some(ref val) = updates.description {
+            issue.description.clone_from(val);
+            add_update(
+                "description",
+                Box::new(val.as_deref().unwrap_or("").to_string()),
+            );
+        }
+        if let Some(ref val) = updates.design {
+            issue.design.clone_from(val);
+            add_update("design", Box::new(val.as_deref().unwrap_or("").to_string()));
+        }
+        if let Some(ref val) = updates.acceptance_criteria {
+            issue.acceptance_criteria.clone_from(val);
+            add_update(
+                "acceptance_criteria",
+                Box::new(val.as_deref().unwrap_or("").to_string()),
+            );
+        }
+        if let Some(ref val) = updates.notes {
+            issue.notes.clone_from(val);
+            add_update("notes", Box::new(val.as_deref().unwrap_or("").to_string()));
+        }
 
-                // Record Closed event if status is now Closed
-                if *status == Status::Closed {
-                    let reason = updates.close_reason.as_ref().and_then(Clone::clone);
-                    ctx.record_event(EventType::Closed, id, reason);
+        // Status
+        if let Some(ref status) = updates.status {
+            let old_status = issue.status.as_str().to_string();
+            issue.status.clone_from(status);
+            add_update("status", Box::new(status.as_str().to_string()));
+            ctx.record_field_event(
+                EventType::StatusChanged,
+                id,
+                "status",
+                Some(old_status),
+                Some(status.as_str().to_string()),
+                None,
+            );
 
-                    // Auto-set closed_at if not provided
-                    if updates.closed_at.is_none() && issue.closed_at.is_none() {
-                        let now = Utc::now();
-                        issue.closed_at = Some(now);
-                        add_update("closed_at", Box::new(Some(now.to_rfc3339())));
-                    }
-                } else if issue.closed_at.is_some() && updates.closed_at.is_none() {
-                    // Reopening (or fixing state): Clear closed_at if it was set
-                    issue.closed_at = None;
-                    add_update("closed_at", Box::new(None::<String>));
-                }
+            // Record Closed event if status is now Closed
+            if *status == Status::Closed {
+                let reason = updates.close_reason.as_ref().and_then(Clone::clone);
+                ctx.record_event(EventType::Closed, id, reason);
 
-                if !updates.skip_cache_rebuild {
-                    ctx.invalidate_cache();
+                // Auto-set closed_at if not provided
+                if updates.closed_at.is_none() && issue.closed_at.is_none() {
+                    let now = Utc::now();
+                    issue.closed_at = Some(now);
+                    add_update("closed_at", Box::new(Some(now.to_rfc3339())));
                 }
+            } else if issue.closed_at.is_some() && updates.closed_at.is_none() {
+                // Reopening (or fixing state): Clear closed_at if it was set
+                issue.closed_at = None;
+                add_update("closed_at", Box::new(None::<String>));
             }
 
-            // Priority
-            if let Some(priority) = updates.priority {
-                let old_priority = issue.priority.0;
-                issue.priority = priority;
-                add_update("priority", Box::new(priority.0));
-                if priority.0 != old_priority {
-                    ctx.record_field_change(
-                        EventType::PriorityChanged,
-                        id,
-                        Some(old_priority.to_string()),
-                        Some(priority.0.to_string()),
-                        None,
-                    );
-                }
+            if !updates.skip_cache_rebuild {
+                ctx.invalidate_cache();
             }
+        }
 
-            // Issue type
-            if let Some(ref issue_type) = updates.issue_type {
-                issue.issue_type.clone_from(issue_type);
-                add_update("issue_type", Box::new(issue_type.as_str().to_string()));
+        // Priority
+        if let Some(priority) = updates.priority {
+            let old_priority = issue.priority.0;
+            issue.priority = priority;
+            add_update("priority", Box::new(priority.0));
+            if priority.0 != old_priority {
+                ctx.record_field_event(
+                    EventType::PriorityChanged,
+                    id,
+                    "priority",
+                    Some(old_priority.to_string()),
+                    Some(priority.0.to_string()),
+                    None,
+                );
             }
+        }
 
-            // Assignee
-            if let Some(ref assignee_opt) = updates.assignee {
-                let old_assignee = issue.assignee.clone();
-                issue.assignee.clone_from(assignee_opt);
-                add_update("assignee", Box::new(assignee_opt.clone()));
-                if old_assignee != *assignee_opt {
-                    ctx.record_field_change(
-                        EventType::AssigneeChanged,
-                        id,
-                        old_assignee,
-                        assignee_opt.clone(),
-                        None,
-                    );
-                }
-            }
+        // Issue type
+        if let Some(ref issue_type) = updates.issue_type {
+            issue.issue_type.clone_from(issue_type);
+            add_update("issue_type", Box::new(issue_type.as_str().to_string()));
+        }
 
-            // Simple Option fields - use empty string instead of NULL for bd compatibility
-            if let Some(ref val) = updates.owner {
-                issue.owner.clone_from(val);
-                add_update("owner", Box::new(val.as_deref().unwrap_or("").to_string()));
-            }
-            if let Some(ref val) = updates.estimated_minutes {
-                issue.estimated_minutes = *val;
-                add_update("estimated_minutes", Box::new(*val));
-            }
-            if let Some(ref val) = updates.external_ref {
-                issue.external_ref.clone_from(val);
-                add_update("external_ref", Box::new(val.clone()));
-            }
-            // Use empty string instead of NULL for bd compatibility
-            if let Some(ref val) = updates.close_reason {
-                issue.close_reason.clone_from(val);
-                add_update(
-                    "close_reason",
-                    Box::new(val.as_deref().unwrap_or("").to_string()),
-                );
-            }
-            if let Some(ref val) = updates.closed_by_session {
-                issue.closed_by_session.clone_from(val);
-                add_update(
-                    "closed_by_session",
-                    Box::new(val.as_deref().unwrap_or("").to_string()),
+        // Assignee
+        if let Some(ref assignee_opt) = updates.assignee {
+            let old_assignee = issue.assignee.clone();
+            issue.assignee.clone_from(assignee_opt);
+            add_update("assignee", Box::new(assignee_opt.clone()));
+            if old_assignee != *assignee_opt {
+                ctx.record_field_event(
+                    EventType::AssigneeChanged,
+                    id,
+                    "assignee",
+                    old_assignee,
+                    assignee_opt.clone(),
+                    None,
                 );
             }
+        }
 
-            // Tombstone fields
-            if let Some(ref val) = updates.deleted_at {
-                issue.deleted_at = *val;
-                add_update("deleted_at", Box::new(val.map(|d| d.to_rfc3339())));
-            }
-            // Use empty string instead of NULL for bd compatibility
-            if let Some(ref val) = updates.deleted_by {
-                issue.deleted_by.clone_from(val);
-                add_update(
-                    "deleted_by",
-                    Box::new(val.as_deref().unwrap_or("").to_string()),
-                );
-            }
-            if let Some(ref val) = updates.delete_reason {
-                issue.delete_reason.clone_from(val);
-                add_update(
-                    "delete_reason",
-                    Box::new(val.as_deref().unwrap_or("").to_string()),
-                );
-            }
+        // Simple Option fields - use empty string instead of NULL for bd compatibility
+        if let Some(ref val) = updates.owner {
+            issue.owner.clone_from(val);
+            add_update("owner", Box::new(val.as_deref().unwrap_or("").to_string()));
+        }
+        if let Some(ref val) = updates.estimated_minutes {
+            issue.estimated_minutes = *val;
+            add_update("estimated_minutes", Box::new(*val));
+        }
+        if let Some(ref val) = updates.external_ref {
+            issue.external_ref.clone_from(val);
+            add_update("external_ref", Box::new(val.clone()));
+        }
+        // Use empty string instead of NULL for bd compatibility
+        if let Some(ref val) = updates.close_reason {
+            issue.close_reason.clone_from(val);
+            add_update(
+                "close_reason",
+                Box::new(val.as_deref().unwrap_or("").to_string()),
+            );
+        }
+        if let Some(ref val) = updates.closed_by_session {
+            issue.closed_by_session.clone_from(val);
+            add_update(
+                "closed_by_session",
+                Box::new(val.as_deref().unwrap_or("").to_string()),
+            );
+        }
 
-            // Date fields
-            if let Some(ref val) = updates.due_at {
-                issue.due_at = *val;
-                add_update("due_at", Box::new(val.map(|d| d.to_rfc3339())));
-            }
-            if let Some(ref val) = updates.defer_until {
-                issue.defer_until = *val;
-                add_update("defer_until", Box::new(val.map(|d| d.to_rfc3339())));
-            }
-            if let Some(ref val) = updates.closed_at {
-                issue.closed_at = *val;
-                add_update("closed_at", Box::new(val.map(|d| d.to_rfc3339())));
-            }
+        // Tombstone fields
+        if let Some(ref val) = updates.deleted_at {
+            issue.deleted_at = *val;
+            add_update("deleted_at", Box::new(val.map(|d| d.to_rfc3339())));
+        }
+        // Use empty string instead of NULL for bd compatibility
+        if let Some(ref val) = updates.deleted_by {
+            issue.deleted_by.clone_from(val);
+            add_update(
+                "deleted_by",
+                Box::new(val.as_deref().unwrap_or("").to_string()),
+            );
+        }
+        if let Some(ref val) = updates.delete_reason {
+            issue.delete_reason.clone_from(val);
+            add_update(
+                "delete_reason",
+                Box::new(val.as_deref().unwrap_or("").to_string()),
+            );
+        }
 
-            // Always update updated_at
-            set_clauses.push("updated_at = ?".to_string());
-            params.push(Box::new(Utc::now().to_rfc3339()));
+        // Date fields
+        if let Some(ref val) = updates.due_at {
+            issue.due_at = *val;
+            add_update("due_at", Box::new(val.map(|d| d.to_rfc3339())));
+        }
+        if let Some(ref val) = updates.defer_until {
+            issue.defer_until = *val;
+            add_update("defer_until", Box::new(val.map(|d| d.to_rfc3339())));
+        }
+        if let Some(ref val) = updates.closed_at {
+            issue.closed_at = *val;
+            add_update("closed_at", Box::new(val.map(|d| d.to_rfc3339())));
+        }
 
-            // Update content hash
-            let new_hash = issue.compute_content_hash();
-            set_clauses.push("content_hash = ?".to_string());
-            params.push(Box::new(new_hash));
+        // Remaining field changes are recorded generically so undo can replay them
+        undo::record_field_changes(ctx, &before, &issue, undo::EXPLICIT_EVENT_FIELDS);
 
-            // Build and execute SQL
-            let sql = format!("UPDATE issues SET {} WHERE id = ? ", set_clauses.join(", "));
-            params.push(Box::new(id.to_string()));
+        // Always update updated_at
+        set_clauses.push("updated_at = ?".to_string());
+        params.push(Box::new(Utc::now().to_rfc3339()));
 
-            let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(AsRef::as_ref).collect();
-            tx.execute(&sql, params_refs.as_slice())?;
+        // Update content hash
+        let new_hash = issue.compute_content_hash();
+        set_clauses.push("content_hash = ?".to_string());
+        params.push(Box::new(new_hash));
 
-            ctx.mark_dirty(id);
+        // Build and execute SQL
+        let sql = format!("UPDATE issues SET {} WHERE id = ? ", set_clauses.join(", "));
+        params.push(Box::new(id.to_string()));
 
-            Ok(())
-        })?;
+        let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(AsRef::as_ref).collect();
+        tx.execute(&sql, params_refs.as_slice())?;
 
-        // Return updated issue
-        self.get_issue(id)?
-            .ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() })
+        ctx.mark_dirty(id);
+
+        // A lease only covers the claim it was taken with
+        if updates.status.is_some() || updates.assignee.is_some() {
+            lease::release_stale_in_tx(tx, &issue)?;
+        }
+
+        Self::fetch_issue(tx, id)?.ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() })
     }
 
     /// Delete an issue by creating a tombstone.
@@ -653,6 +971,12 @@ impl SqliteStorage {
 
         let original_type = issue.issue_type.as_str().to_string();
         let timestamp = deleted_at.unwrap_or_else(Utc::now);
+        let mut tombstone = issue.clone();
+        tombstone.status = Status::Tombstone;
+        tombstone.deleted_at = Some(timestamp);
+        tombstone.deleted_by = Some(actor.to_string());
+        tombstone.delete_reason = Some(reason.to_string());
+        tombstone.original_type = Some(original_type.clone());
 
         self.mutate("delete_issue", actor, |tx, ctx| {
             tx.execute(
@@ -674,6 +998,7 @@ impl SqliteStorage {
                 ],
             )?;
 
+            undo::record_field_changes(ctx, &issue, &tombstone, &[]);
             ctx.record_event(
                 EventType::Deleted,
                 id,
@@ -689,12 +1014,126 @@ impl SqliteStorage {
             .ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() })
     }
 
+    /// Permanently remove issues and everything attached to them.
+    ///
+    /// Labels, comments, events and outgoing dependencies go with the issue
+    /// rows (`ON DELETE CASCADE`). Dependencies pointing at a purged issue
+    /// are deleted too, and their owners are marked dirty. Nothing is
+    /// recorded for `undo`; operations left without events are dropped.
+    ///
+    /// Returns the number of issues removed. Unknown IDs are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    pub fn purge_issues(&mut self, ids: &[String]) -> Result<usize> {
+        let tx = self
+            .conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+        let now = Utc::now().to_rfc3339();
+        let mut purged = 0;
+        for id in ids {
+            let dependents: Vec<String> = {
+                let mut stmt = tx.prepare_cached(
+                    "SELECT DISTINCT issue_id FROM dependencies
+                     WHERE depends_on_id = ?1 AND issue_id != ?1",
+                )?;
+                stmt.query_map([id], |row| row.get(0))?
+                    .collect::<rusqlite::Result<_>>()?
+            };
+            tx.execute("DELETE FROM dependencies WHERE depends_on_id = ?", [id])?;
+            for dependent in &dependents {
+                tx.execute(
+                    "INSERT OR REPLACE INTO dirty_issues (issue_id, marked_at) VALUES (?, ?)",
+                    rusqlite::params![dependent, now],
+                )?;
+            }
+            tx.execute(
+                "DELETE FROM issues_fts WHERE rowid IN (SELECT rowid FROM issues WHERE id = ?)",
+                [id],
+            )?;
+            purged += tx.execute("DELETE FROM issues WHERE id = ?", [id])?;
+        }
+        if purged > 0 {
+            tx.execute(
+                "DELETE FROM operations
+                 WHERE id NOT IN (SELECT op_id FROM events WHERE op_id IS NOT NULL)",
+                [],
+            )?;
+            Self::rebuild_blocked_cache_impl(&tx)?;
+        }
+        tx.commit()?;
+        Ok(purged)
+    }
+
+    /// Replace an issue's text with its compacted form.
+    ///
+    /// Writes the description, design, acceptance criteria and notes of
+    /// `compacted` along with its compaction metadata, and records a
+    /// `compacted` event. Field changes are recorded so `undo` restores the
+    /// original text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the issue doesn't exist or the update fails.
+    pub fn compact_issue(&mut self, compacted: &Issue, actor: &str) -> Result<()> {
+        let id = compacted.id.as_str();
+        let before = self
+            .get_issue(id)?
+            .ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() })?;
+        let level = compacted.compaction_level.unwrap_or(0);
+
+        self.mutate("compact", actor, |tx, ctx| {
+            tx.execute(
+                "UPDATE issues SET
+                    description = ?,
+                    design = ?,
+                    acceptance_criteria = ?,
+                    notes = ?,
+                    compaction_level = ?,
+                    compacted_at = ?,
+                    compacted_at_commit = ?,
+                    original_size = ?,
+                    updated_at = ?,
+                    content_hash = ?
+                 WHERE id = ?",
+                rusqlite::params![
+                    compacted.description.as_deref().unwrap_or(""),
+                    compacted.design.as_deref().unwrap_or(""),
+                    compacted.acceptance_criteria.as_deref().unwrap_or(""),
+                    compacted.notes.as_deref().unwrap_or(""),
+                    level,
+                    compacted.compacted_at.map(|dt| dt.to_rfc3339()),
+                    compacted.compacted_at_commit,
+                    compacted.original_size,
+                    Utc::now().to_rfc3339(),
+                    compacted.compute_content_hash(),
+                    id
+                ],
+            )?;
+
+            undo::record_field_changes(ctx, &before, compacted, &[]);
+            ctx.record_event(
+                EventType::Compacted,
+                id,
+                Some(format!("Compacted to level {level}")),
+            );
+            ctx.mark_dirty(id);
+            Ok(())
+        })
+    }
+
     /// Get an issue by ID.
     ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
     pub fn get_issue(&self, id: &str) -> Result<Option<Issue>> {
+        Self::fetch_issue(&self.conn, id)
+    }
+
+    /// Fetch an issue on an arbitrary connection (e.g. inside a mutation).
+    pub(crate) fn fetch_issue(conn: &Connection, id: &str) -> Result<Option<Issue>> {
         let sql = r"
             SELECT id, content_hash, title, description, design, acceptance_criteria, notes,
                    status, priority, issue_type, assignee, owner, estimated_minutes,
@@ -706,8 +1145,8 @@ impl SqliteStorage {
             FROM issues WHERE id = ?
         ";
 
-        let mut stmt = self.conn.prepare_cached(sql)?;
-        let result = stmt.query_row([id], |row| self.issue_from_row(row));
+        let mut stmt = conn.prepare_cached(sql)?;
+        let result = stmt.query_row([id], Self::issue_from_row);
 
         match result {
             Ok(issue) => Ok(Some(issue)),
@@ -749,7 +1188,7 @@ impl SqliteStorage {
 
             let mut stmt = self.conn.prepare(&sql)?;
             let chunk_issues = stmt
-                .query_map(params.as_slice(), |row| self.issue_from_row(row))?
+                .query_map(params.as_slice(), Self::issue_from_row)?
                 .collect::<std::result::Result<Vec<_>, _>>()?;
             issues.extend(chunk_issues);
         }
@@ -818,11 +1257,13 @@ impl SqliteStorage {
         }
 
         if !filters.include_closed {
-            if filters.include_deferred {
-                sql.push_str(" AND status NOT IN ('closed', 'tombstone')");
+            let extra: &[&str] = if filters.include_deferred {
+                &[]
             } else {
-                sql.push_str(" AND status NOT IN ('closed', 'tombstone', 'deferred')");
-            }
+                &["deferred"]
+            };
+            let excluded = status_sql_list(StatusCategory::Terminal, extra);
+            let _ = write!(sql, " AND status NOT IN ({excluded})");
         }
 
         if !filters.include_templates {
@@ -866,6 +1307,10 @@ impl SqliteStorage {
             params.push(Box::new(ts.to_rfc3339()));
         }
 
+        if let Some(ref expr) = filters.filter_expr {
+            expr.push_sql(&mut sql, &mut params);
+        }
+
         // Apply custom sort if provided
         if let Some(ref sort_field) = filters.sort {
             let order = if filters.reverse { "DESC" } else { "ASC" };
@@ -912,7 +1357,7 @@ impl SqliteStorage {
         let mut stmt = self.conn.prepare(&sql)?;
         let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(AsRef::as_ref).collect();
         let issues = stmt
-            .query_map(params_refs.as_slice(), |row| self.issue_from_row(row))?
+            .query_map(params_refs.as_slice(), Self::issue_from_row)?
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
         Ok(issues)
@@ -920,32 +1365,79 @@ impl SqliteStorage {
 
     /// Search issues by query with optional filters.
     ///
+    /// Thin wrapper over [`Self::search_issues_ranked`] that drops ranking data.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the database query fails.
-    #[allow(clippy::too_many_lines)]
+    /// Returns an error if the query syntax is invalid or the database query fails.
     pub fn search_issues(&self, query: &str, filters: &ListFilters) -> Result<Vec<Issue>> {
+        Ok(self
+            .search_issues_ranked(query, filters)?
+            .into_iter()
+            .map(|hit| hit.issue)
+            .collect())
+    }
+
+    /// Full-text search over title, description, design, acceptance criteria,
+    /// notes, and comments, ranked by bm25.
+    ///
+    /// The query supports FTS5 syntax: `"quoted phrases"`, `prefix*`,
+    /// `AND`/`OR`/`NOT`, parentheses, and `column:term` for the indexed
+    /// columns. Other punctuation is treated literally. The classic substring
+    /// match on title, description, and ID is kept as a fallback; those hits
+    /// carry no score and sort after ranked matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error for malformed query syntax, or an error if
+    /// the database query fails.
+    #[allow(clippy::too_many_lines)]
+    pub fn search_issues_ranked(
+        &self,
+        query: &str,
+        filters: &ListFilters,
+    ) -> Result<Vec<SearchHit>> {
         let trimmed = query.trim();
         if trimmed.is_empty() {
             return Ok(Vec::new());
         }
 
-        let mut sql = String::from(
-            r"SELECT id, content_hash, title, description, design, acceptance_criteria, notes,
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        let mut sql = match build_fts_query(trimmed) {
+            Some(fts_query) => {
+                params.push(Box::new(fts_query));
+                String::from(
+                    r"WITH matches AS MATERIALIZED (
+                  SELECT issue_id,
+                         bm25(issues_fts, 0.0, 10.0, 5.0, 2.0, 2.0, 2.0, 1.0) AS rank,
+                         snippet(issues_fts, -1, '**', '**', '...', 16) AS snippet
+                  FROM issues_fts
+                  WHERE issues_fts MATCH ?
+              )",
+                )
+            }
+            None => String::from(
+                "WITH matches AS (SELECT NULL AS issue_id, NULL AS rank, NULL AS snippet WHERE 0)",
+            ),
+        };
+
+        sql.push_str(
+            r"
+              SELECT id, content_hash, title, description, design, acceptance_criteria, notes,
                      status, priority, issue_type, assignee, owner, estimated_minutes,
                      created_at, created_by, updated_at, closed_at, close_reason, closed_by_session,
                      due_at, defer_until, external_ref, source_system, source_repo,
                      deleted_at, deleted_by, delete_reason, original_type,
                      compaction_level, compacted_at, compacted_at_commit, original_size,
-                     sender, ephemeral, pinned, is_template
+                     sender, ephemeral, pinned, is_template,
+                     matches.rank, matches.snippet
               FROM issues
-              WHERE 1=1",
-        );
-
-        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
-
-        sql.push_str(
-            " AND (title LIKE ? ESCAPE '\\' OR description LIKE ? ESCAPE '\\' OR id LIKE ? ESCAPE '\\')",
+              LEFT JOIN matches ON matches.issue_id = issues.id
+              WHERE (matches.issue_id IS NOT NULL
+                     OR title LIKE ? ESCAPE '\'
+                     OR description LIKE ? ESCAPE '\'
+                     OR issues.id LIKE ? ESCAPE '\')",
         );
         let escaped = escape_like_pattern(trimmed);
         let pattern = format!("%{escaped}%");
@@ -994,7 +1486,8 @@ impl SqliteStorage {
         }
 
         if !filters.include_closed {
-            sql.push_str(" AND status NOT IN ('closed', 'tombstone')");
+            let excluded = status_sql_list(StatusCategory::Terminal, &[]);
+            let _ = write!(sql, " AND status NOT IN ({excluded})");
         }
 
         if !filters.include_templates {
@@ -1028,7 +1521,11 @@ impl SqliteStorage {
             params.push(Box::new(format!("%{escaped}%")));
         }
 
-        sql.push_str(" ORDER BY priority ASC, created_at DESC");
+        if let Some(ref expr) = filters.filter_expr {
+            expr.push_sql(&mut sql, &mut params);
+        }
+
+        sql.push_str(" ORDER BY COALESCE(matches.rank, 0.0) ASC, priority ASC, created_at DESC");
 
         if let Some(limit) = filters.limit {
             if limit > 0 {
@@ -1037,30 +1534,104 @@ impl SqliteStorage {
             }
         }
 
-        let mut stmt = self.conn.prepare(&sql)?;
+        let mut stmt = self.conn.prepare(&sql).map_err(map_fts_error)?;
         let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(AsRef::as_ref).collect();
-        let issues = stmt
-            .query_map(params_refs.as_slice(), |row| self.issue_from_row(row))?
-            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let hits = stmt
+            .query_map(params_refs.as_slice(), |row| {
+                let rank: Option<f64> = row.get(36)?;
+                Ok(SearchHit {
+                    issue: Self::issue_from_row(row)?,
+                    score: rank.map(|r| -r),
+                    snippet: row.get(37)?,
+                })
+            })
+            .map_err(map_fts_error)?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(map_fts_error)?;
 
-        Ok(issues)
+        Ok(hits)
     }
 
     /// Get ready issues (unblocked, not deferred, not pinned, not ephemeral).
     ///
-    /// Ready definition:
-    /// 1. Status is `open` OR `in_progress`
-    /// 2. NOT in `blocked_issues_cache`
-    /// 3. `defer_until` is NULL or <= now (unless `include_deferred`)
-    /// 4. `pinned = 0` (not pinned)
-    /// 5. `ephemeral = 0` AND ID does not contain `-wisp-`
+    /// Ready definition:
+    /// 1. Status is `open` OR `in_progress`
+    /// 2. NOT in `blocked_issues_cache`
+    /// 3. `defer_until` is NULL or <= now (unless `include_deferred`)
+    /// 4. `pinned = 0` (not pinned)
+    /// 5. `ephemeral = 0` AND ID does not contain `-wisp-`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn get_ready_issues(
+        &self,
+        filters: &ReadyFilters,
+        sort: ReadySortPolicy,
+    ) -> Result<Vec<Issue>> {
+        Self::query_ready_issues(&self.conn, filters, sort)
+    }
+
+    /// Claim the first issue `get_ready_issues` would list that `accept`
+    /// allows, choosing and claiming inside one IMMEDIATE transaction so
+    /// concurrent callers never get the same issue.
+    ///
+    /// Expired leases are released first. Only unassigned issues are
+    /// considered. The claimed issue becomes `in_progress` and assigned to
+    /// `actor`. Returns `None` when nothing qualifies.
     ///
     /// # Errors
     ///
-    /// Returns an error if the database query fails.
+    /// Returns an error if the query or the write fails.
+    pub fn claim_next_ready(
+        &mut self,
+        filters: &ReadyFilters,
+        sort: ReadySortPolicy,
+        actor: &str,
+        accept: impl Fn(&Issue) -> bool,
+    ) -> Result<Option<Issue>> {
+        let filters = ReadyFilters {
+            unassigned: true,
+            limit: None,
+            ..filters.clone()
+        };
+        self.mutate("claim_next", actor, |tx, ctx| {
+            // A lapsed claim is fair game; hand it back before choosing.
+            lease::expire_in_tx(tx, ctx, Utc::now())?;
+            let Some(before) = Self::query_ready_issues(tx, &filters, sort)?
+                .into_iter()
+                .find(|issue| accept(issue))
+            else {
+                return Ok(None);
+            };
+
+            let mut after = before.clone();
+            after.status = Status::InProgress;
+            after.assignee = Some(actor.to_string());
+            after.closed_at = None;
+            after.updated_at = Utc::now();
+            tx.execute(
+                "UPDATE issues SET status = ?, assignee = ?, closed_at = NULL, updated_at = ?,
+                 content_hash = ? WHERE id = ?",
+                rusqlite::params![
+                    after.status.as_str(),
+                    actor,
+                    after.updated_at.to_rfc3339(),
+                    after.compute_content_hash(),
+                    after.id
+                ],
+            )?;
+            lease::release_stale_in_tx(tx, &after)?;
+            undo::record_field_changes(ctx, &before, &after, &[]);
+            ctx.mark_dirty(&after.id);
+            ctx.invalidate_cache();
+            Ok(Some(after))
+        })
+    }
+
     #[allow(clippy::too_many_lines)]
-    pub fn get_ready_issues(
-        &self,
+    fn query_ready_issues(
+        conn: &Connection,
         filters: &ReadyFilters,
         sort: ReadySortPolicy,
     ) -> Result<Vec<Issue>> {
@@ -1077,12 +1648,14 @@ impl SqliteStorage {
 
         let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
 
-        // Ready condition 1: status is `open` OR `in_progress`
-        if filters.include_deferred {
-            sql.push_str(" AND status IN ('open', 'in_progress', 'deferred')");
+        // Ready condition 1: status is active (`open`, `in_progress`, custom active)
+        let extra: &[&str] = if filters.include_deferred {
+            &["deferred"]
         } else {
-            sql.push_str(" AND status IN ('open', 'in_progress')");
-        }
+            &[]
+        };
+        let ready_statuses = status_sql_list(StatusCategory::Active, extra);
+        let _ = write!(sql, " AND status IN ({ready_statuses})");
 
         // Ready condition 2: NOT in blocked_issues_cache (NOT EXISTS is faster than NOT IN)
         sql.push_str(
@@ -1105,6 +1678,15 @@ impl SqliteStorage {
         // Exclude templates
         sql.push_str(" AND (is_template = 0 OR is_template IS NULL)");
 
+        // Exclude issues under someone else's live lease
+        if let Some(ref actor) = filters.lease_actor {
+            sql.push_str(
+                " AND NOT EXISTS (SELECT 1 FROM leases WHERE leases.issue_id = issues.id
+                    AND leases.actor != ? AND datetime(leases.expires_at) > datetime('now'))",
+            );
+            params.push(Box::new(actor.clone()));
+        }
+
         // Filter by types
         if let Some(ref types) = filters.types {
             if !types.is_empty() {
@@ -1191,6 +1773,11 @@ impl SqliteStorage {
             params.push(Box::new(parent_id.clone()));
         }
 
+        // Filter expression (--where)
+        if let Some(ref expr) = filters.filter_expr {
+            expr.push_sql(&mut sql, &mut params);
+        }
+
         // Sorting
         match sort {
             ReadySortPolicy::Hybrid => {
@@ -1203,26 +1790,74 @@ impl SqliteStorage {
             ReadySortPolicy::Oldest => {
                 sql.push_str(" ORDER BY created_at ASC");
             }
+            ReadySortPolicy::Score => {
+                // Tie order; the score itself is applied below.
+                sql.push_str(" ORDER BY priority ASC, created_at ASC");
+            }
         }
 
         // Apply limit in SQL to avoid fetching extra rows.
-        if let Some(limit) = filters.limit {
-            if limit > 0 {
-                sql.push_str(" LIMIT ?");
-                let limit_i64 = i64::try_from(limit).unwrap_or(i64::MAX);
-                params.push(Box::new(limit_i64));
-            }
+        let limit = filters.limit.filter(|limit| *limit > 0);
+        if let Some(limit) = limit.filter(|_| sort != ReadySortPolicy::Score) {
+            sql.push_str(" LIMIT ?");
+            let limit_i64 = i64::try_from(limit).unwrap_or(i64::MAX);
+            params.push(Box::new(limit_i64));
         }
 
-        let mut stmt = self.conn.prepare(&sql)?;
+        let mut stmt = conn.prepare(&sql)?;
         let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(AsRef::as_ref).collect();
-        let issues: Vec<Issue> = stmt
-            .query_map(params_refs.as_slice(), |row| self.issue_from_row(row))?
+        let mut issues: Vec<Issue> = stmt
+            .query_map(params_refs.as_slice(), Self::issue_from_row)?
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
+        if sort == ReadySortPolicy::Score {
+            let scores = Self::score_issues_in(conn, &issues, &filters.score_weights, Utc::now())?;
+            issues.sort_by(|a, b| scores[&b.id].total.total_cmp(&scores[&a.id].total));
+            if let Some(limit) = limit {
+                issues.truncate(limit);
+            }
+        }
+
         Ok(issues)
     }
 
+    /// Score issues for ready work; see [`crate::storage::scoring`] for the terms.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if labels or dependents cannot be read.
+    pub fn score_issues(
+        &self,
+        issues: &[Issue],
+        weights: &ScoreWeights,
+        now: DateTime<Utc>,
+    ) -> Result<HashMap<String, ScoreBreakdown>> {
+        Self::score_issues_in(&self.conn, issues, weights, now)
+    }
+
+    fn score_issues_in(
+        conn: &Connection,
+        issues: &[Issue],
+        weights: &ScoreWeights,
+        now: DateTime<Utc>,
+    ) -> Result<HashMap<String, ScoreBreakdown>> {
+        let ids: Vec<String> = issues.iter().map(|issue| issue.id.clone()).collect();
+        let labels = Self::labels_for_issues_in(conn, &ids)?;
+        let dependents = Self::count_dependents_in(conn, &ids, true)?;
+        Ok(issues
+            .iter()
+            .map(|issue| {
+                let score = weights.score(
+                    issue,
+                    labels.get(&issue.id).map_or(&[], Vec::as_slice),
+                    dependents.get(&issue.id).copied().unwrap_or(0),
+                    now,
+                );
+                (issue.id.clone(), score)
+            })
+            .collect())
+    }
+
     /// Get IDs of blocked issues from cache.
     ///
     /// # Errors
@@ -1249,17 +1884,18 @@ impl SqliteStorage {
     pub fn get_blocked_by_blocks_deps_only(&self) -> Result<HashSet<String>> {
         // Returns issues that:
         // 1. Have a 'blocks' type dependency
-        // 2. Where the blocker is not closed/tombstone
-        // 3. AND the blocked issue itself is not closed/tombstone
-        let mut stmt = self.conn.prepare_cached(
+        // 2. Where the blocker is not terminal (closed/tombstone/custom terminal)
+        // 3. AND the blocked issue itself is not terminal
+        let terminal = status_sql_list(StatusCategory::Terminal, &[]);
+        let mut stmt = self.conn.prepare_cached(&format!(
             r"SELECT DISTINCT d.issue_id
               FROM dependencies d
               LEFT JOIN issues blocker ON d.depends_on_id = blocker.id
               LEFT JOIN issues blocked ON d.issue_id = blocked.id
               WHERE d.type = 'blocks'
-                AND blocker.status NOT IN ('closed', 'tombstone')
-                AND blocked.status NOT IN ('closed', 'tombstone')",
-        )?;
+                AND blocker.status NOT IN ({terminal})
+                AND blocked.status NOT IN ({terminal})"
+        ))?;
         let ids = stmt
             .query_map([], |row| row.get(0))?
             .collect::<std::result::Result<HashSet<String>, _>>()?;
@@ -1290,8 +1926,29 @@ impl SqliteStorage {
     ///
     /// Returns an error if the database query fails.
     pub fn get_blockers(&self, issue_id: &str) -> Result<Vec<String>> {
-        let json_opt: Option<String> = self
-            .conn
+        Self::blockers_in(&self.conn, issue_id)
+    }
+
+    /// Blockers of an issue as seen inside an open mutation. The blocked
+    /// cache is rebuilt first if earlier steps invalidated it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache rebuild or query fails.
+    pub(crate) fn blockers_in_tx(
+        tx: &Transaction<'_>,
+        ctx: &mut MutationContext,
+        issue_id: &str,
+    ) -> Result<Vec<String>> {
+        if ctx.invalidate_blocked_cache {
+            Self::rebuild_blocked_cache_impl(tx)?;
+            ctx.invalidate_blocked_cache = false;
+        }
+        Self::blockers_in(tx, issue_id)
+    }
+
+    fn blockers_in(conn: &Connection, issue_id: &str) -> Result<Vec<String>> {
+        let json_opt: Option<String> = conn
             .prepare_cached("SELECT blocked_by FROM blocked_issues_cache WHERE issue_id = ?")?
             .query_row([issue_id], |row| row.get(0))
             .optional()?;
@@ -1356,19 +2013,20 @@ impl SqliteStorage {
         let mut blocked_issues_map: std::collections::HashMap<String, Vec<String>> =
             std::collections::HashMap::new();
         {
-            let mut stmt = conn.prepare(
+            let terminal = status_sql_list(StatusCategory::Terminal, &[]);
+            let mut stmt = conn.prepare(&format!(
                 r"SELECT DISTINCT d.issue_id, d.depends_on_id || ':' || COALESCE(i.status, 'unknown')
                   FROM dependencies d
                   LEFT JOIN issues i ON d.depends_on_id = i.id
                   WHERE d.type IN ('blocks', 'conditional-blocks', 'waits-for')
                     AND (
                       -- The blocker is in a blocking state (anything not terminal)
-                      i.status NOT IN ('closed', 'tombstone')
+                      i.status NOT IN ({terminal})
                       -- Or it's a missing local dependency (orphan)
                       -- External dependencies are resolved at runtime in the CLI
                       OR (i.id IS NULL AND d.depends_on_id NOT LIKE 'external:%')
-                    )",
-            )?;
+                    )"
+            ))?;
 
             let rows = stmt.query_map([], |row| {
                 Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
@@ -1471,7 +2129,8 @@ impl SqliteStorage {
     ///
     /// Returns an error if the database query fails.
     pub fn get_blocked_issues(&self) -> Result<Vec<(Issue, Vec<String>)>> {
-        let mut stmt = self.conn.prepare(
+        let active = status_sql_list(StatusCategory::Active, &[]);
+        let mut stmt = self.conn.prepare(&format!(
             r"SELECT i.id, i.content_hash, i.title, i.description, i.design, i.acceptance_criteria, i.notes,
                      i.status, i.priority, i.issue_type, i.assignee, i.owner, i.estimated_minutes,
                      i.created_at, i.created_by, i.updated_at, i.closed_at, i.close_reason, i.closed_by_session,
@@ -1482,13 +2141,13 @@ impl SqliteStorage {
                      bc.blocked_by
               FROM issues i
               INNER JOIN blocked_issues_cache bc ON i.id = bc.issue_id
-              WHERE i.status IN ('open', 'in_progress')
-              ORDER BY i.priority ASC, i.created_at ASC",
-        )?;
+              WHERE i.status IN ({active})
+              ORDER BY i.priority ASC, i.created_at ASC"
+        ))?;
 
         let results = stmt
             .query_map([], |row| {
-                let issue = self.issue_from_row(row)?;
+                let issue = Self::issue_from_row(row)?;
                 let blockers_json: String = row.get(36)?;
                 Ok((issue, blockers_json))
             })?
@@ -1748,16 +2407,17 @@ impl SqliteStorage {
     ///
     /// Returns an error if the database query fails.
     pub fn get_epic_counts(&self) -> Result<std::collections::HashMap<String, (usize, usize)>> {
-        let mut stmt = self.conn.prepare(
+        let terminal = status_sql_list(StatusCategory::Terminal, &[]);
+        let mut stmt = self.conn.prepare(&format!(
             "SELECT
                 d.depends_on_id AS epic_id,
                 COUNT(*) AS total,
-                SUM(CASE WHEN i.status = 'closed' OR i.status = 'tombstone' THEN 1 ELSE 0 END) AS closed
+                SUM(CASE WHEN i.status IN ({terminal}) THEN 1 ELSE 0 END) AS closed
              FROM dependencies d
              JOIN issues i ON d.issue_id = i.id
              WHERE d.type = 'parent-child'
-             GROUP BY d.depends_on_id",
-        )?;
+             GROUP BY d.depends_on_id"
+        ))?;
         let mut counts = std::collections::HashMap::new();
         #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
         let rows = stmt.query_map([], |row| {
@@ -1785,10 +2445,30 @@ impl SqliteStorage {
         depends_on_id: &str,
         dep_type: &str,
         actor: &str,
+    ) -> Result<bool> {
+        self.mutate("add_dependency", actor, |tx, ctx| {
+            Self::add_dependency_in_tx(tx, ctx, issue_id, depends_on_id, dep_type, actor)
+        })
+    }
+
+    /// Add a dependency inside an open mutation. Returns false if the link
+    /// already exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a blocking dependency would create a cycle or the
+    /// write fails.
+    pub(crate) fn add_dependency_in_tx(
+        tx: &Transaction<'_>,
+        ctx: &mut MutationContext,
+        issue_id: &str,
+        depends_on_id: &str,
+        dep_type: &str,
+        actor: &str,
     ) -> Result<bool> {
         // Check for cycles if this is a blocking dependency
         if let Ok(dt) = dep_type.parse::<DependencyType>() {
-            if dt.is_blocking() && self.would_create_cycle(issue_id, depends_on_id, true)? {
+            if dt.is_blocking() && Self::check_cycle(tx, issue_id, depends_on_id, true)? {
                 return Err(BeadsError::DependencyCycle {
                     path: format!(
                         "Adding dependency {issue_id} -> {depends_on_id} would create a cycle"
@@ -1797,45 +2477,46 @@ impl SqliteStorage {
             }
         }
 
-        self.mutate("add_dependency", actor, |tx, ctx| {
-            let exists: i64 = tx.query_row(
-                "SELECT count(*) FROM dependencies WHERE issue_id = ? AND depends_on_id = ?",
-                rusqlite::params![issue_id, depends_on_id],
-                |row| row.get(0),
-            )?;
+        let exists: i64 = tx.query_row(
+            "SELECT count(*) FROM dependencies WHERE issue_id = ? AND depends_on_id = ?",
+            rusqlite::params![issue_id, depends_on_id],
+            |row| row.get(0),
+        )?;
 
-            if exists > 0 {
-                return Ok(false);
-            }
+        if exists > 0 {
+            return Ok(false);
+        }
 
-            tx.execute(
-                "INSERT INTO dependencies (issue_id, depends_on_id, type, created_at, created_by)
-                 VALUES (?, ?, ?, ?, ?)",
-                rusqlite::params![
-                    issue_id,
-                    depends_on_id,
-                    dep_type,
-                    Utc::now().to_rfc3339(),
-                    actor
-                ],
-            )?;
+        let created_at = Utc::now().to_rfc3339();
+        tx.execute(
+            "INSERT INTO dependencies (issue_id, depends_on_id, type, created_at, created_by)
+             VALUES (?, ?, ?, ?, ?)",
+            rusqlite::params![issue_id, depends_on_id, dep_type, created_at, actor],
+        )?;
 
-            // Bump updated_at
-            tx.execute(
-                "UPDATE issues SET updated_at = ? WHERE id = ?",
-                rusqlite::params![Utc::now().to_rfc3339(), issue_id],
-            )?;
+        // Bump updated_at
+        tx.execute(
+            "UPDATE issues SET updated_at = ? WHERE id = ?",
+            rusqlite::params![Utc::now().to_rfc3339(), issue_id],
+        )?;
 
-            ctx.record_event(
-                EventType::DependencyAdded,
-                issue_id,
-                Some(format!("Added dependency on {depends_on_id} ({dep_type})")),
-            );
-            ctx.mark_dirty(issue_id);
-            ctx.invalidate_cache();
+        ctx.record_field_event(
+            EventType::DependencyAdded,
+            issue_id,
+            "dependencies",
+            None,
+            Some(undo::dependency_value(
+                depends_on_id,
+                dep_type,
+                &created_at,
+                actor,
+            )),
+            Some(format!("Added dependency on {depends_on_id} ({dep_type})")),
+        );
+        ctx.mark_dirty(issue_id);
+        ctx.invalidate_cache();
 
-            Ok(true)
-        })
+        Ok(true)
     }
 
     /// Remove a dependency link.
@@ -1850,6 +2531,11 @@ impl SqliteStorage {
         actor: &str,
     ) -> Result<bool> {
         self.mutate("remove_dependency", actor, |tx, ctx| {
+            let links = undo::dependency_links(
+                tx,
+                "issue_id = ? AND depends_on_id = ?",
+                rusqlite::params![issue_id, depends_on_id],
+            )?;
             let rows = tx.execute(
                 "DELETE FROM dependencies WHERE issue_id = ? AND depends_on_id = ?",
                 rusqlite::params![issue_id, depends_on_id],
@@ -1862,11 +2548,16 @@ impl SqliteStorage {
                     rusqlite::params![Utc::now().to_rfc3339(), issue_id],
                 )?;
 
-                ctx.record_event(
-                    EventType::DependencyRemoved,
-                    issue_id,
-                    Some(format!("Removed dependency on {depends_on_id}")),
-                );
+                for (_, value) in links {
+                    ctx.record_field_event(
+                        EventType::DependencyRemoved,
+                        issue_id,
+                        "dependencies",
+                        Some(value),
+                        None,
+                        Some(format!("Removed dependency on {depends_on_id}")),
+                    );
+                }
                 ctx.mark_dirty(issue_id);
                 ctx.invalidate_cache();
             }
@@ -1890,6 +2581,12 @@ impl SqliteStorage {
             let affected: Vec<String> = stmt
                 .query_map(rusqlite::params![issue_id, issue_id], |row| row.get(0))?
                 .collect::<std::result::Result<Vec<_>, _>>()?;
+            drop(stmt);
+            let links = undo::dependency_links(
+                tx,
+                "issue_id = ?1 OR depends_on_id = ?1",
+                rusqlite::params![issue_id],
+            )?;
 
             let outgoing = tx.execute("DELETE FROM dependencies WHERE issue_id = ?", [issue_id])?;
             let incoming = tx.execute(
@@ -1915,11 +2612,17 @@ impl SqliteStorage {
                     )?;
                 }
 
-                ctx.record_event(
-                    EventType::DependencyRemoved,
-                    issue_id,
-                    Some(format!("Removed {total} dependency links")),
-                );
+                // One event per link, on the issue that owns it
+                for (owner, value) in links {
+                    ctx.record_field_event(
+                        EventType::DependencyRemoved,
+                        &owner,
+                        "dependencies",
+                        Some(value),
+                        None,
+                        Some(format!("Removed {total} dependency links")),
+                    );
+                }
                 ctx.mark_dirty(issue_id);
                 for affected_id in affected {
                     ctx.mark_dirty(&affected_id);
@@ -1938,6 +2641,11 @@ impl SqliteStorage {
     /// Returns an error if the database update fails.
     pub fn remove_parent(&mut self, issue_id: &str, actor: &str) -> Result<bool> {
         self.mutate("remove_parent", actor, |tx, ctx| {
+            let links = undo::dependency_links(
+                tx,
+                "issue_id = ? AND type = 'parent-child'",
+                rusqlite::params![issue_id],
+            )?;
             let rows = tx.execute(
                 "DELETE FROM dependencies WHERE issue_id = ? AND type = 'parent-child'",
                 rusqlite::params![issue_id],
@@ -1950,11 +2658,16 @@ impl SqliteStorage {
                     rusqlite::params![Utc::now().to_rfc3339(), issue_id],
                 )?;
 
-                ctx.record_event(
-                    EventType::DependencyRemoved,
-                    issue_id,
-                    Some("Removed parent".to_string()),
-                );
+                for (_, value) in links {
+                    ctx.record_field_event(
+                        EventType::DependencyRemoved,
+                        issue_id,
+                        "dependencies",
+                        Some(value),
+                        None,
+                        Some("Removed parent".to_string()),
+                    );
+                }
                 ctx.mark_dirty(issue_id);
                 ctx.invalidate_cache();
             }
@@ -1970,36 +2683,53 @@ impl SqliteStorage {
     /// Returns an error if the database update fails.
     pub fn add_label(&mut self, issue_id: &str, label: &str, actor: &str) -> Result<bool> {
         self.mutate("add_label", actor, |tx, ctx| {
-            let exists: i64 = tx.query_row(
-                "SELECT count(*) FROM labels WHERE issue_id = ? AND label = ?",
-                rusqlite::params![issue_id, label],
-                |row| row.get(0),
-            )?;
+            Self::add_label_in_tx(tx, ctx, issue_id, label)
+        })
+    }
 
-            if exists > 0 {
-                return Ok(false);
-            }
+    /// Add a label inside an open mutation. Returns false if it was already set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database update fails.
+    pub(crate) fn add_label_in_tx(
+        tx: &Transaction<'_>,
+        ctx: &mut MutationContext,
+        issue_id: &str,
+        label: &str,
+    ) -> Result<bool> {
+        let exists: i64 = tx.query_row(
+            "SELECT count(*) FROM labels WHERE issue_id = ? AND label = ?",
+            rusqlite::params![issue_id, label],
+            |row| row.get(0),
+        )?;
 
-            tx.execute(
-                "INSERT INTO labels (issue_id, label) VALUES (?, ?)",
-                rusqlite::params![issue_id, label],
-            )?;
+        if exists > 0 {
+            return Ok(false);
+        }
 
-            ctx.record_event(
-                EventType::LabelAdded,
-                issue_id,
-                Some(format!("Added label {label}")),
-            );
-            ctx.mark_dirty(issue_id);
+        tx.execute(
+            "INSERT INTO labels (issue_id, label) VALUES (?, ?)",
+            rusqlite::params![issue_id, label],
+        )?;
 
-            // Update timestamp
-            tx.execute(
-                "UPDATE issues SET updated_at = ? WHERE id = ?",
-                rusqlite::params![Utc::now().to_rfc3339(), issue_id],
-            )?;
+        ctx.record_field_event(
+            EventType::LabelAdded,
+            issue_id,
+            "labels",
+            None,
+            Some(label.to_string()),
+            Some(format!("Added label {label}")),
+        );
+        ctx.mark_dirty(issue_id);
 
-            Ok(true)
-        })
+        // Update timestamp
+        tx.execute(
+            "UPDATE issues SET updated_at = ? WHERE id = ?",
+            rusqlite::params![Utc::now().to_rfc3339(), issue_id],
+        )?;
+
+        Ok(true)
     }
 
     /// Remove a label from an issue.
@@ -2009,28 +2739,45 @@ impl SqliteStorage {
     /// Returns an error if the database update fails.
     pub fn remove_label(&mut self, issue_id: &str, label: &str, actor: &str) -> Result<bool> {
         self.mutate("remove_label", actor, |tx, ctx| {
-            let rows = tx.execute(
-                "DELETE FROM labels WHERE issue_id = ? AND label = ?",
-                rusqlite::params![issue_id, label],
-            )?;
+            Self::remove_label_in_tx(tx, ctx, issue_id, label)
+        })
+    }
 
-            if rows > 0 {
-                // Bump updated_at
-                tx.execute(
-                    "UPDATE issues SET updated_at = ? WHERE id = ?",
-                    rusqlite::params![Utc::now().to_rfc3339(), issue_id],
-                )?;
+    /// Remove a label inside an open mutation. Returns false if it was not set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database update fails.
+    pub(crate) fn remove_label_in_tx(
+        tx: &Transaction<'_>,
+        ctx: &mut MutationContext,
+        issue_id: &str,
+        label: &str,
+    ) -> Result<bool> {
+        let rows = tx.execute(
+            "DELETE FROM labels WHERE issue_id = ? AND label = ?",
+            rusqlite::params![issue_id, label],
+        )?;
 
-                ctx.record_event(
-                    EventType::LabelRemoved,
-                    issue_id,
-                    Some(format!("Removed label {label}")),
-                );
-                ctx.mark_dirty(issue_id);
-            }
+        if rows > 0 {
+            // Bump updated_at
+            tx.execute(
+                "UPDATE issues SET updated_at = ? WHERE id = ?",
+                rusqlite::params![Utc::now().to_rfc3339(), issue_id],
+            )?;
 
-            Ok(rows > 0)
-        })
+            ctx.record_field_event(
+                EventType::LabelRemoved,
+                issue_id,
+                "labels",
+                Some(label.to_string()),
+                None,
+                Some(format!("Removed label {label}")),
+            );
+            ctx.mark_dirty(issue_id);
+        }
+
+        Ok(rows > 0)
     }
 
     /// Remove all labels from an issue.
@@ -2040,6 +2787,13 @@ impl SqliteStorage {
     /// Returns an error if the database update fails.
     pub fn remove_all_labels(&mut self, issue_id: &str, actor: &str) -> Result<usize> {
         self.mutate("remove_all_labels", actor, |tx, ctx| {
+            let mut stmt =
+                tx.prepare("SELECT label FROM labels WHERE issue_id = ? ORDER BY label")?;
+            let old_labels: Vec<String> = stmt
+                .query_map([issue_id], |row| row.get(0))?
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            drop(stmt);
+
             let rows = tx.execute(
                 "DELETE FROM labels WHERE issue_id = ?",
                 rusqlite::params![issue_id],
@@ -2052,11 +2806,16 @@ impl SqliteStorage {
                     rusqlite::params![Utc::now().to_rfc3339(), issue_id],
                 )?;
 
-                ctx.record_event(
-                    EventType::LabelRemoved,
-                    issue_id,
-                    Some(format!("Removed {rows} labels")),
-                );
+                for label in old_labels {
+                    ctx.record_field_event(
+                        EventType::LabelRemoved,
+                        issue_id,
+                        "labels",
+                        Some(label),
+                        None,
+                        Some(format!("Removed {rows} labels")),
+                    );
+                }
                 ctx.mark_dirty(issue_id);
             }
 
@@ -2112,11 +2871,27 @@ impl SqliteStorage {
                             .join(", ")
                     ));
                 }
-                ctx.record_event(
-                    EventType::Updated,
-                    issue_id,
-                    Some(format!("Labels {}", details.join("; "))),
-                );
+                let summary = format!("Labels {}", details.join("; "));
+                for label in removed {
+                    ctx.record_field_event(
+                        EventType::LabelRemoved,
+                        issue_id,
+                        "labels",
+                        Some(label.clone()),
+                        None,
+                        Some(summary.clone()),
+                    );
+                }
+                for label in added {
+                    ctx.record_field_event(
+                        EventType::LabelAdded,
+                        issue_id,
+                        "labels",
+                        None,
+                        Some(label.clone()),
+                        Some(summary.clone()),
+                    );
+                }
                 ctx.mark_dirty(issue_id);
 
                 // Bump updated_at
@@ -2153,6 +2928,13 @@ impl SqliteStorage {
     pub fn get_labels_for_issues(
         &self,
         issue_ids: &[String],
+    ) -> Result<HashMap<String, Vec<String>>> {
+        Self::labels_for_issues_in(&self.conn, issue_ids)
+    }
+
+    fn labels_for_issues_in(
+        conn: &Connection,
+        issue_ids: &[String],
     ) -> Result<HashMap<String, Vec<String>>> {
         const SQLITE_VAR_LIMIT: usize = 900;
 
@@ -2173,7 +2955,7 @@ impl SqliteStorage {
             let params: Vec<&dyn rusqlite::ToSql> =
                 chunk.iter().map(|s| s as &dyn rusqlite::ToSql).collect();
 
-            let mut stmt = self.conn.prepare(&sql)?;
+            let mut stmt = conn.prepare(&sql)?;
             let rows = stmt.query_map(params.as_slice(), |row| {
                 Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
             })?;
@@ -2277,11 +3059,25 @@ impl SqliteStorage {
             // Mark all affected issues as dirty and record events
             let now = Utc::now().to_rfc3339();
             for issue_id in &issue_ids {
-                ctx.record_event(
+                let comment = format!("Renamed label {old_name} to {new_name}");
+                ctx.record_field_event(
                     EventType::LabelRemoved,
                     issue_id,
-                    Some(format!("Renamed label {old_name} to {new_name}")),
+                    "labels",
+                    Some(old_name.to_string()),
+                    None,
+                    Some(comment.clone()),
                 );
+                if !conflicts.contains(issue_id) {
+                    ctx.record_field_event(
+                        EventType::LabelAdded,
+                        issue_id,
+                        "labels",
+                        None,
+                        Some(new_name.to_string()),
+                        Some(comment),
+                    );
+                }
                 ctx.mark_dirty(issue_id);
 
                 // Update timestamp
@@ -2330,18 +3126,41 @@ impl SqliteStorage {
     /// Returns an error if the database update fails.
     pub fn add_comment(&mut self, issue_id: &str, author: &str, text: &str) -> Result<Comment> {
         self.mutate("add_comment", author, |tx, ctx| {
-            let comment_id = insert_comment_row(tx, issue_id, author, text)?;
+            Self::add_comment_in_tx(tx, ctx, issue_id, author, text)
+        })
+    }
 
-            tx.execute(
-                "UPDATE issues SET updated_at = ? WHERE id = ?",
-                rusqlite::params![Utc::now().to_rfc3339(), issue_id],
-            )?;
+    /// Add a comment inside an open mutation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database update fails.
+    pub(crate) fn add_comment_in_tx(
+        tx: &Transaction<'_>,
+        ctx: &mut MutationContext,
+        issue_id: &str,
+        author: &str,
+        text: &str,
+    ) -> Result<Comment> {
+        let comment_id = insert_comment_row(tx, issue_id, author, text)?;
+
+        tx.execute(
+            "UPDATE issues SET updated_at = ? WHERE id = ?",
+            rusqlite::params![Utc::now().to_rfc3339(), issue_id],
+        )?;
 
-            ctx.record_event(EventType::Commented, issue_id, Some(text.to_string()));
-            ctx.mark_dirty(issue_id);
+        let comment = fetch_comment(tx, comment_id)?;
+        ctx.record_field_event(
+            EventType::Commented,
+            issue_id,
+            "comments",
+            None,
+            Some(undo::comment_value(&comment)),
+            Some(text.to_string()),
+        );
+        ctx.mark_dirty(issue_id);
 
-            fetch_comment(tx, comment_id)
-        })
+        Ok(comment)
     }
 
     /// Get dependencies with metadata.
@@ -2582,6 +3401,18 @@ impl SqliteStorage {
     pub fn count_dependents_for_issues(
         &self,
         issue_ids: &[String],
+    ) -> Result<HashMap<String, usize>> {
+        Self::count_dependents_in(&self.conn, issue_ids, false)
+    }
+
+    /// Count dependents per issue. With `waiting_only`, count just the
+    /// non-terminal dependents this issue blocks (`blocks`,
+    /// `conditional-blocks`, `waits-for`), i.e. the issues finishing this
+    /// one would move toward ready.
+    fn count_dependents_in(
+        conn: &Connection,
+        issue_ids: &[String],
+        waiting_only: bool,
     ) -> Result<HashMap<String, usize>> {
         const SQLITE_VAR_LIMIT: usize = 900;
 
@@ -2593,15 +3424,29 @@ impl SqliteStorage {
 
         for chunk in issue_ids.chunks(SQLITE_VAR_LIMIT) {
             let placeholders: Vec<&str> = chunk.iter().map(|_| "?").collect();
+            // A child is not blocked by its open parent, so parent-child
+            // links unblock nothing when the parent closes.
+            let waiting = if waiting_only {
+                format!(
+                    "AND d.type IN ('blocks', 'conditional-blocks', 'waits-for')
+                     AND EXISTS (SELECT 1 FROM issues i WHERE i.id = d.issue_id
+                                 AND i.status NOT IN ({}))",
+                    status_sql_list(StatusCategory::Terminal, &[])
+                )
+            } else {
+                String::new()
+            };
             let sql = format!(
-                "SELECT depends_on_id, COUNT(*) FROM dependencies WHERE depends_on_id IN ({}) GROUP BY depends_on_id",
+                "SELECT d.depends_on_id, COUNT(*) FROM dependencies d
+                 WHERE d.depends_on_id IN ({}) {waiting}
+                 GROUP BY d.depends_on_id",
                 placeholders.join(",")
             );
 
             let params: Vec<&dyn rusqlite::ToSql> =
                 chunk.iter().map(|s| s as &dyn rusqlite::ToSql).collect();
 
-            let mut stmt = self.conn.prepare(&sql)?;
+            let mut stmt = conn.prepare(&sql)?;
             let rows = stmt.query_map(params.as_slice(), |row| {
                 Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
             })?;
@@ -2702,7 +3547,7 @@ impl SqliteStorage {
 
         let mut stmt = self.conn.prepare_cached(sql)?;
         let issues = stmt
-            .query_map([], |row| self.issue_from_row(row))?
+            .query_map([], Self::issue_from_row)?
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
         Ok(issues)
@@ -2788,6 +3633,45 @@ impl SqliteStorage {
         Ok(map)
     }
 
+    /// Get the Org extras of one issue.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails or the stored JSON is invalid.
+    pub fn get_org_extras(&self, issue_id: &str) -> Result<Option<OrgExtras>> {
+        let data: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT data FROM org_extras WHERE issue_id = ?",
+                [issue_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        data.map(|data| serde_json::from_str(&data).map_err(BeadsError::Json))
+            .transpose()
+    }
+
+    /// Get the Org extras of all issues that have any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails or the stored JSON is invalid.
+    pub fn get_all_org_extras(&self) -> Result<HashMap<String, OrgExtras>> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT issue_id, data FROM org_extras")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut map = HashMap::new();
+        for row in rows {
+            let (issue_id, data) = row?;
+            map.insert(issue_id, serde_json::from_str(&data)?);
+        }
+        Ok(map)
+    }
+
     /// Get the count of dirty issues (issues modified since last export).
     ///
     /// # Errors
@@ -2876,6 +3760,21 @@ impl SqliteStorage {
         }
     }
 
+    /// Get the stored export hashes of all exported issues, by issue ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn get_all_export_hashes(&self) -> Result<HashMap<String, String>> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT issue_id, content_hash FROM export_hashes")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        Ok(rows.collect::<std::result::Result<_, _>>()?)
+    }
+
     /// Set the export hash for an issue after successful export.
     ///
     /// # Errors
@@ -3033,9 +3932,10 @@ impl SqliteStorage {
         include_events: bool,
         event_limit: usize,
     ) -> Result<Option<IssueDetails>> {
-        let Some(issue) = self.get_issue(id)? else {
+        let Some(mut issue) = self.get_issue(id)? else {
             return Ok(None);
         };
+        issue.org_extras = self.get_org_extras(id)?;
 
         let labels = self.get_labels(id)?;
         let dependencies = self.get_dependencies_with_metadata(id)?;
@@ -3063,6 +3963,38 @@ impl SqliteStorage {
         }))
     }
 
+    /// Get issue details as they were at `as_of`, rebuilt from events.
+    ///
+    /// Returns `None` if the issue does not exist or had not been created yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn get_issue_details_as_of(
+        &self,
+        id: &str,
+        as_of: DateTime<Utc>,
+    ) -> Result<Option<IssueDetails>> {
+        let Some(details) = self.get_issue_details(id, true, false, 0)? else {
+            return Ok(None);
+        };
+        timeline::rewind(&self.conn, details, as_of)
+    }
+
+    /// Get the timeline of changes to one field of an issue, oldest first.
+    ///
+    /// `field` is an event field name (see [`timeline::canonical_field`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the issue is not found or the query fails.
+    pub fn get_field_history(&self, id: &str, field: &str) -> Result<Vec<FieldChange>> {
+        let issue = self
+            .get_issue(id)?
+            .ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() })?;
+        timeline::field_history(&self.conn, &issue, field)
+    }
+
     /// Convert empty string to None for bd compatibility.
     /// The database stores empty strings for NOT NULL DEFAULT '' fields,
     /// but the API contract expects None for unset values.
@@ -3071,8 +4003,7 @@ impl SqliteStorage {
         s.filter(|v| !v.is_empty())
     }
 
-    #[allow(clippy::unused_self)]
-    fn issue_from_row(&self, row: &rusqlite::Row) -> rusqlite::Result<Issue> {
+    fn issue_from_row(row: &rusqlite::Row) -> rusqlite::Result<Issue> {
         Ok(Issue {
             id: row.get(0)?,
             content_hash: row.get::<_, Option<String>>(1)?,
@@ -3133,6 +4064,7 @@ impl SqliteStorage {
             labels: vec![],       // Loaded separately if needed
             dependencies: vec![], // Loaded separately if needed
             comments: vec![],     // Loaded separately if needed
+            org_extras: None,     // Loaded separately if needed
         })
     }
 
@@ -3186,6 +4118,18 @@ pub struct ListFilters {
     pub updated_before: Option<DateTime<Utc>>,
     /// Filter by `updated_at` >= timestamp
     pub updated_after: Option<DateTime<Utc>>,
+    /// Filter expression (`--where`), ANDed with the other filters
+    pub filter_expr: Option<FilterExpr>,
+}
+
+/// A single full-text search result.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub issue: Issue,
+    /// Relevance score (negated bm25; higher is better). `None` for substring-only matches.
+    pub score: Option<f64>,
+    /// Best-matching excerpt with hits wrapped in `**`.
+    pub snippet: Option<String>,
 }
 
 /// Fields to update on an issue.
@@ -3265,6 +4209,12 @@ pub struct ReadyFilters {
     pub parent: Option<String>,
     /// Include all descendants (grandchildren, etc.) not just direct children.
     pub recursive: bool,
+    /// Filter expression (`--where`), ANDed with the ready conditions.
+    pub filter_expr: Option<FilterExpr>,
+    /// Hide issues under a live lease held by anyone but this actor.
+    pub lease_actor: Option<String>,
+    /// Weights for `ReadySortPolicy::Score`.
+    pub score_weights: ScoreWeights,
 }
 
 /// Sort policy for ready issues.
@@ -3277,6 +4227,8 @@ pub enum ReadySortPolicy {
     Priority,
     /// Sort by `created_at` ASC only
     Oldest,
+    /// Weighted score from `ReadyFilters::score_weights`, highest first
+    Score,
 }
 
 fn parse_status(s: Option<&str>) -> Status {
@@ -3328,7 +4280,8 @@ fn query_external_project_capabilities(
             "SELECT DISTINCT l.label
              FROM labels l
              INNER JOIN issues i ON i.id = l.issue_id
-             WHERE i.status IN ('closed', 'tombstone') AND l.label IN ({})",
+             WHERE i.status IN ({}) AND l.label IN ({})",
+            status_sql_list(StatusCategory::Terminal, &[]),
             placeholders.join(",")
         );
         let params: Vec<&dyn rusqlite::ToSql> = chunk
@@ -3365,15 +4318,124 @@ fn parse_datetime(s: &str) -> rusqlite::Result<DateTime<Utc>> {
     ))
 }
 
+/// Quoted, comma-separated SQL list of every status in `category`, plus `extra`.
+///
+/// Custom status names are restricted to `[a-z0-9_-]` at registration, so the
+/// list can be inlined into `IN (...)`; quotes are doubled regardless.
+fn status_sql_list(category: StatusCategory, extra: &[&str]) -> String {
+    Status::names_in_category(category)
+        .iter()
+        .map(String::as_str)
+        .chain(extra.iter().copied())
+        .map(|name| format!("'{}'", name.replace('\'', "''")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Escape special LIKE pattern characters (%, _, \) for literal matching.
 ///
 /// Use with `LIKE ? ESCAPE '\\'` in SQL queries.
-fn escape_like_pattern(s: &str) -> String {
+pub(crate) fn escape_like_pattern(s: &str) -> String {
     s.replace('\\', "\\\\")
         .replace('%', "\\%")
         .replace('_', "\\_")
 }
 
+/// Columns of `issues_fts` that may be targeted with `column:term`.
+const FTS_COLUMNS: &[&str] = &[
+    "title",
+    "description",
+    "design",
+    "acceptance_criteria",
+    "notes",
+    "comments",
+];
+
+/// Translate a user search query into a safe FTS5 MATCH expression.
+///
+/// Phrases, `prefix*`, `AND`/`OR`/`NOT`, parentheses, and `column:term` pass
+/// through; any other bareword containing punctuation is quoted so it is
+/// matched literally instead of tripping the FTS5 parser. Returns `None` when
+/// nothing searchable remains.
+fn build_fts_query(query: &str) -> Option<String> {
+    fn push_term(tokens: &mut Vec<String>, word: &str) {
+        let (word, prefix) = word
+            .strip_suffix('*')
+            .map_or((word, false), |stripped| (stripped, true));
+        if word.is_empty() {
+            return;
+        }
+        let star = if prefix { "*" } else { "" };
+        if word.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            tokens.push(format!("{word}{star}"));
+        } else if word.chars().any(char::is_alphanumeric) {
+            tokens.push(format!("\"{word}\"{star}"));
+        }
+    }
+
+    let mut tokens: Vec<String> = Vec::new();
+    let mut chars = query.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' | ')' => tokens.push(c.to_string()),
+            '"' => {
+                let phrase: String = chars.by_ref().take_while(|&ch| ch != '"').collect();
+                let star = if chars.peek() == Some(&'*') {
+                    chars.next();
+                    "*"
+                } else {
+                    ""
+                };
+                if phrase.chars().any(char::is_alphanumeric) {
+                    tokens.push(format!("\"{}\"{star}", phrase.trim()));
+                }
+            }
+            _ => {
+                let mut word = String::from(c);
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || matches!(next, '(' | ')' | '"') {
+                        break;
+                    }
+                    word.push(next);
+                    chars.next();
+                }
+                match word.as_str() {
+                    "AND" | "OR" | "NOT" => tokens.push(word),
+                    _ => match word.split_once(':') {
+                        Some((column, term)) if FTS_COLUMNS.contains(&column) => {
+                            let mut scoped = Vec::new();
+                            push_term(&mut scoped, term);
+                            if let Some(term) = scoped.pop() {
+                                tokens.push(format!("{column} : {term}"));
+                            }
+                        }
+                        _ => push_term(&mut tokens, &word),
+                    },
+                }
+            }
+        }
+    }
+
+    let has_terms = tokens
+        .iter()
+        .any(|t| !matches!(t.as_str(), "AND" | "OR" | "NOT" | "(" | ")"));
+    has_terms.then(|| tokens.join(" "))
+}
+
+/// Surface FTS5 syntax errors as validation errors on the query.
+fn map_fts_error(err: rusqlite::Error) -> BeadsError {
+    let message = err.to_string();
+    if message.contains("fts5") {
+        BeadsError::Validation {
+            field: "query".to_string(),
+            reason: format!("invalid search syntax: {message}"),
+        }
+    } else {
+        BeadsError::Database(err)
+    }
+}
+
 // ============================================================================
 // EXPORT/SYNC METHODS
 // ============================================================================
@@ -3612,7 +4674,7 @@ impl SqliteStorage {
                      pinned, is_template
                FROM issues WHERE external_ref = ?",
             [external_ref],
-            |row| self.issue_from_row(row),
+            Self::issue_from_row,
         );
         match result {
             Ok(issue) => Ok(Some(issue)),
@@ -3637,7 +4699,7 @@ impl SqliteStorage {
                      pinned, is_template
                FROM issues WHERE content_hash = ?",
             [content_hash],
-            |row| self.issue_from_row(row),
+            Self::issue_from_row,
         );
         match result {
             Ok(issue) => Ok(Some(issue)),
@@ -3684,6 +4746,12 @@ impl SqliteStorage {
         let deleted_at_str = issue.deleted_at.map(|dt| dt.to_rfc3339());
         let compacted_at_str = issue.compacted_at.map(|dt| dt.to_rfc3339());
 
+        // INSERT OR REPLACE assigns a fresh rowid, so drop the old index row first.
+        self.conn.execute(
+            "DELETE FROM issues_fts WHERE rowid IN (SELECT rowid FROM issues WHERE id = ?)",
+            [&issue.id],
+        )?;
+
         let rows = self.conn.execute(
             r"INSERT OR REPLACE INTO issues (
                 id, content_hash, title, description, design, acceptance_criteria, notes,
@@ -3736,6 +4804,8 @@ impl SqliteStorage {
             ],
         )?;
 
+        Self::refresh_fts_row(&self.conn, &issue.id)?;
+
         Ok(rows > 0)
     }
 
@@ -3822,6 +4892,33 @@ impl SqliteStorage {
             )?;
         }
 
+        Self::refresh_fts_row(&self.conn, issue_id)?;
+
+        Ok(())
+    }
+
+    /// Sync Org extras for an issue (replace or remove).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    pub fn sync_org_extras_for_import(
+        &mut self,
+        issue_id: &str,
+        extras: Option<&OrgExtras>,
+    ) -> Result<()> {
+        match extras.filter(|extras| !extras.is_empty()) {
+            Some(extras) => {
+                self.conn.execute(
+                    "INSERT OR REPLACE INTO org_extras (issue_id, data) VALUES (?, ?)",
+                    rusqlite::params![issue_id, serde_json::to_string(extras)?],
+                )?;
+            }
+            None => {
+                self.conn
+                    .execute("DELETE FROM org_extras WHERE issue_id = ?", [issue_id])?;
+            }
+        }
         Ok(())
     }
 }
@@ -3951,6 +5048,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         }
     }
 
@@ -4003,6 +5101,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         };
 
         storage.create_issue(&issue, "tester").unwrap();
@@ -4158,6 +5257,37 @@ mod tests {
         assert!(is_tombstone);
     }
 
+    #[test]
+    fn test_purge_issues_removes_everything_attached() {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let t1 = Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap();
+
+        let gone = make_issue("bd-p1", "Purge me", Status::Open, 2, None, t1, None);
+        let keep = make_issue("bd-p2", "Keep me", Status::Open, 2, None, t1, None);
+        storage.create_issue(&gone, "tester").unwrap();
+        storage.create_issue(&keep, "tester").unwrap();
+        storage.add_label("bd-p1", "old", "tester").unwrap();
+        storage.add_comment("bd-p1", "tester", "note").unwrap();
+        storage
+            .add_dependency("bd-p2", "bd-p1", "blocks", "tester")
+            .unwrap();
+        storage
+            .delete_issue("bd-p1", "tester", "cleanup", None)
+            .unwrap();
+        storage.clear_dirty_issues(&["bd-p2".to_string()]).unwrap();
+
+        let purged = storage
+            .purge_issues(&["bd-p1".to_string(), "bd-missing".to_string()])
+            .unwrap();
+        assert_eq!(purged, 1);
+        assert!(storage.get_issue("bd-p1").unwrap().is_none());
+        assert!(storage.get_labels("bd-p1").unwrap().is_empty());
+        assert!(storage.get_comments("bd-p1").unwrap().is_empty());
+        assert!(storage.get_events("bd-p1", 100).unwrap().is_empty());
+        assert!(storage.get_dependencies("bd-p2").unwrap().is_empty());
+        assert_eq!(storage.get_dirty_issue_ids().unwrap(), vec!["bd-p2"]);
+    }
+
     #[test]
     fn test_get_blocked_issues_lists_blockers() {
         let mut storage = SqliteStorage::open_memory().unwrap();
@@ -4178,6 +5308,143 @@ mod tests {
         assert_eq!(blocked_issues[0].1.len(), 1);
     }
 
+    #[test]
+    fn test_custom_status_categories_drive_ready_and_blocked() {
+        use crate::model::register_custom_status;
+
+        register_custom_status("sq_review", StatusCategory::Active).unwrap();
+        register_custom_status("sq_qa", StatusCategory::Waiting).unwrap();
+        register_custom_status("sq_wontfix", StatusCategory::Terminal).unwrap();
+
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let t1 = Utc.with_ymd_and_hms(2025, 4, 2, 0, 0, 0).unwrap();
+        let custom = |name: &str| Status::Custom(name.to_string());
+
+        let wontfix = make_issue("bd-w1", "Wontfix", custom("sq_wontfix"), 1, None, t1, None);
+        let dependent = make_issue("bd-w2", "Dependent", Status::Open, 2, None, t1, None);
+        let review = make_issue("bd-w3", "Review", custom("sq_review"), 2, None, t1, None);
+        let qa = make_issue("bd-w4", "QA", custom("sq_qa"), 2, None, t1, None);
+        for issue in [&wontfix, &dependent, &review, &qa] {
+            storage.create_issue(issue, "tester").unwrap();
+        }
+        storage
+            .add_dependency("bd-w2", "bd-w1", "blocks", "tester")
+            .unwrap();
+
+        // A terminal custom status no longer blocks its dependents.
+        assert!(!storage.is_blocked("bd-w2").unwrap());
+
+        let ready: Vec<String> = storage
+            .get_ready_issues(&ReadyFilters::default(), ReadySortPolicy::Oldest)
+            .unwrap()
+            .into_iter()
+            .map(|issue| issue.id)
+            .collect();
+        assert!(ready.contains(&"bd-w2".to_string()));
+        assert!(ready.contains(&"bd-w3".to_string()));
+        assert!(!ready.contains(&"bd-w4".to_string()));
+        assert!(!ready.contains(&"bd-w1".to_string()));
+
+        let listed: Vec<String> = storage
+            .list_issues(&ListFilters::default())
+            .unwrap()
+            .into_iter()
+            .map(|issue| issue.id)
+            .collect();
+        assert!(!listed.contains(&"bd-w1".to_string()));
+        assert!(listed.contains(&"bd-w4".to_string()));
+    }
+
+    #[test]
+    fn test_claim_next_ready_takes_each_issue_once() {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let t1 = Utc.with_ymd_and_hms(2025, 5, 1, 0, 0, 0).unwrap();
+        let t2 = Utc.with_ymd_and_hms(2025, 5, 2, 0, 0, 0).unwrap();
+        let low = make_issue("bd-n1", "Low", Status::Open, 3, None, t1, None);
+        let high = make_issue("bd-n2", "High", Status::Open, 0, None, t2, None);
+        let taken = make_issue("bd-n3", "Taken", Status::Open, 0, Some("bob"), t1, None);
+        let skipped = make_issue("bd-n4", "Skipped", Status::Open, 0, None, t1, None);
+        for issue in [&low, &high, &taken, &skipped] {
+            storage.create_issue(issue, "tester").unwrap();
+        }
+
+        let filters = ReadyFilters::default();
+        let accept = |issue: &Issue| issue.id != "bd-n4";
+        let first = storage
+            .claim_next_ready(&filters, ReadySortPolicy::Priority, "alice", accept)
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.id, "bd-n2");
+        assert_eq!(first.status, Status::InProgress);
+        assert_eq!(first.assignee.as_deref(), Some("alice"));
+        let stored = storage.get_issue("bd-n2").unwrap().unwrap();
+        assert_eq!(stored.assignee.as_deref(), Some("alice"));
+
+        let second = storage
+            .claim_next_ready(&filters, ReadySortPolicy::Priority, "carol", accept)
+            .unwrap()
+            .unwrap();
+        assert_eq!(second.id, "bd-n1");
+        assert!(
+            storage
+                .claim_next_ready(&filters, ReadySortPolicy::Priority, "dave", accept)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_score_counts_only_waiting_dependents() {
+        use crate::model::register_custom_status;
+
+        register_custom_status("sq_dropped", StatusCategory::Terminal).unwrap();
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let t1 = Utc.with_ymd_and_hms(2025, 5, 1, 0, 0, 0).unwrap();
+        for id in ["bd-s1", "bd-s2", "bd-s3", "bd-s4", "bd-e1", "bd-e2"] {
+            let issue = make_issue(id, id, Status::Open, 2, None, t1, None);
+            storage.create_issue(&issue, "tester").unwrap();
+        }
+        let dropped = Status::Custom("sq_dropped".to_string());
+        let dropped = make_issue("bd-s5", "Dropped", dropped, 2, None, t1, None);
+        storage.create_issue(&dropped, "tester").unwrap();
+        storage
+            .add_dependency("bd-s5", "bd-s1", "blocks", "tester")
+            .unwrap();
+        // An open child does not wait on its epic.
+        storage
+            .add_dependency("bd-e2", "bd-e1", "parent-child", "tester")
+            .unwrap();
+        storage
+            .add_dependency("bd-s2", "bd-s1", "blocks", "tester")
+            .unwrap();
+        storage
+            .add_dependency("bd-s3", "bd-s1", "related", "tester")
+            .unwrap();
+        storage
+            .add_dependency("bd-s4", "bd-s1", "blocks", "tester")
+            .unwrap();
+        let close = IssueUpdate {
+            status: Some(Status::Closed),
+            ..IssueUpdate::default()
+        };
+        storage.update_issue("bd-s4", &close, "tester").unwrap();
+
+        let targets = [
+            storage.get_issue("bd-s1").unwrap().unwrap(),
+            storage.get_issue("bd-e1").unwrap().unwrap(),
+        ];
+        let weights = ScoreWeights::default();
+        let scores = storage.score_issues(&targets, &weights, t1).unwrap();
+        assert!((scores["bd-s1"].unblocks - weights.unblocks).abs() < f64::EPSILON);
+        assert!(scores["bd-e1"].unblocks.abs() < f64::EPSILON);
+        // Listing still shows every dependent.
+        let counts = storage
+            .count_dependents_for_issues(&["bd-s1".to_string(), "bd-e1".to_string()])
+            .unwrap();
+        assert_eq!(counts["bd-s1"], 4);
+        assert_eq!(counts["bd-e1"], 1);
+    }
+
     #[test]
     fn test_add_and_remove_labels_sorted() {
         let mut storage = SqliteStorage::open_memory().unwrap();
@@ -4301,6 +5568,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         };
         storage.create_issue(&issue, "tester").unwrap();
 
@@ -4370,6 +5638,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         };
         storage.create_issue(&issue, "tester").unwrap();
 
@@ -4431,6 +5700,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         };
         storage.create_issue(&issue, "tester").unwrap();
 
@@ -4844,6 +6114,165 @@ mod tests {
         assert_eq!(results[0].id, "bd-s1");
     }
 
+    #[test]
+    fn test_search_ranks_and_indexes_comments() {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let t1 = Utc.with_ymd_and_hms(2025, 9, 1, 0, 0, 0).unwrap();
+
+        let in_title = make_issue(
+            "bd-f1",
+            "Kernel panic on boot",
+            Status::Open,
+            2,
+            None,
+            t1,
+            None,
+        );
+        let in_comment = make_issue("bd-f2", "Flaky startup", Status::Open, 0, None, t1, None);
+        storage.create_issue(&in_title, "tester").unwrap();
+        storage.create_issue(&in_comment, "tester").unwrap();
+        storage
+            .add_comment("bd-f2", "tester", "Looks like a kernel regression")
+            .unwrap();
+
+        let hits = storage
+            .search_issues_ranked("kernel", &ListFilters::default())
+            .unwrap();
+        let ids: Vec<_> = hits.iter().map(|h| h.issue.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["bd-f1", "bd-f2"],
+            "title hits outrank comment hits"
+        );
+        assert!(hits.iter().all(|h| h.score.is_some()));
+        assert!(
+            hits[1]
+                .snippet
+                .as_deref()
+                .is_some_and(|s| s.contains("**kernel**"))
+        );
+    }
+
+    #[test]
+    fn test_search_fts_operators_and_updates() {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let t1 = Utc.with_ymd_and_hms(2025, 9, 1, 0, 0, 0).unwrap();
+
+        let a = make_issue(
+            "bd-o1",
+            "Database migration tool",
+            Status::Open,
+            2,
+            None,
+            t1,
+            None,
+        );
+        let b = make_issue(
+            "bd-o2",
+            "Migration of docs",
+            Status::Open,
+            2,
+            None,
+            t1,
+            None,
+        );
+        storage.create_issue(&a, "tester").unwrap();
+        storage.create_issue(&b, "tester").unwrap();
+        let filters = ListFilters::default();
+
+        let ids = |results: Vec<Issue>| results.into_iter().map(|i| i.id).collect::<Vec<_>>();
+
+        assert_eq!(
+            ids(storage
+                .search_issues("\"database migration\"", &filters)
+                .unwrap()),
+            vec!["bd-o1"]
+        );
+        assert_eq!(
+            ids(storage.search_issues("migra* NOT docs", &filters).unwrap()),
+            vec!["bd-o1"]
+        );
+        assert_eq!(
+            ids(storage
+                .search_issues("title:docs OR tool", &filters)
+                .unwrap())
+            .len(),
+            2
+        );
+
+        let update = IssueUpdate {
+            title: Some("Schema upgrade tool".to_string()),
+            ..IssueUpdate::default()
+        };
+        storage.update_issue("bd-o1", &update, "tester").unwrap();
+        assert!(
+            storage
+                .search_issues("database", &filters)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            ids(storage.search_issues("upgrade", &filters).unwrap()),
+            vec!["bd-o1"]
+        );
+
+        let err = storage.search_issues("tool AND", &filters).unwrap_err();
+        assert!(matches!(err, BeadsError::Validation { ref field, .. } if field == "query"));
+    }
+
+    #[test]
+    fn test_search_fts_follows_import_sync() {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let t1 = Utc.with_ymd_and_hms(2025, 9, 1, 0, 0, 0).unwrap();
+
+        let issue = make_issue("bd-i1", "Imported issue", Status::Open, 2, None, t1, None);
+        storage.upsert_issue_for_import(&issue).unwrap();
+        storage
+            .sync_comments_for_import(
+                "bd-i1",
+                &[Comment {
+                    id: 7,
+                    issue_id: "bd-i1".to_string(),
+                    author: "importer".to_string(),
+                    body: "mentions zeppelin".to_string(),
+                    created_at: t1,
+                }],
+            )
+            .unwrap();
+
+        let filters = ListFilters::default();
+        assert_eq!(
+            storage.search_issues("zeppelin", &filters).unwrap().len(),
+            1
+        );
+
+        // Re-import replaces the row (new rowid) without leaving stale index entries.
+        let renamed = make_issue("bd-i1", "Renamed import", Status::Open, 2, None, t1, None);
+        storage.upsert_issue_for_import(&renamed).unwrap();
+        assert!(
+            storage
+                .search_issues("imported", &filters)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(storage.search_issues("renamed", &filters).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_build_fts_query_sanitizes_input() {
+        assert_eq!(build_fts_query("login bug").as_deref(), Some("login bug"));
+        assert_eq!(build_fts_query("bd-12").as_deref(), Some("\"bd-12\""));
+        assert_eq!(
+            build_fts_query("notes:auth* OR \"two words\"").as_deref(),
+            Some("notes : auth* OR \"two words\"")
+        );
+        assert_eq!(
+            build_fts_query("(a OR b) NOT c").as_deref(),
+            Some("( a OR b ) NOT c")
+        );
+        assert_eq!(build_fts_query("- * ()"), None);
+    }
+
     #[test]
     fn test_list_issues_filter_by_updated_date() {
         let mut storage = SqliteStorage::open_memory().unwrap();
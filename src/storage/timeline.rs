@@ -0,0 +1,466 @@
+//! Point-in-time views of issues, rebuilt from the events table.
+//!
+//! An issue is rewound from its current state by reverting, newest first,
+//! every event recorded after the requested moment. Field-level events (see
+//! [`super::undo`]) carry the old and new value of each change. Events
+//! written before those existed only describe status, priority, assignee,
+//! title, label and comment changes; other edits from that era keep their
+//! current value.
+
+use crate::error::Result;
+use crate::format::{IssueDetails, IssueWithDependencyMetadata};
+use crate::model::{Event, EventType, Issue, Priority, Status};
+use crate::storage::events::{get_dependency_events_targeting, get_events};
+use crate::storage::sqlite::SqliteStorage;
+use crate::storage::undo;
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use serde::Serialize;
+
+/// Fields that hold a collection rather than a single issue column.
+pub const COLLECTION_FIELDS: &[&str] = &["labels", "dependencies", "comments"];
+
+/// One change to a field, as shown by `history field`.
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldChange {
+    pub at: DateTime<Utc>,
+    pub actor: String,
+    pub event_type: EventType,
+    /// Value before the change (for collections: the entry removed).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_value: Option<String>,
+    /// Value after the change (for collections: the entry added).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_value: Option<String>,
+    /// Operation that made the change (see `undo --list`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub op_id: Option<String>,
+}
+
+/// Resolve a user-supplied field name (`type`, `acceptance-criteria`, ...)
+/// to the name used in events.
+#[must_use]
+pub fn canonical_field(name: &str) -> Option<&'static str> {
+    let name = name.trim().to_lowercase().replace('-', "_");
+    let name = match name.as_str() {
+        "type" => "issue_type",
+        "label" => "labels",
+        "deps" | "dependency" => "dependencies",
+        "comment" => "comments",
+        other => other,
+    };
+    undo::issue_field_names()
+        .chain(COLLECTION_FIELDS.iter().copied())
+        .find(|field| *field == name)
+}
+
+/// All field names accepted by [`canonical_field`].
+#[must_use]
+pub fn field_names() -> Vec<&'static str> {
+    undo::issue_field_names()
+        .chain(COLLECTION_FIELDS.iter().copied())
+        .collect()
+}
+
+/// The field an event changed, with its old and new value.
+///
+/// Returns `None` for events that cannot be replayed (creation, companion
+/// events, legacy events without values).
+fn field_change(event: &Event) -> Option<(&str, Option<String>, Option<String>)> {
+    if let Some(field) = event.field.as_deref() {
+        return Some((field, event.old_value.clone(), event.new_value.clone()));
+    }
+    // Events recorded before field-level tracking.
+    let comment = event.comment.as_deref().unwrap_or_default();
+    let scalar =
+        |field: &'static str| Some((field, event.old_value.clone(), event.new_value.clone()));
+    match event.event_type {
+        EventType::StatusChanged => scalar("status"),
+        EventType::PriorityChanged => scalar("priority"),
+        EventType::AssigneeChanged => scalar("assignee"),
+        EventType::Updated if comment == "Title changed" => scalar("title"),
+        EventType::LabelAdded => event
+            .new_value
+            .as_deref()
+            .or_else(|| comment.strip_prefix("Added label "))
+            .map(|label| ("labels", None, Some(label.to_string()))),
+        EventType::LabelRemoved => event
+            .old_value
+            .as_deref()
+            .or_else(|| comment.strip_prefix("Removed label "))
+            .map(|label| ("labels", Some(label.to_string()), None)),
+        EventType::Commented => Some(("comments", None, event.comment.clone())),
+        _ => None,
+    }
+}
+
+/// Rewind `details` (the current state) to how the issue looked at `as_of`.
+///
+/// Returns `None` if the issue had not been created yet.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn rewind(
+    conn: &Connection,
+    mut details: IssueDetails,
+    as_of: DateTime<Utc>,
+) -> Result<Option<IssueDetails>> {
+    if details.issue.created_at > as_of {
+        return Ok(None);
+    }
+    let id = details.issue.id.clone();
+
+    let mut last_change = None;
+    for event in get_events(conn, &id, 0)? {
+        if event.created_at <= as_of {
+            last_change = Some(event.created_at);
+            break;
+        }
+        let Some((field, old, new)) = field_change(&event) else {
+            continue;
+        };
+        match field {
+            "labels" => {
+                if let Some(label) = new {
+                    details.labels.retain(|existing| *existing != label);
+                }
+                if let Some(label) = old {
+                    if !details.labels.contains(&label) {
+                        details.labels.push(label);
+                    }
+                }
+            }
+            "dependencies" => {
+                revert_link(conn, &mut details.dependencies, old, new, None)?;
+            }
+            "comments" => {}
+            _ => apply_field(&mut details.issue, field, old.as_deref()),
+        }
+    }
+
+    for event in get_dependency_events_targeting(conn, &id)? {
+        if event.created_at <= as_of {
+            break;
+        }
+        revert_link(
+            conn,
+            &mut details.dependents,
+            event.old_value,
+            event.new_value,
+            Some((&id, &event.issue_id)),
+        )?;
+    }
+
+    details.labels.sort();
+    details
+        .comments
+        .retain(|comment| comment.created_at <= as_of);
+    details.events.retain(|event| event.created_at <= as_of);
+    details.parent = details
+        .dependencies
+        .iter()
+        .find(|dep| dep.dep_type == "parent-child")
+        .map(|dep| dep.id.clone());
+    details.issue.updated_at = last_change
+        .unwrap_or(details.issue.created_at)
+        .max(details.issue.created_at);
+    // The stored hash describes the current state, not the rewound one.
+    details.issue.content_hash = None;
+    Ok(Some(details))
+}
+
+/// Revert one dependency event on a list of linked issues.
+///
+/// For dependencies the listed id is the link target; for dependents
+/// (`dependent = Some((target, owner))`) it is the owning issue, and only
+/// links pointing at `target` are considered.
+fn revert_link(
+    conn: &Connection,
+    links: &mut Vec<IssueWithDependencyMetadata>,
+    old: Option<String>,
+    new: Option<String>,
+    dependent: Option<(&str, &str)>,
+) -> Result<()> {
+    let listed = |value: Option<String>| {
+        let (target, dep_type) = undo::decode_dependency(value.as_deref()?)?;
+        match dependent {
+            Some((wanted, owner)) => (target == wanted).then(|| (owner.to_string(), dep_type)),
+            None => Some((target, dep_type)),
+        }
+    };
+    if let Some((id, _)) = listed(new) {
+        links.retain(|link| link.id != id);
+    }
+    if let Some((id, dep_type)) = listed(old) {
+        if !links.iter().any(|link| link.id == id) {
+            let issue = SqliteStorage::fetch_issue(conn, &id)?;
+            links.push(IssueWithDependencyMetadata {
+                title: issue
+                    .as_ref()
+                    .map(|issue| issue.title.clone())
+                    .unwrap_or_default(),
+                status: issue
+                    .as_ref()
+                    .map(|issue| issue.status.clone())
+                    .unwrap_or_default(),
+                priority: issue.map_or(Priority::MEDIUM, |issue| issue.priority),
+                id,
+                dep_type,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Set one tracked issue field from its string encoding.
+fn apply_field(issue: &mut Issue, field: &str, value: Option<&str>) {
+    let value = value.filter(|v| !v.is_empty());
+    let text = || value.map(str::to_string);
+    let time = || {
+        value
+            .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+            .map(|dt| dt.with_timezone(&Utc))
+    };
+    match field {
+        "title" => issue.title = value.unwrap_or_default().to_string(),
+        "description" => issue.description = text(),
+        "design" => issue.design = text(),
+        "acceptance_criteria" => issue.acceptance_criteria = text(),
+        "notes" => issue.notes = text(),
+        "status" => {
+            if let Some(v) = value {
+                issue.status = v.parse().unwrap_or_else(|_| Status::Custom(v.to_string()));
+            }
+        }
+        "priority" => {
+            if let Some(priority) = value.and_then(|v| v.parse().ok()) {
+                issue.priority = priority;
+            }
+        }
+        "issue_type" => {
+            if let Some(issue_type) = value.and_then(|v| v.parse().ok()) {
+                issue.issue_type = issue_type;
+            }
+        }
+        "assignee" => issue.assignee = text(),
+        "owner" => issue.owner = text(),
+        "estimated_minutes" => issue.estimated_minutes = value.and_then(|v| v.parse().ok()),
+        "due_at" => issue.due_at = time(),
+        "defer_until" => issue.defer_until = time(),
+        "external_ref" => issue.external_ref = text(),
+        "closed_at" => issue.closed_at = time(),
+        "close_reason" => issue.close_reason = text(),
+        "closed_by_session" => issue.closed_by_session = text(),
+        "deleted_at" => issue.deleted_at = time(),
+        "deleted_by" => issue.deleted_by = text(),
+        "delete_reason" => issue.delete_reason = text(),
+        "original_type" => issue.original_type = text(),
+        _ => {}
+    }
+}
+
+/// Render a collection entry for display (`bd-2 (blocks)`, `alice: text`).
+fn describe(field: &str, value: Option<String>) -> Option<String> {
+    let value = value.filter(|v| !v.is_empty())?;
+    let decoded = match field {
+        "dependencies" => undo::decode_dependency(&value)
+            .map(|(target, dep_type)| format!("{target} ({dep_type})")),
+        "comments" => {
+            undo::decode_comment(&value).map(|(author, text)| format!("{author}: {text}"))
+        }
+        _ => None,
+    };
+    Some(decoded.unwrap_or(value))
+}
+
+/// Timeline of changes to one field of `issue`, oldest first.
+///
+/// For single-valued fields the timeline opens with the value the issue was
+/// created with.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn field_history(conn: &Connection, issue: &Issue, field: &str) -> Result<Vec<FieldChange>> {
+    let mut events = get_events(conn, &issue.id, 0)?;
+    events.reverse();
+
+    let mut created = None;
+    let mut changes = Vec::new();
+    for event in events {
+        if event.event_type == EventType::Created {
+            created = Some(event);
+            continue;
+        }
+        let Some((name, old, new)) = field_change(&event) else {
+            continue;
+        };
+        if name != field {
+            continue;
+        }
+        changes.push(FieldChange {
+            at: event.created_at,
+            actor: event.actor,
+            event_type: event.event_type,
+            old_value: describe(field, old),
+            new_value: describe(field, new),
+            op_id: event.op_id,
+        });
+    }
+
+    if let Some(created) = created.filter(|_| !COLLECTION_FIELDS.contains(&field)) {
+        let initial = changes.first().map_or_else(
+            || undo::field_value(issue, field),
+            |first| first.old_value.clone(),
+        );
+        changes.insert(
+            0,
+            FieldChange {
+                at: created.created_at,
+                actor: created.actor,
+                event_type: EventType::Created,
+                old_value: None,
+                new_value: initial,
+                op_id: created.op_id,
+            },
+        );
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::DependencyType;
+    use crate::storage::sqlite::IssueUpdate;
+    use std::time::Duration;
+
+    fn issue(id: &str, title: &str) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: title.to_string(),
+            ..Issue::default()
+        }
+    }
+
+    /// A timestamp strictly between what came before and what comes after.
+    fn checkpoint() -> DateTime<Utc> {
+        std::thread::sleep(Duration::from_millis(5));
+        let now = Utc::now();
+        std::thread::sleep(Duration::from_millis(5));
+        now
+    }
+
+    #[test]
+    fn rewind_restores_fields_labels_and_dependencies() {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        storage
+            .create_issue(&issue("bd-t1", "Original"), "alice")
+            .unwrap();
+        storage
+            .create_issue(&issue("bd-t2", "Blocker"), "alice")
+            .unwrap();
+        storage.add_label("bd-t1", "early", "alice").unwrap();
+        let as_of = checkpoint();
+
+        let update = IssueUpdate {
+            title: Some("Renamed".to_string()),
+            notes: Some(Some("later".to_string())),
+            status: Some(Status::Closed),
+            ..IssueUpdate::default()
+        };
+        storage.update_issue("bd-t1", &update, "bob").unwrap();
+        storage.remove_label("bd-t1", "early", "bob").unwrap();
+        storage.add_label("bd-t1", "late", "bob").unwrap();
+        storage
+            .add_dependency("bd-t1", "bd-t2", DependencyType::Blocks.as_str(), "bob")
+            .unwrap();
+        storage
+            .add_comment("bd-t1", "bob", "after the fact")
+            .unwrap();
+
+        let past = storage
+            .get_issue_details_as_of("bd-t1", as_of)
+            .unwrap()
+            .unwrap();
+        assert_eq!(past.issue.title, "Original");
+        assert_eq!(past.issue.notes, None);
+        assert_eq!(past.issue.status, Status::Open);
+        assert_eq!(past.issue.closed_at, None);
+        assert_eq!(past.labels, vec!["early"]);
+        assert!(past.dependencies.is_empty());
+        assert!(past.comments.is_empty());
+
+        let blocker = storage
+            .get_issue_details_as_of("bd-t2", as_of)
+            .unwrap()
+            .unwrap();
+        assert!(blocker.dependents.is_empty());
+
+        let current = storage
+            .get_issue_details_as_of("bd-t1", Utc::now())
+            .unwrap()
+            .unwrap();
+        assert_eq!(current.issue.title, "Renamed");
+        assert_eq!(current.labels, vec!["late"]);
+        assert_eq!(current.dependencies.len(), 1);
+    }
+
+    #[test]
+    fn rewind_before_creation_is_none() {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let mut created = issue("bd-t3", "New");
+        created.created_at = Utc::now();
+        storage.create_issue(&created, "alice").unwrap();
+        let before = created.created_at - chrono::Duration::days(1);
+        assert!(
+            storage
+                .get_issue_details_as_of("bd-t3", before)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn field_history_lists_initial_value_and_changes() {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        storage
+            .create_issue(&issue("bd-t4", "First"), "alice")
+            .unwrap();
+        for title in ["Second", "Third"] {
+            let update = IssueUpdate {
+                title: Some(title.to_string()),
+                ..IssueUpdate::default()
+            };
+            storage.update_issue("bd-t4", &update, "bob").unwrap();
+        }
+        storage.add_label("bd-t4", "x", "carol").unwrap();
+
+        let history = storage.get_field_history("bd-t4", "title").unwrap();
+        let values: Vec<_> = history
+            .iter()
+            .map(|change| change.new_value.as_deref().unwrap_or_default())
+            .collect();
+        assert_eq!(values, vec!["First", "Second", "Third"]);
+        assert_eq!(history[0].event_type, EventType::Created);
+        assert_eq!(history[2].actor, "bob");
+        assert_eq!(history[2].old_value.as_deref(), Some("Second"));
+
+        let labels = storage.get_field_history("bd-t4", "labels").unwrap();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].new_value.as_deref(), Some("x"));
+        assert_eq!(labels[0].actor, "carol");
+    }
+
+    #[test]
+    fn canonical_field_accepts_aliases() {
+        assert_eq!(canonical_field("type"), Some("issue_type"));
+        assert_eq!(
+            canonical_field("Acceptance-Criteria"),
+            Some("acceptance_criteria")
+        );
+        assert_eq!(canonical_field("deps"), Some("dependencies"));
+        assert_eq!(canonical_field("bogus"), None);
+    }
+}
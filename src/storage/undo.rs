@@ -0,0 +1,1084 @@
+//! Operation-grouped undo.
+//!
+//! Every [`SqliteStorage::mutate`] call stamps its events with the id of the
+//! current operation (one per storage session, i.e. one CLI invocation) and
+//! records an `operations` row. Events that change something replayable carry
+//! a `field`:
+//!
+//! - issue columns (`title`, `status`, `closed_at`, ...) store the old and new
+//!   value, string-encoded (timestamps as RFC 3339, empty as NULL)
+//! - `labels` stores the label in `new_value` (added) or `old_value` (removed)
+//! - `dependencies` stores the link as JSON on the issue that owns it
+//! - `comments` stores the comment row as JSON
+//!
+//! Undoing an operation walks its events newest first and applies the
+//! inverse, provided the current state still matches what the event left
+//! behind. Any mismatch means a later edit touched the same data; the whole
+//! undo is then refused with [`BeadsError::UndoConflict`].
+//!
+//! [`SqliteStorage::mutate`]: crate::storage::SqliteStorage::mutate
+
+use crate::error::{BeadsError, Result};
+use crate::model::{Comment, DependencyType, EventType, Issue};
+use crate::storage::events::{get_operation_events, parse_event_timestamp};
+use crate::storage::sqlite::{MutationContext, SqliteStorage};
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension, Transaction};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Fields whose changes `update_issue` records with a dedicated event type.
+pub const EXPLICIT_EVENT_FIELDS: &[&str] = &["title", "status", "priority", "assignee"];
+
+/// How an issue field is stored in the `issues` table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    /// Nullable (or always-set) text.
+    Text,
+    /// NOT NULL text where unset is stored as `''`.
+    Blank,
+    /// Integer.
+    Integer,
+    /// RFC 3339 timestamp, nullable.
+    Timestamp,
+}
+
+/// Issue fields tracked for undo, in recording order.
+const ISSUE_FIELDS: &[(&str, Column)] = &[
+    ("title", Column::Text),
+    ("description", Column::Blank),
+    ("design", Column::Blank),
+    ("acceptance_criteria", Column::Blank),
+    ("notes", Column::Blank),
+    ("status", Column::Text),
+    ("priority", Column::Integer),
+    ("issue_type", Column::Text),
+    ("assignee", Column::Text),
+    ("owner", Column::Blank),
+    ("estimated_minutes", Column::Integer),
+    ("due_at", Column::Timestamp),
+    ("defer_until", Column::Timestamp),
+    ("external_ref", Column::Text),
+    ("closed_at", Column::Timestamp),
+    ("close_reason", Column::Blank),
+    ("closed_by_session", Column::Blank),
+    ("deleted_at", Column::Timestamp),
+    ("deleted_by", Column::Blank),
+    ("delete_reason", Column::Blank),
+    ("original_type", Column::Blank),
+    ("compaction_level", Column::Integer),
+    ("compacted_at", Column::Timestamp),
+    ("compacted_at_commit", Column::Text),
+    ("original_size", Column::Integer),
+];
+
+/// A recorded operation, as shown by `undo --list`.
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationSummary {
+    pub id: String,
+    pub name: String,
+    pub actor: String,
+    pub created_at: DateTime<Utc>,
+    /// Number of events recorded by the operation.
+    pub events: usize,
+    /// Issues the operation touched.
+    pub issues: Vec<String>,
+    /// Operations this one reverted, if it is an undo.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub undoes: Option<Vec<String>>,
+    /// The undo operation that reverted this one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub undone_by: Option<String>,
+}
+
+/// Outcome of an undo.
+#[derive(Debug, Clone, Serialize)]
+pub struct UndoReport {
+    /// Id of the operation recording the undo (undo it to redo).
+    pub op_id: String,
+    /// Operations that were reverted, newest first.
+    pub undone: Vec<String>,
+    /// Issues changed by the undo.
+    pub issues: Vec<String>,
+    /// Number of individual changes replayed.
+    pub changes: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DependencyValue {
+    depends_on_id: String,
+    #[serde(rename = "type")]
+    dep_type: String,
+    #[serde(default)]
+    created_at: Option<String>,
+    #[serde(default)]
+    created_by: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CommentValue {
+    id: i64,
+    author: String,
+    text: String,
+    created_at: String,
+}
+
+/// Encode a dependency link for a `dependencies` event.
+#[must_use]
+pub fn dependency_value(
+    depends_on_id: &str,
+    dep_type: &str,
+    created_at: &str,
+    created_by: &str,
+) -> String {
+    serde_json::to_string(&DependencyValue {
+        depends_on_id: depends_on_id.to_string(),
+        dep_type: dep_type.to_string(),
+        created_at: Some(created_at.to_string()),
+        created_by: Some(created_by.to_string()),
+    })
+    .unwrap_or_default()
+}
+
+/// Encode a comment row for a `comments` event.
+#[must_use]
+pub fn comment_value(comment: &Comment) -> String {
+    serde_json::to_string(&CommentValue {
+        id: comment.id,
+        author: comment.author.clone(),
+        text: comment.body.clone(),
+        created_at: comment.created_at.to_rfc3339(),
+    })
+    .unwrap_or_default()
+}
+
+/// Decode a `dependencies` event value into `(depends_on_id, type)`.
+pub(crate) fn decode_dependency(value: &str) -> Option<(String, String)> {
+    let link: DependencyValue = serde_json::from_str(value).ok()?;
+    Some((link.depends_on_id, link.dep_type))
+}
+
+/// Decode a `comments` event value into `(author, text)`.
+pub(crate) fn decode_comment(value: &str) -> Option<(String, String)> {
+    let comment: CommentValue = serde_json::from_str(value).ok()?;
+    Some((comment.author, comment.text))
+}
+
+/// Select dependency links matching `condition` as `(owner, encoded link)`.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn dependency_links(
+    conn: &Connection,
+    condition: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT issue_id, depends_on_id, type, created_at, created_by
+         FROM dependencies WHERE {condition}
+         ORDER BY issue_id, depends_on_id"
+    ))?;
+    let links = stmt
+        .query_map(params, |row| {
+            let owner: String = row.get(0)?;
+            let depends_on_id: String = row.get(1)?;
+            let dep_type: String = row.get(2)?;
+            let created_at: Option<String> = row.get(3)?;
+            let created_by: Option<String> = row.get(4)?;
+            Ok((
+                owner,
+                dependency_value(
+                    &depends_on_id,
+                    &dep_type,
+                    created_at.as_deref().unwrap_or(""),
+                    created_by.as_deref().unwrap_or(""),
+                ),
+            ))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(links)
+}
+
+/// String-encoded values of every tracked issue field.
+fn field_values(issue: &Issue) -> Vec<(&'static str, Option<String>)> {
+    let text = |value: Option<&str>| value.filter(|v| !v.is_empty()).map(str::to_string);
+    let time = |value: Option<DateTime<Utc>>| value.map(|dt| dt.to_rfc3339());
+    vec![
+        ("title", Some(issue.title.clone())),
+        ("description", text(issue.description.as_deref())),
+        ("design", text(issue.design.as_deref())),
+        (
+            "acceptance_criteria",
+            text(issue.acceptance_criteria.as_deref()),
+        ),
+        ("notes", text(issue.notes.as_deref())),
+        ("status", Some(issue.status.as_str().to_string())),
+        ("priority", Some(issue.priority.0.to_string())),
+        ("issue_type", Some(issue.issue_type.as_str().to_string())),
+        ("assignee", text(issue.assignee.as_deref())),
+        ("owner", text(issue.owner.as_deref())),
+        (
+            "estimated_minutes",
+            issue.estimated_minutes.map(|m| m.to_string()),
+        ),
+        ("due_at", time(issue.due_at)),
+        ("defer_until", time(issue.defer_until)),
+        ("external_ref", text(issue.external_ref.as_deref())),
+        ("closed_at", time(issue.closed_at)),
+        ("close_reason", text(issue.close_reason.as_deref())),
+        (
+            "closed_by_session",
+            text(issue.closed_by_session.as_deref()),
+        ),
+        ("deleted_at", time(issue.deleted_at)),
+        ("deleted_by", text(issue.deleted_by.as_deref())),
+        ("delete_reason", text(issue.delete_reason.as_deref())),
+        ("original_type", text(issue.original_type.as_deref())),
+        // Never NULL, for bd compatibility
+        (
+            "compaction_level",
+            Some(issue.compaction_level.unwrap_or(0).to_string()),
+        ),
+        ("compacted_at", time(issue.compacted_at)),
+        (
+            "compacted_at_commit",
+            text(issue.compacted_at_commit.as_deref()),
+        ),
+        ("original_size", issue.original_size.map(|n| n.to_string())),
+    ]
+}
+
+/// Names of the tracked issue fields.
+pub(crate) fn issue_field_names() -> impl Iterator<Item = &'static str> {
+    ISSUE_FIELDS.iter().map(|(name, _)| *name)
+}
+
+/// String-encoded value of one tracked issue field.
+pub(crate) fn field_value(issue: &Issue, field: &str) -> Option<String> {
+    field_values(issue)
+        .into_iter()
+        .find(|(name, _)| *name == field)
+        .and_then(|(_, value)| value)
+}
+
+/// Record an `Updated` event for each tracked field that differs, except
+/// those in `skip` (already recorded by the caller).
+pub fn record_field_changes(
+    ctx: &mut MutationContext,
+    before: &Issue,
+    after: &Issue,
+    skip: &[&str],
+) {
+    for ((field, old), (_, new)) in field_values(before).into_iter().zip(field_values(after)) {
+        if old != new && !skip.contains(&field) {
+            ctx.record_field_event(event_type_for(field), &after.id, field, old, new, None);
+        }
+    }
+}
+
+/// Event type used when recording a change to `field`.
+fn event_type_for(field: &str) -> EventType {
+    match field {
+        "status" => EventType::StatusChanged,
+        "priority" => EventType::PriorityChanged,
+        "assignee" => EventType::AssigneeChanged,
+        _ => EventType::Updated,
+    }
+}
+
+fn column_of(field: &str) -> Option<Column> {
+    ISSUE_FIELDS
+        .iter()
+        .find(|(name, _)| *name == field)
+        .map(|(_, column)| *column)
+}
+
+/// Normalize an encoded value for comparison (empty is unset, timestamps are
+/// re-rendered so equivalent spellings compare equal).
+fn normalize(column: Column, value: Option<&str>) -> Option<String> {
+    let value = value.filter(|v| !v.is_empty())?;
+    if column == Column::Timestamp {
+        if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+            return Some(dt.with_timezone(&Utc).to_rfc3339());
+        }
+    }
+    Some(value.to_string())
+}
+
+fn display(value: Option<&str>) -> String {
+    value.map_or_else(|| "(empty)".to_string(), |v| format!("'{v}'"))
+}
+
+/// List recorded operations, newest first (0 = no limit).
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn list_operations(conn: &Connection, limit: usize) -> Result<Vec<OperationSummary>> {
+    let limit = if limit == 0 {
+        -1
+    } else {
+        i64::try_from(limit).unwrap_or(i64::MAX)
+    };
+    let mut stmt = conn.prepare(
+        "SELECT o.id, o.name, o.actor, o.created_at, o.undoes, o.undone_by,
+                COUNT(e.id), GROUP_CONCAT(DISTINCT e.issue_id)
+         FROM operations o
+         LEFT JOIN events e ON e.op_id = o.id
+         GROUP BY o.id
+         ORDER BY o.rowid DESC
+         LIMIT ?",
+    )?;
+    let operations = stmt
+        .query_map([limit], |row| {
+            let created_at: String = row.get(3)?;
+            let undoes: Option<String> = row.get(4)?;
+            let events: i64 = row.get(6)?;
+            let issues: Option<String> = row.get(7)?;
+            let mut issues: Vec<String> = issues
+                .unwrap_or_default()
+                .split(',')
+                .filter(|id| !id.is_empty())
+                .map(str::to_string)
+                .collect();
+            issues.sort();
+            Ok(OperationSummary {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                actor: row.get(2)?,
+                created_at: parse_event_timestamp(&created_at),
+                events: usize::try_from(events).unwrap_or(0),
+                issues,
+                undoes: undoes.map(|ids| ids.split(',').map(str::to_string).collect()),
+                undone_by: row.get(5)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(operations)
+}
+
+/// Ids of the `steps` most recent operations that can still be undone.
+///
+/// Undo operations are skipped; revert one explicitly by id to redo.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn undoable_operations(conn: &Connection, steps: usize) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT id FROM operations
+         WHERE undone_by IS NULL AND undoes IS NULL
+         ORDER BY rowid DESC
+         LIMIT ?",
+    )?;
+    let ids = stmt
+        .query_map([i64::try_from(steps).unwrap_or(i64::MAX)], |row| row.get(0))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(ids)
+}
+
+/// Revert `op_ids` inside an open mutation.
+///
+/// # Errors
+///
+/// Returns a validation error for unknown or already-undone operations, and
+/// `BeadsError::UndoConflict` if later edits changed what they touched.
+pub fn undo_in_tx(
+    tx: &Transaction<'_>,
+    ctx: &mut MutationContext,
+    op_ids: &[String],
+) -> Result<UndoReport> {
+    let mut targets: Vec<(i64, String)> = Vec::new();
+    for op_id in op_ids {
+        let row: Option<(i64, Option<String>)> = tx
+            .query_row(
+                "SELECT rowid, undone_by FROM operations WHERE id = ?",
+                [op_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        match row {
+            None => {
+                return Err(BeadsError::validation(
+                    "op-id",
+                    format!("unknown operation '{op_id}'"),
+                ));
+            }
+            Some((_, Some(undone_by))) => {
+                return Err(BeadsError::validation(
+                    "op-id",
+                    format!("operation '{op_id}' was already undone by {undone_by}"),
+                ));
+            }
+            Some((rowid, None)) => {
+                if !targets.iter().any(|(_, id)| id == op_id) {
+                    targets.push((rowid, op_id.clone()));
+                }
+            }
+        }
+    }
+    // Newest first, so each inverse sees the state its event left behind
+    targets.sort_by_key(|(rowid, _)| std::cmp::Reverse(*rowid));
+    let batch: HashSet<String> = targets.iter().map(|(_, id)| id.clone()).collect();
+
+    let first_event = ctx.events.len();
+    let mut replay = Replay::new(tx, ctx);
+    for (rowid, op_id) in &targets {
+        replay.revert_operation(*rowid, op_id, &batch)?;
+    }
+    if let Some((op_id, conflicts)) = replay.conflict.take() {
+        return Err(BeadsError::UndoConflict { op_id, conflicts });
+    }
+    let issues = replay.finish()?;
+
+    let undone: Vec<String> = targets.into_iter().map(|(_, id)| id).collect();
+    let now = Utc::now().to_rfc3339();
+    tx.execute(
+        "INSERT OR IGNORE INTO operations (id, name, actor, created_at, undoes)
+         VALUES (?, ?, ?, ?, ?)",
+        rusqlite::params![ctx.op_id, ctx.op_name, ctx.actor, now, undone.join(",")],
+    )?;
+    for op_id in &undone {
+        tx.execute(
+            "UPDATE operations SET undone_by = ? WHERE id = ?",
+            rusqlite::params![ctx.op_id, op_id],
+        )?;
+    }
+
+    Ok(UndoReport {
+        op_id: ctx.op_id.clone(),
+        undone,
+        issues,
+        changes: ctx.events.len() - first_event,
+    })
+}
+
+/// Replays inverse changes, staging issue-field writes until the end so that
+/// related columns (status and `closed_at`) change together.
+struct Replay<'a, 'c> {
+    tx: &'a Transaction<'c>,
+    ctx: &'a mut MutationContext,
+    /// Current field values per issue, including staged writes.
+    state: BTreeMap<String, BTreeMap<&'static str, Option<String>>>,
+    /// Staged field writes per issue.
+    staged: BTreeMap<String, BTreeSet<&'static str>>,
+    touched: BTreeSet<String>,
+    /// First conflicting operation and every conflict found.
+    conflict: Option<(String, Vec<String>)>,
+}
+
+impl<'a, 'c> Replay<'a, 'c> {
+    fn new(tx: &'a Transaction<'c>, ctx: &'a mut MutationContext) -> Self {
+        Self {
+            tx,
+            ctx,
+            state: BTreeMap::new(),
+            staged: BTreeMap::new(),
+            touched: BTreeSet::new(),
+            conflict: None,
+        }
+    }
+
+    fn conflict(&mut self, op_id: &str, message: String) {
+        self.conflict
+            .get_or_insert_with(|| (op_id.to_string(), Vec::new()))
+            .1
+            .push(message);
+    }
+
+    fn revert_operation(&mut self, rowid: i64, op_id: &str, batch: &HashSet<String>) -> Result<()> {
+        let events = get_operation_events(self.tx, op_id)?;
+        let created: HashSet<String> = events
+            .iter()
+            .filter(|event| event.event_type == EventType::Created)
+            .map(|event| event.issue_id.clone())
+            .collect();
+        let note = format!("Undo {op_id}");
+
+        for event in events.iter().rev() {
+            let issue_id = event.issue_id.as_str();
+            let old = event.old_value.as_deref();
+            let new = event.new_value.as_deref();
+
+            // Creating an issue is reverted as a whole by tombstoning it
+            if created.contains(issue_id) {
+                if event.event_type == EventType::Created {
+                    self.revert_create(rowid, op_id, issue_id, batch, &note)?;
+                }
+                continue;
+            }
+
+            match (event.field.as_deref(), &event.event_type) {
+                (Some("labels"), _) => self.revert_label(op_id, issue_id, old, new, &note)?,
+                (Some("dependencies"), _) => {
+                    self.revert_dependency(op_id, issue_id, old, new, &note)?;
+                }
+                (Some("comments"), _) => self.revert_comment(op_id, issue_id, old, new, &note)?,
+                (Some(field), _) if column_of(field).is_some() => {
+                    self.revert_field(op_id, issue_id, field, old, new, &note)?;
+                }
+                // Companion events; the field events next to them carry the change
+                (
+                    None,
+                    EventType::Closed
+                    | EventType::Reopened
+                    | EventType::Deleted
+                    | EventType::Restored,
+                ) => {}
+                (field, event_type) => self.conflict(
+                    op_id,
+                    format!(
+                        "{issue_id}: cannot undo {} event{}",
+                        event_type.as_str(),
+                        field.map(|f| format!(" on '{f}'")).unwrap_or_default()
+                    ),
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Load (once) the current field values of an issue.
+    fn load(&mut self, issue_id: &str) -> Result<bool> {
+        if self.state.contains_key(issue_id) {
+            return Ok(true);
+        }
+        let Some(issue) = SqliteStorage::fetch_issue(self.tx, issue_id)? else {
+            return Ok(false);
+        };
+        self.state.insert(
+            issue_id.to_string(),
+            field_values(&issue).into_iter().collect(),
+        );
+        Ok(true)
+    }
+
+    fn current(&self, issue_id: &str, field: &str) -> Option<String> {
+        self.state
+            .get(issue_id)
+            .and_then(|fields| fields.get(field))
+            .cloned()
+            .flatten()
+    }
+
+    /// Stage a field write and record it as an event.
+    fn set(&mut self, issue_id: &str, field: &'static str, value: Option<String>, note: &str) {
+        let old = self.current(issue_id, field);
+        if let Some(fields) = self.state.get_mut(issue_id) {
+            fields.insert(field, value.clone());
+        }
+        self.staged
+            .entry(issue_id.to_string())
+            .or_default()
+            .insert(field);
+        self.touched.insert(issue_id.to_string());
+        self.ctx.record_field_event(
+            event_type_for(field),
+            issue_id,
+            field,
+            old,
+            value,
+            Some(note.to_string()),
+        );
+    }
+
+    fn revert_field(
+        &mut self,
+        op_id: &str,
+        issue_id: &str,
+        field: &str,
+        old: Option<&str>,
+        new: Option<&str>,
+        note: &str,
+    ) -> Result<()> {
+        let Some(&(name, column)) = ISSUE_FIELDS.iter().find(|(name, _)| *name == field) else {
+            return Ok(());
+        };
+        if !self.load(issue_id)? {
+            self.conflict(op_id, format!("{issue_id}: issue no longer exists"));
+            return Ok(());
+        }
+        let current = self.current(issue_id, name);
+        let expected = normalize(column, new);
+        if normalize(column, current.as_deref()) != expected {
+            self.conflict(
+                op_id,
+                format!(
+                    "{issue_id}: {name} is now {}, expected {}",
+                    display(current.as_deref()),
+                    display(expected.as_deref())
+                ),
+            );
+            return Ok(());
+        }
+        self.set(issue_id, name, normalize(column, old), note);
+        Ok(())
+    }
+
+    fn revert_create(
+        &mut self,
+        rowid: i64,
+        op_id: &str,
+        issue_id: &str,
+        batch: &HashSet<String>,
+        note: &str,
+    ) -> Result<()> {
+        if !self.load(issue_id)? {
+            self.conflict(op_id, format!("{issue_id}: issue no longer exists"));
+            return Ok(());
+        }
+        let mut stmt = self.tx.prepare(
+            "SELECT DISTINCT e.op_id FROM events e
+             JOIN operations o ON o.id = e.op_id
+             WHERE e.issue_id = ? AND o.rowid > ? AND o.undone_by IS NULL AND o.undoes IS NULL",
+        )?;
+        let later: Vec<String> = stmt
+            .query_map(rusqlite::params![issue_id, rowid], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        drop(stmt);
+        if let Some(later) = later.iter().find(|id| !batch.contains(*id)) {
+            self.conflict(
+                op_id,
+                format!("{issue_id}: changed by later operation {later}"),
+            );
+            return Ok(());
+        }
+        if self.current(issue_id, "status").as_deref() == Some("tombstone") {
+            self.conflict(op_id, format!("{issue_id}: already deleted"));
+            return Ok(());
+        }
+
+        let issue_type = self.current(issue_id, "issue_type");
+        self.set(issue_id, "status", Some("tombstone".to_string()), note);
+        self.set(issue_id, "deleted_at", Some(Utc::now().to_rfc3339()), note);
+        self.set(issue_id, "deleted_by", Some(self.ctx.actor.clone()), note);
+        self.set(issue_id, "delete_reason", Some(note.to_string()), note);
+        self.set(issue_id, "original_type", issue_type, note);
+        self.ctx
+            .record_event(EventType::Deleted, issue_id, Some(note.to_string()));
+        Ok(())
+    }
+
+    fn revert_label(
+        &mut self,
+        op_id: &str,
+        issue_id: &str,
+        old: Option<&str>,
+        new: Option<&str>,
+        note: &str,
+    ) -> Result<()> {
+        let (label, was_added) = match (old, new) {
+            (None, Some(label)) => (label, true),
+            (Some(label), None) => (label, false),
+            _ => {
+                self.conflict(op_id, format!("{issue_id}: malformed label event"));
+                return Ok(());
+            }
+        };
+        let present: bool = self
+            .tx
+            .query_row(
+                "SELECT 1 FROM labels WHERE issue_id = ? AND label = ?",
+                rusqlite::params![issue_id, label],
+                |_| Ok(true),
+            )
+            .optional()?
+            .unwrap_or(false);
+
+        if was_added {
+            if !present {
+                self.conflict(
+                    op_id,
+                    format!("{issue_id}: label '{label}' already removed"),
+                );
+                return Ok(());
+            }
+            self.tx.execute(
+                "DELETE FROM labels WHERE issue_id = ? AND label = ?",
+                rusqlite::params![issue_id, label],
+            )?;
+            self.ctx.record_field_event(
+                EventType::LabelRemoved,
+                issue_id,
+                "labels",
+                Some(label.to_string()),
+                None,
+                Some(note.to_string()),
+            );
+        } else {
+            if present {
+                self.conflict(op_id, format!("{issue_id}: label '{label}' was re-added"));
+                return Ok(());
+            }
+            self.tx.execute(
+                "INSERT INTO labels (issue_id, label) VALUES (?, ?)",
+                rusqlite::params![issue_id, label],
+            )?;
+            self.ctx.record_field_event(
+                EventType::LabelAdded,
+                issue_id,
+                "labels",
+                None,
+                Some(label.to_string()),
+                Some(note.to_string()),
+            );
+        }
+        self.touched.insert(issue_id.to_string());
+        Ok(())
+    }
+
+    fn revert_dependency(
+        &mut self,
+        op_id: &str,
+        issue_id: &str,
+        old: Option<&str>,
+        new: Option<&str>,
+        note: &str,
+    ) -> Result<()> {
+        let (raw, was_added) = match (old, new) {
+            (None, Some(value)) => (value, true),
+            (Some(value), None) => (value, false),
+            _ => {
+                self.conflict(op_id, format!("{issue_id}: malformed dependency event"));
+                return Ok(());
+            }
+        };
+        let link: DependencyValue = serde_json::from_str(raw)?;
+        let target = link.depends_on_id.as_str();
+        let existing = dependency_links(
+            self.tx,
+            "issue_id = ? AND depends_on_id = ?",
+            rusqlite::params![issue_id, target],
+        )?;
+
+        if was_added {
+            let Some((_, current)) = existing.into_iter().next() else {
+                self.conflict(
+                    op_id,
+                    format!("{issue_id}: dependency on {target} already removed"),
+                );
+                return Ok(());
+            };
+            self.tx.execute(
+                "DELETE FROM dependencies WHERE issue_id = ? AND depends_on_id = ?",
+                rusqlite::params![issue_id, target],
+            )?;
+            self.ctx.record_field_event(
+                EventType::DependencyRemoved,
+                issue_id,
+                "dependencies",
+                Some(current),
+                None,
+                Some(note.to_string()),
+            );
+        } else {
+            if !existing.is_empty() {
+                self.conflict(
+                    op_id,
+                    format!("{issue_id}: dependency on {target} was re-added"),
+                );
+                return Ok(());
+            }
+            let blocking = link
+                .dep_type
+                .parse::<DependencyType>()
+                .is_ok_and(|dep_type| dep_type.is_blocking());
+            if blocking && SqliteStorage::check_cycle(self.tx, issue_id, target, true)? {
+                self.conflict(
+                    op_id,
+                    format!("{issue_id}: restoring dependency on {target} would create a cycle"),
+                );
+                return Ok(());
+            }
+            let created_at = link
+                .created_at
+                .clone()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| Utc::now().to_rfc3339());
+            let created_by = link.created_by.clone().unwrap_or_default();
+            self.tx.execute(
+                "INSERT INTO dependencies (issue_id, depends_on_id, type, created_at, created_by)
+                 VALUES (?, ?, ?, ?, ?)",
+                rusqlite::params![issue_id, target, link.dep_type, created_at, created_by],
+            )?;
+            self.ctx.record_field_event(
+                EventType::DependencyAdded,
+                issue_id,
+                "dependencies",
+                None,
+                Some(raw.to_string()),
+                Some(note.to_string()),
+            );
+        }
+        self.ctx.invalidate_cache();
+        self.touched.insert(issue_id.to_string());
+        Ok(())
+    }
+
+    fn revert_comment(
+        &mut self,
+        op_id: &str,
+        issue_id: &str,
+        old: Option<&str>,
+        new: Option<&str>,
+        note: &str,
+    ) -> Result<()> {
+        let (raw, was_added) = match (old, new) {
+            (None, Some(value)) => (value, true),
+            (Some(value), None) => (value, false),
+            _ => {
+                self.conflict(op_id, format!("{issue_id}: malformed comment event"));
+                return Ok(());
+            }
+        };
+        let comment: CommentValue = serde_json::from_str(raw)?;
+        let exists: bool = self
+            .tx
+            .query_row(
+                "SELECT 1 FROM comments WHERE id = ? AND issue_id = ?",
+                rusqlite::params![comment.id, issue_id],
+                |_| Ok(true),
+            )
+            .optional()?
+            .unwrap_or(false);
+
+        if was_added {
+            if !exists {
+                self.conflict(
+                    op_id,
+                    format!("{issue_id}: comment {} already removed", comment.id),
+                );
+                return Ok(());
+            }
+            self.tx
+                .execute("DELETE FROM comments WHERE id = ?", [comment.id])?;
+            self.ctx.record_field_event(
+                EventType::Updated,
+                issue_id,
+                "comments",
+                Some(raw.to_string()),
+                None,
+                Some(note.to_string()),
+            );
+        } else {
+            if exists {
+                self.conflict(
+                    op_id,
+                    format!("{issue_id}: comment {} was restored", comment.id),
+                );
+                return Ok(());
+            }
+            self.tx.execute(
+                "INSERT INTO comments (id, issue_id, author, text, created_at)
+                 VALUES (?, ?, ?, ?, ?)",
+                rusqlite::params![
+                    comment.id,
+                    issue_id,
+                    comment.author,
+                    comment.text,
+                    comment.created_at
+                ],
+            )?;
+            self.ctx.record_field_event(
+                EventType::Commented,
+                issue_id,
+                "comments",
+                None,
+                Some(raw.to_string()),
+                Some(comment.text.clone()),
+            );
+        }
+        self.touched.insert(issue_id.to_string());
+        Ok(())
+    }
+
+    /// Write staged fields (one UPDATE per issue), refresh hashes and
+    /// timestamps, and mark everything touched as dirty.
+    fn finish(mut self) -> Result<Vec<String>> {
+        let now = Utc::now().to_rfc3339();
+        for (issue_id, fields) in &self.staged {
+            let mut sets = Vec::new();
+            let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+            for field in fields {
+                let value = self.current(issue_id, field);
+                sets.push(format!("{field} = ?"));
+                match column_of(field) {
+                    Some(Column::Blank) => params.push(Box::new(value.unwrap_or_default())),
+                    Some(Column::Integer) => {
+                        params.push(Box::new(value.and_then(|v| v.parse::<i64>().ok())));
+                    }
+                    _ => params.push(Box::new(value)),
+                }
+            }
+            params.push(Box::new(issue_id.clone()));
+            let sql = format!("UPDATE issues SET {} WHERE id = ?", sets.join(", "));
+            let refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(AsRef::as_ref).collect();
+            self.tx.execute(&sql, refs.as_slice())?;
+
+            if let Some(issue) = SqliteStorage::fetch_issue(self.tx, issue_id)? {
+                self.tx.execute(
+                    "UPDATE issues SET content_hash = ? WHERE id = ?",
+                    rusqlite::params![issue.compute_content_hash(), issue_id],
+                )?;
+            }
+        }
+
+        for issue_id in &self.touched {
+            self.tx.execute(
+                "UPDATE issues SET updated_at = ? WHERE id = ?",
+                rusqlite::params![now, issue_id],
+            )?;
+            self.ctx.mark_dirty(issue_id);
+        }
+        if !self.staged.is_empty() {
+            self.ctx.invalidate_cache();
+        }
+        Ok(self.touched.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Priority, Status};
+    use crate::storage::sqlite::IssueUpdate;
+
+    fn issue(id: &str, title: &str) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: title.to_string(),
+            ..Issue::default()
+        }
+    }
+
+    fn storage_with(ids: &[&str]) -> SqliteStorage {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        for id in ids {
+            storage.create_issue(&issue(id, id), "tester").unwrap();
+        }
+        storage
+    }
+
+    /// Run `f` as its own operation and return the operation id.
+    fn op(storage: &mut SqliteStorage, f: impl FnOnce(&mut SqliteStorage)) -> String {
+        let id = storage.begin_operation();
+        f(storage);
+        id
+    }
+
+    #[test]
+    fn undo_restores_fields_labels_and_close_state() {
+        let mut storage = storage_with(&["bd-u1"]);
+        storage.add_label("bd-u1", "keep", "tester").unwrap();
+
+        let op_id = op(&mut storage, |s| {
+            let update = IssueUpdate {
+                title: Some("Renamed".to_string()),
+                description: Some(Some("details".to_string())),
+                priority: Some(Priority::LOW),
+                status: Some(Status::Closed),
+                ..IssueUpdate::default()
+            };
+            s.update_issue("bd-u1", &update, "tester").unwrap();
+            s.set_labels("bd-u1", &["new".to_string()], "tester")
+                .unwrap();
+        });
+
+        let report = storage.undo_operations(&[op_id.clone()], "tester").unwrap();
+        assert_eq!(report.undone, vec![op_id]);
+        assert_eq!(report.issues, vec!["bd-u1".to_string()]);
+
+        let restored = storage.get_issue("bd-u1").unwrap().unwrap();
+        assert_eq!(restored.title, "bd-u1");
+        assert_eq!(restored.description, None);
+        assert_eq!(restored.priority, Priority::default());
+        assert_eq!(restored.status, Status::Open);
+        assert_eq!(restored.closed_at, None);
+        assert_eq!(
+            restored.content_hash.as_deref(),
+            Some(restored.compute_content_hash().as_str())
+        );
+        assert_eq!(storage.get_labels("bd-u1").unwrap(), vec!["keep"]);
+        assert!(
+            storage
+                .get_dirty_issue_ids()
+                .unwrap()
+                .contains(&"bd-u1".to_string())
+        );
+    }
+
+    #[test]
+    fn undo_refuses_when_later_edit_conflicts() {
+        let mut storage = storage_with(&["bd-u2"]);
+        let first = op(&mut storage, |s| {
+            let update = IssueUpdate {
+                title: Some("First".to_string()),
+                ..IssueUpdate::default()
+            };
+            s.update_issue("bd-u2", &update, "tester").unwrap();
+        });
+        op(&mut storage, |s| {
+            let update = IssueUpdate {
+                title: Some("Second".to_string()),
+                ..IssueUpdate::default()
+            };
+            s.update_issue("bd-u2", &update, "tester").unwrap();
+        });
+
+        let err = storage
+            .undo_operations(&[first.clone()], "tester")
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            BeadsError::UndoConflict { ref op_id, ref conflicts }
+                if *op_id == first && conflicts[0].contains("title")
+        ));
+        assert_eq!(storage.get_issue("bd-u2").unwrap().unwrap().title, "Second");
+
+        // Undoing both, newest first, succeeds.
+        let both = storage.undoable_operations(2).unwrap();
+        storage.undo_operations(&both, "tester").unwrap();
+        assert_eq!(storage.get_issue("bd-u2").unwrap().unwrap().title, "bd-u2");
+    }
+
+    #[test]
+    fn undo_reverts_dependencies_comments_and_creation() {
+        let mut storage = storage_with(&["bd-u3", "bd-u4"]);
+        let op_id = op(&mut storage, |s| {
+            s.add_dependency("bd-u3", "bd-u4", "blocks", "tester")
+                .unwrap();
+            s.add_comment("bd-u3", "tester", "note").unwrap();
+            s.create_issue(&issue("bd-u5", "Fresh"), "tester").unwrap();
+        });
+
+        storage.undo_operations(&[op_id], "tester").unwrap();
+        assert!(storage.get_dependencies("bd-u3").unwrap().is_empty());
+        assert!(storage.get_comments("bd-u3").unwrap().is_empty());
+        let created = storage.get_issue("bd-u5").unwrap().unwrap();
+        assert_eq!(created.status, Status::Tombstone);
+    }
+
+    #[test]
+    fn undoing_an_undo_redoes_it() {
+        let mut storage = storage_with(&["bd-u6"]);
+        let added = op(&mut storage, |s| {
+            s.add_label("bd-u6", "x", "tester").unwrap();
+        });
+        let report = storage.undo_operations(&[added.clone()], "tester").unwrap();
+        assert!(storage.get_labels("bd-u6").unwrap().is_empty());
+
+        // Already undone, and undo operations are never picked by --steps.
+        let err = storage.undo_operations(&[added], "tester").unwrap_err();
+        assert!(matches!(err, BeadsError::Validation { .. }));
+        assert!(
+            !storage
+                .undoable_operations(10)
+                .unwrap()
+                .contains(&report.op_id)
+        );
+
+        storage.undo_operations(&[report.op_id], "tester").unwrap();
+        assert_eq!(storage.get_labels("bd-u6").unwrap(), vec!["x"]);
+    }
+}
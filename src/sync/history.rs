@@ -53,7 +53,8 @@ pub fn backup_before_export(
 
     let history_dir = beads_dir.join(".br_history");
 
-    if !target_path.exists() {
+    // Directory exports (per-issue markdown) are versioned file by file in git.
+    if !target_path.is_file() {
         return Ok(());
     }
 
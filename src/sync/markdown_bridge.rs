@@ -0,0 +1,507 @@
+//! Bridge module for the per-issue markdown export.
+//!
+//! Instead of one export file, each issue lives in `.beads/issues/<id>.md`,
+//! so concurrent edits to different issues touch different files:
+//!
+//! ```markdown
+//! ---
+//! id: bd-abc
+//! title: Fix login
+//! status: open
+//! ...
+//! labels:
+//! - backend
+//! dependencies:
+//! - issue_id: bd-abc
+//!   depends_on_id: bd-xyz
+//!   type: blocks
+//!   ...
+//! ---
+//!
+//! ## Description
+//!
+//! Login fails on Safari.
+//!
+//! ## Comments
+//!
+//! ### #1 alice at 2026-01-02T03:04:05Z
+//!
+//! Reproduced on 17.2.
+//! ```
+//!
+//! The YAML frontmatter carries every field except the long text fields,
+//! which become `##` sections, and comments, which become `###` entries.
+//! Labels and dependencies are sorted and comments ordered by time, so an
+//! unchanged issue always renders to the same bytes.
+//!
+//! Text lines that would read as a section or comment heading, or as a
+//! git conflict marker, are escaped with a leading backslash (the markdown
+//! escape), and one backslash is removed again on read.
+
+use crate::error::{BeadsError, Result};
+use crate::model::{Comment, Issue};
+use chrono::{DateTime, SecondsFormat, Utc};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Default directory name of the markdown export inside `.beads/`.
+pub const MARKDOWN_EXPORT_DIR: &str = "issues";
+
+const FRONTMATTER_FENCE: &str = "---";
+const COMMENTS_HEADING: &str = "## Comments";
+const COMMENT_HEADING_PREFIX: &str = "### #";
+const CONFLICT_PREFIXES: &[&str] = &["<<<<<<<", "=======", ">>>>>>>"];
+
+/// Text sections in render order.
+const SECTIONS: &[(&str, TextField)] = &[
+    ("## Description", TextField::Description),
+    ("## Design", TextField::Design),
+    ("## Acceptance Criteria", TextField::AcceptanceCriteria),
+    ("## Notes", TextField::Notes),
+];
+
+#[derive(Debug, Clone, Copy)]
+enum TextField {
+    Description,
+    Design,
+    AcceptanceCriteria,
+    Notes,
+}
+
+impl TextField {
+    const fn get(self, issue: &Issue) -> Option<&String> {
+        match self {
+            Self::Description => issue.description.as_ref(),
+            Self::Design => issue.design.as_ref(),
+            Self::AcceptanceCriteria => issue.acceptance_criteria.as_ref(),
+            Self::Notes => issue.notes.as_ref(),
+        }
+    }
+
+    fn set(self, issue: &mut Issue, text: String) {
+        let slot = match self {
+            Self::Description => &mut issue.description,
+            Self::Design => &mut issue.design,
+            Self::AcceptanceCriteria => &mut issue.acceptance_criteria,
+            Self::Notes => &mut issue.notes,
+        };
+        *slot = Some(text);
+    }
+}
+
+/// Whether an export path is a markdown directory rather than a file.
+///
+/// Export files always carry an extension (`.jsonl`, `.org`); a path
+/// without one names a directory of `<id>.md` files.
+#[must_use]
+pub fn is_markdown_export(path: &Path) -> bool {
+    path.is_dir() || path.extension().is_none()
+}
+
+/// File name for an issue, refusing IDs that would escape the directory.
+///
+/// # Errors
+///
+/// Returns an error if the ID is empty, starts with a dot, or contains a
+/// path separator.
+pub fn file_name(id: &str) -> Result<String> {
+    if id.is_empty() || id.starts_with('.') || id.contains(['/', '\\', '\0']) {
+        return Err(BeadsError::Config(format!(
+            "Issue ID {id:?} cannot be used as a markdown file name"
+        )));
+    }
+    Ok(format!("{id}.md"))
+}
+
+/// Render one issue as a markdown document.
+///
+/// # Errors
+///
+/// Returns an error if the frontmatter cannot be serialized.
+pub fn issue_to_markdown(issue: &Issue) -> Result<String> {
+    let mut front = issue.clone();
+    front.description = None;
+    front.design = None;
+    front.acceptance_criteria = None;
+    front.notes = None;
+    front.comments = Vec::new();
+    front.labels.sort();
+    front.labels.dedup();
+    front.dependencies.sort_by(|a, b| {
+        a.depends_on_id
+            .cmp(&b.depends_on_id)
+            .then_with(|| a.dep_type.as_str().cmp(b.dep_type.as_str()))
+    });
+
+    let mut out = format!("{FRONTMATTER_FENCE}\n");
+    out.push_str(&serde_yaml::to_string(&front)?);
+    out.push_str(FRONTMATTER_FENCE);
+    out.push('\n');
+
+    for (heading, field) in SECTIONS {
+        if let Some(text) = field.get(issue).filter(|text| !text.is_empty()) {
+            push_block(&mut out, heading, text);
+        }
+    }
+
+    if !issue.comments.is_empty() {
+        let mut comments: Vec<&Comment> = issue.comments.iter().collect();
+        comments.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        out.push('\n');
+        out.push_str(COMMENTS_HEADING);
+        out.push('\n');
+        for comment in comments {
+            let heading = format!(
+                "{COMMENT_HEADING_PREFIX}{} {} at {}",
+                comment.id,
+                comment.author,
+                comment
+                    .created_at
+                    .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+            );
+            push_block(&mut out, &heading, &comment.body);
+        }
+    }
+
+    Ok(out)
+}
+
+fn push_block(out: &mut String, heading: &str, text: &str) {
+    out.push('\n');
+    out.push_str(heading);
+    out.push_str("\n\n");
+    out.push_str(&escape(text));
+    out.push('\n');
+}
+
+/// Parse one markdown document back into an issue.
+///
+/// # Errors
+///
+/// Returns an error if the frontmatter is missing or invalid, or a comment
+/// heading cannot be parsed.
+pub fn markdown_to_issue(text: &str) -> Result<Issue> {
+    let (front, body) = split_frontmatter(text)
+        .ok_or_else(|| BeadsError::Config("missing YAML frontmatter".to_string()))?;
+    let mut issue: Issue = serde_yaml::from_str(front)?;
+
+    let blocks = split_blocks(body);
+    for (heading, content) in blocks {
+        if let Some(rest) = heading.strip_prefix(COMMENT_HEADING_PREFIX) {
+            issue
+                .comments
+                .push(parse_comment(&issue.id, rest, content)?);
+        } else if let Some((_, field)) = SECTIONS.iter().find(|(name, _)| *name == heading) {
+            field.set(&mut issue, content);
+        }
+    }
+    Ok(issue)
+}
+
+fn split_frontmatter(text: &str) -> Option<(&str, &str)> {
+    let rest = text.strip_prefix(FRONTMATTER_FENCE)?.strip_prefix('\n')?;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end_matches(['\n', '\r']) == FRONTMATTER_FENCE {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Split the body at heading lines, undoing the framing of [`push_block`].
+fn split_blocks(body: &str) -> Vec<(&str, String)> {
+    let mut raw: Vec<(&str, usize, usize)> = Vec::new();
+    let mut offset = 0;
+    for line in body.split_inclusive('\n') {
+        let trimmed = line.trim_end_matches('\n');
+        if is_heading(trimmed) {
+            if let Some(last) = raw.last_mut() {
+                last.2 = offset;
+            }
+            raw.push((trimmed, offset + line.len(), body.len()));
+        }
+        offset += line.len();
+    }
+
+    let count = raw.len();
+    raw.into_iter()
+        .enumerate()
+        .map(|(idx, (heading, start, end))| {
+            let mut content = &body[start..end];
+            content = content.strip_prefix('\n').unwrap_or(content);
+            content = content.strip_suffix('\n').unwrap_or(content);
+            if idx + 1 < count {
+                content = content.strip_suffix('\n').unwrap_or(content);
+            }
+            (heading, unescape(content))
+        })
+        .collect()
+}
+
+fn is_heading(line: &str) -> bool {
+    line == COMMENTS_HEADING
+        || line.starts_with(COMMENT_HEADING_PREFIX)
+        || SECTIONS.iter().any(|(heading, _)| *heading == line)
+}
+
+fn parse_comment(issue_id: &str, heading: &str, body: String) -> Result<Comment> {
+    let invalid = || BeadsError::Config(format!("Invalid comment heading: {heading:?}"));
+    let (id, rest) = heading.split_once(' ').ok_or_else(invalid)?;
+    let (author, created_at) = rest.rsplit_once(" at ").ok_or_else(invalid)?;
+    Ok(Comment {
+        id: id.parse().map_err(|_| invalid())?,
+        issue_id: issue_id.to_string(),
+        author: author.to_string(),
+        body,
+        created_at: DateTime::parse_from_rfc3339(created_at)
+            .map_err(|_| invalid())?
+            .with_timezone(&Utc),
+    })
+}
+
+fn is_reserved(line: &str) -> bool {
+    let line = line.trim_start_matches('\\');
+    is_heading(line)
+        || CONFLICT_PREFIXES
+            .iter()
+            .any(|marker| line.starts_with(marker))
+}
+
+fn escape(text: &str) -> String {
+    text.split('\n')
+        .map(|line| {
+            if is_reserved(line) {
+                format!("\\{line}")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn unescape(text: &str) -> String {
+    text.split('\n')
+        .map(|line| match line.strip_prefix('\\') {
+            Some(rest) if is_reserved(line) => rest,
+            _ => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render issues as `(file name, contents)` pairs, sorted by file name.
+///
+/// # Errors
+///
+/// Returns an error if an ID is not a valid file name or an issue cannot be
+/// serialized.
+pub fn issues_to_files(issues: &[Issue]) -> Result<Vec<(String, String)>> {
+    let mut files = issues
+        .iter()
+        .map(|issue| Ok((file_name(&issue.id)?, issue_to_markdown(issue)?)))
+        .collect::<Result<Vec<_>>>()?;
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(files)
+}
+
+/// Content hash of a markdown export, over file names and contents.
+#[must_use]
+pub fn hash_files(files: &[(String, String)]) -> String {
+    let mut hasher = Sha256::new();
+    for (name, contents) in files {
+        hasher.update(name.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(contents.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Read the `.md` files of an export directory, sorted by file name.
+///
+/// A missing directory reads as empty.
+///
+/// # Errors
+///
+/// Returns an error if the directory or a file cannot be read.
+pub fn read_files(dir: &Path) -> Result<Vec<(String, String)>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_file() || path.extension().is_none_or(|ext| ext != "md") {
+            continue;
+        }
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        files.push((name.to_string(), fs::read_to_string(&path)?));
+    }
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(files)
+}
+
+/// Read all issues from an export directory.
+///
+/// # Errors
+///
+/// Returns an error naming the file if one cannot be read or parsed.
+pub fn read_issues(dir: &Path) -> Result<Vec<Issue>> {
+    read_files(dir)?
+        .into_iter()
+        .map(|(name, contents)| {
+            markdown_to_issue(&contents)
+                .map_err(|e| BeadsError::Config(format!("Invalid markdown issue {name}: {e}")))
+        })
+        .collect()
+}
+
+/// Write an export directory: changed files are replaced atomically,
+/// unchanged ones are left alone, and `.md` files for issues no longer
+/// exported are removed.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be created or a file cannot be
+/// written or removed.
+pub fn write_files(dir: &Path, files: &[(String, String)]) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    let existing = read_files(dir)?;
+
+    for (name, contents) in files {
+        let unchanged = existing
+            .binary_search_by(|(candidate, _)| candidate.cmp(name))
+            .is_ok_and(|idx| existing[idx].1 == *contents);
+        if unchanged {
+            continue;
+        }
+        let path = dir.join(name);
+        let temp_path = dir.join(format!("{name}.tmp"));
+        fs::write(&temp_path, contents)?;
+        fs::rename(&temp_path, &path)?;
+    }
+
+    for (name, _) in &existing {
+        if files.binary_search_by(|(kept, _)| kept.cmp(name)).is_err() {
+            fs::remove_file(dir.join(name))?;
+        }
+    }
+    Ok(())
+}
+
+/// Latest modification time of the directory or any `.md` file in it.
+///
+/// Editing a file in place leaves the directory's own mtime untouched, so
+/// staleness checks need the newest file.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be read.
+pub fn latest_mtime(dir: &Path) -> Result<SystemTime> {
+    let mut latest = fs::symlink_metadata(dir)?.modified()?;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.path().extension().is_some_and(|ext| ext == "md") {
+            latest = latest.max(entry.metadata()?.modified()?);
+        }
+    }
+    Ok(latest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Dependency, DependencyType, Status};
+    use chrono::TimeZone;
+
+    fn sample() -> Issue {
+        let at = Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap();
+        Issue {
+            id: "bd-abc".to_string(),
+            title: "Fix login: Safari".to_string(),
+            description: Some("Login fails.\n\n## Design\n=======\n\n".to_string()),
+            notes: Some("\\## Notes".to_string()),
+            status: Status::InProgress,
+            created_at: at,
+            updated_at: at,
+            labels: vec!["web".to_string(), "backend".to_string()],
+            dependencies: vec![Dependency {
+                issue_id: "bd-abc".to_string(),
+                depends_on_id: "bd-xyz".to_string(),
+                dep_type: DependencyType::Blocks,
+                created_at: at,
+                created_by: None,
+                metadata: None,
+                thread_id: None,
+            }],
+            comments: vec![
+                Comment {
+                    id: 2,
+                    issue_id: "bd-abc".to_string(),
+                    author: "bob smith".to_string(),
+                    body: "### #9 fake at heading".to_string(),
+                    created_at: at,
+                },
+                Comment {
+                    id: 1,
+                    issue_id: "bd-abc".to_string(),
+                    author: String::new(),
+                    body: String::new(),
+                    created_at: at,
+                },
+            ],
+            ..Issue::default()
+        }
+    }
+
+    #[test]
+    fn markdown_round_trips_awkward_text() {
+        let issue = sample();
+        let text = issue_to_markdown(&issue).unwrap();
+        assert!(text.starts_with("---\nid: bd-abc\n"), "{text}");
+        assert!(text.contains("\n\\## Design\n\\=======\n"), "{text}");
+
+        let parsed = markdown_to_issue(&text).unwrap();
+        assert_eq!(parsed.description, issue.description);
+        assert_eq!(parsed.notes, issue.notes);
+        assert_eq!(parsed.labels, vec!["backend", "web"]);
+        assert_eq!(parsed.dependencies, issue.dependencies);
+        assert_eq!(parsed.comments.len(), 2);
+        assert_eq!(parsed.comments[0].id, 1);
+        assert_eq!(parsed.comments[0].author, "");
+        assert_eq!(parsed.comments[1].author, "bob smith");
+        assert_eq!(parsed.comments[1].body, "### #9 fake at heading");
+        assert_eq!(issue_to_markdown(&parsed).unwrap(), text);
+    }
+
+    #[test]
+    fn file_names_stay_inside_the_directory() {
+        assert_eq!(file_name("bd-1").unwrap(), "bd-1.md");
+        assert!(file_name("../x").is_err());
+        assert!(file_name(".hidden").is_err());
+        assert!(file_name("").is_err());
+    }
+
+    #[test]
+    fn write_files_replaces_changed_and_removes_stale() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join(MARKDOWN_EXPORT_DIR);
+        let one = ("bd-1.md".to_string(), "one".to_string());
+        let two = ("bd-2.md".to_string(), "two".to_string());
+        write_files(&dir, &[one.clone(), two]).unwrap();
+        fs::write(dir.join("README.txt"), "keep").unwrap();
+
+        let changed = ("bd-1.md".to_string(), "uno".to_string());
+        write_files(&dir, std::slice::from_ref(&changed)).unwrap();
+        let files = read_files(&dir).unwrap();
+        assert_eq!(files, vec![changed]);
+        assert!(dir.join("README.txt").exists());
+        assert_ne!(hash_files(&[one]), hash_files(&files));
+    }
+}
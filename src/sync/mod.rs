@@ -8,8 +8,11 @@
 //! - Path validation and allowlist enforcement
 
 pub mod history;
+pub mod markdown_bridge;
 pub mod org_bridge;
 pub mod path;
+pub mod purge;
+pub mod splice;
 
 pub use path::{
     ALLOWED_EXACT_NAMES, ALLOWED_EXTENSIONS, PathValidation, is_sync_path_allowed,
@@ -53,6 +56,8 @@ pub struct ExportConfig {
     pub show_progress: bool,
     /// Configuration for history backups.
     pub history: HistoryConfig,
+    /// Nest child issues under their parent when writing Org.
+    pub org_nested: bool,
 }
 
 /// Export error handling policy.
@@ -1045,12 +1050,29 @@ const CONFLICT_START: &str = "<<<<<<<";
 const CONFLICT_SEPARATOR: &str = "=======";
 const CONFLICT_END: &str = ">>>>>>>";
 
-/// Scan a file for merge conflict markers.
+/// Scan a file (or each file of a markdown export) for merge conflict markers.
 ///
 /// # Errors
 ///
 /// Returns an error if the file cannot be read.
 pub fn scan_conflict_markers(path: &Path) -> Result<Vec<ConflictMarker>> {
+    if path.is_dir() {
+        let mut markers = Vec::new();
+        for (name, contents) in markdown_bridge::read_files(path)? {
+            for (line_num, line) in contents.lines().enumerate() {
+                if let Some((marker_type, branch)) = detect_conflict_marker(line) {
+                    markers.push(ConflictMarker {
+                        path: path.join(&name),
+                        line: line_num + 1,
+                        marker_type,
+                        branch,
+                    });
+                }
+            }
+        }
+        return Ok(markers);
+    }
+
     let file = File::open(path)?;
     let reader = BufReader::with_capacity(2 * 1024 * 1024, file);
     let mut markers = Vec::new();
@@ -1127,6 +1149,14 @@ struct PartialId {
 ///
 /// Returns an error if the file cannot be read or contains invalid JSON.
 pub fn analyze_jsonl(path: &Path) -> Result<(usize, HashSet<String>)> {
+    if markdown_bridge::is_markdown_export(path) {
+        let ids: HashSet<String> = markdown_bridge::read_issues(path)?
+            .into_iter()
+            .map(|issue| issue.id)
+            .collect();
+        return Ok((ids.len(), ids));
+    }
+
     let file = match File::open(path) {
         Ok(f) => f,
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((0, HashSet::new())),
@@ -1139,7 +1169,7 @@ pub fn analyze_jsonl(path: &Path) -> Result<(usize, HashSet<String>)> {
         .and_then(|e| e.to_str()) == Some("org");
 
     if is_org_format {
-        // Count level-1 headings in Org file
+        // Count issue headings in Org file
         return count_issues_in_org(path);
     }
 
@@ -1172,7 +1202,7 @@ pub fn analyze_jsonl(path: &Path) -> Result<(usize, HashSet<String>)> {
     Ok((count, ids))
 }
 
-/// Count issues in an Org file by counting level-1 headings and extracting IDs.
+/// Count issues in an Org file by counting issue headings and extracting IDs.
 fn count_issues_in_org(path: &Path) -> Result<(usize, HashSet<String>)> {
     let content = fs::read_to_string(path)?;
     let entries = org2jsonl::org_to_json::org_to_entries_with_keywords(
@@ -1184,19 +1214,27 @@ fn count_issues_in_org(path: &Path) -> Result<(usize, HashSet<String>)> {
     let mut count = 0;
     let mut ids = HashSet::new();
 
-    for entry in entries {
-        if let org2jsonl::model::EntryContent::Heading(heading) = entry.content {
+    // Level-1 headings, and issue headings nested below them
+    let mut headings = Vec::new();
+    for entry in &entries {
+        if let org2jsonl::model::EntryContent::Heading(heading) = &entry.content {
             if heading.level == 1 {
-                // Extract ID from properties
-                for prop in &heading.properties {
-                    if prop.key == "ID" {
-                        ids.insert(prop.value.trim().to_string());
-                        break;
-                    }
+                headings.push(heading);
+            }
+        }
+    }
+    while let Some(heading) = headings.pop() {
+        if heading.level == 1 || org_bridge::is_issue_heading(heading) {
+            // Extract ID from properties
+            for prop in &heading.properties {
+                if prop.key == "ID" {
+                    ids.insert(prop.value.trim().to_string());
+                    break;
                 }
-                count += 1;
             }
+            count += 1;
         }
+        headings.extend(&heading.children);
     }
 
     Ok((count, ids))
@@ -1226,6 +1264,7 @@ pub fn get_issue_ids_from_jsonl(path: &Path) -> Result<HashSet<String>> {
 /// - Include tombstones (for sync propagation)
 /// - Exclude ephemerals/wisps
 /// - Sort by ID for deterministic output
+/// - Keep the entry order of a previous export still in sync (see [`splice`])
 /// - Populate dependencies and labels for each issue
 /// - Atomic write (temp file -> rename)
 /// - Safety guard against empty DB overwriting non-empty JSONL
@@ -1305,7 +1344,11 @@ pub fn export_to_jsonl_with_policy(
 
         // Check 2: prevent exporting stale database that would lose issues
         if !jsonl_ids.is_empty() {
-            let db_ids: HashSet<String> = issues.iter().map(|i| i.id.clone()).collect();
+            let mut db_ids: HashSet<String> = issues.iter().map(|i| i.id.clone()).collect();
+            // Tombstones purged by `br gc` are meant to disappear from the export.
+            if let Some(ref beads_dir) = config.beads_dir {
+                db_ids.extend(purge::load_purged_ids(beads_dir)?);
+            }
             let missing: Vec<_> = jsonl_ids.difference(&db_ids).collect();
 
             if !missing.is_empty() {
@@ -1381,6 +1424,17 @@ pub fn export_to_jsonl_with_policy(
             None
         }
     };
+    let all_org_extras = match storage.get_all_org_extras() {
+        Ok(map) => Some(map),
+        Err(err) => {
+            ctx.handle_error(ExportError::new(
+                ExportEntityType::Issue,
+                "all",
+                err.to_string(),
+            ))?;
+            None
+        }
+    };
 
     for issue in &mut issues {
         if let Some(deps) = all_deps.as_ref().and_then(|map| map.get(&issue.id)) {
@@ -1403,6 +1457,78 @@ pub fn export_to_jsonl_with_policy(
         } else {
             issue.comments.clear();
         }
+        issue.org_extras = all_org_extras
+            .as_ref()
+            .and_then(|map| map.get(&issue.id))
+            .cloned();
+    }
+
+    // Compute hash and prepare data
+    let mut hasher = Sha256::new();
+    let mut exported_ids = Vec::new();
+    let mut skipped_tombstone_ids = Vec::new();
+    let mut issue_hashes = Vec::new();
+    let mut issues_for_export = Vec::new();
+
+    // Filter and collect issues for export
+    for issue in &issues {
+        // Skip expired tombstones
+        if issue.is_expired_tombstone(config.retention_days) {
+            skipped_tombstone_ids.push(issue.id.clone());
+            progress.inc(1);
+            continue;
+        }
+
+        exported_ids.push(issue.id.clone());
+        issue_hashes.push((
+            issue.id.clone(),
+            issue
+                .content_hash
+                .clone()
+                .unwrap_or_else(|| crate::util::content_hash(issue)),
+        ));
+        report.issues_exported += 1;
+        report.dependencies_exported += issue.dependencies.len();
+        report.labels_exported += issue.labels.len();
+        report.comments_exported += issue.comments.len();
+        issues_for_export.push(issue.clone());
+        progress.inc(1);
+    }
+
+    // Per-issue markdown export: each file is replaced on its own and
+    // unchanged files are left alone.
+    if markdown_bridge::is_markdown_export(output_path) {
+        let files = markdown_bridge::issues_to_files(&issues_for_export)?;
+        if let Some(ref beads_dir) = config.beads_dir {
+            require_safe_sync_overwrite_path(
+                output_path,
+                beads_dir,
+                config.allow_external_jsonl,
+                "overwrite markdown export",
+            )?;
+        }
+        markdown_bridge::write_files(output_path, &files)?;
+        progress.finish_with_message("Export complete");
+
+        let written = markdown_bridge::read_files(output_path)?.len();
+        if written != exported_ids.len() {
+            return Err(BeadsError::Config(format!(
+                "Export verification failed: expected {} issues, {} has {written} files",
+                exported_ids.len(),
+                output_path.display()
+            )));
+        }
+
+        report.errors = ctx.errors;
+        let result = ExportResult {
+            exported_count: exported_ids.len(),
+            exported_ids,
+            skipped_tombstone_ids,
+            content_hash: markdown_bridge::hash_files(&files),
+            output_path: Some(output_path.to_string_lossy().to_string()),
+            issue_hashes,
+        };
+        return Ok((result, report));
     }
 
     // Write to temp file for atomic rename
@@ -1440,45 +1566,38 @@ pub fn export_to_jsonl_with_policy(
         );
     }
 
+    // Splice into the previous export when it is still in sync, so the
+    // order of its entries survives and unchanged entries keep their bytes
+    let previous = previous_export_text(storage, output_path)?;
+    let unchanged = if previous.is_some() {
+        unchanged_since_export(storage, &issues_for_export)?
+    } else {
+        HashSet::new()
+    };
+
     let temp_file = File::create(&temp_path)?;
     let mut writer = BufWriter::new(temp_file);
 
-    // Compute hash and prepare data
-    let mut hasher = Sha256::new();
-    let mut exported_ids = Vec::new();
-    let mut skipped_tombstone_ids = Vec::new();
-    let mut issue_hashes = Vec::new();
-    let mut issues_for_export = Vec::new();
-
-    // Filter and collect issues for export
-    for issue in &issues {
-        // Skip expired tombstones
-        if issue.is_expired_tombstone(config.retention_days) {
-            skipped_tombstone_ids.push(issue.id.clone());
-            progress.inc(1);
-            continue;
-        }
-
-        exported_ids.push(issue.id.clone());
-        issue_hashes.push((
-            issue.id.clone(),
-            issue
-                .content_hash
-                .clone()
-                .unwrap_or_else(|| crate::util::content_hash(issue)),
-        ));
-        report.issues_exported += 1;
-        report.dependencies_exported += issue.dependencies.len();
-        report.labels_exported += issue.labels.len();
-        report.comments_exported += issue.comments.len();
-        issues_for_export.push(issue.clone());
-        progress.inc(1);
-    }
-
     // Write in the appropriate format
     if is_org_format {
         // Write Org-mode format
-        let org_text = org_bridge::issues_to_org_text(&issues_for_export);
+        let org_text = previous
+            .as_deref()
+            .and_then(|previous| {
+                org_bridge::splice_org_text(
+                    previous,
+                    &issues_for_export,
+                    &unchanged,
+                    config.org_nested,
+                )
+            })
+            .unwrap_or_else(|| {
+                if config.org_nested {
+                    org_bridge::issues_to_nested_org_text(&issues_for_export)
+                } else {
+                    org_bridge::issues_to_org_text(&issues_for_export)
+                }
+            });
         if let Err(err) = writer.write_all(org_text.as_bytes()) {
             ctx.handle_error(ExportError::new(
                 ExportEntityType::Issue,
@@ -1489,31 +1608,41 @@ pub fn export_to_jsonl_with_policy(
         hasher.update(org_text.as_bytes());
     } else {
         // Write JSONL format
+        let mut lines = std::collections::HashMap::new();
         for issue in &issues_for_export {
-            let json = match serde_json::to_string(issue) {
-                Ok(json) => json,
+            match serde_json::to_string(issue) {
+                Ok(json) => {
+                    lines.insert(issue.id.as_str(), json + "\n");
+                }
                 Err(err) => {
                     ctx.handle_error(ExportError::new(
                         ExportEntityType::Issue,
                         issue.id.clone(),
                         err.to_string(),
                     ))?;
-                    continue;
                 }
-            };
-
-            if let Err(err) = writeln!(writer, "{json}") {
-                ctx.handle_error(ExportError::new(
-                    ExportEntityType::Issue,
-                    issue.id.clone(),
-                    err.to_string(),
-                ))?;
-                continue;
             }
+        }
+        let order: Vec<&str> = issues_for_export
+            .iter()
+            .map(|issue| issue.id.as_str())
+            .collect();
+        let segments = previous
+            .as_deref()
+            .map(splice::jsonl_segments)
+            .unwrap_or_default();
+        let jsonl_text = splice::splice(&segments, &order, &unchanged, |id, _| {
+            lines.get(id).cloned()
+        });
 
-            hasher.update(json.as_bytes());
-            hasher.update(b"\n");
+        if let Err(err) = writer.write_all(jsonl_text.as_bytes()) {
+            ctx.handle_error(ExportError::new(
+                ExportEntityType::Issue,
+                "all".to_string(),
+                err.to_string(),
+            ))?;
         }
+        hasher.update(jsonl_text.as_bytes());
     }
 
     progress.finish_with_message("Export complete");
@@ -1578,6 +1707,41 @@ pub fn export_to_jsonl_with_policy(
     Ok((result, report))
 }
 
+/// Text of the previous export at `path`, if it is still the file the
+/// database last exported or imported; anything else is regenerated.
+fn previous_export_text(storage: &SqliteStorage, path: &Path) -> Result<Option<String>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let Some(recorded) = storage.get_metadata(METADATA_JSONL_CONTENT_HASH)? else {
+        return Ok(None);
+    };
+    if compute_jsonl_hash(path)? != recorded {
+        tracing::debug!(path = %path.display(), "Export changed since last sync; regenerating");
+        return Ok(None);
+    }
+    Ok(Some(fs::read_to_string(path)?))
+}
+
+/// IDs of issues whose entry in the previous export is still current: not
+/// dirty, and with the content hash recorded in `export_hashes`.
+fn unchanged_since_export(storage: &SqliteStorage, issues: &[Issue]) -> Result<HashSet<String>> {
+    let dirty: HashSet<String> = storage.get_dirty_issue_ids()?.into_iter().collect();
+    let exported = storage.get_all_export_hashes()?;
+    Ok(issues
+        .iter()
+        .filter(|issue| !dirty.contains(&issue.id))
+        .filter(|issue| {
+            let hash = issue
+                .content_hash
+                .clone()
+                .unwrap_or_else(|| crate::util::content_hash(issue));
+            exported.get(&issue.id) == Some(&hash)
+        })
+        .map(|issue| issue.id.clone())
+        .collect())
+}
+
 /// Export issues to a writer (e.g., stdout).
 ///
 /// # Errors
@@ -1638,6 +1802,17 @@ pub fn export_to_writer_with_policy<W: Write>(
             None
         }
     };
+    let all_org_extras = match storage.get_all_org_extras() {
+        Ok(map) => Some(map),
+        Err(err) => {
+            ctx.handle_error(ExportError::new(
+                ExportEntityType::Issue,
+                "all",
+                err.to_string(),
+            ))?;
+            None
+        }
+    };
 
     for issue in &mut issues {
         if let Some(deps) = all_deps.as_ref().and_then(|map| map.get(&issue.id)) {
@@ -1655,6 +1830,10 @@ pub fn export_to_writer_with_policy<W: Write>(
         } else {
             issue.comments.clear();
         }
+        issue.org_extras = all_org_extras
+            .as_ref()
+            .and_then(|map| map.get(&issue.id))
+            .cloned();
     }
 
     let mut hasher = Sha256::new();
@@ -1730,6 +1909,19 @@ pub struct StalenessCheck {
     pub db_newer: bool,
 }
 
+/// Modification time of an export: the file itself, or the newest file of a
+/// markdown export directory.
+///
+/// # Errors
+///
+/// Returns an error if the metadata cannot be read.
+pub fn export_mtime(path: &Path) -> Result<std::time::SystemTime> {
+    if path.is_dir() {
+        return markdown_bridge::latest_mtime(path);
+    }
+    Ok(fs::symlink_metadata(path)?.modified()?)
+}
+
 /// Compute staleness based on JSONL mtime + content hash and DB dirty state.
 ///
 /// Uses Lstat (`symlink_metadata`) for JSONL mtime to match classic bd behavior.
@@ -1744,7 +1936,7 @@ pub fn compute_staleness(storage: &SqliteStorage, jsonl_path: &Path) -> Result<S
     let jsonl_exists = jsonl_path.exists();
 
     let (jsonl_newer, db_newer) = if jsonl_exists {
-        let jsonl_mtime = fs::symlink_metadata(jsonl_path)?.modified()?;
+        let jsonl_mtime = export_mtime(jsonl_path)?;
 
         // JSONL is newer if it was modified after last import
         // If metadata is missing or invalid, assume JSONL is newer (safe default)
@@ -1924,10 +2116,22 @@ pub fn auto_flush(storage: &mut SqliteStorage, beads_dir: &Path) -> Result<AutoF
     }
 
     tracing::debug!(dirty_count, "Auto-flush: exporting dirty issues");
+    flush(storage, beads_dir)
+}
 
+/// Export to the default JSONL path even when no issue is dirty.
+///
+/// Used after changes that remove issues outright (see `br gc`), which
+/// leave nothing to mark dirty.
+///
+/// # Errors
+///
+/// Returns an error if the export fails.
+pub fn flush(storage: &mut SqliteStorage, beads_dir: &Path) -> Result<AutoFlushResult> {
     // Resolve the export path from metadata (respects jsonl_export setting)
-    let jsonl_path = match crate::config::Metadata::load(beads_dir) {
-        Ok(metadata) => {
+    let metadata = crate::config::Metadata::load(beads_dir).ok();
+    let jsonl_path = match &metadata {
+        Some(metadata) => {
             let export_name = &metadata.jsonl_export;
             if export_name.contains('/') || export_name.contains('\\') {
                 PathBuf::from(export_name)
@@ -1935,13 +2139,14 @@ pub fn auto_flush(storage: &mut SqliteStorage, beads_dir: &Path) -> Result<AutoF
                 beads_dir.join(export_name)
             }
         }
-        Err(_) => beads_dir.join(crate::config::DEFAULT_JSONL_FILENAME),
+        None => beads_dir.join(crate::config::DEFAULT_JSONL_FILENAME),
     };
 
     // Configure export with defaults, including beads_dir for path validation
     let export_config = ExportConfig {
         force: false,
         beads_dir: Some(beads_dir.to_path_buf()),
+        org_nested: metadata.is_some_and(|metadata| metadata.org_nested),
         ..Default::default()
     };
 
@@ -1970,6 +2175,10 @@ pub fn auto_flush(storage: &mut SqliteStorage, beads_dir: &Path) -> Result<AutoF
 ///
 /// Returns an error if the file cannot be read or contains invalid JSON.
 pub fn read_issues_from_jsonl(path: &Path) -> Result<Vec<Issue>> {
+    if markdown_bridge::is_markdown_export(path) {
+        return markdown_bridge::read_issues(path);
+    }
+
     // Detect format based on extension
     let is_org_format = path
         .extension()
@@ -2225,7 +2434,12 @@ pub fn import_from_jsonl(
         .extension()
         .and_then(|e| e.to_str()) == Some("org");
 
-    let mut issues = if is_org_format {
+    let mut issues = if markdown_bridge::is_markdown_export(input_path) {
+        let spinner = create_spinner("Reading markdown issues", config.show_progress);
+        let parsed_issues = markdown_bridge::read_issues(input_path)?;
+        spinner.finish_with_message("Read markdown issues");
+        parsed_issues
+    } else if is_org_format {
         let spinner = create_spinner("Reading Org file", config.show_progress);
         let content = fs::read_to_string(input_path)?;
         let parsed_issues = org_bridge::org_text_to_issues(&content)?;
@@ -2376,6 +2590,28 @@ pub fn import_from_jsonl(
         }
     }
 
+    // Step 4.5: Never resurrect tombstones purged by `br gc`, here or in
+    // another clone, and drop local copies of ones purged elsewhere.
+    if let Some(ref beads_dir) = config.beads_dir {
+        let purged = purge::load_purged_ids(beads_dir)?;
+        if !purged.is_empty() {
+            let before = issues.len();
+            issues.retain(|issue| !purged.contains(&issue.id));
+            result.tombstone_skipped += before - issues.len();
+
+            let mut local = Vec::new();
+            for id in &purged {
+                if storage.is_tombstone(id)? {
+                    local.push(id.clone());
+                }
+            }
+            if !local.is_empty() {
+                tracing::debug!(count = local.len(), "Purging tombstones listed in manifest");
+                storage.purge_issues(&local)?;
+            }
+        }
+    }
+
     // Clear export hashes before importing new data.
     storage.clear_all_export_hashes()?;
 
@@ -2529,7 +2765,7 @@ fn process_import_action(
     Ok(())
 }
 
-/// Sync labels, dependencies, and comments for an imported issue.
+/// Sync labels, dependencies, comments, and Org extras for an imported issue.
 fn sync_issue_relations(storage: &mut SqliteStorage, issue: &Issue) -> Result<()> {
     // Sync labels
     storage.sync_labels_for_import(&issue.id, &issue.labels)?;
@@ -2540,6 +2776,9 @@ fn sync_issue_relations(storage: &mut SqliteStorage, issue: &Issue) -> Result<()
     // Sync comments
     storage.sync_comments_for_import(&issue.id, &issue.comments)?;
 
+    // Sync Org extras
+    storage.sync_org_extras_for_import(&issue.id, issue.org_extras.as_ref())?;
+
     Ok(())
 }
 
@@ -2549,6 +2788,12 @@ fn sync_issue_relations(storage: &mut SqliteStorage, issue: &Issue) -> Result<()
 ///
 /// Returns an error if the file cannot be read.
 pub fn compute_jsonl_hash(path: &Path) -> Result<String> {
+    if path.is_dir() {
+        return Ok(markdown_bridge::hash_files(&markdown_bridge::read_files(
+            path,
+        )?));
+    }
+
     let file = File::open(path)?;
     let mut reader = BufReader::new(file);
     let mut hasher = Sha256::new();
@@ -2643,6 +2888,8 @@ pub struct MergeReport {
     pub tombstone_protected: Vec<String>,
     /// Notes about merge decisions.
     pub notes: Vec<(String, String)>,
+    /// Same-field conflicts resolved during field-level merge (both values kept).
+    pub field_conflicts: Vec<FieldConflict>,
 }
 
 impl MergeReport {
@@ -2673,13 +2920,44 @@ pub enum ConflictResolution {
     Manual,
 }
 
+/// Which side of a merge a value came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeSide {
+    /// Local (`SQLite`) side.
+    Local,
+    /// External (JSONL) side.
+    External,
+}
+
+/// A true same-field conflict: both sides changed one field to different values.
+///
+/// The merged issue carries the value from `kept`; both values are retained
+/// here so nothing is silently lost.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldConflict {
+    /// Issue the conflict belongs to.
+    pub issue_id: String,
+    /// Field name (grouped fields such as `status` include their companions).
+    pub field: String,
+    /// Value in the base snapshot.
+    pub base: serde_json::Value,
+    /// Local value.
+    pub local: serde_json::Value,
+    /// External value.
+    pub external: serde_json::Value,
+    /// Side whose value was written to the merged issue.
+    pub kept: MergeSide,
+}
+
 /// Merge a single issue given its state in base, left (local), and right (external).
 ///
 /// This implements the core 3-way merge logic for a single issue:
 /// - New local issues are kept
 /// - New external issues are imported
 /// - Deletions are handled based on whether the other side modified
-/// - Both-modified uses `updated_at` as tiebreaker (or specified strategy)
+/// - Both-modified merges field by field against the base (see
+///   [`merge_issue_with_conflicts`])
 ///
 /// # Arguments
 /// * `base` - The issue in the base (common ancestor) state, if it existed
@@ -2687,14 +2965,32 @@ pub enum ConflictResolution {
 /// * `right` - The issue in the external (JSONL) state, if it exists
 /// * `strategy` - How to resolve conflicts when both sides modified
 #[must_use]
-#[allow(clippy::too_many_lines)]
 pub fn merge_issue(
     base: Option<&Issue>,
     left: Option<&Issue>,
     right: Option<&Issue>,
     strategy: ConflictResolution,
 ) -> MergeResult {
-    match (base, left, right) {
+    merge_issue_with_conflicts(base, left, right, strategy).0
+}
+
+/// Merge a single issue and return any same-field conflicts alongside the result.
+///
+/// When an issue exists in all three states, each field is merged against the
+/// base: a change on one side wins, identical changes agree, and only fields
+/// changed differently on both sides are conflicts. Conflicts are resolved per
+/// field by `strategy` (`PreferNewer`/`Manual` use `updated_at`). Labels and
+/// dependencies merge as sets (additions and removals from either side apply),
+/// and comments are unioned by id.
+#[must_use]
+#[allow(clippy::too_many_lines)]
+pub fn merge_issue_with_conflicts(
+    base: Option<&Issue>,
+    left: Option<&Issue>,
+    right: Option<&Issue>,
+    strategy: ConflictResolution,
+) -> (MergeResult, Vec<FieldConflict>) {
+    let result = match (base, left, right) {
         // Case 1: Only in base (deleted in both local and external) -> no action
         (Some(_), None, None) => MergeResult::Delete,
 
@@ -2762,57 +3058,7 @@ pub fn merge_issue(
         }
 
         // Case 6: In all three (potentially modified in one or both)
-        (Some(b), Some(l), Some(r)) => {
-            let left_changed = l.content_hash != b.content_hash;
-            let right_changed = r.content_hash != b.content_hash;
-
-            match (left_changed, right_changed) {
-                // Neither changed OR only left changed - keep left
-                (false | true, false) => MergeResult::Keep(l.clone()),
-                // Only right changed - keep right
-                (false, true) => MergeResult::Keep(r.clone()),
-                // Both changed - use strategy
-                (true, true) => match strategy {
-                    ConflictResolution::PreferLocal => MergeResult::KeepWithNote(
-                        l.clone(),
-                        "Both modified - kept local".to_string(),
-                    ),
-                    ConflictResolution::PreferExternal => MergeResult::KeepWithNote(
-                        r.clone(),
-                        "Both modified - kept external".to_string(),
-                    ),
-                    ConflictResolution::PreferNewer => {
-                        if l.updated_at >= r.updated_at {
-                            MergeResult::KeepWithNote(
-                                l.clone(),
-                                "Both modified - kept local (newer)".to_string(),
-                            )
-                        } else {
-                            MergeResult::KeepWithNote(
-                                r.clone(),
-                                "Both modified - kept external (newer)".to_string(),
-                            )
-                        }
-                    }
-                    ConflictResolution::Manual => {
-                        // For manual, we still need to pick one, so use newer but mark as note
-                        if l.updated_at >= r.updated_at {
-                            MergeResult::KeepWithNote(
-                                l.clone(),
-                                "Both modified - kept local (newer), review recommended"
-                                    .to_string(),
-                            )
-                        } else {
-                            MergeResult::KeepWithNote(
-                                r.clone(),
-                                "Both modified - kept external (newer), review recommended"
-                                    .to_string(),
-                            )
-                        }
-                    }
-                },
-            }
-        }
+        (Some(b), Some(l), Some(r)) => return merge_fields(b, l, r, strategy),
 
         // Case 7: In left and right but not base (convergent creation)
         (None, Some(l), Some(r)) => {
@@ -2849,7 +3095,375 @@ pub fn merge_issue(
 
         // Case 8: Not in any (impossible in practice, but handle gracefully)
         (None, None, None) => MergeResult::NoAction,
+    };
+    (result, Vec::new())
+}
+
+/// Status and the fields that must move together with it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct StatusFields {
+    status: crate::model::Status,
+    closed_at: Option<chrono::DateTime<chrono::Utc>>,
+    close_reason: Option<String>,
+    closed_by_session: Option<String>,
+    deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+    deleted_by: Option<String>,
+    delete_reason: Option<String>,
+    original_type: Option<String>,
+}
+
+impl StatusFields {
+    fn of(issue: &Issue) -> Self {
+        Self {
+            status: issue.status.clone(),
+            closed_at: issue.closed_at,
+            close_reason: issue.close_reason.clone(),
+            closed_by_session: issue.closed_by_session.clone(),
+            deleted_at: issue.deleted_at,
+            deleted_by: issue.deleted_by.clone(),
+            delete_reason: issue.delete_reason.clone(),
+            original_type: issue.original_type.clone(),
+        }
+    }
+
+    fn apply(self, issue: &mut Issue) {
+        issue.status = self.status;
+        issue.closed_at = self.closed_at;
+        issue.close_reason = self.close_reason;
+        issue.closed_by_session = self.closed_by_session;
+        issue.deleted_at = self.deleted_at;
+        issue.deleted_by = self.deleted_by;
+        issue.delete_reason = self.delete_reason;
+        issue.original_type = self.original_type;
+    }
+}
+
+/// Compaction bookkeeping, merged as a unit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct CompactionFields {
+    compaction_level: Option<i32>,
+    compacted_at: Option<chrono::DateTime<chrono::Utc>>,
+    compacted_at_commit: Option<String>,
+    original_size: Option<i32>,
+}
+
+impl CompactionFields {
+    fn of(issue: &Issue) -> Self {
+        Self {
+            compaction_level: issue.compaction_level,
+            compacted_at: issue.compacted_at,
+            compacted_at_commit: issue.compacted_at_commit.clone(),
+            original_size: issue.original_size,
+        }
+    }
+
+    fn apply(self, issue: &mut Issue) {
+        issue.compaction_level = self.compaction_level;
+        issue.compacted_at = self.compacted_at;
+        issue.compacted_at_commit = self.compacted_at_commit;
+        issue.original_size = self.original_size;
+    }
+}
+
+/// Field-level merge state for one issue.
+struct FieldMerger<'a> {
+    issue_id: &'a str,
+    winner: MergeSide,
+    conflicts: Vec<FieldConflict>,
+}
+
+impl FieldMerger<'_> {
+    /// Three-way merge a single value, recording a conflict if both sides diverged.
+    fn merge<T: Clone + PartialEq + Serialize>(
+        &mut self,
+        field: &str,
+        base: &T,
+        local: &T,
+        external: &T,
+    ) -> T {
+        if local == external || external == base {
+            return local.clone();
+        }
+        if local == base {
+            return external.clone();
+        }
+
+        let to_json = |value: &T| serde_json::to_value(value).unwrap_or(serde_json::Value::Null);
+        self.conflicts.push(FieldConflict {
+            issue_id: self.issue_id.to_string(),
+            field: field.to_string(),
+            base: to_json(base),
+            local: to_json(local),
+            external: to_json(external),
+            kept: self.winner,
+        });
+        match self.winner {
+            MergeSide::Local => local.clone(),
+            MergeSide::External => external.clone(),
+        }
+    }
+}
+
+/// Merge an issue modified on either or both sides, field by field.
+#[allow(clippy::too_many_lines)]
+fn merge_fields(
+    base: &Issue,
+    local: &Issue,
+    external: &Issue,
+    strategy: ConflictResolution,
+) -> (MergeResult, Vec<FieldConflict>) {
+    let winner = match strategy {
+        ConflictResolution::PreferLocal => MergeSide::Local,
+        ConflictResolution::PreferExternal => MergeSide::External,
+        ConflictResolution::PreferNewer | ConflictResolution::Manual => {
+            if local.updated_at >= external.updated_at {
+                MergeSide::Local
+            } else {
+                MergeSide::External
+            }
+        }
+    };
+    let mut merger = FieldMerger {
+        issue_id: &local.id,
+        winner,
+        conflicts: Vec::new(),
+    };
+    let (b, l, r) = (base, local, external);
+
+    let mut merged = local.clone();
+    merged.title = merger.merge("title", &b.title, &l.title, &r.title);
+    merged.description = merger.merge(
+        "description",
+        &b.description,
+        &l.description,
+        &r.description,
+    );
+    merged.design = merger.merge("design", &b.design, &l.design, &r.design);
+    merged.acceptance_criteria = merger.merge(
+        "acceptance_criteria",
+        &b.acceptance_criteria,
+        &l.acceptance_criteria,
+        &r.acceptance_criteria,
+    );
+    merged.notes = merger.merge("notes", &b.notes, &l.notes, &r.notes);
+    merger
+        .merge(
+            "status",
+            &StatusFields::of(b),
+            &StatusFields::of(l),
+            &StatusFields::of(r),
+        )
+        .apply(&mut merged);
+    merged.priority = merger.merge("priority", &b.priority, &l.priority, &r.priority);
+    merged.issue_type = merger.merge("issue_type", &b.issue_type, &l.issue_type, &r.issue_type);
+    merged.assignee = merger.merge("assignee", &b.assignee, &l.assignee, &r.assignee);
+    merged.owner = merger.merge("owner", &b.owner, &l.owner, &r.owner);
+    merged.estimated_minutes = merger.merge(
+        "estimated_minutes",
+        &b.estimated_minutes,
+        &l.estimated_minutes,
+        &r.estimated_minutes,
+    );
+    merged.created_at = merger.merge("created_at", &b.created_at, &l.created_at, &r.created_at);
+    merged.created_by = merger.merge("created_by", &b.created_by, &l.created_by, &r.created_by);
+    merged.due_at = merger.merge("due_at", &b.due_at, &l.due_at, &r.due_at);
+    merged.defer_until = merger.merge(
+        "defer_until",
+        &b.defer_until,
+        &l.defer_until,
+        &r.defer_until,
+    );
+    merged.external_ref = merger.merge(
+        "external_ref",
+        &b.external_ref,
+        &l.external_ref,
+        &r.external_ref,
+    );
+    merged.source_system = merger.merge(
+        "source_system",
+        &b.source_system,
+        &l.source_system,
+        &r.source_system,
+    );
+    merged.source_repo = merger.merge(
+        "source_repo",
+        &b.source_repo,
+        &l.source_repo,
+        &r.source_repo,
+    );
+    merger
+        .merge(
+            "compaction",
+            &CompactionFields::of(b),
+            &CompactionFields::of(l),
+            &CompactionFields::of(r),
+        )
+        .apply(&mut merged);
+    merged.sender = merger.merge("sender", &b.sender, &l.sender, &r.sender);
+    merged.ephemeral = merger.merge("ephemeral", &b.ephemeral, &l.ephemeral, &r.ephemeral);
+    merged.pinned = merger.merge("pinned", &b.pinned, &l.pinned, &r.pinned);
+    merged.is_template = merger.merge(
+        "is_template",
+        &b.is_template,
+        &l.is_template,
+        &r.is_template,
+    );
+    merged.org_extras = merger.merge("org_extras", &b.org_extras, &l.org_extras, &r.org_extras);
+
+    merged.labels = merge_label_sets(&b.labels, &l.labels, &r.labels);
+    merged.dependencies = merge_dependency_sets(&b.dependencies, &l.dependencies, &r.dependencies);
+    merged.comments = union_comments(&l.comments, &r.comments);
+    merged.updated_at = l.updated_at.max(r.updated_at);
+
+    let conflicts = merger.conflicts;
+    let same_as = |side: &Issue| {
+        let mut candidate = side.clone();
+        candidate.content_hash.clone_from(&merged.content_hash);
+        candidate == merged
+    };
+    let matches_local = same_as(l);
+    let matches_external = same_as(r);
+
+    if conflicts.is_empty() {
+        if matches_local {
+            return (MergeResult::Keep(l.clone()), conflicts);
+        }
+        if matches_external {
+            return (MergeResult::Keep(r.clone()), conflicts);
+        }
+    }
+
+    if matches_external && !matches_local {
+        merged.content_hash.clone_from(&r.content_hash);
+    } else if !matches_local {
+        merged.content_hash = Some(merged.compute_content_hash());
+    }
+
+    let note = if conflicts.is_empty() {
+        "Both modified - merged fields without conflicts".to_string()
+    } else {
+        let side = match winner {
+            MergeSide::Local => "local",
+            MergeSide::External => "external",
+        };
+        let newer = if matches!(
+            strategy,
+            ConflictResolution::PreferNewer | ConflictResolution::Manual
+        ) {
+            " (newer)"
+        } else {
+            ""
+        };
+        let fields: Vec<&str> = conflicts.iter().map(|c| c.field.as_str()).collect();
+        format!(
+            "Both modified - merged fields; conflicting {} kept {side}{newer}",
+            fields.join(", ")
+        )
+    };
+
+    (MergeResult::KeepWithNote(merged, note), conflicts)
+}
+
+/// Three-way merge of label sets: additions and removals from either side apply.
+fn merge_label_sets(base: &[String], local: &[String], external: &[String]) -> Vec<String> {
+    let base: HashSet<&String> = base.iter().collect();
+    let local_set: HashSet<&String> = local.iter().collect();
+    let external_set: HashSet<&String> = external.iter().collect();
+    if local_set == external_set || external_set == base {
+        return local.to_vec();
+    }
+    if local_set == base {
+        return external.to_vec();
+    }
+
+    let mut merged: Vec<String> = local_set
+        .union(&external_set)
+        .filter(|label| {
+            let in_both = local_set.contains(*label) && external_set.contains(*label);
+            in_both || !base.contains(*label)
+        })
+        .map(|label| (*label).clone())
+        .collect();
+    merged.sort();
+    merged
+}
+
+/// Three-way merge of dependency sets keyed by target and type.
+fn merge_dependency_sets(
+    base: &[crate::model::Dependency],
+    local: &[crate::model::Dependency],
+    external: &[crate::model::Dependency],
+) -> Vec<crate::model::Dependency> {
+    let key = |dep: &crate::model::Dependency| (dep.depends_on_id.clone(), dep.dep_type.clone());
+    let base: HashSet<_> = base.iter().map(key).collect();
+    let local_keys: HashSet<_> = local.iter().map(key).collect();
+    let external_keys: HashSet<_> = external.iter().map(key).collect();
+    if local_keys == external_keys || external_keys == base {
+        return local.to_vec();
+    }
+    if local_keys == base {
+        return external.to_vec();
+    }
+
+    let keep = |k: &(String, crate::model::DependencyType)| {
+        (local_keys.contains(k) && external_keys.contains(k)) || !base.contains(k)
+    };
+
+    let mut merged: Vec<crate::model::Dependency> = local
+        .iter()
+        .filter(|dep| keep(&key(dep)))
+        .cloned()
+        .collect();
+    merged.extend(
+        external
+            .iter()
+            .filter(|dep| !local_keys.contains(&key(dep)) && keep(&key(dep)))
+            .cloned(),
+    );
+    merged.sort_by(|a, b| {
+        a.depends_on_id
+            .cmp(&b.depends_on_id)
+            .then_with(|| a.dep_type.as_str().cmp(b.dep_type.as_str()))
+    });
+    merged
+}
+
+/// Union comments by id. Distinct comments that collide on an id (created
+/// independently on each side) keep the local id; the external one is renumbered.
+fn union_comments(
+    local: &[crate::model::Comment],
+    external: &[crate::model::Comment],
+) -> Vec<crate::model::Comment> {
+    if external.iter().all(|c| local.contains(c)) {
+        return local.to_vec();
+    }
+    if local.iter().all(|c| external.contains(c)) {
+        return external.to_vec();
+    }
+
+    let mut merged: Vec<crate::model::Comment> = local.to_vec();
+    let mut next_id = local
+        .iter()
+        .chain(external)
+        .map(|c| c.id)
+        .max()
+        .unwrap_or(0);
+
+    for comment in external {
+        match merged.iter().position(|c| c.id == comment.id) {
+            Some(idx) if merged[idx] == *comment => {}
+            Some(_) => {
+                next_id += 1;
+                let mut renumbered = comment.clone();
+                renumbered.id = next_id;
+                merged.push(renumbered);
+            }
+            None => merged.push(comment.clone()),
+        }
     }
+
+    merged.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+    merged
 }
 
 /// Perform a 3-way merge across all issues in the context.
@@ -2889,7 +3503,8 @@ pub fn three_way_merge(
             }
         }
 
-        let result = merge_issue(base, left, right, strategy);
+        let (result, field_conflicts) = merge_issue_with_conflicts(base, left, right, strategy);
+        report.field_conflicts.extend(field_conflicts);
 
         match result {
             MergeResult::NoAction => {}
@@ -3032,6 +3647,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         }
     }
 
@@ -3077,6 +3693,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         }
     }
 
@@ -4653,6 +5270,7 @@ mod tests {
             labels: vec![],
             dependencies: vec![],
             comments: vec![],
+            org_extras: None,
         }
     }
 
@@ -5000,6 +5618,190 @@ mod tests {
         assert!(report.tombstone_protected.is_empty());
     }
 
+    // ========================================================================
+    // Field-level merge tests
+    // ========================================================================
+
+    fn with_edit(base: &Issue, seconds: i64, edit: impl FnOnce(&mut Issue)) -> Issue {
+        let mut issue = base.clone();
+        issue.updated_at = fixed_time_merge(seconds);
+        issue.content_hash = Some(format!("hash-{seconds}"));
+        edit(&mut issue);
+        issue
+    }
+
+    #[test]
+    fn test_merge_disjoint_field_edits_both_kept() {
+        let base = make_issue_with_hash("bd-f1", "Base", fixed_time_merge(100), Some("h0"));
+        let local = with_edit(&base, 200, |i| i.priority = Priority::CRITICAL);
+        let external = with_edit(&base, 300, |i| i.description = Some("Ext".to_string()));
+
+        let (result, conflicts) = merge_issue_with_conflicts(
+            Some(&base),
+            Some(&local),
+            Some(&external),
+            ConflictResolution::PreferNewer,
+        );
+
+        assert!(conflicts.is_empty());
+        let MergeResult::KeepWithNote(merged, _) = result else {
+            panic!("expected merged issue");
+        };
+        assert_eq!(merged.priority, Priority::CRITICAL);
+        assert_eq!(merged.description.as_deref(), Some("Ext"));
+        assert_eq!(merged.updated_at, fixed_time_merge(300));
+        assert_eq!(
+            merged.content_hash.as_deref(),
+            Some(merged.compute_content_hash().as_str())
+        );
+    }
+
+    #[test]
+    fn test_merge_same_field_conflict_keeps_both_values() {
+        let base = make_issue_with_hash("bd-f2", "Base", fixed_time_merge(100), Some("h0"));
+        let local = with_edit(&base, 300, |i| i.title = "Local title".to_string());
+        let external = with_edit(&base, 200, |i| {
+            i.title = "External title".to_string();
+            i.notes = Some("External notes".to_string());
+        });
+
+        let (result, conflicts) = merge_issue_with_conflicts(
+            Some(&base),
+            Some(&local),
+            Some(&external),
+            ConflictResolution::PreferNewer,
+        );
+
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.field, "title");
+        assert_eq!(conflict.local, serde_json::json!("Local title"));
+        assert_eq!(conflict.external, serde_json::json!("External title"));
+        assert_eq!(conflict.base, serde_json::json!("Base"));
+        assert_eq!(conflict.kept, MergeSide::Local);
+
+        let MergeResult::KeepWithNote(merged, note) = result else {
+            panic!("expected merged issue");
+        };
+        assert_eq!(merged.title, "Local title");
+        assert_eq!(merged.notes.as_deref(), Some("External notes"));
+        assert!(note.contains("title"));
+    }
+
+    #[test]
+    fn test_merge_status_moves_with_close_fields() {
+        let base = make_issue_with_hash("bd-f3", "Base", fixed_time_merge(100), Some("h0"));
+        let local = with_edit(&base, 200, |i| {
+            i.status = Status::Closed;
+            i.closed_at = Some(fixed_time_merge(200));
+            i.close_reason = Some("done".to_string());
+        });
+        let external = with_edit(&base, 300, |i| i.status = Status::InProgress);
+
+        let (result, conflicts) = merge_issue_with_conflicts(
+            Some(&base),
+            Some(&local),
+            Some(&external),
+            ConflictResolution::PreferNewer,
+        );
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].field, "status");
+        let MergeResult::KeepWithNote(merged, _) = result else {
+            panic!("expected merged issue");
+        };
+        assert_eq!(merged.status, Status::InProgress);
+        assert!(merged.closed_at.is_none());
+        assert!(merged.close_reason.is_none());
+    }
+
+    #[test]
+    fn test_merge_labels_dependencies_and_comments_as_sets() {
+        let dep = |target: &str| crate::model::Dependency {
+            issue_id: "bd-f4".to_string(),
+            depends_on_id: target.to_string(),
+            dep_type: crate::model::DependencyType::Blocks,
+            created_at: fixed_time_merge(50),
+            created_by: None,
+            metadata: None,
+            thread_id: None,
+        };
+        let comment = |id: i64, body: &str, at: i64| crate::model::Comment {
+            id,
+            issue_id: "bd-f4".to_string(),
+            author: "alice".to_string(),
+            body: body.to_string(),
+            created_at: fixed_time_merge(at),
+        };
+
+        let mut base = make_issue_with_hash("bd-f4", "Base", fixed_time_merge(100), Some("h0"));
+        base.labels = vec!["keep".to_string(), "drop-local".to_string()];
+        base.dependencies = vec![dep("bd-a"), dep("bd-b")];
+        base.comments = vec![comment(1, "first", 60)];
+
+        let local = with_edit(&base, 200, |i| {
+            i.labels = vec!["keep".to_string(), "add-local".to_string()];
+            i.dependencies = vec![dep("bd-a"), dep("bd-b"), dep("bd-c")];
+            i.comments.push(comment(2, "local second", 150));
+        });
+        let external = with_edit(&base, 300, |i| {
+            i.labels.push("add-external".to_string());
+            i.dependencies = vec![dep("bd-a")];
+            i.comments.push(comment(2, "external second", 160));
+        });
+
+        let (result, conflicts) = merge_issue_with_conflicts(
+            Some(&base),
+            Some(&local),
+            Some(&external),
+            ConflictResolution::PreferNewer,
+        );
+        assert!(conflicts.is_empty());
+        let MergeResult::KeepWithNote(merged, _) = result else {
+            panic!("expected merged issue");
+        };
+
+        assert_eq!(merged.labels, vec!["add-external", "add-local", "keep"]);
+        let targets: Vec<&str> = merged
+            .dependencies
+            .iter()
+            .map(|d| d.depends_on_id.as_str())
+            .collect();
+        assert_eq!(targets, vec!["bd-a", "bd-c"]);
+        let bodies: Vec<(i64, &str)> = merged
+            .comments
+            .iter()
+            .map(|c| (c.id, c.body.as_str()))
+            .collect();
+        assert_eq!(
+            bodies,
+            vec![(1, "first"), (2, "local second"), (3, "external second")]
+        );
+    }
+
+    #[test]
+    fn test_three_way_merge_reports_field_conflicts() {
+        let base = make_issue_with_hash("bd-f5", "Base", fixed_time_merge(100), Some("h0"));
+        let local = with_edit(&base, 200, |i| i.priority = Priority::HIGH);
+        let external = with_edit(&base, 300, |i| i.priority = Priority::LOW);
+
+        let context = MergeContext::new(
+            std::collections::HashMap::from([("bd-f5".to_string(), base)]),
+            std::collections::HashMap::from([("bd-f5".to_string(), local)]),
+            std::collections::HashMap::from([("bd-f5".to_string(), external)]),
+        );
+        let report = three_way_merge(&context, ConflictResolution::PreferLocal, None);
+
+        assert!(
+            !report.has_conflicts(),
+            "field conflicts are resolved, not fatal"
+        );
+        assert_eq!(report.field_conflicts.len(), 1);
+        assert_eq!(report.field_conflicts[0].kept, MergeSide::Local);
+        assert_eq!(report.kept.len(), 1);
+        assert_eq!(report.kept[0].priority, Priority::HIGH);
+    }
+
     #[test]
     fn test_three_way_merge_deletions() {
         // Setup: issue in base but deleted in both left and right
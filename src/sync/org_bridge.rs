@@ -5,13 +5,50 @@
 //! - `org_text_to_issues()`: Parses Org-mode text into Issue structs
 //!
 //! The Org format is more human-readable than JSONL while preserving all issue data.
+//!
+//! `due_at`, `defer_until` and `closed_at` are written as `DEADLINE:`,
+//! `SCHEDULED:` and `CLOSED:` planning timestamps in local time, so they show
+//! up in org-agenda. Planning timestamps have minute precision and no zone,
+//! so the exact value is also kept in the property drawer (`:DUE_AT:` etc.)
+//! with the writer's UTC offset. It wins on import unless the planning line
+//! no longer matches it in that offset, i.e. was edited. Deleting the
+//! planning line clears the date; only entries from before planning lines
+//! were written (`:BEADS_SCHEMA_VERSION: 1`) take it from the property.
+//!
+//! Content a user adds by hand that has no issue field is carried in
+//! [`OrgExtras`]: `:LOGBOOK:` lines (whose `CLOCK:` entries are summed as time
+//! spent), unknown properties, other drawers, and subheadings other than the
+//! issue sections.
+//!
+//! [`issues_to_nested_org_text()`] writes child issues as subheadings of their
+//! parent. Import accepts either layout: a heading nested under an issue
+//! heading is a child of that issue, whatever its dependencies still say.
 
 use crate::error::{BeadsError, Result};
-use crate::model::{Issue, IssueType, Priority, Status};
-use chrono::{DateTime, Utc};
+use crate::model::{Dependency, DependencyType, Issue, IssueType, OrgExtras, Priority, Status};
+use crate::sync::splice::{Segment, splice};
+use crate::util::id::parse_id;
+use chrono::{
+    DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc,
+};
 use org2jsonl::model::{Element, EntryContent, Heading, InlineContent};
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
+/// `:BEADS_SCHEMA_VERSION:` of written entries. Version 2 entries put every
+/// closed/due/defer date on the planning line; version 1 only had the
+/// property.
+const ORG_SCHEMA_VERSION: u32 = 2;
+
+/// Child headings holding issue fields; any other subheading is kept verbatim.
+const ISSUE_SECTIONS: &[&str] = &[
+    "Design",
+    "Acceptance Criteria",
+    "Notes",
+    "Dependencies",
+    "Comments",
+];
+
 /// Convert a collection of issues to Org-mode text format.
 ///
 /// The output starts with a file header specifying TODO keywords, followed by
@@ -20,31 +57,155 @@ use std::str::FromStr;
 /// - Priority tag ([#A], [#B], etc)
 /// - Title
 /// - Tags (labels)
+/// - Planning line (deadline, scheduled, closed)
 /// - Properties drawer (metadata)
+/// - Logbook and other kept drawers
 /// - Body paragraphs (description)
 /// - Child headings for design, acceptance criteria, notes
 /// - JSON src blocks for dependencies and comments
+/// - Kept subheadings
 #[must_use]
 pub fn issues_to_org_text(issues: &[Issue]) -> String {
-    let mut output = String::new();
+    let mut output = org_file_header();
 
-    // File header with TODO sequence
-    output.push_str("#+TITLE: Beads Issues\n");
-    output.push_str("#+SEQ_TODO: TODO DOING WAIT DEFER NOTE | DONE CANCELED\n");
-    output.push('\n');
+    for issue in issues {
+        emit_issue_heading(&mut output, issue, 1);
+    }
+
+    output
+}
 
+/// Convert a collection of issues to Org-mode text with child issues nested.
+///
+/// An issue whose first parent-child dependency points at another exported
+/// issue is written as a subheading of that issue, after the parent's own
+/// sections. Siblings are ordered by their child number (`bd-abc.2` before
+/// `bd-abc.10`), keeping input order otherwise. Dependencies are still
+/// written in full, so both layouts import to the same issues.
+#[must_use]
+pub fn issues_to_nested_org_text(issues: &[Issue]) -> String {
+    let ids: HashSet<&str> = issues.iter().map(|issue| issue.id.as_str()).collect();
+    let mut roots = Vec::new();
+    let mut children: HashMap<&str, Vec<&Issue>> = HashMap::new();
     for issue in issues {
-        emit_issue_heading(&mut output, issue);
+        match nesting_parent(issue, |id| ids.contains(id)) {
+            Some(parent) => children.entry(parent).or_default().push(issue),
+            None => roots.push(issue),
+        }
+    }
+    for siblings in children.values_mut() {
+        siblings.sort_by_cached_key(|issue| parse_id(&issue.id).map(|id| id.child_path).ok());
+    }
+
+    let mut output = org_file_header();
+    let mut emitted = HashSet::new();
+    for issue in roots {
+        emit_issue_tree(&mut output, issue, 1, &children, &mut emitted);
+    }
+    // Issues on a parent-child cycle have no root above them
+    for issue in issues {
+        emit_issue_tree(&mut output, issue, 1, &children, &mut emitted);
+    }
+
+    output
+}
+
+/// Whether Org text nests issue headings, as
+/// [`issues_to_nested_org_text()`] writes them.
+#[must_use]
+pub fn is_nested_org_text(org_text: &str) -> bool {
+    org_segments(org_text)
+        .iter()
+        .any(|segment| segment.id.is_some() && segment.level > 1)
+}
+
+/// Re-export issues over the previous Org export, keeping its order of
+/// headings (see [`crate::sync::splice`]).
+///
+/// Returns `None` when the previous file cannot be kept: it uses the other
+/// layout, or, nested, an issue is no longer under the heading of the
+/// parent it would be nested under now.
+#[must_use]
+pub fn splice_org_text(
+    previous: &str,
+    issues: &[Issue],
+    unchanged: &HashSet<String>,
+    nested: bool,
+) -> Option<String> {
+    if is_nested_org_text(previous) != nested {
+        return None;
+    }
+    let segments = org_segments(previous);
+    let by_id: HashMap<&str, &Issue> = issues
+        .iter()
+        .map(|issue| (issue.id.as_str(), issue))
+        .collect();
+    if nested {
+        let parents = segment_parents(&segments);
+        let moved = issues.iter().any(|issue| {
+            parents.get(issue.id.as_str()).copied().flatten()
+                != nesting_parent(issue, |id| by_id.contains_key(id))
+        });
+        if moved {
+            return None;
+        }
     }
 
+    let order: Vec<&str> = issues.iter().map(|issue| issue.id.as_str()).collect();
+    Some(splice(&segments, &order, unchanged, |id, level| {
+        let mut text = String::new();
+        emit_issue_heading(&mut text, by_id.get(id)?, level);
+        Some(text)
+    }))
+}
+
+/// File header with the TODO sequence.
+fn org_file_header() -> String {
+    let mut output = String::new();
+    output.push_str("#+TITLE: Beads Issues\n");
+    output.push_str("#+SEQ_TODO: TODO DOING WAIT DEFER NOTE | DONE CANCELED\n");
+    output.push('\n');
     output
 }
 
-/// Emit a single issue as an Org heading.
+/// The parent an issue is nested under: the target of its first
+/// parent-child dependency on another issue for which `present` holds.
+fn nesting_parent<'a>(issue: &'a Issue, present: impl Fn(&str) -> bool) -> Option<&'a str> {
+    issue
+        .dependencies
+        .iter()
+        .find(|dep| {
+            dep.dep_type == DependencyType::ParentChild
+                && dep.depends_on_id != issue.id
+                && present(&dep.depends_on_id)
+        })
+        .map(|dep| dep.depends_on_id.as_str())
+}
+
+/// Emit an issue and, one level deeper, its not yet emitted children.
+fn emit_issue_tree<'a>(
+    output: &mut String,
+    issue: &'a Issue,
+    level: usize,
+    children: &HashMap<&str, Vec<&'a Issue>>,
+    emitted: &mut HashSet<&'a str>,
+) {
+    if !emitted.insert(issue.id.as_str()) {
+        return;
+    }
+    emit_issue_heading(output, issue, level);
+    for &child in children.get(issue.id.as_str()).into_iter().flatten() {
+        emit_issue_tree(output, child, level + 1, children, emitted);
+    }
+}
+
+/// Emit a single issue as an Org heading at `level`.
 #[allow(clippy::too_many_lines)]
-fn emit_issue_heading(output: &mut String, issue: &Issue) {
+fn emit_issue_heading(output: &mut String, issue: &Issue, level: usize) {
     // Build heading line: * STATUS [#PRIORITY] Title :tag1:tag2:
-    output.push_str("* ");
+    let stars = "*".repeat(level);
+    output.push_str(&stars);
+    output.push(' ');
 
     // Status keyword
     let keyword = status_to_keyword(&issue.status);
@@ -75,9 +236,25 @@ fn emit_issue_heading(output: &mut String, issue: &Issue) {
 
     output.push('\n');
 
+    // Planning line (read by org-agenda)
+    let planning: Vec<String> = [
+        ("CLOSED", issue.closed_at, false),
+        ("DEADLINE", issue.due_at, true),
+        ("SCHEDULED", issue.defer_until, true),
+    ]
+    .into_iter()
+    .filter_map(|(keyword, at, active)| {
+        at.map(|at| format!("{keyword}: {}", org_timestamp(at, active)))
+    })
+    .collect();
+    if !planning.is_empty() {
+        output.push_str(&planning.join(" "));
+        output.push('\n');
+    }
+
     // Properties drawer
     output.push_str(":PROPERTIES:\n");
-    output.push_str(":BEADS_SCHEMA_VERSION: 1\n");
+    output.push_str(&format!(":BEADS_SCHEMA_VERSION: {ORG_SCHEMA_VERSION}\n"));
     output.push_str(&format!(":ID:       {}\n", sanitize_property_value(&issue.id)));
     output.push_str(&format!(":ISSUE_TYPE: {}\n", issue.issue_type.as_str()));
     output.push_str(&format!(":CREATED_AT: {}\n", issue.created_at.to_rfc3339()));
@@ -96,8 +273,10 @@ fn emit_issue_heading(output: &mut String, issue: &Issue) {
     if let Some(ref created_by) = issue.created_by {
         output.push_str(&format!(":CREATED_BY: {}\n", sanitize_property_value(created_by)));
     }
+    // Closed/due/defer also go on the planning line, in local time; the
+    // exact value keeps the seconds and the offset that line was written in
     if let Some(closed_at) = issue.closed_at {
-        output.push_str(&format!(":CLOSED_AT: {}\n", closed_at.to_rfc3339()));
+        output.push_str(&format!(":CLOSED_AT: {}\n", exact_timestamp(closed_at)));
     }
     if let Some(ref close_reason) = issue.close_reason {
         output.push_str(&format!(":CLOSE_REASON: {}\n", sanitize_property_value(close_reason)));
@@ -106,10 +285,10 @@ fn emit_issue_heading(output: &mut String, issue: &Issue) {
         output.push_str(&format!(":CLOSED_BY_SESSION: {}\n", sanitize_property_value(closed_by_session)));
     }
     if let Some(due_at) = issue.due_at {
-        output.push_str(&format!(":DUE_AT: {}\n", due_at.to_rfc3339()));
+        output.push_str(&format!(":DUE_AT: {}\n", exact_timestamp(due_at)));
     }
     if let Some(defer_until) = issue.defer_until {
-        output.push_str(&format!(":DEFER_UNTIL: {}\n", defer_until.to_rfc3339()));
+        output.push_str(&format!(":DEFER_UNTIL: {}\n", exact_timestamp(defer_until)));
     }
     if let Some(ref external_ref) = issue.external_ref {
         output.push_str(&format!(":EXTERNAL_REF: {}\n", sanitize_property_value(external_ref)));
@@ -165,8 +344,32 @@ fn emit_issue_heading(output: &mut String, issue: &Issue) {
         output.push_str(":IS_TEMPLATE: true\n");
     }
 
+    let extras = issue.org_extras.as_ref();
+    for (key, value) in extras.map_or(&[][..], |extras| extras.properties.as_slice()) {
+        output.push_str(&format!(
+            ":{}: {}\n",
+            sanitize_property_value(key),
+            sanitize_property_value(value)
+        ));
+    }
+
     output.push_str(":END:\n");
 
+    if let Some(extras) = extras {
+        if !extras.logbook.is_empty() {
+            output.push_str(":LOGBOOK:\n");
+            for line in &extras.logbook {
+                output.push_str(&sanitize_property_value(line));
+                output.push('\n');
+            }
+            output.push_str(":END:\n");
+        }
+        if let Some(ref drawers) = extras.drawers {
+            output.push_str(drawers);
+            output.push('\n');
+        }
+    }
+
     // Description (body paragraphs)
     if let Some(ref desc) = issue.description {
         output.push('\n');
@@ -179,7 +382,7 @@ fn emit_issue_heading(output: &mut String, issue: &Issue) {
     if let Some(ref design) = issue.design {
         if !design.is_empty() {
             output.push('\n');
-            output.push_str("** Design\n");
+            output.push_str(&format!("{stars}* Design\n"));
             let sanitized = sanitize_org_text(design);
             output.push_str(&sanitized);
             output.push('\n');
@@ -190,7 +393,7 @@ fn emit_issue_heading(output: &mut String, issue: &Issue) {
     if let Some(ref ac) = issue.acceptance_criteria {
         if !ac.is_empty() {
             output.push('\n');
-            output.push_str("** Acceptance Criteria\n");
+            output.push_str(&format!("{stars}* Acceptance Criteria\n"));
             let sanitized = sanitize_org_text(ac);
             output.push_str(&sanitized);
             output.push('\n');
@@ -201,7 +404,7 @@ fn emit_issue_heading(output: &mut String, issue: &Issue) {
     if let Some(ref notes) = issue.notes {
         if !notes.is_empty() {
             output.push('\n');
-            output.push_str("** Notes\n");
+            output.push_str(&format!("{stars}* Notes\n"));
             let sanitized = sanitize_org_text(notes);
             output.push_str(&sanitized);
             output.push('\n');
@@ -211,7 +414,7 @@ fn emit_issue_heading(output: &mut String, issue: &Issue) {
     // Dependencies section
     if !issue.dependencies.is_empty() {
         output.push('\n');
-        output.push_str("** Dependencies\n");
+        output.push_str(&format!("{stars}* Dependencies\n"));
         output.push_str("#+begin_src json\n");
         let json = serde_json::to_string_pretty(&issue.dependencies)
             .unwrap_or_else(|_| "[]".to_string());
@@ -223,7 +426,7 @@ fn emit_issue_heading(output: &mut String, issue: &Issue) {
     // Comments section
     if !issue.comments.is_empty() {
         output.push('\n');
-        output.push_str("** Comments\n");
+        output.push_str(&format!("{stars}* Comments\n"));
         output.push_str("#+begin_src json\n");
         let json = serde_json::to_string_pretty(&issue.comments)
             .unwrap_or_else(|_| "[]".to_string());
@@ -232,6 +435,13 @@ fn emit_issue_heading(output: &mut String, issue: &Issue) {
         output.push_str("#+end_src\n");
     }
 
+    // Subheadings added by hand
+    if let Some(subtrees) = extras.and_then(|extras| extras.subtrees.as_ref()) {
+        output.push('\n');
+        output.push_str(&demote_headings(subtrees, level - 1));
+        output.push('\n');
+    }
+
     // Blank line after issue
     output.push('\n');
 }
@@ -244,11 +454,12 @@ fn sanitize_property_value(value: &str) -> String {
         .replace(":END:", ":END ")
 }
 
-/// Sanitize text for Org-mode (escape lines starting with `*`).
+/// Sanitize text for Org-mode (escape lines starting with `*`, and lines
+/// that would read as a drawer or a clock entry).
 fn sanitize_org_text(text: &str) -> String {
     text.lines()
         .map(|line| {
-            if line.starts_with('*') {
+            if needs_org_escape(line) {
                 format!(",{line}")
             } else {
                 line.to_string()
@@ -258,6 +469,135 @@ fn sanitize_org_text(text: &str) -> String {
         .join("\n")
 }
 
+fn needs_org_escape(line: &str) -> bool {
+    line.starts_with('*') || drawer_name(line).is_some() || is_clock_line(line)
+}
+
+/// Name of the drawer a `:NAME:` line opens (or `END` for a closing line).
+fn drawer_name(line: &str) -> Option<&str> {
+    let name = line.trim().strip_prefix(':')?.strip_suffix(':')?;
+    (!name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+    .then_some(name)
+}
+
+fn is_clock_line(line: &str) -> bool {
+    line.trim_start().starts_with("CLOCK:")
+}
+
+/// Whether a timestamp survives a minute-precision planning line unchanged.
+fn is_minute_precise(at: DateTime<Utc>) -> bool {
+    at.second() == 0 && at.nanosecond() == 0
+}
+
+/// RFC 3339 in local time, recording the offset planning lines are written in.
+fn exact_timestamp(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local).to_rfc3339()
+}
+
+/// Render an Org timestamp in local time: `<2026-01-05 Mon 09:00>`, or
+/// `[...]` for an inactive one. Local midnight is written as a bare date.
+fn org_timestamp(at: DateTime<Utc>, active: bool) -> String {
+    let local = at.with_timezone(&Local);
+    let stamp = if local.hour() == 0 && local.minute() == 0 && is_minute_precise(at) {
+        local.format("%Y-%m-%d %a").to_string()
+    } else {
+        local.format("%Y-%m-%d %a %H:%M").to_string()
+    };
+    if active {
+        format!("<{stamp}>")
+    } else {
+        format!("[{stamp}]")
+    }
+}
+
+/// Parse an Org timestamp (`<2026-01-05 Mon 09:00 +1w>`, `[2026-01-05]`)
+/// as the wall-clock time it shows. Repeaters, warning periods and time
+/// ranges are ignored.
+fn parse_org_timestamp(stamp: &str) -> Option<NaiveDateTime> {
+    let inner = stamp
+        .trim()
+        .strip_prefix(['<', '['])?
+        .strip_suffix(['>', ']'])?;
+    let mut parts = inner.split_whitespace();
+    let date = NaiveDate::parse_from_str(parts.next()?, "%Y-%m-%d").ok()?;
+    let time = parts
+        .find_map(|part| {
+            let start = part.split('-').next().unwrap_or(part);
+            NaiveTime::parse_from_str(start, "%H:%M").ok()
+        })
+        .unwrap_or_default();
+    Some(date.and_time(time))
+}
+
+/// Timestamps on a planning line (`DEADLINE: <...> SCHEDULED: <...>`).
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Planning {
+    closed: Option<NaiveDateTime>,
+    deadline: Option<NaiveDateTime>,
+    scheduled: Option<NaiveDateTime>,
+}
+
+impl Planning {
+    /// Parse a planning line, or `None` if the line is not one.
+    fn parse(line: &str) -> Option<Self> {
+        let trimmed = line.trim_start();
+        if !["CLOSED:", "DEADLINE:", "SCHEDULED:"]
+            .iter()
+            .any(|keyword| trimmed.starts_with(keyword))
+        {
+            return None;
+        }
+        let stamp_after = |keyword: &str| {
+            let rest = trimmed[trimmed.find(keyword)? + keyword.len()..].trim_start();
+            let close = if rest.starts_with('<') { '>' } else { ']' };
+            parse_org_timestamp(&rest[..=rest.find(close)?])
+        };
+        Some(Self {
+            closed: stamp_after("CLOSED:"),
+            deadline: stamp_after("DEADLINE:"),
+            scheduled: stamp_after("SCHEDULED:"),
+        })
+    }
+}
+
+/// Pick between the exact property value and the planning timestamp.
+///
+/// Both are written together, the planning line in the offset the property
+/// records. While the line still shows the property's time to the minute in
+/// that offset, the exact value wins, wherever the file is read; an edited
+/// planning line wins otherwise and is read in local time. With no planning
+/// timestamp the date was removed, unless the entry is from a version that
+/// wrote none (`legacy`), where the property stands alone.
+fn resolve_planning(
+    exact: Option<DateTime<FixedOffset>>,
+    planned: Option<NaiveDateTime>,
+    legacy: bool,
+) -> Option<DateTime<Utc>> {
+    let Some(planned) = planned else {
+        return exact
+            .filter(|_| legacy)
+            .map(|exact| exact.with_timezone(&Utc));
+    };
+    match exact {
+        Some(exact)
+            if exact
+                .naive_local()
+                .with_second(0)
+                .and_then(|at| at.with_nanosecond(0))
+                == Some(planned) =>
+        {
+            Some(exact.with_timezone(&Utc))
+        }
+        _ => Local
+            .from_local_datetime(&planned)
+            .earliest()
+            .map(|local| local.with_timezone(&Utc)),
+    }
+}
+
 /// Convert Issue status to Org keyword.
 fn status_to_keyword(status: &Status) -> String {
     match status {
@@ -326,7 +666,8 @@ pub const BEADS_DONE_KEYWORDS: &[&str] = &[
 /// Parse Org-mode text into a collection of issues.
 ///
 /// Uses the org2jsonl parser to extract headings, then converts each
-/// level-1 heading into an Issue struct.
+/// level-1 heading, and each issue heading nested below one, into an Issue
+/// struct. A nested issue is made a child of the issue heading above it.
 ///
 /// # Errors
 ///
@@ -338,14 +679,15 @@ pub fn org_text_to_issues(org_text: &str) -> Result<Vec<Issue>> {
         BEADS_TODO_KEYWORDS,
         BEADS_DONE_KEYWORDS,
     );
+    let mut raw_sections = scan_raw_sections(org_text);
+    let ids: HashSet<String> = raw_sections.keys().cloned().collect();
     let mut issues = Vec::new();
 
     for entry in entries {
         if let EntryContent::Heading(heading) = entry.content {
             // Only process level-1 headings (top-level issues)
             if heading.level == 1 {
-                let issue = parse_heading_to_issue(&heading)?;
-                issues.push(issue);
+                collect_issue_tree(&heading, None, &ids, &mut raw_sections, &mut issues)?;
             }
         }
         // Skip Section entries (file header)
@@ -354,8 +696,358 @@ pub fn org_text_to_issues(org_text: &str) -> Result<Vec<Issue>> {
     Ok(issues)
 }
 
+/// Convert an issue heading and the issue headings nested anywhere below it.
+fn collect_issue_tree(
+    heading: &Heading,
+    parent: Option<&str>,
+    ids: &HashSet<String>,
+    raw_sections: &mut HashMap<String, RawSection>,
+    issues: &mut Vec<Issue>,
+) -> Result<()> {
+    let mut issue = parse_heading_to_issue(heading, raw_sections)?;
+    if let Some(parent) = parent {
+        nest_under(&mut issue, parent, ids);
+    }
+    let id = issue.id.clone();
+    issues.push(issue);
+    collect_nested_issues(&heading.children, &id, ids, raw_sections, issues)
+}
+
+/// Convert the issue headings among `headings`, looking through the
+/// subheadings that are not issues.
+fn collect_nested_issues(
+    headings: &[Heading],
+    parent: &str,
+    ids: &HashSet<String>,
+    raw_sections: &mut HashMap<String, RawSection>,
+    issues: &mut Vec<Issue>,
+) -> Result<()> {
+    for heading in headings {
+        if is_issue_heading(heading) {
+            collect_issue_tree(heading, Some(parent), ids, raw_sections, issues)?;
+        } else {
+            collect_nested_issues(&heading.children, parent, ids, raw_sections, issues)?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether a heading below level 1 is an issue. An `:ID:` alone is not
+/// enough: org-id puts one on ordinary headings too.
+#[must_use]
+pub fn is_issue_heading(heading: &Heading) -> bool {
+    ["ID", "ISSUE_TYPE"]
+        .iter()
+        .all(|key| heading.properties.iter().any(|prop| prop.key == *key))
+}
+
+/// Make a nested issue a child of the issue heading it sits under.
+///
+/// A parent-child dependency on another issue in the file is the one the
+/// heading was nested under before it was moved, so it is retargeted. The
+/// move counts as an edit, or the import would skip the issue as unchanged.
+fn nest_under(issue: &mut Issue, parent: &str, ids: &HashSet<String>) {
+    if issue
+        .dependencies
+        .iter()
+        .any(|dep| dep.dep_type == DependencyType::ParentChild && dep.depends_on_id == parent)
+    {
+        return;
+    }
+
+    issue.updated_at = Utc::now();
+    let moved_from = nesting_parent(issue, |id| ids.contains(id)).map(ToString::to_string);
+    if let Some(dep) = issue.dependencies.iter_mut().find(|dep| {
+        dep.dep_type == DependencyType::ParentChild
+            && moved_from.as_deref() == Some(dep.depends_on_id.as_str())
+    }) {
+        dep.depends_on_id = parent.to_string();
+        return;
+    }
+    issue.dependencies.push(Dependency {
+        issue_id: issue.id.clone(),
+        depends_on_id: parent.to_string(),
+        dep_type: DependencyType::ParentChild,
+        created_at: issue.updated_at,
+        created_by: None,
+        metadata: None,
+        thread_id: None,
+    });
+}
+
+/// Raw lines of one issue heading that the structured parse drops.
+#[derive(Debug, Default)]
+struct RawSection {
+    id: Option<String>,
+    planning: Planning,
+    /// `:BEADS_SCHEMA_VERSION:`, 0 when missing
+    schema_version: u32,
+    /// `:CLOSED_AT:`, `:DUE_AT:` and `:DEFER_UNTIL:` in the offset written
+    closed_at: Option<DateTime<FixedOffset>>,
+    due_at: Option<DateTime<FixedOffset>>,
+    defer_until: Option<DateTime<FixedOffset>>,
+    description: Option<String>,
+    logbook: Vec<String>,
+    drawers: Vec<String>,
+    subtrees: Vec<String>,
+}
+
+/// Cut Org text into segments, one run of lines per issue heading.
+///
+/// Each line belongs to the innermost issue heading above it, so the lines
+/// of a nested issue are not part of its parent's entry, and subheadings of
+/// the parent after its nested children continue the parent's entry in a
+/// further segment. Lines above the first heading belong to no issue.
+#[must_use]
+pub fn org_segments(org_text: &str) -> Vec<Segment> {
+    let lines: Vec<&str> = org_text.lines().collect();
+    let mut segments: Vec<Segment> = Vec::new();
+    // Level and ID of the issue headings enclosing the current line
+    let mut open: Vec<(usize, Option<&str>)> = Vec::new();
+    for (index, &line) in lines.iter().enumerate() {
+        let mut opens_issue = false;
+        if let Some(level) = heading_level(line) {
+            open.retain(|(open_level, _)| *open_level < level);
+            let properties = heading_properties(&lines[index + 1..]).unwrap_or_default();
+            let property = |key: &str| {
+                properties
+                    .iter()
+                    .find(|(name, _)| *name == key)
+                    .map(|(_, value)| *value)
+            };
+            if level == 1 || (property("ID").is_some() && property("ISSUE_TYPE").is_some()) {
+                open.push((level, property("ID")));
+                opens_issue = true;
+            }
+        }
+
+        let (level, id) = open.last().copied().unwrap_or((0, None));
+        let continues = segments
+            .last()
+            .is_some_and(|last| last.level == level && last.id.as_deref() == id);
+        if opens_issue || !continues {
+            segments.push(Segment::new(
+                id.map(ToString::to_string),
+                level,
+                String::new(),
+            ));
+        }
+        if let Some(segment) = segments.last_mut() {
+            segment.text.push_str(line);
+            segment.text.push('\n');
+        }
+    }
+    segments
+}
+
+/// Issue heading each issue sits under in the segments of an Org file.
+fn segment_parents(segments: &[Segment]) -> HashMap<&str, Option<&str>> {
+    let mut parents = HashMap::new();
+    let mut open: Vec<(usize, &str)> = Vec::new();
+    for segment in segments {
+        let Some(id) = segment.id.as_deref() else {
+            continue;
+        };
+        if parents.contains_key(id) {
+            // The rest of an entry split around its nested children
+            while open.last().is_some_and(|(_, top)| *top != id) {
+                open.pop();
+            }
+            continue;
+        }
+        open.retain(|(level, _)| *level < segment.level);
+        parents.insert(id, open.last().map(|(_, parent)| *parent));
+        open.push((segment.level, id));
+    }
+    parents
+}
+
+/// Scan the issue headings of an Org file line by line, keyed by `:ID:`.
+fn scan_raw_sections(org_text: &str) -> HashMap<String, RawSection> {
+    let segments = org_segments(org_text);
+    let mut bodies: HashMap<&str, (usize, Vec<&str>)> = HashMap::new();
+    for segment in &segments {
+        let Some(id) = segment.id.as_deref() else {
+            continue;
+        };
+        let mut lines = segment.text.lines();
+        let body = bodies.entry(id).or_insert_with(|| {
+            // Skip the heading line itself
+            lines.next();
+            (segment.level, Vec::new())
+        });
+        body.1.extend(lines);
+    }
+    bodies
+        .into_values()
+        .map(|(level, body)| scan_raw_section(&body, level))
+        .filter_map(|section| Some((section.id.clone()?, section)))
+        .collect()
+}
+
+/// Keys and values of the property drawer below a heading line, after an
+/// optional planning line, if there is one.
+fn heading_properties<'a>(mut lines: &[&'a str]) -> Option<Vec<(&'a str, &'a str)>> {
+    if lines
+        .first()
+        .is_some_and(|line| Planning::parse(line).is_some())
+    {
+        lines = &lines[1..];
+    }
+    let len = drawer_len(lines)?;
+    drawer_name(lines[0]).filter(|name| name.eq_ignore_ascii_case("PROPERTIES"))?;
+    Some(
+        lines[1..len - 1]
+            .iter()
+            .filter_map(|line| line.trim().strip_prefix(':')?.split_once(':'))
+            .map(|(key, value)| (key, value.trim()))
+            .collect(),
+    )
+}
+
+/// Scan the lines below the heading line of an issue at `level`.
+fn scan_raw_section(mut lines: &[&str], level: usize) -> RawSection {
+    let mut section = RawSection::default();
+    if let Some(planning) = lines.first().and_then(|line| Planning::parse(line)) {
+        section.planning = planning;
+        lines = &lines[1..];
+    }
+
+    // Body up to the first subheading: drawers, clock lines, description
+    let mut description = Vec::new();
+    while let Some(&line) = lines.first() {
+        if heading_level(line).is_some() {
+            break;
+        }
+        if let Some(len) = drawer_len(lines) {
+            let inner = &lines[1..len - 1];
+            match drawer_name(line).map(str::to_ascii_uppercase).as_deref() {
+                Some("PROPERTIES") => {
+                    for (key, value) in inner
+                        .iter()
+                        .filter_map(|line| line.trim().strip_prefix(':')?.split_once(':'))
+                    {
+                        let value = value.trim();
+                        let exact = || DateTime::parse_from_rfc3339(value).ok();
+                        match key {
+                            "ID" => section.id = Some(value.to_string()),
+                            "BEADS_SCHEMA_VERSION" => {
+                                section.schema_version = value.parse().unwrap_or_default();
+                            }
+                            "CLOSED_AT" => section.closed_at = exact(),
+                            "DUE_AT" => section.due_at = exact(),
+                            "DEFER_UNTIL" => section.defer_until = exact(),
+                            _ => {}
+                        }
+                    }
+                }
+                Some("LOGBOOK") => section.logbook.extend(
+                    inner
+                        .iter()
+                        .map(|line| line.trim().to_string())
+                        .filter(|line| !line.is_empty()),
+                ),
+                _ => section
+                    .drawers
+                    .extend(lines[..len].iter().map(ToString::to_string)),
+            }
+            lines = &lines[len..];
+            continue;
+        }
+        if is_clock_line(line) {
+            section.logbook.push(line.trim().to_string());
+        } else {
+            description.push(line.to_string());
+        }
+        lines = &lines[1..];
+    }
+    section.description = join_lines(&description).map(|text| unsanitize_org_text(&text));
+
+    // Subheadings: issue sections are parsed structurally, the rest kept
+    // with their levels as if the issue were at level 1
+    while let Some(&line) = lines.first() {
+        let len = 1 + lines[1..]
+            .iter()
+            .position(|line| heading_level(line).is_some_and(|sub| sub <= level + 1))
+            .unwrap_or(lines.len() - 1);
+        let title = line.trim_start_matches('*').trim();
+        if heading_level(line) != Some(level + 1) || !ISSUE_SECTIONS.contains(&title) {
+            section.subtrees.extend(lines[..len].iter().map(|line| {
+                if heading_level(line).is_some() {
+                    line[level - 1..].to_string()
+                } else {
+                    (*line).to_string()
+                }
+            }));
+        }
+        lines = &lines[len..];
+    }
+    section
+}
+
+/// Level of a heading line (number of leading stars), if it is one.
+fn heading_level(line: &str) -> Option<usize> {
+    let stars = line.len() - line.trim_start_matches('*').len();
+    (stars > 0 && line[stars..].starts_with([' ', '\t'])).then_some(stars)
+}
+
+/// Add `levels` stars to every heading line of `text`.
+fn demote_headings(text: &str, levels: usize) -> String {
+    let stars = "*".repeat(levels);
+    text.lines()
+        .map(|line| {
+            if heading_level(line).is_some() {
+                format!("{stars}{line}")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Number of lines of the drawer starting at `lines[0]`, including its
+/// `:END:` line. A `:NAME:` line with no `:END:` before the next heading is
+/// plain text, as in Org.
+fn drawer_len(lines: &[&str]) -> Option<usize> {
+    drawer_name(lines.first()?).filter(|name| !name.eq_ignore_ascii_case("END"))?;
+    lines[1..]
+        .iter()
+        .take_while(|line| heading_level(line).is_none())
+        .position(|line| drawer_name(line).is_some_and(|name| name.eq_ignore_ascii_case("END")))
+        .map(|end| end + 2)
+}
+
+/// Join lines, dropping leading and trailing blank ones.
+fn join_lines(lines: &[String]) -> Option<String> {
+    let first = lines.iter().position(|line| !line.trim().is_empty())?;
+    let last = lines.iter().rposition(|line| !line.trim().is_empty())?;
+    Some(lines[first..=last].join("\n"))
+}
+
+/// Apply the raw section of a heading: description, planning and extras.
+fn apply_raw_section(issue: &mut Issue, section: RawSection) {
+    issue.description = section.description;
+    let legacy = section.schema_version < ORG_SCHEMA_VERSION;
+    let planning = &section.planning;
+    issue.closed_at = resolve_planning(section.closed_at, planning.closed, legacy);
+    issue.due_at = resolve_planning(section.due_at, planning.deadline, legacy);
+    issue.defer_until = resolve_planning(section.defer_until, planning.scheduled, legacy);
+
+    let extras = issue.org_extras.get_or_insert_with(OrgExtras::default);
+    extras.logbook = section.logbook;
+    extras.drawers = join_lines(&section.drawers);
+    extras.subtrees = join_lines(&section.subtrees);
+    if extras.is_empty() {
+        issue.org_extras = None;
+    }
+}
+
 /// Convert an Org heading to an Issue.
-fn parse_heading_to_issue(heading: &Heading) -> Result<Issue> {
+fn parse_heading_to_issue(
+    heading: &Heading,
+    raw_sections: &mut HashMap<String, RawSection>,
+) -> Result<Issue> {
     let mut issue = Issue::default();
 
     // Extract status from keyword
@@ -381,15 +1073,20 @@ fn parse_heading_to_issue(heading: &Heading) -> Result<Issue> {
     // Extract properties
     parse_properties(&heading.properties, &mut issue)?;
 
-    // Extract description from body paragraphs
-    let body_text = extract_body_text(&heading.body);
-    if !body_text.is_empty() {
-        issue.description = Some(unsanitize_org_text(&body_text));
+    // Description, planning and kept content come from the raw lines, which
+    // also hold what the structured parse has no element for
+    if let Some(section) = raw_sections.remove(&issue.id) {
+        apply_raw_section(&mut issue, section);
+    } else {
+        let body_text = extract_body_text(&heading.body);
+        if !body_text.is_empty() {
+            issue.description = Some(unsanitize_org_text(&body_text));
+        }
     }
 
     // Extract child sections
     for child in &heading.children {
-        if child.level == 2 {
+        if child.level == heading.level + 1 && !is_issue_heading(child) {
             let child_title = extract_title_text(&child.title);
             let child_body = extract_body_text(&child.body);
 
@@ -565,15 +1262,13 @@ fn extract_json_from_body(body: &[Element]) -> Option<String> {
     None
 }
 
-/// Un-escape Org text (convert `,*` back to `*`).
+/// Un-escape Org text (convert `,*` back to `*`, and likewise for escaped
+/// drawer and clock lines).
 fn unsanitize_org_text(text: &str) -> String {
     text.lines()
-        .map(|line| {
-            if line.starts_with(",*") {
-                line[1..].to_string()
-            } else {
-                line.to_string()
-            }
+        .map(|line| match line.strip_prefix(',') {
+            Some(rest) if needs_org_escape(rest) => rest.to_string(),
+            _ => line.to_string(),
         })
         .collect::<Vec<_>>()
         .join("\n")
@@ -581,7 +1276,6 @@ fn unsanitize_org_text(text: &str) -> String {
 
 /// Parse properties from the property drawer into the Issue struct.
 #[allow(clippy::too_many_lines)]
-#[allow(clippy::wildcard_in_or_patterns)]
 fn parse_properties(properties: &[org2jsonl::model::Property], issue: &mut Issue) -> Result<()> {
     for prop in properties {
         let key = prop.key.as_str();
@@ -732,9 +1426,16 @@ fn parse_properties(properties: &[org2jsonl::model::Property], issue: &mut Issue
             "IS_TEMPLATE" => {
                 issue.is_template = value.eq_ignore_ascii_case("true");
             }
-            "BEADS_SCHEMA_VERSION" | _ => {
+            "BEADS_SCHEMA_VERSION" => {
                 // BEADS_SCHEMA_VERSION is validated elsewhere if needed
-                // Unknown properties are silently ignored
+            }
+            _ => {
+                // Unknown properties are kept for re-export
+                issue
+                    .org_extras
+                    .get_or_insert_with(OrgExtras::default)
+                    .properties
+                    .push((key.to_string(), value.to_string()));
             }
         }
     }
@@ -833,6 +1534,323 @@ mod tests {
         assert!(org_text.contains("Test description"));
     }
 
+    #[test]
+    fn test_sanitize_escapes_drawer_and_clock_lines() {
+        let text = ":tada:\nCLOCK: not a clock\n:END:\nplain";
+        let sanitized = sanitize_org_text(text);
+        assert_eq!(sanitized, ",:tada:\n,CLOCK: not a clock\n,:END:\nplain");
+        assert_eq!(unsanitize_org_text(&sanitized), text);
+    }
+
+    #[test]
+    fn test_planning_line_round_trip() {
+        let due = Local
+            .with_ymd_and_hms(2026, 2, 1, 9, 30, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        let defer = Utc.timestamp_opt(1_780_000_007, 0).unwrap();
+        let issue = Issue {
+            id: "bd-plan".to_string(),
+            title: "Planned".to_string(),
+            due_at: Some(due),
+            defer_until: Some(defer),
+            created_at: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+            updated_at: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+            ..Default::default()
+        };
+
+        let org_text = issues_to_org_text(std::slice::from_ref(&issue));
+        assert!(
+            org_text.contains("\nDEADLINE: <2026-02-01 Sun 09:30> SCHEDULED: <"),
+            "{org_text}"
+        );
+        // The exact values keep seconds and the offset the line was written in.
+        let due_exact = format!(":DUE_AT: {}\n", due.with_timezone(&Local).to_rfc3339());
+        assert!(org_text.contains(&due_exact), "{org_text}");
+        assert!(org_text.contains(":DEFER_UNTIL:"), "{org_text}");
+
+        let parsed = org_text_to_issues(&org_text).unwrap();
+        assert_eq!(parsed[0].due_at, Some(due));
+        assert_eq!(parsed[0].defer_until, Some(defer));
+
+        // An edited planning line wins over the stale exact value.
+        let edited = org_text.replace(
+            &format!("SCHEDULED: {}", org_timestamp(defer, true)),
+            "SCHEDULED: <2027-01-01 Fri>",
+        );
+        assert_ne!(edited, org_text);
+        let parsed = org_text_to_issues(&edited).unwrap();
+        let expected = Local
+            .with_ymd_and_hms(2027, 1, 1, 0, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(parsed[0].defer_until, Some(expected));
+    }
+
+    #[test]
+    fn test_planning_compares_in_the_recorded_offset() {
+        let exact = DateTime::parse_from_rfc3339("2026-02-01T09:30:15-05:00").unwrap();
+        let written = NaiveDate::from_ymd_opt(2026, 2, 1)
+            .unwrap()
+            .and_hms_opt(9, 30, 0)
+            .unwrap();
+        // Read in any timezone, the untouched line matches the property.
+        assert_eq!(
+            resolve_planning(Some(exact), Some(written), false),
+            Some(Utc.with_ymd_and_hms(2026, 2, 1, 14, 30, 15).unwrap())
+        );
+
+        let edited = written.with_hour(11).unwrap();
+        let local = Local
+            .from_local_datetime(&edited)
+            .earliest()
+            .map(|at| at.with_timezone(&Utc));
+        assert_eq!(resolve_planning(Some(exact), Some(edited), false), local);
+
+        // A deleted planning line clears the date; entries written before
+        // planning lines existed keep the property.
+        assert_eq!(resolve_planning(Some(exact), None, false), None);
+        assert_eq!(
+            resolve_planning(Some(exact), None, true),
+            Some(exact.with_timezone(&Utc))
+        );
+    }
+
+    #[test]
+    fn test_hand_edits_survive_round_trip() {
+        let org_text = "\
+#+TITLE: Beads Issues
+
+* DONE [#C] Fix login    :backend:
+CLOSED: [2026-01-07 Wed 09:00] DEADLINE: <2026-01-10 Sat +1w -2d>
+:PROPERTIES:
+:ID:       bd-edit
+:ISSUE_TYPE: task
+:CREATED_AT: 2026-01-01T00:00:00+00:00
+:UPDATED_AT: 2026-01-07T09:00:00+00:00
+:CATEGORY: web
+:END:
+:LOGBOOK:
+- State \"DONE\"       from \"TODO\"       [2026-01-07 Wed 09:00]
+CLOCK: [2026-01-05 Mon 09:00]--[2026-01-05 Mon 10:30] =>  1:30
+:END:
+:NOTES:
+reviewed by bob
+:END:
+
+Login fails on Safari.
+
+- cookies dropped
+- on redirect
+CLOCK: [2026-01-06 Tue 14:00]--[2026-01-06 Tue 14:45] =>  0:45
+
+** Design
+Set SameSite.
+
+** Links
+- [[https://example.com][ticket]]
+*** Older notes
+kept too
+";
+        let issues = org_text_to_issues(org_text).unwrap();
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(
+            issue.description.as_deref(),
+            Some("Login fails on Safari.\n\n- cookies dropped\n- on redirect")
+        );
+        assert_eq!(issue.design.as_deref(), Some("Set SameSite."));
+        assert_eq!(
+            issue.due_at,
+            Some(
+                Local
+                    .with_ymd_and_hms(2026, 1, 10, 0, 0, 0)
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )
+        );
+        assert!(issue.closed_at.is_some());
+
+        let extras = issue.org_extras.as_ref().expect("extras");
+        assert_eq!(
+            extras.properties,
+            vec![("CATEGORY".to_string(), "web".to_string())]
+        );
+        assert_eq!(extras.logbook.len(), 3);
+        assert_eq!(extras.clocked_minutes(), 135);
+        assert_eq!(
+            extras.drawers.as_deref(),
+            Some(":NOTES:\nreviewed by bob\n:END:")
+        );
+        assert_eq!(
+            extras.subtrees.as_deref(),
+            Some("** Links\n- [[https://example.com][ticket]]\n*** Older notes\nkept too")
+        );
+
+        // Re-export keeps everything, and the output is stable from then on.
+        let exported = issues_to_org_text(&issues);
+        for kept in [
+            ":CATEGORY: web",
+            "CLOCK: [2026-01-05",
+            ":NOTES:",
+            "** Links",
+            "*** Older notes",
+        ] {
+            assert!(exported.contains(kept), "missing {kept}:\n{exported}");
+        }
+        let reparsed = org_text_to_issues(&exported).unwrap();
+        assert_eq!(reparsed, issues);
+        assert_eq!(issues_to_org_text(&reparsed), exported);
+    }
+
+    fn nested_issue(id: &str, title: &str, parent: Option<&str>) -> Issue {
+        let at = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        Issue {
+            id: id.to_string(),
+            title: title.to_string(),
+            created_at: at,
+            updated_at: at,
+            dependencies: parent
+                .into_iter()
+                .map(|parent| Dependency {
+                    issue_id: id.to_string(),
+                    depends_on_id: parent.to_string(),
+                    dep_type: DependencyType::ParentChild,
+                    created_at: at,
+                    created_by: None,
+                    metadata: None,
+                    thread_id: None,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_nested_export_round_trip() {
+        let mut second = nested_issue("bd-epic.2", "Second", Some("bd-epic"));
+        second.design = Some("Split it.".to_string());
+        second.org_extras = Some(OrgExtras {
+            subtrees: Some("** Links\n*** Older notes\nkept".to_string()),
+            ..OrgExtras::default()
+        });
+        let issues = vec![
+            nested_issue("bd-epic.10", "Tenth", Some("bd-epic")),
+            nested_issue("bd-epic.2.1", "Grandchild", Some("bd-epic.2")),
+            nested_issue("bd-epic", "Epic", None),
+            second,
+            nested_issue("bd-other", "Other", Some("bd-gone")),
+        ];
+
+        let org_text = issues_to_nested_org_text(&issues);
+        let headings: Vec<&str> = org_text
+            .lines()
+            .filter(|line| heading_level(line).is_some())
+            .collect();
+        assert_eq!(
+            headings,
+            vec![
+                "* TODO [#C] Epic",
+                "** TODO [#C] Second",
+                "*** Design",
+                "*** Dependencies",
+                "*** Links",
+                "**** Older notes",
+                "*** TODO [#C] Grandchild",
+                "**** Dependencies",
+                "** TODO [#C] Tenth",
+                "*** Dependencies",
+                "* TODO [#C] Other",
+                "** Dependencies",
+            ]
+        );
+        assert!(is_nested_org_text(&org_text));
+        assert!(!is_nested_org_text(&issues_to_org_text(&issues)));
+
+        let mut parsed = org_text_to_issues(&org_text).unwrap();
+        assert_eq!(parsed.len(), issues.len());
+        assert_eq!(issues_to_nested_org_text(&parsed), org_text);
+        parsed.sort_by(|a, b| a.id.cmp(&b.id));
+        let mut expected = issues;
+        expected.sort_by(|a, b| a.id.cmp(&b.id));
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn test_moved_heading_changes_parent() {
+        let issues = [
+            nested_issue("bd-a", "Alpha", None),
+            nested_issue("bd-a.1", "Child", Some("bd-a")),
+            nested_issue("bd-b", "Beta", None),
+        ];
+        let org_text = issues_to_nested_org_text(&issues);
+        let child_start = org_text.find("** TODO [#C] Child").unwrap();
+        let beta_start = org_text.find("* TODO [#C] Beta").unwrap();
+        let child = &org_text[child_start..beta_start];
+        // Move the child subtree under Beta, as org-refile would.
+        let moved = format!(
+            "{}{}{child}",
+            &org_text[..child_start],
+            &org_text[beta_start..]
+        );
+
+        let parsed = org_text_to_issues(&moved).unwrap();
+        let child = parsed.iter().find(|issue| issue.id == "bd-a.1").unwrap();
+        assert_eq!(child.dependencies.len(), 1);
+        assert_eq!(child.dependencies[0].depends_on_id, "bd-b");
+        assert_eq!(child.dependencies[0].created_at, issues[1].created_at);
+        assert!(child.updated_at > issues[1].updated_at);
+
+        // Nesting a top-level issue adds the dependency.
+        let beta_heading = moved.find("* TODO [#C] Beta").unwrap();
+        let under_alpha = format!("{}*{}", &moved[..beta_heading], &moved[beta_heading..]);
+        let parsed = org_text_to_issues(&under_alpha).unwrap();
+        let beta = parsed.iter().find(|issue| issue.id == "bd-b").unwrap();
+        assert_eq!(beta.dependencies.len(), 1);
+        assert_eq!(beta.dependencies[0].depends_on_id, "bd-a");
+        assert_eq!(beta.dependencies[0].dep_type, DependencyType::ParentChild);
+    }
+
+    #[test]
+    fn test_splice_keeps_heading_order() {
+        let a = nested_issue("bd-a", "Alpha", None);
+        let b = nested_issue("bd-b", "Beta", None);
+        let c = nested_issue("bd-c", "Gamma", None);
+        let previous = issues_to_org_text(&[c.clone(), a.clone(), b.clone()]);
+
+        let mut renamed = a;
+        renamed.title = "Alpha renamed".to_string();
+        let d = nested_issue("bd-d", "Delta", None);
+        let unchanged: HashSet<String> = ["bd-b", "bd-c"].map(ToString::to_string).into();
+        let current = [renamed.clone(), b.clone(), c.clone(), d.clone()];
+        let spliced = splice_org_text(&previous, &current, &unchanged, false).unwrap();
+        assert_eq!(spliced, issues_to_org_text(&[c, renamed, b, d]));
+        assert_eq!(
+            splice_org_text(&spliced, &current, &HashSet::new(), false).as_deref(),
+            Some(spliced.as_str())
+        );
+        assert!(splice_org_text(&spliced, &current, &unchanged, true).is_none());
+    }
+
+    #[test]
+    fn test_splice_nested_falls_back_when_parent_changes() {
+        let issues = [
+            nested_issue("bd-a", "Alpha", None),
+            nested_issue("bd-a.1", "Child", Some("bd-a")),
+            nested_issue("bd-b", "Beta", None),
+        ];
+        let previous = issues_to_nested_org_text(&issues);
+        let all: HashSet<String> = issues.iter().map(|issue| issue.id.clone()).collect();
+        assert_eq!(
+            splice_org_text(&previous, &issues, &all, true).as_deref(),
+            Some(previous.as_str())
+        );
+
+        let mut moved = issues.clone();
+        moved[1].dependencies[0].depends_on_id = "bd-b".to_string();
+        assert!(splice_org_text(&previous, &moved, &HashSet::new(), true).is_none());
+    }
+
     #[test]
     fn test_flatten_inline_preserves_markup() {
         use org2jsonl::model::InlineContent;
@@ -19,6 +19,7 @@
 //! | `.beads/*.db-shm` | `SQLite` shared memory files |
 //! | `.beads/*.jsonl` | `JSONL` export files |
 //! | `.beads/*.jsonl.tmp` | Temp files for atomic writes |
+//! | `.beads/issues/` | Per-issue markdown export directory |
 //! | `.beads/.manifest.json` | Export manifest |
 //! | `.beads/metadata.json` | Workspace metadata |
 //!
@@ -39,6 +40,7 @@
 //! - `SYNC_SAFETY_INVARIANTS.md`: PC-1, PC-2, PC-3, PC-4, NG-5, NG-6, NGI-1, NGI-3
 
 use crate::error::{BeadsError, Result};
+use crate::sync::markdown_bridge::{MARKDOWN_EXPORT_DIR, is_markdown_export};
 use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
 
@@ -364,6 +366,11 @@ fn validate_extension_and_name(path: &Path) -> PathValidation {
         return PathValidation::Allowed;
     }
 
+    // The per-issue markdown export is a directory, not a file
+    if file_name == MARKDOWN_EXPORT_DIR {
+        return PathValidation::Allowed;
+    }
+
     // Check extension matches
     // Handle compound extensions like .jsonl.tmp
     for allowed_ext in ALLOWED_EXTENSIONS {
@@ -483,9 +490,10 @@ pub fn validate_sync_path_with_external(
             && !file_name.ends_with(".jsonl.tmp")
             && !file_name.ends_with(".org")
             && !file_name.ends_with(".org.tmp")
+            && !is_markdown_export(path)
         {
             return Err(BeadsError::Config(format!(
-                "External path '{}' must be a .jsonl or .org file",
+                "External path '{}' must be a .jsonl or .org file or a markdown directory",
                 path.display()
             )));
         }
@@ -776,6 +784,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_allowed_markdown_export_dir() {
+        let (_temp, beads_dir) = setup_test_beads_dir();
+        let path = beads_dir.join("issues");
+        std::fs::create_dir(&path).expect("mkdir");
+
+        assert!(validate_sync_path(&path, &beads_dir).is_allowed());
+        assert!(!validate_sync_path(&beads_dir.join("notes"), &beads_dir).is_allowed());
+    }
+
     #[test]
     fn test_rejected_absolute_path_outside() {
         let (_temp, beads_dir) = setup_test_beads_dir();
@@ -0,0 +1,130 @@
+//! Manifest of tombstones purged by `br gc`.
+//!
+//! Purging removes a tombstone from the database for good, but other clones
+//! may still carry it in their databases or in an older export. Each purge
+//! is therefore appended to `.beads/deletions.jsonl`, which is tracked in git
+//! next to the export:
+//! - Import skips listed IDs and drops any local tombstone they name
+//! - Export does not count listed IDs as lost when guarding against stale
+//!   databases
+
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// File name of the purge manifest inside `.beads/`.
+pub const PURGE_MANIFEST: &str = "deletions.jsonl";
+
+/// One purged tombstone.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PurgeRecord {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub purged_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub purged_by: String,
+}
+
+/// Path of the purge manifest for a beads directory.
+#[must_use]
+pub fn manifest_path(beads_dir: &Path) -> PathBuf {
+    beads_dir.join(PURGE_MANIFEST)
+}
+
+/// Load the IDs of every purged tombstone.
+///
+/// A missing manifest is empty. Lines that do not parse (for example merge
+/// conflict markers left by concurrent purges) are skipped with a warning,
+/// so the records on both sides of a conflict still count.
+///
+/// # Errors
+///
+/// Returns an error if the manifest exists but cannot be read.
+pub fn load_purged_ids(beads_dir: &Path) -> Result<HashSet<String>> {
+    let path = manifest_path(beads_dir);
+    if !path.exists() {
+        return Ok(HashSet::new());
+    }
+    let contents = fs::read_to_string(&path)?;
+    let mut ids = HashSet::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<PurgeRecord>(line) {
+            Ok(record) => {
+                ids.insert(record.id);
+            }
+            Err(e) => tracing::warn!(
+                path = %path.display(),
+                line = index + 1,
+                error = %e,
+                "Skipping unreadable purge manifest line"
+            ),
+        }
+    }
+    Ok(ids)
+}
+
+/// Append records to the purge manifest, creating it if needed.
+///
+/// # Errors
+///
+/// Returns an error if the manifest cannot be written.
+pub fn append_purge_records(beads_dir: &Path, records: &[PurgeRecord]) -> Result<()> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    let mut buf = String::new();
+    for record in records {
+        buf.push_str(&serde_json::to_string(record)?);
+        buf.push('\n');
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(manifest_path(beads_dir))?;
+    file.write_all(buf.as_bytes())?;
+    file.sync_all()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn append_and_load_round_trip() {
+        let dir = TempDir::new().unwrap();
+        assert!(load_purged_ids(dir.path()).unwrap().is_empty());
+
+        let record = |id: &str| PurgeRecord {
+            id: id.to_string(),
+            deleted_at: None,
+            purged_at: Utc::now(),
+            purged_by: "tester".to_string(),
+        };
+        append_purge_records(dir.path(), &[record("bd-1")]).unwrap();
+        append_purge_records(dir.path(), &[record("bd-2")]).unwrap();
+
+        // Conflict markers from a concurrent purge do not hide either side.
+        let path = manifest_path(dir.path());
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::write(
+            &path,
+            format!("<<<<<<< HEAD\n{contents}=======\n>>>>>>> other\n"),
+        )
+        .unwrap();
+
+        let ids = load_purged_ids(dir.path()).unwrap();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains("bd-1") && ids.contains("bd-2"));
+    }
+}
@@ -0,0 +1,132 @@
+//! Minimal-diff re-export over the previous export file.
+//!
+//! Regenerating the whole export on every flush reorders hand-sorted Org
+//! headings and makes diffs noisy. When the file on disk is still the one
+//! the database last exported or imported, the exporter instead cuts it
+//! into [`Segment`]s and [`splice`]s the new export into it:
+//! - Entries of unchanged issues are kept byte for byte, in place
+//! - Entries of changed issues are re-rendered in place
+//! - Entries of issues no longer exported are removed
+//! - New issues are appended at the end
+//!
+//! An issue counts as unchanged when it is not dirty and its content hash
+//! still matches the one recorded in `export_hashes`.
+
+use std::collections::HashSet;
+
+/// A piece of a previous export file: part of an issue's entry, or text
+/// that belongs to no issue (the Org file header, say).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// Issue the text belongs to.
+    pub id: Option<String>,
+    /// Heading level of the issue (Org); 0 where levels do not apply.
+    pub level: usize,
+    /// The text, including its trailing newline.
+    pub text: String,
+}
+
+impl Segment {
+    #[must_use]
+    pub fn new(id: Option<String>, level: usize, text: String) -> Self {
+        Self { id, level, text }
+    }
+}
+
+/// Cut JSONL text into one segment per line.
+#[must_use]
+pub fn jsonl_segments(text: &str) -> Vec<Segment> {
+    text.lines()
+        .map(|line| {
+            let id = serde_json::from_str::<serde_json::Value>(line)
+                .ok()
+                .and_then(|value| value.get("id")?.as_str().map(ToString::to_string));
+            Segment::new(id, 0, format!("{line}\n"))
+        })
+        .collect()
+}
+
+/// Splice a new export into the segments of the previous one.
+///
+/// `order` lists the exported issues in export order. An issue's entry is
+/// kept verbatim when it is in `unchanged`, and otherwise replaced by
+/// `render(id, level)` at its first segment (an Org entry can be split
+/// around nested child issues; its other segments are dropped with it).
+/// Issues with no entry yet are rendered at level 1 and appended.
+pub fn splice(
+    segments: &[Segment],
+    order: &[&str],
+    unchanged: &HashSet<String>,
+    mut render: impl FnMut(&str, usize) -> Option<String>,
+) -> String {
+    let exported: HashSet<&str> = order.iter().copied().collect();
+    let mut written: HashSet<&str> = HashSet::new();
+    let mut output = String::new();
+
+    for segment in segments {
+        let Some(id) = segment.id.as_deref() else {
+            output.push_str(&segment.text);
+            continue;
+        };
+        if !exported.contains(id) {
+            continue;
+        }
+        if unchanged.contains(id) {
+            written.insert(id);
+            output.push_str(&segment.text);
+        } else if written.insert(id) {
+            if let Some(text) = render(id, segment.level) {
+                output.push_str(&text);
+            }
+        }
+    }
+
+    for id in order {
+        if !written.contains(id) {
+            if let Some(text) = render(id, 1) {
+                output.push_str(&text);
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(id: &str, title: &str) -> String {
+        format!("{{\"id\":\"{id}\",\"title\":\"{title}\"}}\n")
+    }
+
+    #[test]
+    fn splice_keeps_order_and_rewrites_changed_entries() {
+        let previous = [line("bd-c", "C"), line("bd-a", "A"), line("bd-b", "B")].concat();
+        let segments = jsonl_segments(&previous);
+        assert_eq!(segments[1].id.as_deref(), Some("bd-a"));
+
+        let unchanged: HashSet<String> = ["bd-c".to_string()].into_iter().collect();
+        let spliced = splice(&segments, &["bd-a", "bd-c", "bd-d"], &unchanged, |id, _| {
+            Some(line(id, "new"))
+        });
+        assert_eq!(
+            spliced,
+            [line("bd-c", "C"), line("bd-a", "new"), line("bd-d", "new")].concat()
+        );
+    }
+
+    #[test]
+    fn splice_writes_split_entries_once() {
+        let segments = vec![
+            Segment::new(None, 0, "#+TITLE: x\n\n".to_string()),
+            Segment::new(Some("bd-p".to_string()), 1, "* P\n".to_string()),
+            Segment::new(Some("bd-c".to_string()), 2, "** C\n".to_string()),
+            Segment::new(Some("bd-p".to_string()), 1, "** Links\n".to_string()),
+        ];
+        let unchanged: HashSet<String> = ["bd-c".to_string()].into_iter().collect();
+        let spliced = splice(&segments, &["bd-p", "bd-c"], &unchanged, |id, level| {
+            Some(format!("{} {id}\n", "*".repeat(level)))
+        });
+        assert_eq!(spliced, "#+TITLE: x\n\n* bd-p\n** C\n");
+    }
+}
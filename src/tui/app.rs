@@ -0,0 +1,582 @@
+//! Board state and key handling.
+//!
+//! Nothing here touches the terminal or the database: the runner feeds in
+//! issues and details, and key presses that need the database come back as
+//! [`Action`]s.
+
+use crate::format::{IssueDetails, IssueWithDependencyMetadata};
+use crate::model::{Issue, Status, StatusCategory};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::{HashMap, HashSet};
+
+/// Terminal issues kept on the board, most recently closed first.
+pub const CLOSED_LIMIT: usize = 50;
+
+/// Columns shown even when empty.
+const ALWAYS_SHOWN: &[&str] = &["open", "in_progress", "closed"];
+
+/// Keys that follow the numbered relations in the detail pane.
+pub const RELATION_KEYS: &str = "123456789";
+
+/// Lowest and highest priority values.
+const PRIORITY_RANGE: (i32, i32) = (0, 4);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum View {
+    #[default]
+    Board,
+    List,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Filter,
+    Comment,
+}
+
+/// A line being typed in the footer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    pub kind: InputKind,
+    pub buffer: String,
+}
+
+/// Work the runner performs against the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Refresh,
+    Claim(String),
+    SetPriority(String, i32),
+    Comment(String, String),
+}
+
+/// One board column: a status and its visible issues.
+pub struct Column<'a> {
+    pub status: String,
+    pub issues: Vec<&'a Issue>,
+}
+
+#[derive(Default)]
+pub struct App {
+    pub view: View,
+    pub filter: String,
+    pub input: Option<Input>,
+    pub details: Option<IssueDetails>,
+    pub detail_scroll: usize,
+    pub message: Option<String>,
+    issues: Vec<Issue>,
+    labels: HashMap<String, Vec<String>>,
+    selected: Option<String>,
+    history: Vec<String>,
+}
+
+impl App {
+    /// Replace the loaded issues, keeping the selection if it still exists.
+    pub fn set_issues(&mut self, mut issues: Vec<Issue>, labels: HashMap<String, Vec<String>>) {
+        issues.retain(|issue| issue.status != Status::Tombstone);
+        issues.sort_by(|a, b| {
+            let (a_done, b_done) = (a.status.is_terminal(), b.status.is_terminal());
+            a_done.cmp(&b_done).then_with(|| {
+                if a_done {
+                    b.closed_at.cmp(&a.closed_at)
+                } else {
+                    a.priority
+                        .cmp(&b.priority)
+                        .then_with(|| a.created_at.cmp(&b.created_at))
+                }
+            })
+        });
+        let mut terminal = 0;
+        issues.retain(|issue| {
+            if issue.status.is_terminal() {
+                terminal += 1;
+                terminal <= CLOSED_LIMIT
+            } else {
+                true
+            }
+        });
+
+        self.issues = issues;
+        self.labels = labels;
+        self.ensure_selection();
+    }
+
+    /// Attach freshly loaded details for the selected issue.
+    pub fn set_details(&mut self, details: Option<IssueDetails>) {
+        self.details = details;
+    }
+
+    pub fn selected_id(&self) -> Option<&str> {
+        self.selected.as_deref()
+    }
+
+    /// Issues matching the filter, in list order.
+    pub fn visible(&self) -> Vec<&Issue> {
+        let terms: Vec<String> = self
+            .filter
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect();
+        self.issues
+            .iter()
+            .filter(|issue| terms.iter().all(|term| self.haystack(issue).contains(term)))
+            .collect()
+    }
+
+    fn haystack(&self, issue: &Issue) -> String {
+        let mut text = format!(
+            "{} {} {} {} p{} {}",
+            issue.id,
+            issue.title,
+            issue.status,
+            issue.issue_type,
+            issue.priority.0,
+            issue.assignee.as_deref().unwrap_or_default()
+        );
+        for label in self.labels.get(&issue.id).into_iter().flatten() {
+            text.push(' ');
+            text.push_str(label);
+        }
+        text.to_lowercase()
+    }
+
+    /// Visible issues grouped into status columns in workflow order.
+    pub fn columns(&self) -> Vec<Column<'_>> {
+        let visible = self.visible();
+        let present: HashSet<&str> = self.issues.iter().map(|i| i.status.as_str()).collect();
+
+        let mut statuses: Vec<String> = Vec::new();
+        for category in [
+            StatusCategory::Active,
+            StatusCategory::Waiting,
+            StatusCategory::Terminal,
+        ] {
+            for name in Status::names_in_category(category) {
+                if name != Status::Tombstone.as_str()
+                    && (ALWAYS_SHOWN.contains(&name.as_str()) || present.contains(name.as_str()))
+                {
+                    statuses.push(name);
+                }
+            }
+        }
+        // Statuses this workspace doesn't declare (e.g. imported) still get a column.
+        for issue in &self.issues {
+            if !statuses.iter().any(|s| s == issue.status.as_str()) {
+                statuses.push(issue.status.as_str().to_string());
+            }
+        }
+
+        statuses
+            .into_iter()
+            .map(|status| Column {
+                issues: visible
+                    .iter()
+                    .copied()
+                    .filter(|issue| issue.status.as_str() == status)
+                    .collect(),
+                status,
+            })
+            .collect()
+    }
+
+    /// Dependencies then dependents of the selected issue, numbered from 1
+    /// in the detail pane.
+    pub fn relations(&self) -> Vec<(&'static str, &IssueWithDependencyMetadata)> {
+        let Some(details) = self.current_details() else {
+            return Vec::new();
+        };
+        details
+            .dependencies
+            .iter()
+            .map(|dep| ("depends on", dep))
+            .chain(details.dependents.iter().map(|dep| ("blocks", dep)))
+            .collect()
+    }
+
+    /// Details, if they belong to the selected issue.
+    pub fn current_details(&self) -> Option<&IssueDetails> {
+        self.details
+            .as_ref()
+            .filter(|details| Some(details.issue.id.as_str()) == self.selected_id())
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Action::Quit);
+        }
+        if self.input.is_some() {
+            return self.handle_input_key(key);
+        }
+        self.message = None;
+
+        match key.code {
+            KeyCode::Char('q') => return Some(Action::Quit),
+            KeyCode::Esc if self.filter.is_empty() => return Some(Action::Quit),
+            KeyCode::Esc => self.set_filter(String::new()),
+            KeyCode::Char('r') => return Some(Action::Refresh),
+            KeyCode::Tab => {
+                self.view = match self.view {
+                    View::Board => View::List,
+                    View::List => View::Board,
+                };
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.move_vertical(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_vertical(1),
+            KeyCode::Left | KeyCode::Char('h') => self.move_horizontal(-1),
+            KeyCode::Right | KeyCode::Char('l') => self.move_horizontal(1),
+            KeyCode::PageDown => self.detail_scroll += 10,
+            KeyCode::PageUp => self.detail_scroll = self.detail_scroll.saturating_sub(10),
+            KeyCode::Char('/') => {
+                self.input = Some(Input {
+                    kind: InputKind::Filter,
+                    buffer: self.filter.clone(),
+                });
+            }
+            KeyCode::Char('a') if self.selected.is_some() => {
+                self.input = Some(Input {
+                    kind: InputKind::Comment,
+                    buffer: String::new(),
+                });
+            }
+            KeyCode::Char('c') => return self.selected.clone().map(Action::Claim),
+            KeyCode::Char('+' | '=') => return self.shift_priority(-1),
+            KeyCode::Char('-') => return self.shift_priority(1),
+            KeyCode::Char(digit @ '1'..='9') => {
+                self.follow(RELATION_KEYS.find(digit).unwrap_or_default());
+            }
+            KeyCode::Backspace | KeyCode::Char('b') => self.back(),
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_input_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let input = self.input.as_mut()?;
+        match key.code {
+            KeyCode::Esc => {
+                let kind = input.kind;
+                self.input = None;
+                if kind == InputKind::Filter {
+                    self.set_filter(String::new());
+                }
+            }
+            KeyCode::Enter => {
+                let input = self.input.take()?;
+                let text = input.buffer.trim();
+                if input.kind == InputKind::Comment && !text.is_empty() {
+                    return Some(Action::Comment(self.selected.clone()?, text.to_string()));
+                }
+            }
+            KeyCode::Backspace => {
+                input.buffer.pop();
+                self.after_input_edit();
+            }
+            KeyCode::Char(c) => {
+                input.buffer.push(c);
+                self.after_input_edit();
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Filtering is live: every keystroke re-filters the board.
+    fn after_input_edit(&mut self) {
+        if let Some(Input {
+            kind: InputKind::Filter,
+            buffer,
+        }) = &self.input
+        {
+            let filter = buffer.clone();
+            self.set_filter(filter);
+        }
+    }
+
+    fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+        self.ensure_selection();
+    }
+
+    fn shift_priority(&self, delta: i32) -> Option<Action> {
+        let issue = self.selected_issue()?;
+        let priority = (issue.priority.0 + delta).clamp(PRIORITY_RANGE.0, PRIORITY_RANGE.1);
+        (priority != issue.priority.0).then(|| Action::SetPriority(issue.id.clone(), priority))
+    }
+
+    pub fn selected_issue(&self) -> Option<&Issue> {
+        let id = self.selected_id()?;
+        self.issues.iter().find(|issue| issue.id == id)
+    }
+
+    fn select(&mut self, id: String) {
+        if self.selected.as_deref() != Some(id.as_str()) {
+            self.detail_scroll = 0;
+            self.selected = Some(id);
+        }
+    }
+
+    /// Keep the selection on a visible issue.
+    fn ensure_selection(&mut self) {
+        let visible = self.visible();
+        if visible
+            .iter()
+            .any(|issue| Some(issue.id.as_str()) == self.selected_id())
+        {
+            return;
+        }
+        let first = match self.view {
+            View::Board => self
+                .columns()
+                .iter()
+                .find_map(|column| column.issues.first().map(|issue| issue.id.clone())),
+            View::List => visible.first().map(|issue| issue.id.clone()),
+        };
+        match first {
+            Some(id) => self.select(id),
+            None => self.selected = None,
+        }
+    }
+
+    fn move_vertical(&mut self, delta: isize) {
+        let target = match self.view {
+            View::List => step(&self.visible(), self.selected_id(), delta),
+            View::Board => self
+                .columns()
+                .iter()
+                .find(|column| {
+                    column
+                        .issues
+                        .iter()
+                        .any(|i| Some(i.id.as_str()) == self.selected_id())
+                })
+                .and_then(|column| step(&column.issues, self.selected_id(), delta)),
+        };
+        if let Some(id) = target {
+            self.select(id);
+        }
+    }
+
+    /// Move to the nearest non-empty column, keeping the row where possible.
+    fn move_horizontal(&mut self, delta: isize) {
+        if self.view != View::Board {
+            return;
+        }
+        let target = {
+            let columns = self.columns();
+            let Some((col, row)) = columns.iter().enumerate().find_map(|(c, column)| {
+                column
+                    .issues
+                    .iter()
+                    .position(|i| Some(i.id.as_str()) == self.selected_id())
+                    .map(|r| (c, r))
+            }) else {
+                return;
+            };
+
+            let mut next = col;
+            loop {
+                next = match next.checked_add_signed(delta) {
+                    Some(n) if n < columns.len() => n,
+                    _ => break None,
+                };
+                let issues = &columns[next].issues;
+                if !issues.is_empty() {
+                    break Some(issues[row.min(issues.len() - 1)].id.clone());
+                }
+            }
+        };
+        if let Some(id) = target {
+            self.select(id);
+        }
+    }
+
+    /// Jump to the `index`th relation of the selected issue.
+    fn follow(&mut self, index: usize) {
+        let Some(target) = self
+            .relations()
+            .get(index)
+            .map(|(_, related)| related.id.clone())
+        else {
+            self.message = Some(format!("No relation {}", index + 1));
+            return;
+        };
+        if let Some(current) = self.selected.clone() {
+            self.history.push(current);
+        }
+        self.select(target.clone());
+        if !self.visible().iter().any(|issue| issue.id == target) && !self.filter.is_empty() {
+            self.filter.clear();
+        }
+    }
+
+    fn back(&mut self) {
+        if let Some(id) = self.history.pop() {
+            self.select(id);
+        }
+    }
+}
+
+/// The id `delta` rows away from `current`, clamped to the ends.
+fn step(issues: &[&Issue], current: Option<&str>, delta: isize) -> Option<String> {
+    let index = issues
+        .iter()
+        .position(|issue| Some(issue.id.as_str()) == current)?;
+    let target = index
+        .saturating_add_signed(delta)
+        .min(issues.len().saturating_sub(1));
+    issues.get(target).map(|issue| issue.id.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Priority;
+
+    fn issue(id: &str, title: &str, status: Status, priority: i32) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: title.to_string(),
+            status,
+            priority: Priority(priority),
+            ..Issue::default()
+        }
+    }
+
+    fn app() -> App {
+        let mut app = App::default();
+        app.set_issues(
+            vec![
+                issue("bd-1", "Fix login", Status::Open, 1),
+                issue("bd-2", "Write docs", Status::Open, 2),
+                issue("bd-3", "Refactor parser", Status::InProgress, 0),
+                issue("bd-4", "Old work", Status::Closed, 2),
+                issue("bd-5", "Gone", Status::Tombstone, 2),
+            ],
+            HashMap::from([("bd-2".to_string(), vec!["docs".to_string()])]),
+        );
+        app
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            app.handle_key(key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn columns_follow_workflow_order() {
+        let app = app();
+        let columns = app.columns();
+        let names: Vec<&str> = columns.iter().map(|c| c.status.as_str()).collect();
+        assert_eq!(names, ["open", "in_progress", "closed"]);
+        let open: Vec<&str> = columns[0].issues.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(open, ["bd-1", "bd-2"]);
+        assert_eq!(app.selected_id(), Some("bd-1"));
+    }
+
+    #[test]
+    fn filter_is_live_and_matches_labels() {
+        let mut app = app();
+        app.handle_key(key(KeyCode::Char('/')));
+        type_text(&mut app, "docs");
+        let visible: Vec<&str> = app.visible().iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(visible, ["bd-2"]);
+        assert_eq!(app.selected_id(), Some("bd-2"));
+
+        app.handle_key(key(KeyCode::Esc));
+        assert!(app.input.is_none());
+        assert!(app.filter.is_empty());
+        assert_eq!(app.visible().len(), 4);
+    }
+
+    #[test]
+    fn navigation_moves_within_and_across_columns() {
+        let mut app = app();
+        app.handle_key(key(KeyCode::Down));
+        assert_eq!(app.selected_id(), Some("bd-2"));
+        app.handle_key(key(KeyCode::Right));
+        assert_eq!(app.selected_id(), Some("bd-3"));
+        app.handle_key(key(KeyCode::Right));
+        assert_eq!(app.selected_id(), Some("bd-4"));
+        app.handle_key(key(KeyCode::Right));
+        assert_eq!(app.selected_id(), Some("bd-4"));
+
+        app.handle_key(key(KeyCode::Tab));
+        assert_eq!(app.view, View::List);
+        app.handle_key(key(KeyCode::Up));
+        assert_eq!(app.selected_id(), Some("bd-2"));
+    }
+
+    #[test]
+    fn mutations_become_actions() {
+        let mut app = app();
+        assert_eq!(
+            app.handle_key(key(KeyCode::Char('c'))),
+            Some(Action::Claim("bd-1".to_string()))
+        );
+        assert_eq!(
+            app.handle_key(key(KeyCode::Char('+'))),
+            Some(Action::SetPriority("bd-1".to_string(), 0))
+        );
+
+        app.handle_key(key(KeyCode::Char('a')));
+        type_text(&mut app, "on it");
+        assert_eq!(
+            app.handle_key(key(KeyCode::Enter)),
+            Some(Action::Comment("bd-1".to_string(), "on it".to_string()))
+        );
+        // Typing a comment doesn't trigger shortcuts.
+        assert!(app.input.is_none());
+    }
+
+    #[test]
+    fn priority_stops_at_critical() {
+        let mut app = app();
+        app.handle_key(key(KeyCode::Right));
+        assert_eq!(app.selected_id(), Some("bd-3"));
+        assert_eq!(app.handle_key(key(KeyCode::Char('+'))), None);
+    }
+
+    #[test]
+    fn follows_relations_and_goes_back() {
+        let mut app = app();
+        let blocker = IssueWithDependencyMetadata {
+            id: "bd-3".to_string(),
+            title: "Refactor parser".to_string(),
+            status: Status::InProgress,
+            priority: Priority(0),
+            dep_type: "blocks".to_string(),
+        };
+        app.set_details(Some(IssueDetails {
+            issue: issue("bd-1", "Fix login", Status::Open, 1),
+            labels: Vec::new(),
+            dependencies: vec![blocker],
+            dependents: Vec::new(),
+            comments: Vec::new(),
+            events: Vec::new(),
+            parent: None,
+        }));
+
+        app.handle_key(key(KeyCode::Char('2')));
+        assert_eq!(app.message.as_deref(), Some("No relation 2"));
+        app.handle_key(key(KeyCode::Char('1')));
+        assert_eq!(app.selected_id(), Some("bd-3"));
+        app.handle_key(key(KeyCode::Char('b')));
+        assert_eq!(app.selected_id(), Some("bd-1"));
+    }
+
+    #[test]
+    fn closed_column_is_capped() {
+        let mut app = App::default();
+        let issues = (0..CLOSED_LIMIT + 5)
+            .map(|n| issue(&format!("bd-c{n}"), "done", Status::Closed, 2))
+            .collect();
+        app.set_issues(issues, HashMap::new());
+        assert_eq!(app.visible().len(), CLOSED_LIMIT);
+    }
+}
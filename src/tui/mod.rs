@@ -0,0 +1,288 @@
+//! Full-screen terminal board (`br tui`).
+//!
+//! A kanban board by status (or a flat list), live filtering, and a detail
+//! pane with rendered markdown. Claiming, priority changes and comments go
+//! through the same tool layer as `br mcp` and `br serve`, so each is one
+//! undoable operation and is flushed to JSONL like any CLI mutation.
+//!
+//! The board reloads whenever the database, its WAL or the JSONL file
+//! changes on disk, so edits from other agents show up without a keypress.
+
+mod app;
+mod view;
+
+use crate::error::{BeadsError, Result};
+use crate::mcp::{McpServer, Tool};
+use crate::storage::ListFilters;
+use app::{Action, App};
+use crossterm::cursor::{Hide, Show};
+use crossterm::event::{self, Event, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use serde_json::json;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// How often to check for input and on-disk changes.
+const TICK: Duration = Duration::from_millis(250);
+
+/// Run the board until the user quits.
+///
+/// # Errors
+///
+/// Returns an error if stdout is not a terminal, the terminal cannot be
+/// set up, or the initial load fails. Failures while running are shown in
+/// the footer instead.
+pub fn run(mut server: McpServer, color: bool) -> Result<()> {
+    if !io::stdout().is_terminal() {
+        return Err(BeadsError::validation(
+            "tui",
+            "br tui needs an interactive terminal",
+        ));
+    }
+
+    let mut app = App::default();
+    reload(&mut server, &mut app)?;
+    let mut watcher = Watcher::new(&server);
+
+    let mut stdout = io::stdout();
+    let _guard = TerminalGuard::enter(&mut stdout)?;
+    let mut dirty = true;
+
+    loop {
+        if dirty {
+            view::draw(&mut stdout, &app, terminal::size()?, color)?;
+            dirty = false;
+        }
+
+        if event::poll(TICK)? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    match app.handle_key(key) {
+                        Some(Action::Quit) => break,
+                        Some(action) => perform(&mut server, &mut app, action),
+                        // The selection moved: show the new issue.
+                        None if app.current_details().is_none() => {
+                            load_details(&server, &mut app);
+                        }
+                        None => {}
+                    }
+                    dirty = true;
+                }
+                Event::Resize(..) => dirty = true,
+                _ => {}
+            }
+        } else if watcher.changed() {
+            if let Err(e) = reload(&mut server, &mut app) {
+                app.message = Some(e.to_string());
+            }
+            dirty = true;
+        }
+    }
+    Ok(())
+}
+
+/// Puts the terminal in raw mode on an alternate screen until dropped.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter<W: Write>(out: &mut W) -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen, Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Notices writes to the workspace files by anyone, including us.
+struct Watcher {
+    paths: Vec<PathBuf>,
+    stamps: Vec<Option<SystemTime>>,
+}
+
+impl Watcher {
+    fn new(server: &McpServer) -> Self {
+        let paths = &server.workspace().paths;
+        let mut wal = paths.db_path.clone().into_os_string();
+        wal.push("-wal");
+        let paths = vec![
+            paths.db_path.clone(),
+            PathBuf::from(wal),
+            paths.jsonl_path.clone(),
+        ];
+        let stamps = Self::stamps(&paths);
+        Self { paths, stamps }
+    }
+
+    fn stamps(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+        paths
+            .iter()
+            .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+            .collect()
+    }
+
+    fn changed(&mut self) -> bool {
+        let stamps = Self::stamps(&self.paths);
+        let changed = stamps != self.stamps;
+        self.stamps = stamps;
+        changed
+    }
+}
+
+/// Import any JSONL changes, then reload the board and the open issue.
+fn reload(server: &mut McpServer, app: &mut App) -> Result<()> {
+    server.refresh()?;
+    let storage = &server.workspace().storage;
+    let filters = ListFilters {
+        include_closed: true,
+        include_deferred: true,
+        ..ListFilters::default()
+    };
+    let issues = storage.list_issues(&filters)?;
+    let ids: Vec<String> = issues.iter().map(|issue| issue.id.clone()).collect();
+    let labels = storage.get_labels_for_issues(&ids)?;
+    app.set_issues(issues, labels);
+    load_details(server, app);
+    Ok(())
+}
+
+fn load_details(server: &McpServer, app: &mut App) {
+    let Some(id) = app.selected_id().map(str::to_string) else {
+        app.set_details(None);
+        return;
+    };
+    match server
+        .workspace()
+        .storage
+        .get_issue_details(&id, true, false, 0)
+    {
+        Ok(details) => app.set_details(details),
+        Err(e) => app.message = Some(e.to_string()),
+    }
+}
+
+/// Apply an action, reporting the outcome in the footer.
+fn perform(server: &mut McpServer, app: &mut App, action: Action) {
+    let (tool, arguments, done) = match action {
+        Action::Quit => return,
+        Action::Refresh => {
+            app.message = reload(server, app).err().map(|e| e.to_string());
+            return;
+        }
+        Action::Claim(id) => (Tool::Claim, json!({ "id": id }), format!("Claimed {id}")),
+        Action::SetPriority(id, priority) => (
+            Tool::Update,
+            json!({ "id": id, "priority": priority }),
+            format!("{id} is now P{priority}"),
+        ),
+        Action::Comment(id, text) => (
+            Tool::Comment,
+            json!({ "id": id, "text": text }),
+            format!("Commented on {id}"),
+        ),
+    };
+
+    let outcome = server
+        .call(tool, arguments, None)
+        .and_then(|_| reload(server, app));
+    app.message = Some(match outcome {
+        Ok(()) => done,
+        Err(e) => e.to_string(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CliOverrides;
+    use crate::model::{Priority, Status};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use serde_json::Value;
+    use tempfile::TempDir;
+
+    fn workspace() -> (TempDir, McpServer) {
+        let dir = TempDir::new().expect("tempdir");
+        let beads_dir = dir.path().join(".beads");
+        std::fs::create_dir_all(&beads_dir).expect("beads dir");
+        std::fs::write(
+            beads_dir.join("metadata.json"),
+            r#"{"database": "beads.db", "jsonl_export": "issues.jsonl"}"#,
+        )
+        .expect("metadata");
+        let cli = CliOverrides {
+            db: Some(beads_dir.join("beads.db")),
+            actor: Some("tester".to_string()),
+            no_auto_import: Some(true),
+            ..CliOverrides::default()
+        };
+        let server = McpServer::open(&cli, true).expect("open");
+        (dir, server)
+    }
+
+    fn press(server: &mut McpServer, app: &mut App, code: KeyCode) {
+        if let Some(action) = app.handle_key(KeyEvent::new(code, KeyModifiers::NONE)) {
+            perform(server, app, action);
+        }
+    }
+
+    #[test]
+    fn quick_edits_persist_as_undoable_operations_and_flush() {
+        let (dir, mut server) = workspace();
+        let created = server
+            .call(Tool::Create, json!({ "title": "Fix login" }), None)
+            .expect("create");
+        let id = created["id"].as_str().expect("id").to_string();
+        let mut app = App::default();
+        reload(&mut server, &mut app).expect("reload");
+        assert_eq!(app.selected_id(), Some(id.as_str()));
+        let before = server
+            .workspace()
+            .storage
+            .list_operations(0)
+            .expect("operations")
+            .len();
+
+        press(&mut server, &mut app, KeyCode::Char('c'));
+        assert_eq!(app.message, Some(format!("Claimed {id}")));
+        press(&mut server, &mut app, KeyCode::Char('+'));
+        press(&mut server, &mut app, KeyCode::Char('a'));
+        for c in "on it".chars() {
+            press(&mut server, &mut app, KeyCode::Char(c));
+        }
+        press(&mut server, &mut app, KeyCode::Enter);
+        assert_eq!(app.message, Some(format!("Commented on {id}")));
+        // The board reloads after each edit.
+        assert_eq!(
+            app.selected_issue().map(|issue| issue.priority),
+            Some(Priority(1))
+        );
+
+        let storage = &server.workspace().storage;
+        let issue = storage.get_issue(&id).expect("get").expect("issue");
+        assert_eq!(issue.status, Status::InProgress);
+        assert_eq!(issue.assignee.as_deref(), Some("tester"));
+        assert_eq!(issue.priority, Priority(1));
+        // One undo step per edit.
+        let operations = storage.list_operations(0).expect("operations");
+        assert_eq!(operations.len(), before + 3);
+        assert!(operations[..3].iter().all(|op| op.issues == [id.clone()]));
+
+        let export = std::fs::read_to_string(dir.path().join(".beads").join("issues.jsonl"))
+            .expect("export");
+        let exported: Value = export
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).expect("json"))
+            .find(|issue| issue["id"] == id.as_str())
+            .expect("exported issue");
+        assert_eq!(exported["status"], "in_progress");
+        assert_eq!(exported["priority"], 1);
+        assert_eq!(exported["comments"][0]["text"], "on it");
+    }
+}
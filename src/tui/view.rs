@@ -0,0 +1,393 @@
+//! Drawing the board, list and detail panes with crossterm.
+
+use super::app::{App, InputKind, RELATION_KEYS, View};
+use crate::format::context::{OutputContext, OutputMode};
+use crate::format::markdown::render_markdown;
+use crate::format::truncate_title;
+use crate::model::{Issue, Status};
+use crossterm::cursor::MoveTo;
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
+use crossterm::terminal::{BeginSynchronizedUpdate, Clear, ClearType, EndSynchronizedUpdate};
+use crossterm::{QueueableCommand, queue};
+use std::io::{self, Write};
+use unicode_width::UnicodeWidthStr;
+
+const MIN_WIDTH: usize = 60;
+const MIN_HEIGHT: usize = 10;
+const MIN_COLUMN_WIDTH: usize = 24;
+
+const HELP: &str = "q quit  Tab board/list  / filter  c claim  +/- priority  a comment  1-9 follow  b back  r refresh";
+
+/// Redraw the whole screen.
+pub fn draw<W: Write>(out: &mut W, app: &App, size: (u16, u16), color: bool) -> io::Result<()> {
+    let (width, height) = (usize::from(size.0), usize::from(size.1));
+    queue!(out, BeginSynchronizedUpdate, Clear(ClearType::All))?;
+
+    if width < MIN_WIDTH || height < MIN_HEIGHT {
+        queue!(out, MoveTo(0, 0), Print("Terminal too small for the board"))?;
+    } else {
+        let body = height - 2;
+        let detail_width = (width * 2 / 5).max(30);
+        let main_width = width - detail_width - 1;
+
+        draw_header(out, app, width)?;
+        match app.view {
+            View::Board => draw_board(out, app, main_width, body, color)?,
+            View::List => draw_list(out, app, main_width, body, color)?,
+        }
+        for row in 1..=body {
+            queue!(out, at(main_width, row), Print("│"))?;
+        }
+        draw_details(out, app, main_width + 1, detail_width, body, color)?;
+        draw_footer(out, app, width, height - 1)?;
+    }
+
+    queue!(out, EndSynchronizedUpdate)?;
+    out.flush()
+}
+
+fn draw_header<W: Write>(out: &mut W, app: &App, width: usize) -> io::Result<()> {
+    let view = match app.view {
+        View::Board => "Board",
+        View::List => "List",
+    };
+    let mut header = format!(" obr · {view} · {} issues", app.visible().len());
+    if !app.filter.is_empty() {
+        header.push_str(&format!(" · filter: {}", app.filter));
+    }
+    queue!(
+        out,
+        at(0, 0),
+        SetAttribute(Attribute::Reverse),
+        Print(fit(&header, width)),
+        SetAttribute(Attribute::Reset)
+    )
+}
+
+fn draw_board<W: Write>(
+    out: &mut W,
+    app: &App,
+    width: usize,
+    height: usize,
+    color: bool,
+) -> io::Result<()> {
+    let columns = app.columns();
+    let selected = app.selected_id();
+    let selected_column = columns
+        .iter()
+        .position(|c| c.issues.iter().any(|i| Some(i.id.as_str()) == selected))
+        .unwrap_or(0);
+
+    let column_width = (width / columns.len().max(1))
+        .max(MIN_COLUMN_WIDTH)
+        .min(width);
+    let per_screen = (width / column_width).max(1);
+    // Scroll sideways so the selected column stays on screen.
+    let first = (selected_column + 1).saturating_sub(per_screen);
+
+    for (slot, column) in columns.iter().skip(first).take(per_screen).enumerate() {
+        let x = slot * column_width;
+        let inner = column_width - 1;
+        let title = format!("{} ({})", column.status, column.issues.len());
+        queue!(
+            out,
+            at(x, 1),
+            SetAttribute(Attribute::Bold),
+            Print(fit(&title, inner)),
+            SetAttribute(Attribute::Reset)
+        )?;
+
+        let rows = height - 1;
+        let selected_row = column
+            .issues
+            .iter()
+            .position(|i| Some(i.id.as_str()) == selected);
+        let offset = selected_row.map_or(0, |row| (row + 1).saturating_sub(rows));
+        for (row, issue) in column.issues.iter().skip(offset).take(rows).enumerate() {
+            let card = format!("P{} {} {}", issue.priority.0, issue.id, issue.title);
+            draw_row(out, x, row + 2, &fit(&card, inner), issue, selected, color)?;
+        }
+    }
+    Ok(())
+}
+
+fn draw_list<W: Write>(
+    out: &mut W,
+    app: &App,
+    width: usize,
+    height: usize,
+    color: bool,
+) -> io::Result<()> {
+    let issues = app.visible();
+    let selected = app.selected_id();
+    let id_width = issues.iter().map(|i| i.id.width()).max().unwrap_or(2);
+
+    let heading = format!(
+        "{:id_width$}  PRI  {:12} {:12} TITLE",
+        "ID", "STATUS", "ASSIGNEE"
+    );
+    queue!(
+        out,
+        at(0, 1),
+        SetAttribute(Attribute::Bold),
+        Print(fit(&heading, width)),
+        SetAttribute(Attribute::Reset)
+    )?;
+
+    let rows = height - 1;
+    let selected_row = issues.iter().position(|i| Some(i.id.as_str()) == selected);
+    let offset = selected_row.map_or(0, |row| (row + 1).saturating_sub(rows));
+    for (row, issue) in issues.iter().skip(offset).take(rows).enumerate() {
+        let line = format!(
+            "{:id_width$}  P{}   {} {} {}",
+            issue.id,
+            issue.priority.0,
+            fit(issue.status.as_str(), 12),
+            fit(issue.assignee.as_deref().unwrap_or("-"), 12),
+            issue.title
+        );
+        draw_row(out, 0, row + 2, &fit(&line, width), issue, selected, color)?;
+    }
+    Ok(())
+}
+
+fn draw_row<W: Write>(
+    out: &mut W,
+    x: usize,
+    y: usize,
+    text: &str,
+    issue: &Issue,
+    selected: Option<&str>,
+    color: bool,
+) -> io::Result<()> {
+    out.queue(at(x, y))?;
+    if Some(issue.id.as_str()) == selected {
+        out.queue(SetAttribute(Attribute::Reverse))?;
+    }
+    if color {
+        if let Some(fg) = status_color(&issue.status) {
+            out.queue(SetForegroundColor(fg))?;
+        }
+    }
+    queue!(out, Print(text), ResetColor, SetAttribute(Attribute::Reset))
+}
+
+const fn status_color(status: &Status) -> Option<Color> {
+    match status {
+        Status::InProgress => Some(Color::Yellow),
+        Status::Blocked => Some(Color::Red),
+        Status::Deferred | Status::Pinned => Some(Color::DarkGrey),
+        Status::Closed => Some(Color::Green),
+        _ => None,
+    }
+}
+
+fn draw_details<W: Write>(
+    out: &mut W,
+    app: &App,
+    x: usize,
+    width: usize,
+    height: usize,
+    color: bool,
+) -> io::Result<()> {
+    let inner = width - 1;
+    let lines = detail_lines(app, inner, color);
+    for (row, line) in lines
+        .iter()
+        .skip(app.detail_scroll)
+        .take(height)
+        .enumerate()
+    {
+        queue!(
+            out,
+            at(x + 1, row + 1),
+            Print(line),
+            ResetColor,
+            SetAttribute(Attribute::Reset)
+        )?;
+    }
+    Ok(())
+}
+
+/// Lines of the detail pane; markdown lines may carry ANSI styling.
+fn detail_lines(app: &App, width: usize, color: bool) -> Vec<String> {
+    let Some(details) = app.current_details() else {
+        return vec![fit("No issue selected", width)];
+    };
+    let issue = &details.issue;
+    let mut lines = wrap(&issue.title, width);
+    lines.push(fit(
+        &format!(
+            "{} · {} · P{} · {}",
+            issue.id, issue.status, issue.priority.0, issue.issue_type
+        ),
+        width,
+    ));
+    if let Some(assignee) = &issue.assignee {
+        lines.push(fit(&format!("Assignee: {assignee}"), width));
+    }
+    if !details.labels.is_empty() {
+        lines.push(fit(
+            &format!("Labels: {}", details.labels.join(", ")),
+            width,
+        ));
+    }
+    if let Some(due) = issue.due_at {
+        lines.push(fit(&format!("Due: {}", due.format("%Y-%m-%d")), width));
+    }
+
+    let relations = app.relations();
+    if !relations.is_empty() {
+        lines.push(String::new());
+        for ((kind, related), key) in relations.iter().zip(RELATION_KEYS.chars()) {
+            lines.push(fit(
+                &format!(
+                    "[{key}] {kind} {} ({}) {}",
+                    related.id, related.status, related.title
+                ),
+                width,
+            ));
+        }
+    }
+
+    let mode = if color {
+        OutputMode::Rich
+    } else {
+        OutputMode::Plain
+    };
+    let markdown_ctx = OutputContext::new(mode, width, None, true);
+    let push_markdown = |lines: &mut Vec<String>, text: &str| {
+        let rendered = render_markdown(text, &markdown_ctx);
+        for line in rendered.lines() {
+            if color {
+                lines.push(line.to_string());
+            } else {
+                lines.extend(wrap(line, width));
+            }
+        }
+    };
+
+    if let Some(description) = issue
+        .description
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())
+    {
+        lines.push(String::new());
+        push_markdown(&mut lines, description);
+    }
+    if !details.comments.is_empty() {
+        lines.push(String::new());
+        lines.push(fit(
+            &format!("Comments ({})", details.comments.len()),
+            width,
+        ));
+        for comment in &details.comments {
+            lines.push(fit(
+                &format!(
+                    "— {} · {}",
+                    comment.author,
+                    comment.created_at.format("%Y-%m-%d %H:%M")
+                ),
+                width,
+            ));
+            push_markdown(&mut lines, &comment.body);
+        }
+    }
+    lines
+}
+
+fn draw_footer<W: Write>(out: &mut W, app: &App, width: usize, y: usize) -> io::Result<()> {
+    let text = match &app.input {
+        Some(input) => {
+            let prompt = match input.kind {
+                InputKind::Filter => "Filter".to_string(),
+                InputKind::Comment => {
+                    format!("Comment on {}", app.selected_id().unwrap_or_default())
+                }
+            };
+            format!("{prompt}: {}_", input.buffer)
+        }
+        None => app.message.clone().unwrap_or_else(|| HELP.to_string()),
+    };
+    queue!(out, at(0, y), Print(fit(&text, width)))
+}
+
+fn at(x: usize, y: usize) -> MoveTo {
+    MoveTo(
+        u16::try_from(x).unwrap_or(u16::MAX),
+        u16::try_from(y).unwrap_or(u16::MAX),
+    )
+}
+
+/// Truncate or pad `text` to exactly `width` columns.
+fn fit(text: &str, width: usize) -> String {
+    let mut fitted = truncate_title(text, width);
+    let pad = width.saturating_sub(fitted.width());
+    fitted.push_str(&" ".repeat(pad));
+    fitted
+}
+
+/// Word-wrap plain text to `width` columns.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.width() + 1 + word.width() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+        .into_iter()
+        .map(|l| truncate_title(&l, width))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::IssueDetails;
+    use std::collections::HashMap;
+
+    #[test]
+    fn draws_board_and_details() {
+        let issue = Issue {
+            id: "bd-1".to_string(),
+            title: "Fix login".to_string(),
+            description: Some("Users **cannot** log in".to_string()),
+            ..Issue::default()
+        };
+        let mut app = App::default();
+        app.set_issues(vec![issue.clone()], HashMap::new());
+        app.set_details(Some(IssueDetails {
+            issue,
+            labels: vec!["auth".to_string()],
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+            comments: Vec::new(),
+            events: Vec::new(),
+            parent: None,
+        }));
+
+        let mut screen = Vec::new();
+        draw(&mut screen, &app, (120, 30), false).expect("draw");
+        let screen = String::from_utf8_lossy(&screen);
+        assert!(screen.contains("open (1)"));
+        assert!(screen.contains("P0 bd-1 Fix login"));
+        assert!(screen.contains("Labels: auth"));
+        assert!(screen.contains("Users cannot log in"));
+    }
+
+    #[test]
+    fn fit_and_wrap_respect_width() {
+        assert_eq!(fit("abc", 5), "abc  ");
+        assert_eq!(fit("abcdefgh", 6), "abc...");
+        assert_eq!(wrap("one two three", 7), ["one two", "three"]);
+    }
+}
@@ -1,7 +1,7 @@
 //! Time and date parsing utilities.
 
 use crate::error::{BeadsError, Result};
-use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
 
 /// Parse a flexible time specification into a `DateTime<Utc>`.
 ///
@@ -150,6 +150,420 @@ pub fn parse_relative_time(s: &str) -> Option<DateTime<Utc>> {
     }
 }
 
+/// Parse a point in the past, e.g. for `--as-of`.
+///
+/// Accepts everything [`parse_flexible_timestamp`] does, plus durations
+/// counted back from now: `3d`, `2h ago` (same as `-3d`, `-2h`).
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be parsed or lies in the future.
+pub fn parse_past_timestamp(s: &str, field_name: &str) -> Result<DateTime<Utc>> {
+    let s = s.trim();
+    let bare = s.strip_suffix("ago").map_or(s, str::trim_end);
+    let starts_with_digit = bare.chars().next().is_some_and(|c| c.is_ascii_digit());
+    let dt = if starts_with_digit && bare.ends_with(['m', 'h', 'd', 'w']) {
+        parse_flexible_timestamp(&format!("-{bare}"), field_name)?
+    } else {
+        parse_flexible_timestamp(s, field_name)?
+    };
+    if dt > Utc::now() {
+        return Err(BeadsError::validation(field_name, "must be in the past"));
+    }
+    Ok(dt)
+}
+
+/// Parse a positive duration such as `30m`, `2h`, `1d` or `1h30m`.
+///
+/// Units are `s`, `m`, `h`, `d` and `w`; a leading `+` is accepted.
+///
+/// # Errors
+///
+/// Returns an error if the input is empty, has an unknown unit, or is zero.
+pub fn parse_duration(s: &str, field_name: &str) -> Result<Duration> {
+    let invalid = || {
+        BeadsError::validation(
+            field_name,
+            format!("invalid duration '{s}' (try: 30m, 2h, 1d, 1h30m)"),
+        )
+    };
+    let text = s.trim();
+    let text = text.strip_prefix('+').unwrap_or(text);
+    if text.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total = Duration::zero();
+    let mut amount = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            amount.push(c);
+            continue;
+        }
+        let value: i64 = amount.parse().map_err(|_| invalid())?;
+        amount.clear();
+        let part = match c {
+            's' => Duration::try_seconds(value),
+            'm' => Duration::try_minutes(value),
+            'h' => Duration::try_hours(value),
+            'd' => Duration::try_days(value),
+            'w' => Duration::try_weeks(value),
+            _ => None,
+        };
+        total = part
+            .and_then(|p| total.checked_add(&p))
+            .ok_or_else(invalid)?;
+    }
+    if !amount.is_empty() || total <= Duration::zero() {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+/// Format a duration the way [`parse_duration`] reads it, e.g. `1h30m`.
+///
+/// Seconds are only shown for durations under a minute; negative
+/// durations format as `0s`.
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let total = duration.num_seconds().max(0);
+    if total < 60 {
+        return format!("{total}s");
+    }
+    let (days, hours, minutes) = (total / 86_400, total % 86_400 / 3600, total % 3600 / 60);
+    let mut out = String::new();
+    for (value, unit) in [(days, 'd'), (hours, 'h'), (minutes, 'm')] {
+        if value > 0 {
+            out.push_str(&format!("{value}{unit}"));
+        }
+    }
+    out
+}
+
+/// A recurrence rule, evaluated in local time.
+///
+/// Forms (case-insensitive):
+/// - `every day`, `every 3d`, `every weekday`
+/// - `every week`, `every 2w on mon,thu` (default: the start date's weekday)
+/// - `every month`, `every 3mo on 1,15` (default: the start date's day; days
+///   past the end of a month fall on its last day)
+/// - any of the above followed by `at HH:MM` (default 09:00)
+/// - five numeric cron fields: `minute hour day-of-month month day-of-week`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recurrence {
+    Days {
+        every: u32,
+        at: NaiveTime,
+    },
+    Weeks {
+        every: u32,
+        on: Vec<Weekday>,
+        at: NaiveTime,
+    },
+    Months {
+        every: u32,
+        on: Vec<u32>,
+        at: NaiveTime,
+    },
+    Cron(CronSchedule),
+}
+
+/// A parsed cron expression (numbers, `*`, ranges, lists and steps).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: Vec<u32>,
+    hours: Vec<u32>,
+    days: Vec<u32>,
+    months: Vec<u32>,
+    /// 0 = Sunday.
+    weekdays: Vec<u32>,
+    /// Either day field was `*`, so both must match (cron's OR rule
+    /// applies only when both are restricted).
+    any_day: bool,
+}
+
+/// Upper bound on periods scanned when looking for the next occurrence.
+const MAX_PERIODS: i64 = 10_000;
+
+/// Parse a recurrence rule such as `every 2w on mon` or `0 9 1 * *`.
+///
+/// # Errors
+///
+/// Returns a validation error naming `field_name` if the rule is malformed.
+pub fn parse_recurrence(s: &str, field_name: &str) -> Result<Recurrence> {
+    let invalid =
+        |why: &str| BeadsError::validation(field_name, format!("invalid recurrence '{s}': {why}"));
+    let text = s.trim().to_lowercase();
+    let Some(rest) = text.strip_prefix("every ") else {
+        return parse_cron(&text)
+            .map(Recurrence::Cron)
+            .ok_or_else(|| invalid("expected 'every ...' or five cron fields"));
+    };
+
+    let (rest, at) = match rest.split_once(" at ") {
+        Some((rule, time)) => (
+            rule,
+            NaiveTime::parse_from_str(time.trim(), "%H:%M")
+                .map_err(|_| invalid("time must be HH:MM"))?,
+        ),
+        None => (rest, NaiveTime::from_hms_opt(9, 0, 0).unwrap_or_default()),
+    };
+    let (period, on) = match rest.split_once(" on ") {
+        Some((period, on)) => (period.trim(), Some(on.trim())),
+        None => (rest.trim(), None),
+    };
+    if period == "weekday" {
+        if on.is_some() {
+            return Err(invalid("'every weekday' takes no 'on'"));
+        }
+        return Ok(Recurrence::Weeks {
+            every: 1,
+            on: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            at,
+        });
+    }
+
+    let split = period
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(period.len());
+    let (count, unit) = period.split_at(split);
+    let every = if count.is_empty() {
+        1
+    } else {
+        count
+            .parse::<u32>()
+            .map_err(|_| invalid("interval is too large"))?
+    };
+    if every == 0 {
+        return Err(invalid("interval must be positive"));
+    }
+
+    match unit.trim() {
+        "d" | "day" | "days" => {
+            if on.is_some() {
+                return Err(invalid("daily rules take no 'on'"));
+            }
+            Ok(Recurrence::Days { every, at })
+        }
+        "w" | "week" | "weeks" => {
+            let mut days = Vec::new();
+            for day in on.into_iter().flat_map(rule_list) {
+                days.push(
+                    day.parse::<Weekday>()
+                        .map_err(|_| invalid("weekdays are mon, tue, ..."))?,
+                );
+            }
+            days.sort_by_key(Weekday::num_days_from_monday);
+            days.dedup();
+            Ok(Recurrence::Weeks {
+                every,
+                on: days,
+                at,
+            })
+        }
+        "mo" | "month" | "months" => {
+            let mut days = Vec::new();
+            for day in on.into_iter().flat_map(rule_list) {
+                days.push(
+                    day.parse::<u32>()
+                        .ok()
+                        .filter(|day| (1..=31).contains(day))
+                        .ok_or_else(|| invalid("month days are 1-31"))?,
+                );
+            }
+            days.sort_unstable();
+            days.dedup();
+            Ok(Recurrence::Months {
+                every,
+                on: days,
+                at,
+            })
+        }
+        _ => Err(invalid("unit must be d, w or mo")),
+    }
+}
+
+impl Recurrence {
+    /// The first occurrence strictly after `after` and not before `start`.
+    ///
+    /// `start` anchors the series: intervals count from it, and it supplies
+    /// the weekday or month day when the rule names none. Returns `None` if
+    /// no occurrence is found within a bounded search.
+    #[must_use]
+    pub fn next_after(&self, start: DateTime<Utc>, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let first_day = start.with_timezone(&Local).date_naive();
+        let after_day = after.with_timezone(&Local).date_naive();
+        let valid = |dt: DateTime<Utc>| dt > after && dt >= start;
+
+        match self {
+            Self::Days { every, at } => {
+                let every = i64::from(*every);
+                let skip = ((after_day - first_day).num_days().max(0) / every - 1).max(0);
+                (skip..skip + MAX_PERIODS)
+                    .filter_map(|k| local_at(first_day + Duration::days(k * every), *at))
+                    .find(|dt| valid(*dt))
+            }
+            Self::Weeks { every, on, at } => {
+                let every = i64::from(*every);
+                let week0 = first_day
+                    - Duration::days(i64::from(first_day.weekday().num_days_from_monday()));
+                let on = if on.is_empty() {
+                    vec![first_day.weekday()]
+                } else {
+                    on.clone()
+                };
+                let skip = ((after_day - week0).num_days().max(0) / 7 / every - 1).max(0);
+                (skip..skip + MAX_PERIODS)
+                    .flat_map(|k| {
+                        let week = week0 + Duration::weeks(k * every);
+                        on.iter().map(move |day| {
+                            week + Duration::days(i64::from(day.num_days_from_monday()))
+                        })
+                    })
+                    .filter_map(|date| local_at(date, *at))
+                    .find(|dt| valid(*dt))
+            }
+            Self::Months { every, on, at } => {
+                let every = i64::from(*every);
+                let month_index =
+                    |date: NaiveDate| i64::from(date.year()) * 12 + i64::from(date.month0());
+                let base = month_index(first_day);
+                let on = if on.is_empty() {
+                    vec![first_day.day()]
+                } else {
+                    on.clone()
+                };
+                let skip = ((month_index(after_day) - base).max(0) / every - 1).max(0);
+                (skip..skip + MAX_PERIODS)
+                    .flat_map(|k| {
+                        let month = base + k * every;
+                        let year = i32::try_from(month.div_euclid(12)).unwrap_or(i32::MAX);
+                        let month = u32::try_from(month.rem_euclid(12)).unwrap_or(0) + 1;
+                        on.iter().filter_map(move |day| {
+                            NaiveDate::from_ymd_opt(
+                                year,
+                                month,
+                                (*day).min(last_day_of_month(year, month)),
+                            )
+                        })
+                    })
+                    .filter_map(|date| local_at(date, *at))
+                    .find(|dt| valid(*dt))
+            }
+            Self::Cron(cron) => {
+                let from = after_day.max(first_day);
+                (0..MAX_PERIODS)
+                    .map(|offset| from + Duration::days(offset))
+                    .filter(|date| cron.matches_day(*date))
+                    .flat_map(|date| {
+                        cron.hours.iter().flat_map(move |hour| {
+                            cron.minutes
+                                .iter()
+                                .filter_map(move |minute| {
+                                    NaiveTime::from_hms_opt(*hour, *minute, 0)
+                                })
+                                .map(move |time| (date, time))
+                        })
+                    })
+                    .filter_map(|(date, time)| local_at(date, time))
+                    .find(|dt| valid(*dt))
+            }
+        }
+    }
+}
+
+impl CronSchedule {
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        if !self.months.contains(&date.month()) {
+            return false;
+        }
+        let day = self.days.contains(&date.day());
+        let weekday = self
+            .weekdays
+            .contains(&date.weekday().num_days_from_sunday());
+        if self.any_day {
+            day && weekday
+        } else {
+            day || weekday
+        }
+    }
+}
+
+fn parse_cron(text: &str) -> Option<CronSchedule> {
+    let fields: Vec<&str> = text.split_whitespace().collect();
+    let [minute, hour, day, month, weekday] = fields.as_slice() else {
+        return None;
+    };
+    let mut weekdays: Vec<u32> = cron_field(weekday, 0, 7)?
+        .into_iter()
+        .map(|day| day % 7)
+        .collect();
+    weekdays.sort_unstable();
+    weekdays.dedup();
+    Some(CronSchedule {
+        minutes: cron_field(minute, 0, 59)?,
+        hours: cron_field(hour, 0, 23)?,
+        days: cron_field(day, 1, 31)?,
+        months: cron_field(month, 1, 12)?,
+        weekdays,
+        any_day: *day == "*" || *weekday == "*",
+    })
+}
+
+/// Values allowed by one cron field (`*`, `5`, `1-5`, `*/15`, `1,15`).
+fn cron_field(field: &str, min: u32, max: u32) -> Option<Vec<u32>> {
+    let mut values = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<usize>().ok().filter(|s| *s > 0)?),
+            None => (part, 1),
+        };
+        let (low, high) = if range == "*" {
+            (min, max)
+        } else if let Some((low, high)) = range.split_once('-') {
+            (low.parse().ok()?, high.parse().ok()?)
+        } else {
+            let value = range.parse().ok()?;
+            (value, if step > 1 { max } else { value })
+        };
+        if low < min || high > max || low > high {
+            return None;
+        }
+        values.extend((low..=high).step_by(step));
+    }
+    values.sort_unstable();
+    values.dedup();
+    Some(values)
+}
+
+/// Items of a comma-separated `on` list.
+fn rule_list(on: &str) -> impl Iterator<Item = &str> {
+    on.split(',').map(str::trim).filter(|item| !item.is_empty())
+}
+
+fn local_at(date: NaiveDate, time: NaiveTime) -> Option<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn last_day_of_month(year: i32, month: u32) -> u32 {
+    let next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)
+    };
+    next.and_then(NaiveDate::pred_opt)
+        .map_or(28, |last| last.day())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,4 +612,138 @@ mod tests {
         assert!(parse_relative_time("invalid").is_none());
         assert!(parse_relative_time("2025-01-15").is_none());
     }
+
+    #[test]
+    fn test_parse_past_timestamp_bare_duration() {
+        let result = parse_past_timestamp("3d", "test").unwrap();
+        assert!(result < Utc::now() - Duration::days(2));
+        let result = parse_past_timestamp("2h ago", "test").unwrap();
+        assert!(result < Utc::now() - Duration::minutes(119));
+    }
+
+    #[test]
+    fn test_parse_past_timestamp_rejects_future() {
+        assert!(parse_past_timestamp("+1h", "test").is_err());
+        assert!(parse_past_timestamp("2025-01-15T12:00:00Z", "test").is_ok());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30m", "ttl").unwrap(), Duration::minutes(30));
+        assert_eq!(parse_duration("+2h", "ttl").unwrap(), Duration::hours(2));
+        assert_eq!(
+            parse_duration("1h30m", "ttl").unwrap(),
+            Duration::minutes(90)
+        );
+        assert_eq!(parse_duration("1d", "ttl").unwrap(), Duration::days(1));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::seconds(42)), "42s");
+        assert_eq!(format_duration(Duration::minutes(90)), "1h30m");
+        assert_eq!(format_duration(Duration::hours(25)), "1d1h");
+        assert_eq!(format_duration(Duration::seconds(-5)), "0s");
+    }
+
+    #[test]
+    fn test_parse_duration_invalid() {
+        for input in ["", "30", "m", "0m", "5x", "-5m"] {
+            assert!(parse_duration(input, "ttl").is_err(), "{input}");
+        }
+    }
+
+    fn local(date: &str, time: &str) -> DateTime<Utc> {
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+        local_at(date, NaiveTime::parse_from_str(time, "%H:%M").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_recurrence_every_weeks() {
+        // 2025-01-06 is a Monday.
+        let rule = parse_recurrence("every 2w on mon,thu", "rule").unwrap();
+        let start = local("2025-01-06", "08:00");
+        let next = |after| rule.next_after(start, after).unwrap();
+        assert_eq!(next(start), local("2025-01-06", "09:00"));
+        assert_eq!(
+            next(local("2025-01-06", "09:00")),
+            local("2025-01-09", "09:00")
+        );
+        assert_eq!(
+            next(local("2025-01-09", "10:00")),
+            local("2025-01-20", "09:00")
+        );
+        // 2025-06-02 falls in an off week of the series.
+        assert_eq!(
+            next(local("2025-06-01", "00:00")),
+            local("2025-06-09", "09:00")
+        );
+
+        let weekly = parse_recurrence("Every week at 14:30", "rule").unwrap();
+        assert_eq!(
+            weekly.next_after(start, local("2025-01-07", "00:00")),
+            Some(local("2025-01-13", "14:30"))
+        );
+    }
+
+    #[test]
+    fn test_recurrence_days_and_months() {
+        let start = local("2025-01-31", "00:00");
+        let daily = parse_recurrence("every 3d", "rule").unwrap();
+        assert_eq!(
+            daily.next_after(start, local("2025-02-01", "00:00")),
+            Some(local("2025-02-03", "09:00"))
+        );
+
+        let monthly = parse_recurrence("every month", "rule").unwrap();
+        assert_eq!(
+            monthly.next_after(start, local("2025-02-01", "00:00")),
+            Some(local("2025-02-28", "09:00")),
+            "clamped to the end of February"
+        );
+        let mid = parse_recurrence("every 3mo on 15", "rule").unwrap();
+        assert_eq!(
+            mid.next_after(start, start),
+            Some(local("2025-04-15", "09:00"))
+        );
+    }
+
+    #[test]
+    fn test_recurrence_cron() {
+        let start = local("2025-01-01", "00:00");
+        // Mondays at 09:15.
+        let rule = parse_recurrence("15 9 * * 1", "rule").unwrap();
+        assert_eq!(
+            rule.next_after(start, local("2025-01-06", "09:15")),
+            Some(local("2025-01-13", "09:15"))
+        );
+        // The 1st of the month or any Friday.
+        let either = parse_recurrence("0 8 1 * 5", "rule").unwrap();
+        assert_eq!(
+            either.next_after(start, start),
+            Some(local("2025-01-01", "08:00"))
+        );
+        assert_eq!(
+            either.next_after(start, local("2025-01-01", "08:00")),
+            Some(local("2025-01-03", "08:00"))
+        );
+    }
+
+    #[test]
+    fn test_recurrence_invalid() {
+        for input in [
+            "",
+            "every",
+            "every 0d",
+            "every 2x",
+            "every day on mon",
+            "every week on funday",
+            "every month on 32",
+            "every day at noon",
+            "61 * * * *",
+            "* * * *",
+        ] {
+            assert!(parse_recurrence(input, "rule").is_err(), "{input}");
+        }
+    }
 }
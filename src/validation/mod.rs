@@ -464,6 +464,7 @@ mod tests {
             labels: Vec::new(),
             dependencies: Vec::new(),
             comments: Vec::new(),
+            org_extras: None,
         }
     }
 
@@ -52,6 +52,7 @@ pub fn issue(title: &str) -> Issue {
         is_template: false,
         dependencies: vec![],
         comments: vec![],
+        org_extras: None,
     }
 }
 
@@ -1125,6 +1125,9 @@ const KNOWN_OTHER_TABLE_DIFFS: &[(&str, &str, &str)] = &[
     // child_counters: br uses last_child, bd uses next_child_number
     ("child_counters", "last_child", "missing_in_bd"),
     ("child_counters", "next_child_number", "missing_in_br"),
+    // events: br groups events by operation for `undo`
+    ("events", "op_id", "missing_in_bd"),
+    ("events", "field", "missing_in_bd"),
     // Type mismatches: br uses DATETIME, bd uses TEXT (both work the same in SQLite)
     ("comments", "created_at", "type_mismatch"),
     ("dependencies", "created_at", "type_mismatch"),
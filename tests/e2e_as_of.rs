@@ -0,0 +1,135 @@
+//! E2E tests for point-in-time views: `show --as-of` and `history field`.
+
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+use std::thread;
+use std::time::Duration;
+
+fn parse_created_id(stdout: &str) -> String {
+    let line = stdout.lines().next().unwrap_or("");
+    let normalized = line.strip_prefix("✓ ").unwrap_or(line);
+    normalized
+        .strip_prefix("Created ")
+        .and_then(|rest| rest.split(':').next())
+        .unwrap_or("")
+        .trim()
+        .to_string()
+}
+
+/// A timestamp that falls strictly between the commands run before and after.
+fn checkpoint() -> String {
+    thread::sleep(Duration::from_millis(20));
+    let now = chrono::Utc::now().to_rfc3339();
+    thread::sleep(Duration::from_millis(20));
+    now
+}
+
+#[test]
+fn e2e_show_as_of_rebuilds_past_state() {
+    let _log = common::test_log("e2e_show_as_of_rebuilds_past_state");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let create = run_br(
+        &workspace,
+        ["create", "Before", "-p", "3", "--labels", "old"],
+        "create",
+    );
+    assert!(create.status.success(), "create failed: {}", create.stderr);
+    let id = parse_created_id(&create.stdout);
+    let as_of = checkpoint();
+
+    let update = run_br(
+        &workspace,
+        [
+            "update",
+            &id,
+            "--title",
+            "After",
+            "-p",
+            "0",
+            "--remove-label",
+            "old",
+            "--add-label",
+            "new",
+        ],
+        "update",
+    );
+    assert!(update.status.success(), "update failed: {}", update.stderr);
+    let close = run_br(&workspace, ["close", &id], "close");
+    assert!(close.status.success(), "close failed: {}", close.stderr);
+
+    let show = run_br(&workspace, ["show", &id, "--as-of", &as_of, "--json"], "show");
+    assert!(show.status.success(), "show failed: {}", show.stderr);
+    let json: Value = serde_json::from_str(&extract_json_payload(&show.stdout)).expect("json");
+    let issue = &json[0];
+    assert_eq!(issue["title"], "Before");
+    assert_eq!(issue["priority"], 3);
+    assert_eq!(issue["status"], "open");
+    assert_eq!(issue["labels"], serde_json::json!(["old"]));
+
+    let text = run_br(&workspace, ["show", &id, "--as-of", &as_of], "show_text");
+    assert!(text.status.success(), "show failed: {}", text.stderr);
+    assert!(text.stdout.contains("As of "), "{}", text.stdout);
+    assert!(text.stdout.contains("Before"), "{}", text.stdout);
+
+    let too_early = run_br(
+        &workspace,
+        ["show", &id, "--as-of", "2000-01-01T00:00:00Z"],
+        "show_too_early",
+    );
+    assert!(!too_early.status.success());
+    assert!(too_early.stderr.contains("did not exist"), "{}", too_early.stderr);
+}
+
+#[test]
+fn e2e_history_field_lists_changes() {
+    let _log = common::test_log("e2e_history_field_lists_changes");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let create = run_br(&workspace, ["create", "Tracked"], "create");
+    assert!(create.status.success(), "create failed: {}", create.stderr);
+    let id = parse_created_id(&create.stdout);
+    for (status, label) in [("in_progress", "start"), ("blocked", "block")] {
+        let update = run_br(
+            &workspace,
+            ["--actor", "alice", "update", &id, "--status", status],
+            label,
+        );
+        assert!(update.status.success(), "update failed: {}", update.stderr);
+    }
+
+    let history = run_br(
+        &workspace,
+        ["history", "field", &id, "status", "--json"],
+        "history_json",
+    );
+    assert!(history.status.success(), "history failed: {}", history.stderr);
+    let json: Value = serde_json::from_str(&extract_json_payload(&history.stdout)).expect("json");
+    assert_eq!(json["field"], "status");
+    let changes = json["changes"].as_array().expect("changes");
+    let values: Vec<_> = changes
+        .iter()
+        .map(|change| change["new_value"].as_str().unwrap_or_default())
+        .collect();
+    assert_eq!(values, vec!["open", "in_progress", "blocked"]);
+    assert_eq!(changes[2]["actor"], "alice");
+    assert_eq!(changes[2]["old_value"], "in_progress");
+
+    let text = run_br(&workspace, ["history", "field", &id, "status"], "history_text");
+    assert!(text.status.success(), "history failed: {}", text.stderr);
+    assert!(
+        text.stdout.contains("'in_progress' -> 'blocked'"),
+        "{}",
+        text.stdout
+    );
+
+    let unknown = run_br(&workspace, ["history", "field", &id, "bogus"], "history_bad");
+    assert!(!unknown.status.success());
+    assert!(unknown.stderr.contains("unknown field"), "{}", unknown.stderr);
+}
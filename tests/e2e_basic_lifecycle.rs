@@ -63,6 +63,7 @@ fn make_issue(id: &str, title: &str, now: chrono::DateTime<Utc>) -> Issue {
         labels: vec![],
         dependencies: vec![],
         comments: vec![],
+        org_extras: None,
     }
 }
 
@@ -0,0 +1,115 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br, run_br_with_stdin};
+use serde_json::Value;
+
+fn create(workspace: &BrWorkspace, title: &str, label: &str) -> String {
+    let out = run_br(workspace, ["create", title, "--json"], label);
+    assert!(out.status.success(), "create failed: {}", out.stderr);
+    let json: Value = serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json");
+    json["id"].as_str().expect("id").to_string()
+}
+
+fn show(workspace: &BrWorkspace, id: &str, label: &str) -> Value {
+    let out = run_br(workspace, ["show", id, "--json"], label);
+    assert!(out.status.success(), "show failed: {}", out.stderr);
+    let json: Vec<Value> = serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json");
+    json[0].clone()
+}
+
+#[test]
+fn e2e_batch_applies_all_operations() {
+    let _log = common::test_log("e2e_batch_applies_all_operations");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let blocker = create(&workspace, "Existing blocker", "blocker");
+    let input = format!(
+        r#"{{"op":"create","handle":"epic","title":"Auth rewrite","type":"epic"}}
+{{"op":"create","handle":"login","title":"Login form","parent":"$epic","priority":1}}
+
+{{"op":"dep_add","id":"$login","depends_on":"{blocker}"}}
+{{"op":"label","id":"$login","add":["frontend","auth"]}}
+{{"op":"comment","id":"$epic","text":"Split out of the old plan"}}
+{{"op":"close","id":"{blocker}","reason":"landed"}}
+{{"op":"update","id":"$login","status":"in_progress"}}
+"#
+    );
+    let out = run_br_with_stdin(&workspace, ["batch", "--json"], &input, "batch");
+    assert!(out.status.success(), "batch failed: {}", out.stderr);
+    let json: Value = serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json");
+    assert_eq!(json["committed"], true);
+    let results = json["results"].as_array().expect("results");
+    assert_eq!(results.len(), 7);
+    assert!(results.iter().all(|r| r["status"] == "applied"), "{json}");
+    assert_eq!(results[2]["line"], 4, "blank lines still count");
+    let epic = results[0]["id"].as_str().expect("epic id").to_string();
+    let login = results[1]["id"].as_str().expect("login id").to_string();
+    assert_eq!(results[1]["handle"], "login");
+
+    let login_issue = show(&workspace, &login, "show_login");
+    assert_eq!(login_issue["status"], "in_progress");
+    assert_eq!(login_issue["priority"], 1);
+    let labels = login_issue["labels"].as_array().expect("labels");
+    assert_eq!(labels.len(), 2, "{login_issue}");
+    assert_eq!(
+        show(&workspace, &blocker, "show_blocker")["status"],
+        "closed"
+    );
+    let epic_issue = show(&workspace, &epic, "show_epic");
+    assert_eq!(epic_issue["comments"].as_array().map(Vec::len), Some(1));
+
+    // The whole batch is one undo step.
+    let undo = run_br(&workspace, ["undo"], "undo");
+    assert!(undo.status.success(), "undo failed: {}", undo.stderr);
+    assert_eq!(show(&workspace, &blocker, "show_undone")["status"], "open");
+    let list = run_br(&workspace, ["list", "--json"], "list");
+    let list: Value = serde_json::from_str(&extract_json_payload(&list.stdout)).expect("json");
+    assert_eq!(list.as_array().map(Vec::len), Some(1), "{list}");
+}
+
+#[test]
+fn e2e_batch_rolls_back_on_failure() {
+    let _log = common::test_log("e2e_batch_rolls_back_on_failure");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let input = r#"{"op":"create","handle":"a","title":"First"}
+{"op":"create","handle":"b","title":"Second","deps":["$a"]}
+{"op":"close","id":"$b"}
+{"op":"comment","id":"$a","text":"never written"}
+"#;
+    let out = run_br_with_stdin(&workspace, ["batch", "--json"], input, "batch");
+    assert!(!out.status.success(), "blocked close should fail");
+    let json: Value = serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json");
+    assert_eq!(json["committed"], false);
+    let statuses: Vec<&str> = json["results"]
+        .as_array()
+        .expect("results")
+        .iter()
+        .map(|r| r["status"].as_str().unwrap_or_default())
+        .collect();
+    assert_eq!(
+        statuses,
+        ["rolled_back", "rolled_back", "failed", "not_run"]
+    );
+    assert_eq!(json["results"][2]["error"]["code"], "VALIDATION_FAILED");
+
+    let list = run_br(&workspace, ["list", "--json"], "list");
+    let list: Value = serde_json::from_str(&extract_json_payload(&list.stdout)).expect("json");
+    assert_eq!(list.as_array().map(Vec::len), Some(0), "{list}");
+
+    let unknown = run_br_with_stdin(
+        &workspace,
+        ["batch"],
+        "{\"op\":\"label\",\"id\":\"$nope\",\"add\":[\"x\"]}\n",
+        "unknown_handle",
+    );
+    assert!(!unknown.status.success());
+    assert!(unknown.stderr.contains("$nope"), "{}", unknown.stderr);
+
+    let bad = run_br_with_stdin(&workspace, ["batch"], "{\"op\":\"create\"\n", "bad_json");
+    assert!(!bad.status.success());
+}
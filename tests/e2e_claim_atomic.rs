@@ -50,6 +50,7 @@ fn seed_issue(storage: &mut SqliteStorage, id: &str, assignee: Option<&str>) {
         labels: vec![],
         dependencies: vec![],
         comments: vec![],
+        org_extras: None,
     };
     storage.create_issue(&issue, "seed").unwrap();
 }
@@ -0,0 +1,192 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+
+fn json(workspace: &BrWorkspace, args: &[&str], label: &str) -> Value {
+    let mut full = args.to_vec();
+    full.push("--json");
+    let out = run_br(workspace, full, label);
+    assert!(out.status.success(), "{label} failed: {}", out.stderr);
+    serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json")
+}
+
+fn show(workspace: &BrWorkspace, id: &str, label: &str) -> Value {
+    json(workspace, &["show", id], label)[0].clone()
+}
+
+fn closed_issue(workspace: &BrWorkspace, title: &str, description: &str) -> String {
+    let id = json(workspace, &["create", title, "-d", description], "create")["id"]
+        .as_str()
+        .expect("id")
+        .to_string();
+    let close = run_br(workspace, ["close", &id, "-r", "Fixed in 1.2"], "close");
+    assert!(close.status.success(), "close failed: {}", close.stderr);
+    id
+}
+
+#[test]
+fn e2e_compact_levels_keep_originals() {
+    let _log = common::test_log("e2e_compact_levels_keep_originals");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let description = format!(
+        "Login fails on Safari. {}\n\nSet SameSite on the session cookie. {}",
+        "The cookie is dropped on redirect. ".repeat(8),
+        "Cover it with a browser test. ".repeat(8)
+    );
+    let login = closed_issue(&workspace, "Fix login", &description);
+
+    let recent = json(
+        &workspace,
+        &["compact", "--closed-before", "90d"],
+        "compact_recent",
+    );
+    assert_eq!(recent["compacted"].as_array().map(Vec::len), Some(0));
+
+    let cutoff = chrono::Utc::now().to_rfc3339();
+    let dry = json(
+        &workspace,
+        &["compact", "--closed-before", &cutoff, "--dry-run"],
+        "compact_dry_run",
+    );
+    assert_eq!(dry["compacted"][0]["id"], login.as_str());
+    assert_eq!(
+        show(&workspace, &login, "show_dry_run")["description"],
+        description.as_str()
+    );
+
+    json(
+        &workspace,
+        &["compact", "--closed-before", &cutoff],
+        "compact_level_1",
+    );
+    let issue = show(&workspace, &login, "show_level_1");
+    assert_eq!(
+        issue["description"],
+        "Login fails on Safari. Set SameSite on the session cookie."
+    );
+    assert_eq!(issue["compaction_level"], 1);
+    assert_eq!(issue["original_size"], description.len());
+    let history = workspace
+        .root
+        .join(".beads")
+        .join("history")
+        .join(format!("{login}.jsonl"));
+    let saved = std::fs::read_to_string(&history).expect("history file");
+    let record: Value = serde_json::from_str(saved.lines().next().expect("line")).expect("json");
+    assert_eq!(record["level"], 1);
+    assert_eq!(record["original"]["description"], description.as_str());
+
+    let text = run_br(&workspace, ["show", &login], "show_text");
+    assert!(
+        text.stdout.contains("Compacted: level 1"),
+        "{}",
+        text.stdout
+    );
+
+    // Already at level 1: nothing more to do at that level.
+    let again = json(
+        &workspace,
+        &["compact", "--closed-before", &cutoff],
+        "compact_again",
+    );
+    assert_eq!(again["compacted"].as_array().map(Vec::len), Some(0));
+
+    json(
+        &workspace,
+        &["compact", "--closed-before", &cutoff, "--level", "2"],
+        "compact_level_2",
+    );
+    let issue = show(&workspace, &login, "show_level_2");
+    assert!(issue["description"].is_null(), "{issue}");
+    assert_eq!(issue["close_reason"], "Fixed in 1.2");
+    assert_eq!(issue["original_size"], description.len());
+
+    let undo = run_br(&workspace, ["undo"], "undo");
+    assert!(undo.status.success(), "undo failed: {}", undo.stderr);
+    let issue = show(&workspace, &login, "show_undone");
+    assert_eq!(issue["compaction_level"], 1);
+    assert_eq!(
+        issue["description"],
+        "Login fails on Safari. Set SameSite on the session cookie."
+    );
+}
+
+#[test]
+fn e2e_compact_external_summarizer() {
+    let _log = common::test_log("e2e_compact_external_summarizer");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let id = closed_issue(&workspace, "Tune cache", &"Cache misses spike. ".repeat(20));
+    let cutoff = chrono::Utc::now().to_rfc3339();
+
+    let failing = run_br(
+        &workspace,
+        [
+            "compact",
+            "--closed-before",
+            &cutoff,
+            "--summarizer",
+            "exit 3",
+        ],
+        "summarizer_fails",
+    );
+    assert!(!failing.status.success());
+    assert!(failing.stderr.contains("summarizer"), "{}", failing.stderr);
+    assert_eq!(
+        show(&workspace, &id, "show_unchanged")["compaction_level"],
+        0
+    );
+
+    json(
+        &workspace,
+        &[
+            "compact",
+            "--closed-before",
+            &cutoff,
+            "--summarizer",
+            "printf '%s: ' \"$BR_FIELD\"; head -c 19",
+        ],
+        "compact",
+    );
+    assert_eq!(
+        show(&workspace, &id, "show")["description"],
+        "description: Cache misses spike."
+    );
+}
+
+#[test]
+fn e2e_compact_no_db_rewrites_jsonl() {
+    let _log = common::test_log("e2e_compact_no_db_rewrites_jsonl");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let description = format!("Disk fills up. {}", "Rotate the logs daily. ".repeat(10));
+    let id = closed_issue(&workspace, "Rotate logs", &description);
+    let sync = run_br(&workspace, ["sync", "--flush-only"], "sync_flush");
+    assert!(sync.status.success(), "sync failed: {}", sync.stderr);
+
+    let cutoff = chrono::Utc::now().to_rfc3339();
+    json(
+        &workspace,
+        &["--no-db", "compact", "--closed-before", &cutoff],
+        "compact_no_db",
+    );
+
+    let jsonl = workspace.root.join(".beads").join("issues.jsonl");
+    let exported = std::fs::read_to_string(&jsonl).expect("jsonl");
+    let record: Value = exported
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).expect("json"))
+        .find(|record| record["id"] == id.as_str())
+        .expect("compacted issue in jsonl");
+    assert_eq!(record["compaction_level"], 1, "{record}");
+    assert_eq!(record["original_size"], description.len());
+    assert_eq!(show(&workspace, &id, "show_no_db")["compaction_level"], 1);
+}
@@ -634,6 +634,82 @@ fn e2e_structured_error_invalid_status() {
     );
 }
 
+#[test]
+fn e2e_structured_error_filter_parse() {
+    let _log = common::test_log("e2e_structured_error_filter_parse");
+    let workspace = BrWorkspace::new();
+
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success());
+
+    let result = run_br(
+        &workspace,
+        ["list", "--where", "status:open AND bogus:1", "--json"],
+        "list_where_bad_field",
+    );
+    assert!(!result.status.success());
+    assert_eq!(result.status.code(), Some(4), "exit code should be 4");
+
+    let json = parse_error_json(&result.stderr).expect("should be valid JSON");
+    assert!(verify_error_structure(&json), "missing required fields");
+
+    let error = &json["error"];
+    assert_eq!(error["code"], "FILTER_PARSE_ERROR");
+    assert_eq!(error["context"]["column"], 17);
+    assert!(
+        error["context"]["reason"]
+            .as_str()
+            .unwrap()
+            .contains("unknown field 'bogus'")
+    );
+}
+
+#[test]
+fn e2e_structured_error_invalid_transition() {
+    let _log = common::test_log("e2e_structured_error_invalid_transition");
+    let workspace = BrWorkspace::new();
+
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success());
+
+    let config_path = workspace.root.join(".beads/config.yaml");
+    let mut config = fs::read_to_string(&config_path).unwrap_or_default();
+    config.push_str(
+        "\nstatuses:\n  in_review: active\nworkflow:\n  default:\n    open: [in_progress]\n    in_progress: [in_review]\n    in_review: [closed]\n",
+    );
+    fs::write(&config_path, config).expect("write config");
+
+    let create = run_br(&workspace, ["create", "Reviewed work"], "create");
+    assert!(create.status.success(), "create failed: {}", create.stderr);
+    let id = parse_created_id(&create.stdout);
+
+    let skip = run_br(&workspace, ["close", &id, "--json"], "close_from_open");
+    assert!(!skip.status.success());
+    assert_eq!(skip.status.code(), Some(4), "exit code should be 4");
+
+    let json = parse_error_json(&skip.stderr).expect("should be valid JSON");
+    assert!(verify_error_structure(&json), "missing required fields");
+    let error = &json["error"];
+    assert_eq!(error["code"], "INVALID_TRANSITION");
+    assert_eq!(error["context"]["from"], "open");
+    assert_eq!(error["context"]["to"], "closed");
+    assert_eq!(error["context"]["allowed"][0], "in_progress");
+
+    for status in ["in_progress", "in_review"] {
+        let step = run_br(&workspace, ["update", &id, "-s", status], "advance");
+        assert!(step.status.success(), "update failed: {}", step.stderr);
+    }
+
+    let ready = run_br(&workspace, ["ready", "--json"], "ready_in_review");
+    assert!(ready.status.success(), "ready failed: {}", ready.stderr);
+    let ready_json: Vec<Value> =
+        serde_json::from_str(&extract_json_payload(&ready.stdout)).expect("ready json");
+    assert!(ready_json.iter().any(|issue| issue["id"] == id));
+
+    let close = run_br(&workspace, ["close", &id], "close_from_review");
+    assert!(close.status.success(), "close failed: {}", close.stderr);
+}
+
 #[test]
 fn e2e_structured_error_cycle_detected() {
     let _log = common::test_log("e2e_structured_error_cycle_detected");
@@ -0,0 +1,115 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+use std::time::Duration;
+
+fn json(workspace: &BrWorkspace, args: &[&str], label: &str) -> Value {
+    let mut full = args.to_vec();
+    full.push("--json");
+    let out = run_br(workspace, full, label);
+    assert!(out.status.success(), "{label} failed: {}", out.stderr);
+    serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json")
+}
+
+fn export_path(workspace: &BrWorkspace) -> std::path::PathBuf {
+    let beads = workspace.root.join(".beads");
+    let org = beads.join("issues.org");
+    if org.exists() {
+        org
+    } else {
+        beads.join("issues.jsonl")
+    }
+}
+
+#[test]
+fn e2e_gc_purges_expired_tombstones_for_good() {
+    let _log = common::test_log("e2e_gc_purges_expired_tombstones_for_good");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let old = json(&workspace, &["create", "Old idea"], "create_old")["id"]
+        .as_str()
+        .expect("id")
+        .to_string();
+    let keep = json(&workspace, &["create", "Keep me"], "create_keep")["id"]
+        .as_str()
+        .expect("id")
+        .to_string();
+    for (args, label) in [
+        (vec!["label", "add", old.as_str(), "stale"], "label"),
+        (
+            vec!["comments", "add", old.as_str(), "never mind"],
+            "comment",
+        ),
+        (vec!["dep", "add", keep.as_str(), old.as_str()], "dep"),
+        (vec!["delete", old.as_str(), "--force"], "delete"),
+    ] {
+        let out = run_br(&workspace, args, label);
+        assert!(out.status.success(), "{label} failed: {}", out.stderr);
+    }
+
+    let no_retention = run_br(&workspace, ["gc"], "gc_no_retention");
+    assert!(!no_retention.status.success());
+    assert!(
+        no_retention.stderr.contains("deletions_retention_days"),
+        "{}",
+        no_retention.stderr
+    );
+    let fresh = json(&workspace, &["gc", "--older-than", "1h"], "gc_fresh");
+    assert_eq!(fresh["purged"].as_array().map(Vec::len), Some(0));
+
+    // Keep a pre-purge export around to replay as a stale clone would.
+    let export = export_path(&workspace);
+    let stale_export = std::fs::read_to_string(&export).expect("read export");
+
+    std::thread::sleep(Duration::from_millis(1100));
+    let dry = json(
+        &workspace,
+        &["gc", "--older-than", "1s", "--dry-run"],
+        "gc_dry_run",
+    );
+    assert_eq!(dry["dry_run"], true);
+    assert_eq!(dry["purged"][0]["id"], old.as_str());
+    assert!(
+        run_br(&workspace, ["show", &old], "show_after_dry_run")
+            .status
+            .success()
+    );
+
+    let purged = json(&workspace, &["gc", "--older-than", "1s"], "gc");
+    assert_eq!(purged["purged"][0]["id"], old.as_str(), "{purged}");
+    assert!(
+        !run_br(&workspace, ["show", &old], "show_purged")
+            .status
+            .success()
+    );
+    let kept: Vec<Value> =
+        serde_json::from_value(json(&workspace, &["show", &keep], "show_keep")).expect("array");
+    assert!(
+        kept[0]["dependencies"].as_array().is_none_or(Vec::is_empty),
+        "{}",
+        kept[0]
+    );
+
+    let manifest = std::fs::read_to_string(workspace.root.join(".beads").join("deletions.jsonl"))
+        .expect("manifest");
+    assert!(manifest.contains(&old), "{manifest}");
+    let export_text = std::fs::read_to_string(&export).expect("read export");
+    assert!(!export_text.contains(&old), "{export_text}");
+
+    // An export from before the purge must not bring the issue back.
+    std::fs::write(&export, stale_export).expect("restore stale export");
+    let sync = run_br(
+        &workspace,
+        ["sync", "--import-only", "--force"],
+        "import_stale",
+    );
+    assert!(sync.status.success(), "import failed: {}", sync.stderr);
+    assert!(
+        !run_br(&workspace, ["show", &old], "show_after_import")
+            .status
+            .success()
+    );
+}
@@ -403,3 +403,92 @@ fn e2e_graph_chain_depth() {
     assert_eq!(node_b["depth"], 1, "B should be at depth 1");
     assert_eq!(node_c["depth"], 2, "C should be at depth 2");
 }
+
+#[test]
+fn e2e_graph_export_formats() {
+    let _log = common::test_log("e2e_graph_export_formats");
+    let workspace = BrWorkspace::new();
+
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let epic = run_br(
+        &workspace,
+        ["create", "Release", "--type", "epic"],
+        "create_epic",
+    );
+    let epic_id = parse_created_id(&epic.stdout);
+    let task = run_br(
+        &workspace,
+        ["create", "Build \"it\"", "--parent", &epic_id, "-p", "0"],
+        "create_task",
+    );
+    let task_id = parse_created_id(&task.stdout);
+    let follow = run_br(&workspace, ["create", "Follow-up"], "create_follow");
+    let follow_id = parse_created_id(&follow.stdout);
+    let dep_add = run_br(&workspace, ["dep", "add", &follow_id, &task_id], "dep_add");
+    assert!(
+        dep_add.status.success(),
+        "dep add failed: {}",
+        dep_add.stderr
+    );
+
+    let dot = run_br(
+        &workspace,
+        ["graph", "--all", "--format", "dot"],
+        "graph_dot",
+    );
+    assert!(dot.status.success(), "graph failed: {}", dot.stderr);
+    assert!(dot.stdout.starts_with("digraph beads {"), "{}", dot.stdout);
+    assert!(
+        dot.stdout
+            .contains(&format!("subgraph \"cluster_{epic_id}\""))
+    );
+    assert!(dot.stdout.contains(&format!(
+        "\"{follow_id}\" -> \"{task_id}\" [label=\"blocks\"];"
+    )));
+    assert!(dot.stdout.contains("Build \\\"it\\\""));
+
+    let graphml = run_br(
+        &workspace,
+        ["graph", &epic_id, "--format", "graphml"],
+        "graph_graphml",
+    );
+    assert!(graphml.status.success(), "graph failed: {}", graphml.stderr);
+    assert!(graphml.stdout.contains("<graphml"));
+    assert!(
+        graphml
+            .stdout
+            .contains(&format!("<graph id=\"{epic_id}:\""))
+    );
+    assert!(
+        graphml
+            .stdout
+            .contains(&format!("<node id=\"{follow_id}\">"))
+    );
+
+    let d2 = run_br(&workspace, ["graph", "--all", "--format", "d2"], "graph_d2");
+    assert!(d2.status.success(), "graph failed: {}", d2.stderr);
+    assert!(d2.stdout.contains(&format!("\"{epic_id}_epic\": {{")));
+
+    let mermaid = run_br(
+        &workspace,
+        ["graph", "--all", "--format", "mermaid"],
+        "graph_mermaid",
+    );
+    assert!(mermaid.status.success(), "graph failed: {}", mermaid.stderr);
+    assert!(mermaid.stdout.starts_with("graph LR\n"));
+    assert!(mermaid.stdout.contains("-->|blocks|"));
+
+    let bad = run_br(
+        &workspace,
+        ["graph", "--all", "--format", "svg"],
+        "graph_bad",
+    );
+    assert!(!bad.status.success());
+    assert!(
+        bad.stderr.contains("unknown graph format"),
+        "{}",
+        bad.stderr
+    );
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+use std::fs;
+
+const SHEET: &str = "\
+Key,Summary,Sev,Owner,Tags,Issue Type
+QA-1,\"Crash on save, sometimes\",Blocker,alice,\"ui;crash\",bug
+QA-2,Slow search,Major,bob,perf,task
+QA-3,Odd tooltip,Trivial,,ui,bug
+";
+
+fn import(workspace: &BrWorkspace, extra: &[&str], label: &str) -> (bool, Vec<Value>) {
+    let mut args = vec![
+        "import",
+        "csv",
+        "qa.csv",
+        "--map",
+        "Key=external_ref,Summary=title,Sev=priority,Owner=assignee,Tags=labels",
+        "--values",
+        "Sev: Blocker=0,Major=1",
+        "--label-separator",
+        ";",
+        "--json",
+    ];
+    args.extend_from_slice(extra);
+    let out = run_br(workspace, args, label);
+    let json: Value = serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json");
+    let rows = json["rows"].as_array().expect("rows").clone();
+    (out.status.success(), rows)
+}
+
+fn statuses(rows: &[Value]) -> Vec<&str> {
+    rows.iter()
+        .map(|row| row["status"].as_str().expect("status"))
+        .collect()
+}
+
+#[test]
+fn e2e_import_csv_maps_translates_and_dedups() {
+    let _log = common::test_log("e2e_import_csv_maps_translates_and_dedups");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+    fs::write(workspace.root.join("qa.csv"), SHEET).expect("write csv");
+
+    // The dry run previews every row and writes nothing.
+    let (ok, rows) = import(&workspace, &["--dry-run"], "dry_run");
+    assert!(!ok, "an untranslated severity should fail its row");
+    assert_eq!(statuses(&rows), ["would_create", "would_create", "failed"]);
+    assert_eq!(rows[0]["issue"]["priority"], 0);
+    assert_eq!(
+        rows[0]["issue"]["labels"],
+        serde_json::json!(["ui", "crash"])
+    );
+    assert_eq!(rows[2]["line"], 4);
+    assert!(rows[2]["error"]["message"].is_string(), "{}", rows[2]);
+    let list = run_br(&workspace, ["list", "--json"], "list_empty");
+    let list: Value = serde_json::from_str(&extract_json_payload(&list.stdout)).expect("json");
+    assert_eq!(list.as_array().map(Vec::len), Some(0), "{list}");
+
+    let (ok, rows) = import(&workspace, &[], "import");
+    assert!(!ok);
+    assert_eq!(statuses(&rows), ["created", "created", "failed"]);
+    let id = rows[0]["id"].as_str().expect("id");
+    let show = run_br(&workspace, ["show", id, "--json"], "show");
+    let shown: Vec<Value> =
+        serde_json::from_str(&extract_json_payload(&show.stdout)).expect("json");
+    assert_eq!(shown[0]["title"], "Crash on save, sometimes");
+    assert_eq!(shown[0]["issue_type"], "bug");
+    assert_eq!(shown[0]["assignee"], "alice");
+    assert_eq!(shown[0]["external_ref"], "QA-1");
+
+    // Rows already imported are recognized by their external ref.
+    let (_, rows) = import(&workspace, &[], "reimport");
+    assert_eq!(statuses(&rows), ["exists", "exists", "failed"]);
+    assert_eq!(rows[0]["id"], id);
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+
+fn json(out: &common::cli::BrRun) -> Value {
+    serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json")
+}
+
+fn ready_ids(workspace: &BrWorkspace, actor: &str, label: &str) -> Vec<String> {
+    let out = run_br(workspace, ["--actor", actor, "ready", "--json"], label);
+    assert!(out.status.success(), "ready failed: {}", out.stderr);
+    json(&out)
+        .as_array()
+        .expect("ready array")
+        .iter()
+        .map(|issue| issue["id"].as_str().expect("id").to_string())
+        .collect()
+}
+
+#[test]
+fn e2e_lease_hides_work_and_expires() {
+    let _log = common::test_log("e2e_lease_hides_work_and_expires");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let create = run_br(&workspace, ["create", "Leased work", "--json"], "create");
+    assert!(create.status.success(), "create failed: {}", create.stderr);
+    let id = json(&create)["id"].as_str().expect("id").to_string();
+
+    let claim = run_br(
+        &workspace,
+        ["--actor", "alice", "claim", &id, "--ttl", "2s", "--json"],
+        "claim",
+    );
+    assert!(claim.status.success(), "claim failed: {}", claim.stderr);
+    let claimed = json(&claim);
+    assert_eq!(claimed["status"], "in_progress");
+    assert_eq!(claimed["assignee"], "alice");
+    assert_eq!(claimed["lease"]["ttl_seconds"], 2);
+
+    assert!(ready_ids(&workspace, "alice", "ready_alice").contains(&id));
+    assert!(!ready_ids(&workspace, "bob", "ready_bob").contains(&id));
+
+    let stolen = run_br(
+        &workspace,
+        ["--actor", "bob", "heartbeat", &id],
+        "heartbeat_bob",
+    );
+    assert!(!stolen.status.success());
+    assert!(stolen.stderr.contains("held by alice"), "{}", stolen.stderr);
+
+    let beat = run_br(
+        &workspace,
+        ["--actor", "alice", "heartbeat", &id, "--json"],
+        "heartbeat_alice",
+    );
+    assert!(beat.status.success(), "heartbeat failed: {}", beat.stderr);
+    assert_eq!(json(&beat)["actor"], "alice");
+
+    std::thread::sleep(std::time::Duration::from_millis(2500));
+
+    let leases = run_br(&workspace, ["leases", "--json"], "leases");
+    assert!(leases.status.success(), "leases failed: {}", leases.stderr);
+    let leases = json(&leases);
+    assert_eq!(leases["leases"].as_array().expect("leases").len(), 0);
+    assert_eq!(leases["expired"][0]["issue_id"], id.as_str());
+    assert_eq!(leases["expired"][0]["released"], true);
+
+    let show = run_br(&workspace, ["show", &id, "--json"], "show");
+    assert!(show.status.success(), "show failed: {}", show.stderr);
+    let issue = &json(&show)[0];
+    assert_eq!(issue["status"], "open");
+    assert!(issue["assignee"].is_null());
+    assert!(ready_ids(&workspace, "bob", "ready_bob_after").contains(&id));
+
+    let late = run_br(
+        &workspace,
+        ["--actor", "alice", "heartbeat", &id],
+        "heartbeat_late",
+    );
+    assert!(!late.status.success());
+    assert!(late.stderr.contains("no active lease"), "{}", late.stderr);
+}
+
+#[test]
+fn e2e_claim_rejects_bad_ttl() {
+    let _log = common::test_log("e2e_claim_rejects_bad_ttl");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let create = run_br(&workspace, ["create", "Work", "--json"], "create");
+    let id = json(&create)["id"].as_str().expect("id").to_string();
+
+    let bad = run_br(&workspace, ["claim", &id, "--ttl", "soon"], "claim_bad");
+    assert!(!bad.status.success());
+    assert!(bad.stderr.contains("invalid duration"), "{}", bad.stderr);
+
+    let plain = run_br(&workspace, ["claim", &id], "claim_plain");
+    assert!(plain.status.success(), "claim failed: {}", plain.stderr);
+    let leases = run_br(&workspace, ["leases"], "leases");
+    assert!(
+        leases.stdout.contains("No active leases."),
+        "{}",
+        leases.stdout
+    );
+}
+
+#[test]
+fn e2e_ready_hands_back_expired_claims() {
+    let _log = common::test_log("e2e_ready_hands_back_expired_claims");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let create = run_br(&workspace, ["create", "Lapsed work", "--json"], "create");
+    let id = json(&create)["id"].as_str().expect("id").to_string();
+    let claim = run_br(
+        &workspace,
+        ["--actor", "alice", "claim", &id, "--ttl", "1s"],
+        "claim",
+    );
+    assert!(claim.status.success(), "claim failed: {}", claim.stderr);
+    assert!(!ready_ids(&workspace, "bob", "ready_before").contains(&id));
+
+    std::thread::sleep(std::time::Duration::from_millis(1500));
+
+    assert!(ready_ids(&workspace, "bob", "ready_after").contains(&id));
+    let show = run_br(&workspace, ["show", &id, "--json"], "show");
+    let issue = &json(&show)[0];
+    assert_eq!(issue["status"], "open");
+    assert!(issue["assignee"].is_null());
+}
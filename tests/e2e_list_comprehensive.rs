@@ -8,6 +8,7 @@
 //! - Label filtering (--label AND, --label-any OR)
 //! - Assignee filtering (--assignee, --unassigned)
 //! - Text search (--title-contains, --desc-contains)
+//! - Filter expressions (--where)
 //! - Sorting (--sort, --reverse)
 //! - Limiting (--limit)
 //! - Deferred and overdue filtering (--deferred, --overdue)
@@ -710,6 +711,54 @@ fn e2e_list_desc_contains() {
     assert_eq!(issues[0]["id"], ids[5]);
 }
 
+// =============================================================================
+// FILTER EXPRESSION TESTS
+// =============================================================================
+
+#[test]
+fn e2e_list_where_expression() {
+    let _log = common::test_log("e2e_list_where_expression");
+    let (workspace, ids) = setup_diverse_workspace();
+
+    let list = run_br(
+        &workspace,
+        [
+            "list",
+            "--where",
+            "(label:core OR priority<=0) AND NOT assignee:bob",
+            "--json",
+        ],
+        "list_where",
+    );
+    assert!(list.status.success(), "list failed: {}", list.stderr);
+
+    let payload = extract_json_payload(&list.stdout);
+    let issues: Vec<Value> = serde_json::from_str(&payload).expect("json parse");
+    let mut found: Vec<&str> = issues.iter().filter_map(|i| i["id"].as_str()).collect();
+    found.sort_unstable();
+    let mut expected = vec![ids[0].as_str(), ids[1].as_str()];
+    expected.sort_unstable();
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn e2e_list_where_status_includes_closed() {
+    let _log = common::test_log("e2e_list_where_status_includes_closed");
+    let (workspace, ids) = setup_diverse_workspace();
+
+    let list = run_br(
+        &workspace,
+        ["list", "--where", "status:closed", "--json"],
+        "list_where_closed",
+    );
+    assert!(list.status.success(), "list failed: {}", list.stderr);
+
+    let payload = extract_json_payload(&list.stdout);
+    let issues: Vec<Value> = serde_json::from_str(&payload).expect("json parse");
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["id"], ids[3]);
+}
+
 // =============================================================================
 // SORTING TESTS
 // =============================================================================
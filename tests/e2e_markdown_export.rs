@@ -0,0 +1,101 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+use std::fs;
+
+fn create(workspace: &BrWorkspace, args: &[&str]) -> String {
+    let mut full = vec!["create"];
+    full.extend_from_slice(args);
+    full.push("--json");
+    let out = run_br(workspace, full, "create");
+    assert!(out.status.success(), "create failed: {}", out.stderr);
+    let json: Value = serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json");
+    json["id"].as_str().expect("id").to_string()
+}
+
+#[test]
+fn e2e_markdown_export_round_trips_hand_edits() {
+    let _log = common::test_log("e2e_markdown_export_round_trips_hand_edits");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+    let beads = workspace.root.join(".beads");
+    fs::write(
+        beads.join("metadata.json"),
+        r#"{"database": "beads.db", "jsonl_export": "issues"}"#,
+    )
+    .expect("write metadata override");
+
+    let login = create(
+        &workspace,
+        &[
+            "Fix login",
+            "-d",
+            "Login fails on Safari.\n## Not a heading",
+        ],
+    );
+    let cookie = create(&workspace, &["Set SameSite", "-l", "backend"]);
+    for (args, label) in [
+        (vec!["dep", "add", &login, &cookie], "dep_add"),
+        (
+            vec!["comments", "add", &login, "Reproduced on 17.2"],
+            "comment",
+        ),
+        (vec!["sync", "--flush-only"], "flush"),
+    ] {
+        let out = run_br(&workspace, args, label);
+        assert!(out.status.success(), "{label} failed: {}", out.stderr);
+    }
+
+    let path = beads.join("issues").join(format!("{login}.md"));
+    let text = fs::read_to_string(&path).expect("markdown file");
+    assert!(text.starts_with("---\n"), "{text}");
+    assert!(text.contains("title: Fix login"), "{text}");
+    assert!(text.contains(&format!("depends_on_id: {cookie}")), "{text}");
+    assert!(
+        text.contains("## Description\n\nLogin fails on Safari.\n\\## Not a heading\n"),
+        "{text}"
+    );
+    assert!(text.contains("## Comments\n\n### #"), "{text}");
+    assert!(text.contains("Reproduced on 17.2"), "{text}");
+    assert!(beads.join("issues").join(format!("{cookie}.md")).is_file());
+
+    // Edit by hand: rename the issue and bump its timestamp.
+    let edited: String = text
+        .lines()
+        .map(|line| {
+            if line.starts_with("title:") {
+                "title: Fix Safari login".to_string()
+            } else if line.starts_with("updated_at:") {
+                "updated_at: 2030-01-01T00:00:00Z".to_string()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n";
+    fs::write(&path, edited).expect("edit markdown");
+
+    let import = run_br(&workspace, ["sync", "--import-only"], "import");
+    assert!(import.status.success(), "import failed: {}", import.stderr);
+
+    let show = run_br(&workspace, ["show", &login, "--json"], "show");
+    assert!(show.status.success(), "show failed: {}", show.stderr);
+    let issue: Vec<Value> =
+        serde_json::from_str(&extract_json_payload(&show.stdout)).expect("json");
+    assert_eq!(issue[0]["title"], "Fix Safari login");
+    assert_eq!(
+        issue[0]["description"],
+        "Login fails on Safari.\n## Not a heading"
+    );
+    assert_eq!(issue[0]["comments"][0]["text"], "Reproduced on 17.2");
+
+    let doctor = run_br(&workspace, ["doctor", "--json"], "doctor");
+    assert!(
+        doctor.stdout.contains("markdown files"),
+        "{}",
+        doctor.stdout
+    );
+}
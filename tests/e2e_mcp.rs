@@ -0,0 +1,128 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br, run_br_with_stdin};
+use serde_json::{Value, json};
+
+fn mcp_session(workspace: &BrWorkspace, requests: &[Value], label: &str) -> Vec<Value> {
+    let input: String = requests.iter().map(|r| format!("{r}\n")).collect();
+    let run = run_br_with_stdin(workspace, ["mcp"], &input, label);
+    assert!(run.status.success(), "mcp failed: {}", run.stderr);
+    run.stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each stdout line is JSON-RPC"))
+        .collect()
+}
+
+fn tool_call(id: u64, name: &str, arguments: &Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": arguments },
+    })
+}
+
+#[test]
+fn e2e_mcp_session_creates_and_claims_issues() {
+    let _log = common::test_log("e2e_mcp_session_creates_and_claims_issues");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let responses = mcp_session(
+        &workspace,
+        &[
+            json!({
+                "jsonrpc": "2.0",
+                "id": 0,
+                "method": "initialize",
+                "params": { "protocolVersion": "2025-06-18", "capabilities": {} },
+            }),
+            json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+            tool_call(
+                1,
+                "create",
+                &json!({ "title": "Agent task", "priority": 1 }),
+            ),
+            tool_call(2, "ready", &json!({})),
+        ],
+        "mcp_create",
+    );
+    // The notification gets no reply.
+    assert_eq!(responses.len(), 3);
+    assert_eq!(responses[0]["result"]["serverInfo"]["name"], "obr");
+    let created = &responses[1]["result"]["structuredContent"];
+    let id = created["id"].as_str().expect("id").to_string();
+    assert_eq!(
+        responses[2]["result"]["structuredContent"]["issues"][0]["id"],
+        id.as_str()
+    );
+
+    let responses = mcp_session(
+        &workspace,
+        &[
+            tool_call(1, "claim", &json!({ "id": id })),
+            tool_call(2, "claim", &json!({ "id": "nope-123" })),
+        ],
+        "mcp_claim",
+    );
+    assert_eq!(responses[0]["result"]["isError"], false);
+    assert_eq!(responses[1]["result"]["isError"], true);
+    assert_eq!(
+        responses[1]["result"]["structuredContent"]["error"]["code"],
+        "ISSUE_NOT_FOUND"
+    );
+
+    // Changes are flushed like any CLI mutation and visible to the CLI.
+    let show = run_br(&workspace, ["show", &id, "--json"], "show");
+    assert!(show.status.success(), "show failed: {}", show.stderr);
+    let json: Value = serde_json::from_str(&extract_json_payload(&show.stdout)).expect("json");
+    let issue = if json.is_array() { &json[0] } else { &json };
+    assert_eq!(issue["status"], "in_progress");
+    let jsonl = std::fs::read_to_string(workspace.root.join(".beads/issues.jsonl")).expect("jsonl");
+    assert!(jsonl.contains("Agent task"));
+}
+
+#[test]
+fn e2e_mcp_reads_saved_query_resource() {
+    let _log = common::test_log("e2e_mcp_reads_saved_query_resource");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+    let create = run_br(
+        &workspace,
+        ["create", "Urgent bug", "-t", "bug", "-p", "0"],
+        "create",
+    );
+    assert!(create.status.success(), "create failed: {}", create.stderr);
+    let save = run_br(
+        &workspace,
+        ["query", "save", "bugs", "--type", "bug"],
+        "save_query",
+    );
+    assert!(save.status.success(), "save failed: {}", save.stderr);
+
+    let responses = mcp_session(
+        &workspace,
+        &[
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "resources/list" }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "resources/read",
+                "params": { "uri": "beads://queries/bugs" },
+            }),
+        ],
+        "mcp_resources",
+    );
+    assert_eq!(
+        responses[0]["result"]["resources"][0]["uri"],
+        "beads://queries/bugs"
+    );
+    let text = responses[1]["result"]["contents"][0]["text"]
+        .as_str()
+        .expect("text");
+    let body: Value = serde_json::from_str(text).expect("json");
+    assert_eq!(body["query"]["name"], "bugs");
+    assert_eq!(body["issues"][0]["title"], "Urgent bug");
+}
@@ -0,0 +1,213 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn git(root: &Path, args: &[&str]) -> Output {
+    Command::new("git")
+        .current_dir(root)
+        .args(args)
+        .output()
+        .expect("run git")
+}
+
+fn git_ok(root: &Path, args: &[&str]) {
+    let out = git(root, args);
+    assert!(
+        out.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+}
+
+fn create(workspace: &BrWorkspace, title: &str) -> String {
+    let out = run_br(workspace, ["create", title, "--json"], "create");
+    assert!(out.status.success(), "create failed: {}", out.stderr);
+    let json: Value = serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json");
+    json["id"].as_str().expect("id").to_string()
+}
+
+/// Rewrite the committed export, editing (or dropping) issues by id.
+fn edit_export(root: &Path, edit: impl Fn(&mut Value) -> bool) {
+    let path = root.join(".beads").join("issues.jsonl");
+    let mut out = String::new();
+    for line in fs::read_to_string(&path).expect("export").lines() {
+        let mut issue: Value = serde_json::from_str(line).expect("issue json");
+        if edit(&mut issue) {
+            out.push_str(&serde_json::to_string(&issue).expect("serialize"));
+            out.push('\n');
+        }
+    }
+    fs::write(&path, out).expect("write export");
+}
+
+fn exported(root: &Path) -> Vec<Value> {
+    let text = fs::read_to_string(root.join(".beads").join("issues.jsonl")).expect("export");
+    assert!(!text.contains("<<<<<<<"), "conflict markers left:\n{text}");
+    text.lines()
+        .map(|line| serde_json::from_str(line).expect("issue json"))
+        .collect()
+}
+
+fn setup() -> (BrWorkspace, String, String) {
+    let workspace = BrWorkspace::new();
+    let root = workspace.root.clone();
+    git_ok(&root, &["init", "-b", "main"]);
+    git_ok(&root, &["config", "user.email", "test@example.com"]);
+    git_ok(&root, &["config", "user.name", "Test User"]);
+    let driver = format!(
+        "'{}' merge-driver %O %A %B %P",
+        assert_cmd::cargo::cargo_bin!("obr").display()
+    );
+    git_ok(&root, &["config", "merge.beads.driver", &driver]);
+    fs::write(
+        root.join(".gitattributes"),
+        ".beads/issues.jsonl merge=beads\n",
+    )
+    .expect("attrs");
+
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+    // JSONL export, so the test can edit issues line by line.
+    fs::write(
+        root.join(".beads").join("metadata.json"),
+        r#"{"database": "beads.db", "jsonl_export": "issues.jsonl"}"#,
+    )
+    .expect("write metadata override");
+    let alpha = create(&workspace, "Alpha");
+    let beta = create(&workspace, "Beta");
+
+    git_ok(&root, &["add", ".gitattributes", ".beads/issues.jsonl"]);
+    git_ok(&root, &["commit", "-m", "base"]);
+    (workspace, alpha, beta)
+}
+
+#[test]
+fn e2e_merge_driver_merges_fields_of_one_issue() {
+    let _log = common::test_log("e2e_merge_driver_merges_fields_of_one_issue");
+    let (workspace, alpha, _beta) = setup();
+    let root = workspace.root.as_path();
+
+    git_ok(root, &["checkout", "-b", "feature"]);
+    edit_export(root, |issue| {
+        if issue["id"] == alpha.as_str() {
+            issue["title"] = "Alpha renamed".into();
+            issue["updated_at"] = "2030-01-01T00:00:00Z".into();
+        }
+        true
+    });
+    git_ok(root, &["commit", "-am", "rename alpha"]);
+
+    git_ok(root, &["checkout", "main"]);
+    edit_export(root, |issue| {
+        if issue["id"] == alpha.as_str() {
+            issue["priority"] = 0.into();
+            issue["updated_at"] = "2030-01-02T00:00:00Z".into();
+        }
+        true
+    });
+    git_ok(root, &["commit", "-am", "raise alpha"]);
+
+    // Both edits touch the same line, which a line merge cannot resolve.
+    git_ok(root, &["merge", "--no-edit", "feature"]);
+
+    let issues = exported(root);
+    assert_eq!(issues.len(), 2);
+    let merged = issues
+        .iter()
+        .find(|issue| issue["id"] == alpha.as_str())
+        .expect("alpha");
+    assert_eq!(merged["title"], "Alpha renamed");
+    assert_eq!(merged["priority"], 0);
+}
+
+#[test]
+fn e2e_merge_driver_reports_delete_vs_modify() {
+    let _log = common::test_log("e2e_merge_driver_reports_delete_vs_modify");
+    let (workspace, _alpha, beta) = setup();
+    let root = workspace.root.as_path();
+
+    git_ok(root, &["checkout", "-b", "feature"]);
+    edit_export(root, |issue| issue["id"] != beta.as_str());
+    git_ok(root, &["commit", "-am", "drop beta"]);
+
+    git_ok(root, &["checkout", "main"]);
+    edit_export(root, |issue| {
+        if issue["id"] == beta.as_str() {
+            issue["title"] = "Beta edited".into();
+            issue["updated_at"] = "2030-01-01T00:00:00Z".into();
+        }
+        true
+    });
+    git_ok(root, &["commit", "-am", "edit beta"]);
+
+    let merge = git(root, &["merge", "--no-edit", "feature"]);
+    assert!(!merge.status.success(), "merge should report a conflict");
+    let stderr = String::from_utf8_lossy(&merge.stderr);
+    assert!(stderr.contains(&beta), "{stderr}");
+
+    // The modified version is kept and the file stays parseable.
+    let issues = exported(root);
+    assert!(
+        issues
+            .iter()
+            .any(|issue| issue["id"] == beta.as_str() && issue["title"] == "Beta edited")
+    );
+}
+
+#[test]
+fn e2e_merge_driver_reports_same_field_conflict() {
+    let _log = common::test_log("e2e_merge_driver_reports_same_field_conflict");
+    let (workspace, alpha, _beta) = setup();
+    let root = workspace.root.as_path();
+
+    git_ok(root, &["checkout", "-b", "feature"]);
+    edit_export(root, |issue| {
+        if issue["id"] == alpha.as_str() {
+            issue["title"] = "Alpha from feature".into();
+            issue["updated_at"] = "2030-01-01T00:00:00Z".into();
+        }
+        true
+    });
+    git_ok(root, &["commit", "-am", "feature title"]);
+
+    git_ok(root, &["checkout", "main"]);
+    edit_export(root, |issue| {
+        if issue["id"] == alpha.as_str() {
+            issue["title"] = "Alpha from main".into();
+            issue["updated_at"] = "2030-01-02T00:00:00Z".into();
+        }
+        true
+    });
+    git_ok(root, &["commit", "-am", "main title"]);
+
+    // The newer title is written, but git must not take it silently.
+    let merge = git(root, &["merge", "--no-edit", "feature"]);
+    assert!(!merge.status.success(), "merge should report a conflict");
+    let stderr = String::from_utf8_lossy(&merge.stderr);
+    assert!(
+        stderr.contains(&alpha) && stderr.contains("title"),
+        "{stderr}"
+    );
+    let title = |issues: Vec<Value>| {
+        issues
+            .into_iter()
+            .find(|issue| issue["id"] == alpha.as_str())
+            .expect("alpha")["title"]
+            .clone()
+    };
+    assert_eq!(title(exported(root)), "Alpha from main");
+    git_ok(root, &["merge", "--abort"]);
+
+    // --keep-newer accepts the newer value.
+    let driver = format!(
+        "'{}' merge-driver --keep-newer %O %A %B %P",
+        assert_cmd::cargo::cargo_bin!("obr").display()
+    );
+    git_ok(root, &["config", "merge.beads.driver", &driver]);
+    git_ok(root, &["merge", "--no-edit", "feature"]);
+    assert_eq!(title(exported(root)), "Alpha from main");
+}
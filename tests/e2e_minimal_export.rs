@@ -0,0 +1,81 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+use std::fs;
+
+fn create(workspace: &BrWorkspace, title: &str) -> String {
+    let out = run_br(workspace, ["create", title, "--json"], "create");
+    assert!(out.status.success(), "create failed: {}", out.stderr);
+    let json: Value = serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json");
+    json["id"].as_str().expect("id").to_string()
+}
+
+/// Split an Org export into its header and its level-1 entries.
+fn entries(text: &str) -> (String, Vec<String>) {
+    let mut parts = text
+        .split_inclusive('\n')
+        .fold(vec![String::new()], |mut parts, line| {
+            if line.starts_with("* ") {
+                parts.push(String::new());
+            }
+            parts.last_mut().expect("part").push_str(line);
+            parts
+        });
+    let header = parts.remove(0);
+    (header, parts)
+}
+
+fn titles(entries: &[String]) -> Vec<String> {
+    entries
+        .iter()
+        .map(|entry| entry.lines().next().expect("heading").to_string())
+        .map(|heading| heading.rsplit("] ").next().expect("title").to_string())
+        .collect()
+}
+
+#[test]
+fn e2e_reexport_keeps_hand_order() {
+    let _log = common::test_log("e2e_reexport_keeps_hand_order");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+    fs::write(
+        workspace.root.join(".beads").join("metadata.json"),
+        r#"{"database": "beads.db", "jsonl_export": "issues.org"}"#,
+    )
+    .expect("write metadata override");
+
+    create(&workspace, "Alpha");
+    let beta = create(&workspace, "Beta");
+    create(&workspace, "Gamma");
+
+    // Reverse the ID-sorted headings by hand and record the new order.
+    let path = workspace.root.join(".beads").join("issues.org");
+    let (header, mut hand) = entries(&fs::read_to_string(&path).expect("org export"));
+    hand.reverse();
+    fs::write(&path, format!("{header}{}", hand.concat())).expect("edit org");
+    let import = run_br(&workspace, ["sync", "--import-only"], "import");
+    assert!(import.status.success(), "import failed: {}", import.stderr);
+
+    let update = run_br(&workspace, ["update", &beta, "--priority", "0"], "update");
+    assert!(update.status.success(), "update failed: {}", update.stderr);
+    let text = fs::read_to_string(&path).expect("org export");
+    let (_, spliced) = entries(&text);
+    assert_eq!(titles(&spliced), titles(&hand), "{text}");
+    // Only Beta's entry changed.
+    for (before, after) in hand.iter().zip(&spliced) {
+        if before.contains("Beta") {
+            assert!(after.starts_with("* TODO [#A] Beta"), "{after}");
+        } else {
+            assert_eq!(before, after);
+        }
+    }
+
+    // Flushing again without changes leaves the file byte-identical.
+    for _ in 0..2 {
+        let flush = run_br(&workspace, ["sync", "--flush-only", "--force"], "flush");
+        assert!(flush.status.success(), "flush failed: {}", flush.stderr);
+        assert_eq!(fs::read_to_string(&path).expect("org export"), text);
+    }
+}
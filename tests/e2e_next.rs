@@ -0,0 +1,124 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+
+fn create(workspace: &BrWorkspace, args: &[&str], label: &str) -> String {
+    let mut full = vec!["create"];
+    full.extend_from_slice(args);
+    full.push("--json");
+    let out = run_br(workspace, full, label);
+    assert!(out.status.success(), "create failed: {}", out.stderr);
+    let json: Value = serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json");
+    json["id"].as_str().expect("id").to_string()
+}
+
+fn next_json(workspace: &BrWorkspace, args: &[&str], label: &str) -> Value {
+    let mut full = vec!["next", "--json"];
+    full.extend_from_slice(args);
+    let out = run_br(workspace, full, label);
+    assert!(out.status.success(), "next failed: {}", out.stderr);
+    serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json")
+}
+
+#[test]
+fn e2e_next_claims_in_rank_order() {
+    let _log = common::test_log("e2e_next_claims_in_rank_order");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let backlog = create(&workspace, &["Backlog chore", "-p", "3"], "backlog");
+    let urgent = create(
+        &workspace,
+        &["Urgent bug", "-p", "0", "-t", "bug", "-l", "auth"],
+        "urgent",
+    );
+    let blocker = create(&workspace, &["Blocker", "-p", "2"], "blocker");
+    let blocked = create(&workspace, &["Blocked", "-p", "0"], "blocked");
+    let dep = run_br(&workspace, ["dep", "add", &blocked, &blocker], "dep");
+    assert!(dep.status.success(), "dep add failed: {}", dep.stderr);
+
+    // Without --claim nothing changes.
+    let peek = next_json(&workspace, &[], "peek");
+    assert_eq!(peek["id"], urgent.as_str());
+    assert_eq!(peek["status"], "open");
+    let again = next_json(&workspace, &[], "peek_again");
+    assert_eq!(again["id"], urgent.as_str());
+
+    let first = next_json(&workspace, &["--claim", "--sort", "priority"], "claim_1");
+    assert_eq!(first["id"], urgent.as_str());
+    assert_eq!(first["status"], "in_progress");
+    assert!(first["assignee"].is_string());
+    assert_eq!(first["labels"], serde_json::json!(["auth"]), "{first}");
+
+    let second = next_json(&workspace, &["--claim", "--sort", "priority"], "claim_2");
+    assert_eq!(second["id"], blocker.as_str());
+    let third = next_json(&workspace, &["--claim", "--sort", "priority"], "claim_3");
+    assert_eq!(third["id"], backlog.as_str());
+
+    let none = run_br(&workspace, ["next", "--claim", "--json"], "claim_none");
+    assert_eq!(none.status.code(), Some(9), "stderr: {}", none.stderr);
+    assert!(none.stderr.contains("NO_READY_WORK"), "{}", none.stderr);
+}
+
+#[test]
+fn e2e_next_filters() {
+    let _log = common::test_log("e2e_next_filters");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    create(
+        &workspace,
+        &["Frontend", "-p", "0", "-l", "frontend"],
+        "frontend",
+    );
+    let backend = create(
+        &workspace,
+        &["Backend", "-p", "2", "-l", "backend"],
+        "backend",
+    );
+    let bug = create(&workspace, &["Bug", "-p", "4", "-t", "bug"], "bug");
+
+    let labelled = next_json(&workspace, &["-l", "backend"], "by_label");
+    assert_eq!(labelled["id"], backend.as_str());
+    let typed = next_json(&workspace, &["-t", "bug"], "by_type");
+    assert_eq!(typed["id"], bug.as_str());
+
+    let text = run_br(&workspace, ["next", "-l", "backend", "--claim"], "text");
+    assert!(text.status.success(), "next failed: {}", text.stderr);
+    assert!(text.stdout.contains(&backend), "{}", text.stdout);
+
+    let missing = run_br(&workspace, ["next", "-l", "nope"], "no_match");
+    assert_eq!(missing.status.code(), Some(9), "stderr: {}", missing.stderr);
+}
+
+#[test]
+fn e2e_next_claims_lapsed_lease() {
+    let _log = common::test_log("e2e_next_claims_lapsed_lease");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let id = create(&workspace, &["Abandoned work"], "create");
+    let claim = run_br(
+        &workspace,
+        ["--actor", "alice", "claim", &id, "--ttl", "1s"],
+        "claim",
+    );
+    assert!(claim.status.success(), "claim failed: {}", claim.stderr);
+    let none = run_br(
+        &workspace,
+        ["--actor", "bob", "next", "--claim", "--json"],
+        "claim_held",
+    );
+    assert_eq!(none.status.code(), Some(9), "stderr: {}", none.stderr);
+
+    std::thread::sleep(std::time::Duration::from_millis(1500));
+
+    let taken = next_json(&workspace, &["--claim", "--actor", "bob"], "claim_lapsed");
+    assert_eq!(taken["id"], id.as_str());
+    assert_eq!(taken["status"], "in_progress");
+    assert_eq!(taken["assignee"], "bob");
+}
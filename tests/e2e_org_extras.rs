@@ -0,0 +1,204 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br, run_br_with_env};
+use serde_json::Value;
+use std::fs;
+
+#[test]
+fn e2e_org_hand_edits_survive_reexport() {
+    let _log = common::test_log("e2e_org_hand_edits_survive_reexport");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let create = run_br(
+        &workspace,
+        ["create", "Fix login", "--due", "2026-02-01", "--json"],
+        "create",
+    );
+    assert!(create.status.success(), "create failed: {}", create.stderr);
+    let created: Value = serde_json::from_str(&extract_json_payload(&create.stdout)).expect("json");
+    let id = created["id"].as_str().expect("id").to_string();
+
+    let path = workspace.root.join(".beads").join("issues.org");
+    let text = fs::read_to_string(&path).expect("org export");
+    assert!(
+        text.contains("\nDEADLINE: <2026-02-01 Sun 09:00>\n"),
+        "{text}"
+    );
+    assert!(text.contains(":DUE_AT: 2026-02-01T09:00:00"), "{text}");
+
+    // Edit the way Emacs would: clock time, add a property and a subheading.
+    let mut edited = String::new();
+    for line in text.lines() {
+        if line.starts_with(":UPDATED_AT:") {
+            edited.push_str(":UPDATED_AT: 2030-01-01T00:00:00+00:00\n");
+            continue;
+        }
+        if line == ":END:" && !edited.contains(":LOGBOOK:") {
+            edited.push_str(":CATEGORY: web\n:END:\n:LOGBOOK:\n");
+            edited.push_str("CLOCK: [2026-01-05 Mon 09:00]--[2026-01-05 Mon 10:30] =>  1:30\n");
+        }
+        edited.push_str(line);
+        edited.push('\n');
+    }
+    edited.push_str("** Links\n- [[https://example.com][ticket]]\n");
+    fs::write(&path, edited).expect("edit org");
+
+    let import = run_br(&workspace, ["sync", "--import-only"], "import");
+    assert!(import.status.success(), "import failed: {}", import.stderr);
+
+    let show = run_br(&workspace, ["show", &id, "--json"], "show_json");
+    assert!(show.status.success(), "show failed: {}", show.stderr);
+    let shown: Vec<Value> =
+        serde_json::from_str(&extract_json_payload(&show.stdout)).expect("json");
+    let extras = &shown[0]["org_extras"];
+    assert_eq!(extras["properties"][0][0], "CATEGORY");
+    assert_eq!(extras["logbook"].as_array().map(Vec::len), Some(1));
+    assert!(
+        extras["subtrees"]
+            .as_str()
+            .is_some_and(|text| text.starts_with("** Links")),
+        "{extras}"
+    );
+
+    let text = run_br(&workspace, ["show", &id], "show_text");
+    assert!(text.stdout.contains("1h30m"), "{}", text.stdout);
+
+    // A later change re-exports the issue with the hand edits intact.
+    let update = run_br(&workspace, ["update", &id, "--priority", "1"], "update");
+    assert!(update.status.success(), "update failed: {}", update.stderr);
+    let flush = run_br(&workspace, ["sync", "--flush-only"], "flush");
+    assert!(flush.status.success(), "flush failed: {}", flush.stderr);
+    let text = fs::read_to_string(&path).expect("org export");
+    for kept in [
+        "DEADLINE: <2026-02-01 Sun 09:00>",
+        ":CATEGORY: web",
+        "CLOCK: [2026-01-05 Mon 09:00]--[2026-01-05 Mon 10:30] =>  1:30",
+        "** Links",
+    ] {
+        assert!(text.contains(kept), "missing {kept}:\n{text}");
+    }
+}
+
+#[test]
+fn e2e_org_planning_survives_timezone_change() {
+    let _log = common::test_log("e2e_org_planning_survives_timezone_change");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    // Written in New York winter time (UTC-5).
+    let create = run_br_with_env(
+        &workspace,
+        [
+            "create",
+            "Ship it",
+            "--due",
+            "2026-02-01T14:30:15Z",
+            "--json",
+        ],
+        [("TZ", "EST5")],
+        "create",
+    );
+    assert!(create.status.success(), "create failed: {}", create.stderr);
+    let created: Value = serde_json::from_str(&extract_json_payload(&create.stdout)).expect("json");
+    let id = created["id"].as_str().expect("id").to_string();
+
+    let path = workspace.root.join(".beads").join("issues.org");
+    let text = fs::read_to_string(&path).expect("org export");
+    assert!(
+        text.contains("\nDEADLINE: <2026-02-01 Sun 09:30>\n"),
+        "{text}"
+    );
+    assert!(
+        text.contains(":DUE_AT: 2026-02-01T09:30:15-05:00\n"),
+        "{text}"
+    );
+
+    // Imported in Tokyo (UTC+9) after an unrelated edit.
+    let edited = text
+        .replace("Ship it", "Ship it today")
+        .lines()
+        .map(|line| {
+            if line.starts_with(":UPDATED_AT:") {
+                ":UPDATED_AT: 2030-01-01T00:00:00+00:00"
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    fs::write(&path, edited + "\n").expect("edit org");
+    let import = run_br_with_env(
+        &workspace,
+        ["sync", "--import-only"],
+        [("TZ", "JST-9")],
+        "import",
+    );
+    assert!(import.status.success(), "import failed: {}", import.stderr);
+
+    let show = run_br(&workspace, ["show", &id, "--json"], "show");
+    assert!(show.status.success(), "show failed: {}", show.stderr);
+    let shown: Vec<Value> =
+        serde_json::from_str(&extract_json_payload(&show.stdout)).expect("json");
+    assert_eq!(shown[0]["title"], "Ship it today");
+    let due = chrono::DateTime::parse_from_rfc3339(shown[0]["due_at"].as_str().expect("due_at"))
+        .expect("rfc3339");
+    assert_eq!(
+        due,
+        chrono::DateTime::parse_from_rfc3339("2026-02-01T14:30:15Z").expect("rfc3339")
+    );
+}
+
+#[test]
+fn e2e_org_deleted_deadline_clears_due() {
+    let _log = common::test_log("e2e_org_deleted_deadline_clears_due");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let create = run_br(
+        &workspace,
+        ["create", "Someday", "--due", "2026-02-01", "--json"],
+        "create",
+    );
+    assert!(create.status.success(), "create failed: {}", create.stderr);
+    let created: Value = serde_json::from_str(&extract_json_payload(&create.stdout)).expect("json");
+    let id = created["id"].as_str().expect("id").to_string();
+
+    // Drop the deadline in Emacs; the stale :DUE_AT: stays in the drawer.
+    let path = workspace.root.join(".beads").join("issues.org");
+    let text = fs::read_to_string(&path).expect("org export");
+    assert!(text.contains(":DUE_AT:"), "{text}");
+    let mut edited = String::new();
+    for line in text.lines() {
+        if line.starts_with("DEADLINE:") {
+            continue;
+        }
+        if line.starts_with(":UPDATED_AT:") {
+            edited.push_str(":UPDATED_AT: 2030-01-01T00:00:00+00:00\n");
+            continue;
+        }
+        edited.push_str(line);
+        edited.push('\n');
+    }
+    fs::write(&path, edited).expect("edit org");
+
+    let import = run_br(&workspace, ["sync", "--import-only"], "import");
+    assert!(import.status.success(), "import failed: {}", import.stderr);
+    let show = run_br(&workspace, ["show", &id, "--json"], "show");
+    assert!(show.status.success(), "show failed: {}", show.stderr);
+    let shown: Vec<Value> =
+        serde_json::from_str(&extract_json_payload(&show.stdout)).expect("json");
+    assert!(shown[0]["due_at"].is_null(), "{}", shown[0]);
+
+    // Nor does a re-export bring it back.
+    let update = run_br(&workspace, ["update", &id, "--priority", "1"], "update");
+    assert!(update.status.success(), "update failed: {}", update.stderr);
+    let flush = run_br(&workspace, ["sync", "--flush-only"], "flush");
+    assert!(flush.status.success(), "flush failed: {}", flush.stderr);
+    let text = fs::read_to_string(&path).expect("org export");
+    assert!(!text.contains("DEADLINE:"), "{text}");
+    assert!(!text.contains(":DUE_AT:"), "{text}");
+}
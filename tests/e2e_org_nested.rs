@@ -0,0 +1,75 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+use std::fs;
+
+fn create(workspace: &BrWorkspace, args: &[&str]) -> String {
+    let mut full = vec!["create"];
+    full.extend_from_slice(args);
+    full.push("--json");
+    let out = run_br(workspace, full, "create");
+    assert!(out.status.success(), "create failed: {}", out.stderr);
+    let json: Value = serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json");
+    json["id"].as_str().expect("id").to_string()
+}
+
+fn parents(workspace: &BrWorkspace, id: &str) -> Vec<String> {
+    let show = run_br(workspace, ["show", id, "--json"], "show");
+    assert!(show.status.success(), "show failed: {}", show.stderr);
+    let shown: Vec<Value> =
+        serde_json::from_str(&extract_json_payload(&show.stdout)).expect("json");
+    shown[0]["dependencies"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|dep| dep["dependency_type"] == "parent-child")
+        .map(|dep| dep["id"].as_str().expect("id").to_string())
+        .collect()
+}
+
+#[test]
+fn e2e_org_nested_export_and_refile() {
+    let _log = common::test_log("e2e_org_nested_export_and_refile");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+    fs::write(
+        workspace.root.join(".beads").join("metadata.json"),
+        r#"{"database": "beads.db", "jsonl_export": "issues.org", "org_nested": true}"#,
+    )
+    .expect("write metadata override");
+
+    let alpha = create(&workspace, &["Alpha epic", "-t", "epic"]);
+    let beta = create(&workspace, &["Beta epic", "-t", "epic"]);
+    let child = create(&workspace, &["Child task", "--parent", &alpha]);
+
+    let path = workspace.root.join(".beads").join("issues.org");
+    let text = fs::read_to_string(&path).expect("org export");
+    let child_start = text.find("** TODO [#C] Child task").expect("nested child");
+    let alpha_start = text.find("* TODO [#C] Alpha epic").expect("alpha");
+    assert!(alpha_start < child_start, "{text}");
+    let beta_start = text.find("* TODO [#C] Beta epic").expect("beta");
+    assert!(child_start < beta_start, "{text}");
+
+    // Refile the child under Beta, as org-refile would.
+    let child_text = &text[child_start..beta_start];
+    let moved = format!(
+        "{}{}{child_text}",
+        &text[..child_start],
+        &text[beta_start..]
+    );
+    fs::write(&path, moved).expect("edit org");
+
+    let import = run_br(&workspace, ["sync", "--import-only"], "import");
+    assert!(import.status.success(), "import failed: {}", import.stderr);
+    assert_eq!(parents(&workspace, &child), vec![beta.clone()]);
+
+    // The next export writes the child under its new parent.
+    let update = run_br(&workspace, ["update", &beta, "--priority", "1"], "update");
+    assert!(update.status.success(), "update failed: {}", update.stderr);
+    let text = fs::read_to_string(&path).expect("org export");
+    let beta_start = text.find("* TODO [#B] Beta epic").expect("beta");
+    let child_start = text.find("** TODO [#C] Child task").expect("nested child");
+    assert!(beta_start < child_start, "{text}");
+}
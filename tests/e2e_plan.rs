@@ -0,0 +1,152 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+
+fn create(workspace: &BrWorkspace, args: &[&str], label: &str) -> String {
+    let mut full = vec!["create"];
+    full.extend_from_slice(args);
+    full.push("--json");
+    let out = run_br(workspace, full, label);
+    assert!(out.status.success(), "create failed: {}", out.stderr);
+    let json: Value = serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json");
+    json["id"].as_str().expect("id").to_string()
+}
+
+fn dep(workspace: &BrWorkspace, args: &[&str], label: &str) {
+    let mut full = vec!["dep", "add"];
+    full.extend_from_slice(args);
+    let out = run_br(workspace, full, label);
+    assert!(out.status.success(), "dep add failed: {}", out.stderr);
+}
+
+fn plan_json(workspace: &BrWorkspace, args: &[&str], label: &str) -> Value {
+    let mut full = vec!["plan", "--json"];
+    full.extend_from_slice(args);
+    let out = run_br(workspace, full, label);
+    assert!(out.status.success(), "plan failed: {}", out.stderr);
+    serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json")
+}
+
+#[test]
+fn e2e_plan_critical_path_and_capacity() {
+    let _log = common::test_log("e2e_plan_critical_path_and_capacity");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let epic = create(&workspace, &["Release", "--type", "epic"], "epic");
+    let design = create(
+        &workspace,
+        &["Design", "-e", "120", "--parent", &epic],
+        "design",
+    );
+    let build = create(
+        &workspace,
+        &["Build", "-e", "480", "--parent", &epic, "--due", "+1d"],
+        "build",
+    );
+    let docs = create(&workspace, &["Docs", "-e", "60", "--parent", &epic], "docs");
+    let ship = create(&workspace, &["Ship", "--parent", &epic], "ship");
+    let unrelated = create(&workspace, &["Unrelated", "-e", "30"], "unrelated");
+    dep(&workspace, &[&build, &design], "build_after_design");
+    dep(&workspace, &[&docs, &design], "docs_after_design");
+    dep(&workspace, &[&ship, &build], "ship_after_build");
+    dep(&workspace, &[&ship, &docs], "ship_after_docs");
+
+    let plan = plan_json(&workspace, &["--epic", &epic], "plan_epic");
+    assert_eq!(plan["epic"], epic.as_str());
+    assert_eq!(
+        plan["critical_path"],
+        serde_json::json!([design, build, ship])
+    );
+    // Ship has no estimate and uses the 60 minute default.
+    assert_eq!(plan["makespan_minutes"], 120 + 480 + 60);
+    assert_eq!(plan["unestimated"], serde_json::json!([ship]));
+
+    let tasks = plan["tasks"].as_array().expect("tasks");
+    assert_eq!(tasks.len(), 4, "epic and unrelated work are not scheduled");
+    assert!(tasks.iter().all(|t| t["id"] != unrelated.as_str()));
+    let docs_task = tasks
+        .iter()
+        .find(|t| t["id"] == docs.as_str())
+        .expect("docs");
+    assert_eq!(docs_task["slack"], 420);
+    assert_eq!(docs_task["critical"], false);
+
+    // 10 hours of work at 8h days can't be done within a calendar day.
+    let at_risk = plan["at_risk"].as_array().expect("at_risk");
+    assert_eq!(at_risk.len(), 1);
+    assert_eq!(at_risk[0]["id"], build.as_str());
+    assert_eq!(at_risk[0]["cause"], "dependencies");
+
+    // One worker has to do docs and build one after the other.
+    let serial = plan_json(
+        &workspace,
+        &["--epic", &epic, "--capacity", "1"],
+        "plan_serial",
+    );
+    assert_eq!(serial["makespan_minutes"], 120 + 480 + 60 + 60);
+    assert!(
+        serial["tasks"]
+            .as_array()
+            .expect("tasks")
+            .iter()
+            .all(|t| t["worker"] == 1)
+    );
+
+    let all = plan_json(&workspace, &[], "plan_all");
+    assert_eq!(all["tasks"].as_array().expect("tasks").len(), 5);
+}
+
+#[test]
+fn e2e_plan_text_and_mermaid() {
+    let _log = common::test_log("e2e_plan_text_and_mermaid");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let first = create(&workspace, &["First: setup", "-e", "60"], "first");
+    let second = create(&workspace, &["Second", "-e", "120"], "second");
+    dep(&workspace, &[&second, &first], "dep");
+
+    let text = run_br(&workspace, ["plan"], "plan_text");
+    assert!(text.status.success(), "plan failed: {}", text.stderr);
+    assert!(text.stdout.contains("Critical chain:"), "{}", text.stdout);
+    assert!(text.stdout.contains(&first));
+    assert!(text.stdout.contains('#'), "critical bars: {}", text.stdout);
+
+    let mermaid = run_br(&workspace, ["plan", "--format", "mermaid"], "plan_mermaid");
+    assert!(mermaid.status.success(), "plan failed: {}", mermaid.stderr);
+    assert!(mermaid.stdout.starts_with("gantt\n"), "{}", mermaid.stdout);
+    assert!(mermaid.stdout.contains("section Critical chain"));
+    assert!(mermaid.stdout.contains(":crit, "));
+    assert!(
+        !mermaid.stdout.contains("First: setup"),
+        "titles are escaped"
+    );
+}
+
+#[test]
+fn e2e_plan_rejects_bad_options() {
+    let _log = common::test_log("e2e_plan_rejects_bad_options");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let zero = run_br(&workspace, ["plan", "--capacity", "0"], "plan_zero");
+    assert!(!zero.status.success());
+    assert!(zero.stderr.contains("capacity"), "{}", zero.stderr);
+
+    let format = run_br(&workspace, ["plan", "--format", "svg"], "plan_format");
+    assert!(!format.status.success());
+    assert!(
+        format.stderr.contains("unknown format"),
+        "{}",
+        format.stderr
+    );
+
+    let empty = run_br(&workspace, ["plan"], "plan_empty");
+    assert!(empty.status.success(), "plan failed: {}", empty.stderr);
+    assert!(empty.stdout.contains("Nothing to schedule"));
+}
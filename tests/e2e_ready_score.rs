@@ -0,0 +1,109 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+fn create(workspace: &BrWorkspace, args: &[&str], label: &str) -> String {
+    let mut full = vec!["create"];
+    full.extend_from_slice(args);
+    full.push("--json");
+    let out = run_br(workspace, full, label);
+    assert!(out.status.success(), "create failed: {}", out.stderr);
+    let json: Value = serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json");
+    json["id"].as_str().expect("id").to_string()
+}
+
+fn ready_json(workspace: &BrWorkspace, args: &[&str], label: &str) -> Vec<Value> {
+    let mut full = vec!["ready", "--json"];
+    full.extend_from_slice(args);
+    let out = run_br(workspace, full, label);
+    assert!(out.status.success(), "ready failed: {}", out.stderr);
+    let json: Value = serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json");
+    json.as_array().expect("ready array").clone()
+}
+
+fn append_config(workspace: &BrWorkspace, yaml: &str) {
+    let mut file = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(workspace.root.join(".beads").join("config.yaml"))
+        .expect("open config.yaml");
+    writeln!(file, "\n{yaml}").expect("write config.yaml");
+}
+
+#[test]
+fn e2e_ready_score_ranks_quick_wins_first() {
+    let _log = common::test_log("e2e_ready_score_ranks_quick_wins_first");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let leaf = create(&workspace, &["Leaf task", "-p", "2", "-e", "480"], "leaf");
+    let quick = create(&workspace, &["Quick win", "-p", "2", "-e", "30"], "quick");
+    for (title, label) in [("Waits A", "dep_a"), ("Waits B", "dep_b")] {
+        let waiting = create(&workspace, &[title, "-p", "3"], label);
+        let dep = run_br(&workspace, ["dep", "add", &waiting, &quick], label);
+        assert!(dep.status.success(), "dep add failed: {}", dep.stderr);
+    }
+
+    let hybrid = ready_json(&workspace, &[], "hybrid");
+    assert_eq!(hybrid[0]["id"], leaf.as_str());
+    assert!(hybrid[0].get("score").is_none(), "{}", hybrid[0]);
+
+    let scored = ready_json(&workspace, &["--sort", "score", "--explain"], "scored");
+    assert_eq!(scored.len(), 2);
+    assert_eq!(scored[0]["id"], quick.as_str());
+    assert_eq!(scored[0]["score"]["unblocks"], 10.0);
+    assert_eq!(scored[0]["score"]["priority"], 20.0);
+    assert_eq!(scored[1]["id"], leaf.as_str());
+    assert_eq!(scored[1]["score"]["estimate"], -8.0);
+
+    let text = run_br(
+        &workspace,
+        ["ready", "--sort", "score", "--explain"],
+        "text",
+    );
+    assert!(text.status.success(), "ready failed: {}", text.stderr);
+    assert!(
+        text.stdout
+            .contains("score 29.5 = priority 20 + unblocks 10 + estimate -0.5"),
+        "{}",
+        text.stdout
+    );
+
+    let next = run_br(&workspace, ["next", "--sort", "score", "--json"], "next");
+    assert!(next.status.success(), "next failed: {}", next.stderr);
+    let next: Value = serde_json::from_str(&extract_json_payload(&next.stdout)).expect("json");
+    assert_eq!(next["id"], quick.as_str());
+}
+
+#[test]
+fn e2e_ready_score_uses_config_weights() {
+    let _log = common::test_log("e2e_ready_score_uses_config_weights");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    create(&workspace, &["Important", "-p", "0"], "important");
+    let boosted = create(
+        &workspace,
+        &["Boosted", "-p", "4", "-l", "focus"],
+        "boosted",
+    );
+
+    append_config(
+        &workspace,
+        "ready:\n  score:\n    priority: 1\n    labels:\n      focus: 50",
+    );
+    let scored = ready_json(&workspace, &["--sort", "score", "--explain"], "scored");
+    assert_eq!(scored[0]["id"], boosted.as_str());
+    assert_eq!(scored[0]["score"]["labels"], 50.0);
+    assert_eq!(scored[1]["score"]["priority"], 4.0);
+
+    append_config(&workspace, "ready.score.speed: 3");
+    let bad = run_br(&workspace, ["ready", "--sort", "score"], "bad_config");
+    assert!(!bad.status.success());
+    assert!(bad.stderr.contains("ready.score.speed"), "{}", bad.stderr);
+}
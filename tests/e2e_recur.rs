@@ -0,0 +1,111 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+
+fn json(workspace: &BrWorkspace, args: &[&str], label: &str) -> Value {
+    let mut full = args.to_vec();
+    full.push("--json");
+    let out = run_br(workspace, full, label);
+    assert!(out.status.success(), "{label} failed: {}", out.stderr);
+    serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json")
+}
+
+#[test]
+fn e2e_recur_run_is_idempotent() {
+    let _log = common::test_log("e2e_recur_run_is_idempotent");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let template = json(
+        &workspace,
+        &["template", "create", "Dependency audit {{date}} ({{team}})"],
+        "template",
+    )["id"]
+        .as_str()
+        .expect("id")
+        .to_string();
+
+    let missing_var = run_br(
+        &workspace,
+        ["recur", "add", &template, "every week on mon"],
+        "missing_var",
+    );
+    assert!(!missing_var.status.success());
+    assert!(
+        missing_var.stderr.contains("team"),
+        "{}",
+        missing_var.stderr
+    );
+    let bad_rule = run_br(
+        &workspace,
+        [
+            "recur",
+            "add",
+            &template,
+            "every fortnight",
+            "--var",
+            "team=ops",
+        ],
+        "bad_rule",
+    );
+    assert!(!bad_rule.status.success());
+
+    let added = json(
+        &workspace,
+        &[
+            "recur",
+            "add",
+            &template,
+            "every week on mon",
+            "--lead",
+            "2d",
+            "--var",
+            "team=ops",
+        ],
+        "add",
+    );
+    assert_eq!(added["template"], template.as_str());
+    assert!(added["next"].is_string(), "{added}");
+
+    let first = json(&workspace, &["recur", "run"], "run_1");
+    assert_eq!(first[0]["status"], "created", "{first}");
+    let id = first[0]["id"].as_str().expect("id").to_string();
+    assert_eq!(first[0]["occurrence"], added["next"]);
+
+    let second = json(&workspace, &["recur", "run"], "run_2");
+    assert_eq!(second[0]["status"], "exists", "{second}");
+    assert_eq!(second[0]["id"], id.as_str());
+
+    let listed = json(&workspace, &["list"], "list");
+    assert_eq!(listed.as_array().map(Vec::len), Some(1), "{listed}");
+
+    let shown = json(&workspace, &["show", &id], "show");
+    let issue = &shown[0];
+    assert!(
+        issue["title"]
+            .as_str()
+            .is_some_and(|t| t.starts_with("Dependency audit 20") && t.ends_with("(ops)")),
+        "{issue}"
+    );
+    assert!(
+        issue["external_ref"]
+            .as_str()
+            .is_some_and(|r| r.starts_with(&format!("recur:{template}:"))),
+        "{issue}"
+    );
+    assert!(issue["due_at"].is_string());
+    assert!(issue["defer_until"].is_string());
+    let deps = issue["dependencies"].as_array().expect("dependencies");
+    assert!(
+        deps.iter()
+            .any(|d| d["id"] == template.as_str() && d["dependency_type"] == "discovered-from"),
+        "{issue}"
+    );
+
+    let removed = json(&workspace, &["recur", "remove", &template], "remove");
+    assert_eq!(removed["removed"], true);
+    let after = json(&workspace, &["recur", "run"], "run_after_remove");
+    assert_eq!(after.as_array().map(Vec::len), Some(0));
+}
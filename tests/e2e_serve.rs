@@ -0,0 +1,140 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+fn start_daemon(workspace: &BrWorkspace) -> (Child, PathBuf) {
+    let child = Command::new(assert_cmd::cargo::cargo_bin!("obr"))
+        .current_dir(&workspace.root)
+        .arg("serve")
+        .env("NO_COLOR", "1")
+        .env("HOME", &workspace.root)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn serve");
+    let socket_path = workspace.root.join(".beads/obr.sock");
+    for _ in 0..200 {
+        if UnixStream::connect(&socket_path).is_ok() {
+            return (child, socket_path);
+        }
+        std::thread::sleep(Duration::from_millis(25));
+    }
+    panic!(
+        "daemon did not start listening on {}",
+        socket_path.display()
+    );
+}
+
+fn shutdown(socket_path: &Path) {
+    let mut stream = UnixStream::connect(socket_path).expect("connect");
+    stream
+        .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"shutdown\"}\n")
+        .expect("send shutdown");
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line).expect("read");
+    assert!(line.contains("\"result\""), "unexpected reply: {line}");
+}
+
+fn json_of(stdout: &str) -> Value {
+    serde_json::from_str(&extract_json_payload(stdout)).expect("json")
+}
+
+#[test]
+fn e2e_serve_forwards_json_commands() {
+    let _log = common::test_log("e2e_serve_forwards_json_commands");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let (mut child, socket_path) = start_daemon(&workspace);
+
+    let create = run_br(
+        &workspace,
+        ["create", "Through the daemon", "-p", "1", "--json"],
+        "create",
+    );
+    assert!(create.status.success(), "create failed: {}", create.stderr);
+    assert!(
+        create.stderr.contains("Forwarding command to daemon"),
+        "create was not forwarded: {}",
+        create.stderr
+    );
+    let created = json_of(&create.stdout);
+    let id = created["id"].as_str().expect("id").to_string();
+    assert_eq!(created["priority"], 1);
+
+    let claim = run_br(&workspace, ["update", &id, "--claim", "--json"], "claim");
+    assert!(claim.status.success(), "claim failed: {}", claim.stderr);
+    assert_eq!(json_of(&claim.stdout)[0]["status"], "in_progress");
+
+    // Forwarded output is identical to direct output.
+    let forwarded = run_br(&workspace, ["show", &id, "--json"], "show_forwarded");
+    let direct = run_br(
+        &workspace,
+        ["show", &id, "--json", "--no-daemon"],
+        "show_direct",
+    );
+    assert!(
+        !direct.stderr.contains("Forwarding command to daemon"),
+        "--no-daemon was forwarded"
+    );
+    assert_eq!(
+        extract_json_payload(&forwarded.stdout),
+        extract_json_payload(&direct.stdout)
+    );
+
+    let missing = run_br(&workspace, ["show", "bd-zzzz", "--json"], "show_missing");
+    assert!(!missing.status.success());
+    assert!(
+        missing.stderr.contains("ISSUE_NOT_FOUND"),
+        "{}",
+        missing.stderr
+    );
+
+    let info = run_br(&workspace, ["info", "--json"], "info");
+    assert_eq!(json_of(&info.stdout)["daemon_connected"], true);
+
+    shutdown(&socket_path);
+    assert!(child.wait().expect("wait").success());
+    assert!(!socket_path.exists());
+
+    // Changes were flushed like any CLI mutation.
+    let jsonl = std::fs::read_to_string(workspace.root.join(".beads/issues.jsonl")).expect("jsonl");
+    assert!(jsonl.contains("Through the daemon"));
+}
+
+#[test]
+fn e2e_serve_stale_socket_falls_back_to_direct() {
+    let _log = common::test_log("e2e_serve_stale_socket_falls_back_to_direct");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let (mut child, socket_path) = start_daemon(&workspace);
+    child.kill().expect("kill daemon");
+    child.wait().expect("wait");
+    assert!(
+        socket_path.exists(),
+        "a killed daemon leaves its socket behind"
+    );
+
+    let create = run_br(&workspace, ["create", "Direct", "--json"], "create");
+    assert!(create.status.success(), "create failed: {}", create.stderr);
+    assert_eq!(json_of(&create.stdout)["title"], "Direct");
+
+    let info = run_br(&workspace, ["info", "--json"], "info");
+    let info = json_of(&info.stdout);
+    assert_eq!(info["daemon_connected"], false);
+    assert_eq!(info["mode"], "direct");
+
+    // A new daemon replaces the stale socket.
+    let (mut child, socket_path) = start_daemon(&workspace);
+    shutdown(&socket_path);
+    assert!(child.wait().expect("wait").success());
+}
@@ -0,0 +1,184 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+
+fn json(workspace: &BrWorkspace, args: &[&str], label: &str) -> Value {
+    let mut full = args.to_vec();
+    full.push("--json");
+    let out = run_br(workspace, full, label);
+    assert!(out.status.success(), "{label} failed: {}", out.stderr);
+    serde_json::from_str(&extract_json_payload(&out.stdout)).expect("json")
+}
+
+fn ids(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .expect("array")
+        .iter()
+        .map(|item| item["id"].as_str().expect("id").to_string())
+        .collect()
+}
+
+#[test]
+fn e2e_template_from_yaml_file() {
+    let _log = common::test_log("e2e_template_from_yaml_file");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let dir = workspace.root.join(".beads").join("templates");
+    std::fs::create_dir_all(&dir).expect("templates dir");
+    std::fs::write(
+        dir.join("release.yaml"),
+        r#"vars:
+  version:
+  channel: stable
+title: "Release {{version}}"
+type: epic
+labels: [release]
+children:
+  - key: build
+    title: "Build {{version}} ({{channel}})"
+    estimate: 60
+  - key: publish
+    title: "Publish {{version}}"
+    description: "Push {{version}} to the {{channel}} channel"
+    deps: [build]
+"#,
+    )
+    .expect("write template");
+
+    let listed = json(&workspace, &["template", "list"], "list_templates");
+    assert_eq!(listed[0]["name"], "release");
+    assert_eq!(listed[0]["source"], "file");
+    assert_eq!(listed[0]["issues"], 3);
+    assert_eq!(listed[0]["vars"], serde_json::json!(["channel", "version"]));
+
+    let missing = run_br(
+        &workspace,
+        ["template", "instantiate", "release"],
+        "missing",
+    );
+    assert!(!missing.status.success());
+    assert!(missing.stderr.contains("version"), "{}", missing.stderr);
+
+    let made = json(
+        &workspace,
+        &["template", "instantiate", "release", "--var", "version=2.1"],
+        "instantiate",
+    );
+    let root = made["root"].as_str().expect("root").to_string();
+    let issues = made["issues"].as_array().expect("issues");
+    assert_eq!(issues.len(), 3);
+    assert_eq!(issues[0]["title"], "Release 2.1");
+    assert_eq!(issues[1]["title"], "Build 2.1 (stable)");
+    assert_eq!(issues[2]["id"], format!("{root}.2"));
+    let publish = issues[2]["id"].as_str().expect("id").to_string();
+
+    let shown: Vec<Value> =
+        serde_json::from_value(json(&workspace, &["show", &publish], "show_publish"))
+            .expect("show array");
+    assert_eq!(shown[0]["description"], "Push 2.1 to the stable channel");
+
+    let ready = ids(&json(&workspace, &["ready"], "ready"));
+    assert!(ready.contains(&format!("{root}.1")), "{ready:?}");
+    assert!(!ready.contains(&publish), "publish waits for build");
+}
+
+#[test]
+fn e2e_template_from_database_issues() {
+    let _log = common::test_log("e2e_template_from_database_issues");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let root = json(
+        &workspace,
+        &["template", "create", "Incident {{name}}", "-t", "epic"],
+        "tpl_root",
+    )["id"]
+        .as_str()
+        .expect("id")
+        .to_string();
+    let triage = json(
+        &workspace,
+        &["template", "create", "Triage {{name}}", "--parent", &root],
+        "tpl_triage",
+    )["id"]
+        .as_str()
+        .expect("id")
+        .to_string();
+    json(
+        &workspace,
+        &[
+            "template",
+            "create",
+            "Postmortem for {{name}}",
+            "--parent",
+            &root,
+            "--deps",
+            &triage,
+        ],
+        "tpl_postmortem",
+    );
+
+    // Templates stay out of normal work.
+    assert!(ids(&json(&workspace, &["list"], "list_empty")).is_empty());
+    let listed = json(&workspace, &["template", "list"], "list_templates");
+    assert_eq!(listed.as_array().map(Vec::len), Some(1), "{listed}");
+    assert_eq!(listed[0]["name"], root.as_str());
+    assert_eq!(listed[0]["issues"], 3);
+
+    let dry = json(
+        &workspace,
+        &[
+            "template",
+            "instantiate",
+            &root,
+            "--var",
+            "name=db",
+            "--dry-run",
+        ],
+        "dry_run",
+    );
+    assert_eq!(dry["dry_run"], true);
+    assert!(ids(&json(&workspace, &["list"], "list_after_dry_run")).is_empty());
+
+    let typo = run_br(
+        &workspace,
+        ["template", "instantiate", &root, "--var", "nmae=db"],
+        "typo",
+    );
+    assert!(!typo.status.success());
+    assert!(typo.stderr.contains("nmae"), "{}", typo.stderr);
+
+    let made = json(
+        &workspace,
+        &["template", "instantiate", &root, "--var", "name=db-outage"],
+        "instantiate",
+    );
+    let new_root = made["root"].as_str().expect("root");
+    assert_ne!(new_root, root);
+    let titles: Vec<&str> = made["issues"]
+        .as_array()
+        .expect("issues")
+        .iter()
+        .map(|issue| issue["title"].as_str().unwrap_or_default())
+        .collect();
+    assert_eq!(
+        titles,
+        [
+            "Incident db-outage",
+            "Triage db-outage",
+            "Postmortem for db-outage"
+        ]
+    );
+    let listed = ids(&json(&workspace, &["list"], "list_instances"));
+    assert_eq!(listed.len(), 3, "{listed:?}");
+    let ready = ids(&json(&workspace, &["ready"], "ready"));
+    assert!(
+        !ready.contains(&format!("{new_root}.2")),
+        "postmortem waits for triage: {ready:?}"
+    );
+}
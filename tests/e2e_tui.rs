@@ -0,0 +1,19 @@
+mod common;
+
+use common::cli::{BrWorkspace, run_br};
+
+#[test]
+fn e2e_tui_requires_a_terminal() {
+    let _log = common::test_log("e2e_tui_requires_a_terminal");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let tui = run_br(&workspace, ["tui", "--json"], "tui");
+    assert!(!tui.status.success());
+    assert!(
+        tui.stderr.contains("interactive terminal"),
+        "unexpected stderr: {}",
+        tui.stderr
+    );
+}
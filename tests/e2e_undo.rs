@@ -0,0 +1,119 @@
+mod common;
+
+use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use serde_json::Value;
+
+fn parse_created_id(stdout: &str) -> String {
+    let line = stdout.lines().next().unwrap_or("");
+    let normalized = line.strip_prefix("✓ ").unwrap_or(line);
+    normalized
+        .strip_prefix("Created ")
+        .and_then(|rest| rest.split(':').next())
+        .unwrap_or("")
+        .trim()
+        .to_string()
+}
+
+fn show_json(workspace: &BrWorkspace, id: &str, label: &str) -> Value {
+    let show = run_br(workspace, ["show", id, "--json"], label);
+    assert!(show.status.success(), "show failed: {}", show.stderr);
+    let json: Value = serde_json::from_str(&extract_json_payload(&show.stdout)).expect("show json");
+    if json.is_array() {
+        json[0].clone()
+    } else {
+        json
+    }
+}
+
+#[test]
+fn e2e_undo_reverts_last_command_and_redoes() {
+    let _log = common::test_log("e2e_undo_reverts_last_command_and_redoes");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let create = run_br(&workspace, ["create", "Undo me", "-p", "2"], "create");
+    assert!(create.status.success(), "create failed: {}", create.stderr);
+    let id = parse_created_id(&create.stdout);
+
+    let update = run_br(
+        &workspace,
+        [
+            "update",
+            &id,
+            "--title",
+            "Changed",
+            "-p",
+            "0",
+            "--add-label",
+            "hot",
+        ],
+        "update",
+    );
+    assert!(update.status.success(), "update failed: {}", update.stderr);
+    let close = run_br(&workspace, ["close", &id], "close");
+    assert!(close.status.success(), "close failed: {}", close.stderr);
+
+    // Undo the close and the update in one go.
+    let undo = run_br(&workspace, ["undo", "--steps", "2", "--json"], "undo");
+    assert!(undo.status.success(), "undo failed: {}", undo.stderr);
+    let report: Value = serde_json::from_str(&extract_json_payload(&undo.stdout)).expect("json");
+    assert_eq!(report["undone"].as_array().map(Vec::len), Some(2));
+    assert_eq!(report["issues"][0], id.as_str());
+
+    let issue = show_json(&workspace, &id, "show_after_undo");
+    assert_eq!(issue["title"], "Undo me");
+    assert_eq!(issue["priority"], 2);
+    assert_eq!(issue["status"], "open");
+    assert!(issue.get("closed_at").is_none_or(Value::is_null));
+    assert!(
+        issue
+            .get("labels")
+            .and_then(Value::as_array)
+            .is_none_or(Vec::is_empty)
+    );
+
+    // Undoing the undo restores the closed state.
+    let undo_op = report["op_id"].as_str().expect("op id").to_string();
+    let redo = run_br(&workspace, ["undo", "--op-id", &undo_op], "redo");
+    assert!(redo.status.success(), "redo failed: {}", redo.stderr);
+    let issue = show_json(&workspace, &id, "show_after_redo");
+    assert_eq!(issue["status"], "closed");
+    assert_eq!(issue["title"], "Changed");
+
+    let list = run_br(&workspace, ["undo", "--list", "--json"], "list");
+    assert!(list.status.success(), "list failed: {}", list.stderr);
+    let ops: Value = serde_json::from_str(&extract_json_payload(&list.stdout)).expect("json");
+    assert_eq!(ops[0]["name"], "undo");
+    assert_eq!(ops[0]["undoes"][0], undo_op.as_str());
+}
+
+#[test]
+fn e2e_undo_refuses_conflicting_later_edit() {
+    let _log = common::test_log("e2e_undo_refuses_conflicting_later_edit");
+    let workspace = BrWorkspace::new();
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    let create = run_br(&workspace, ["create", "Contested"], "create");
+    assert!(create.status.success(), "create failed: {}", create.stderr);
+    let id = parse_created_id(&create.stdout);
+
+    let first = run_br(&workspace, ["update", &id, "--title", "First"], "first");
+    assert!(first.status.success(), "update failed: {}", first.stderr);
+    let list = run_br(&workspace, ["undo", "--list", "--json"], "list");
+    let ops: Value = serde_json::from_str(&extract_json_payload(&list.stdout)).expect("json");
+    let first_op = ops[0]["id"].as_str().expect("op id").to_string();
+
+    let second = run_br(&workspace, ["update", &id, "--title", "Second"], "second");
+    assert!(second.status.success(), "update failed: {}", second.stderr);
+
+    let undo = run_br(&workspace, ["undo", "--op-id", &first_op, "--json"], "undo");
+    assert!(!undo.status.success());
+    assert_eq!(undo.status.code(), Some(3));
+    let json: Value = serde_json::from_str(&extract_json_payload(&undo.stderr)).expect("json");
+    assert_eq!(json["error"]["code"], "UNDO_CONFLICT");
+    assert_eq!(json["error"]["context"]["op_id"], first_op.as_str());
+
+    assert_eq!(show_json(&workspace, &id, "show")["title"], "Second");
+}
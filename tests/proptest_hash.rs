@@ -64,6 +64,7 @@ fn make_issue(title: &str, description: Option<&str>) -> Issue {
         labels: vec![],
         dependencies: vec![],
         comments: vec![],
+        org_extras: None,
     }
 }
 
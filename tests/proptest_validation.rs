@@ -64,6 +64,7 @@ fn make_valid_issue(title: &str) -> Issue {
         labels: vec![],
         dependencies: vec![],
         comments: vec![],
+        org_extras: None,
     }
 }
 
@@ -47,6 +47,7 @@ fn make_issue(id: &str, title: &str) -> Issue {
         labels: vec![],
         dependencies: vec![],
         comments: vec![],
+        org_extras: None,
     }
 }
 
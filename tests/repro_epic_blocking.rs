@@ -43,6 +43,7 @@ fn create_issue(id: &str, title: &str, issue_type: IssueType) -> Issue {
         labels: vec![],
         dependencies: vec![],
         comments: vec![],
+        org_extras: None,
     }
 }
 
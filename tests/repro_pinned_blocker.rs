@@ -43,6 +43,7 @@ fn make_issue(id: &str, title: &str, status: Status) -> Issue {
         labels: vec![],
         dependencies: vec![],
         comments: vec![],
+        org_extras: None,
     }
 }
 
@@ -510,6 +510,8 @@ pub fn normalize_json(json: &Value) -> Value {
                         Value::String("TIMESTAMP".to_string())
                     }
                     "content_hash" => Value::String("HASH".to_string()),
+                    // Relevance scores depend on corpus statistics
+                    "score" if value.is_number() => Value::String("SCORE".to_string()),
                     // Normalize actor/user fields that vary by system
                     "created_by" | "assignee" | "owner" | "author" | "deleted_by"
                     | "closed_by_session" | "actor" => {
@@ -79,6 +79,7 @@ fn create_issue_all_fields_populated() {
         compacted_at_commit: None,
         original_size: None,
         sender: None,
+        org_extras: None,
     };
 
     storage.create_issue(&issue, "tester").unwrap();
@@ -443,6 +444,7 @@ fn update_issue_clear_optional_fields() {
         labels: vec![],
         dependencies: vec![],
         comments: vec![],
+        org_extras: None,
     };
 
     storage.create_issue(&issue, "tester").unwrap();
@@ -717,6 +719,7 @@ fn upsert_issue_stores_all_fields() {
         compacted_at_commit: None,
         original_size: None,
         sender: None,
+        org_extras: None,
     };
 
     storage.upsert_issue_for_import(&issue).unwrap();
@@ -589,6 +589,7 @@ fn content_hash_trait_implementation() {
         labels: vec![],
         dependencies: vec![],
         comments: vec![],
+        org_extras: None,
     };
 
     let hash_trait = issue.content_hash();